@@ -1,7 +1,35 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use clap::Parser;
 
+/// options that change how the application runs, they can be combined with
+/// any `RuntimeBehavior` that runs the application
+#[derive(Debug, Default, PartialEq)]
+pub struct RuntimeOptions {
+    /// render inline on the current screen instead of the alternate screen
+    pub inline: bool,
+    /// open a scratch request that is never saved instead of the collection
+    /// list
+    pub scratch: bool,
+    /// how an imported collection named like an existing one is saved,
+    /// falling back to the configured behavior when not given
+    pub on_conflict: Option<ImportConflictArg>,
+}
+
+/// ways of saving an imported collection that clashes with an existing one,
+/// as given to --on-conflict
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ImportConflictArg {
+    /// ask which of the other behaviors to use
+    Prompt,
+    /// replace the existing collection with the imported one
+    Overwrite,
+    /// combine directories and requests with the same name
+    Merge,
+    /// keep both, adding a numeric suffix to the name of the imported one
+    Suffix,
+}
+
 /// How the runtime should behave. Dictated by the flags provided to  `Cli`
 #[derive(Debug, PartialEq)]
 pub enum RuntimeBehavior {
@@ -18,11 +46,36 @@ pub enum RuntimeBehavior {
     /// will run the application with all disk-synchronization disabled. That
     /// means `HAC` wont't save any files or changes to collection to disk.
     DryRun,
+    /// will import every request of the given har file into a new collection
+    /// instead of running the application.
+    ImportHar(PathBuf),
+    /// will import every operation of an OpenAPI spec into a new collection
+    /// instead of running the application, reading the spec from a file or
+    /// downloading it, with an optional bearer token, from an url.
+    ImportOpenApi(OpenApiSource),
+    /// will send a single request of a collection and print its response
+    /// instead of running the application, resolving variables against the
+    /// environment and env file when given. `body` replaces the body of the
+    /// request, where `-` means it is read from stdin.
+    RunRequest {
+        collection: String,
+        request: String,
+        env: Option<String>,
+        env_file: Option<PathBuf>,
+        body: Option<String>,
+    },
     /// the default running behavior of the application, this is the default
     /// behavior for `HAC`.
     Run,
 }
 
+/// where an OpenAPI spec is read from
+#[derive(Debug, PartialEq)]
+pub enum OpenApiSource {
+    File(PathBuf),
+    Url { url: String, token: Option<String> },
+}
+
 #[derive(Parser, Debug)]
 pub struct Cli {
     /// prints the directory in which the config file is being loaded from
@@ -38,26 +91,104 @@ pub struct Cli {
     /// specified, no collection, request, or anything will be saved to disk.
     #[arg(long)]
     dry_run: bool,
+    /// runs inline on the current screen instead of taking over the whole
+    /// terminal, preserving the scrollback of the session.
+    #[arg(long)]
+    inline: bool,
+    /// opens a scratch request that isn't saved to any collection, everything
+    /// is discarded when the application quits.
+    #[arg(long)]
+    scratch: bool,
+    /// imports the requests of a har file, as exported by the browser devtools,
+    /// into a new collection named after the file.
+    #[arg(long, value_name = "PATH")]
+    import_har: Option<PathBuf>,
+    /// imports the operations of an OpenAPI spec, written as json, into a new
+    /// collection named after the file.
+    #[arg(long, value_name = "PATH")]
+    import_openapi: Option<PathBuf>,
+    /// downloads an OpenAPI spec, written as json, and imports its operations
+    /// into a new collection named after the host of the url.
+    #[arg(long, value_name = "URL", conflicts_with = "import_openapi")]
+    import_openapi_url: Option<String>,
+    /// bearer token sent when downloading the spec of --import-openapi-url.
+    #[arg(long, value_name = "TOKEN", requires = "import_openapi_url")]
+    token: Option<String>,
+    /// what to do when an imported collection is named like an existing one,
+    /// when not given the importers ask on a terminal, defaulting to the
+    /// configured `on_import_conflict`.
+    #[arg(long, value_name = "BEHAVIOR")]
+    on_conflict: Option<ImportConflictArg>,
+    /// sends a request of a collection, both given by name, and prints the
+    /// response to stdout.
+    #[arg(long, num_args = 2, value_names = ["COLLECTION", "REQUEST"])]
+    run_request: Option<Vec<String>>,
+    /// environment of the collection used to resolve the variables of the
+    /// request sent with --run-request.
+    #[arg(long, value_name = "NAME", requires = "run_request")]
+    env: Option<String>,
+    /// `.env` file whose variables are used beneath the environment given by
+    /// --env, replacing the env file of the collection.
+    #[arg(long, value_name = "PATH", requires = "run_request")]
+    env_file: Option<PathBuf>,
+    /// body sent with --run-request instead of the body of the request, `-`
+    /// reads it from stdin, eg: `cat payload.json | hac --body - ...`.
+    #[arg(long, value_name = "BODY", requires = "run_request")]
+    body: Option<String>,
 }
 
 impl Cli {
-    pub fn parse_args() -> RuntimeBehavior {
+    pub fn parse_args() -> (RuntimeBehavior, RuntimeOptions) {
         let args = Cli::parse();
+        let options = RuntimeOptions {
+            inline: args.inline,
+            scratch: args.scratch,
+            on_conflict: args.on_conflict,
+        };
 
         if args.config_dir {
-            return RuntimeBehavior::PrintConfigPath;
+            return (RuntimeBehavior::PrintConfigPath, options);
         }
         if args.data_dir {
-            return RuntimeBehavior::PrintDataPath;
+            return (RuntimeBehavior::PrintDataPath, options);
         }
         if args.config_dump {
-            return RuntimeBehavior::DumpDefaultConfig;
+            return (RuntimeBehavior::DumpDefaultConfig, options);
+        }
+        if let Some(path) = args.import_har {
+            return (RuntimeBehavior::ImportHar(path), options);
+        }
+        if let Some(path) = args.import_openapi {
+            let source = OpenApiSource::File(path);
+            return (RuntimeBehavior::ImportOpenApi(source), options);
+        }
+        if let Some(url) = args.import_openapi_url {
+            let source = OpenApiSource::Url {
+                url,
+                token: args.token,
+            };
+            return (RuntimeBehavior::ImportOpenApi(source), options);
+        }
+        if let Some(mut names) = args.run_request {
+            // clap guarantees exactly two values for this flag
+            let request = names.pop().unwrap();
+            let collection = names.pop().unwrap();
+            return (
+                RuntimeBehavior::RunRequest {
+                    collection,
+                    request,
+                    env: args.env,
+                    env_file: args.env_file,
+                    body: args.body,
+                },
+                options,
+            );
         }
         if args.dry_run {
-            return RuntimeBehavior::DryRun;
+            return (RuntimeBehavior::DryRun, options);
         }
 
-        RuntimeBehavior::Run
+        (RuntimeBehavior::Run, options)
     }
 
     pub fn print_data_path<P>(data_path: P)
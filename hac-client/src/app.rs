@@ -1,59 +1,203 @@
+use hac_config::EmptyCollections;
+use hac_core::collection::types::ClientSettings;
 use hac_core::{collection::Collection, command::Command};
 
 use crate::event_pool::{Event, EventPool};
 use crate::pages::{Eventful, Renderable};
+use crate::recent;
 use crate::screen_manager::ScreenManager;
+use crate::toast;
 
 use std::io::Stdout;
+use std::rc::Rc;
+use std::time::Duration;
 
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{backend::CrosstermBackend, Terminal, TerminalOptions, Viewport};
 use tokio::sync::mpsc;
 
 pub struct App<'app> {
     event_pool: EventPool,
     terminal: Terminal<CrosstermBackend<Stdout>>,
     should_quit: bool,
+    dry_run: bool,
+    /// renders below the current contents of the terminal instead of taking
+    /// over the alternate screen, keeping the scrollback intact
+    inline: bool,
     screen_manager: ScreenManager<'app>,
+    /// collection opened as soon as the app starts, instead of the collection list
+    startup_collection: Option<Collection>,
+    startup_scratch: bool,
+    /// set when there are no collections, telling what to offer instead of
+    /// the empty list
+    startup_empty: Option<EmptyCollections>,
+    /// how long transient messages stay on screen
+    toast_duration: Duration,
+    config: Rc<hac_config::Config>,
 }
 
 impl<'app> App<'app> {
     pub fn new(
         colors: &'app hac_colors::Colors,
         collections: Vec<Collection>,
-        config: &'app hac_config::Config,
+        config: Rc<hac_config::Config>,
         dry_run: bool,
+        inline: bool,
     ) -> anyhow::Result<Self> {
-        let terminal = Terminal::new(CrosstermBackend::new(std::io::stdout()))?;
+        let backend = CrosstermBackend::new(std::io::stdout());
+        let mut terminal = match inline {
+            // the inline viewport spans the height of the terminal, pushing what was on
+            // the screen into the scrollback instead of hiding it
+            true => {
+                let (_, height) = crossterm::terminal::size()?;
+                let viewport = Viewport::Inline(height);
+                Terminal::with_options(backend, TerminalOptions { viewport })?
+            }
+            false => Terminal::new(backend)?,
+        };
+        let size = terminal.get_frame().size();
+        if config.persist_recent_requests && !dry_run {
+            recent::load(hac_config::load_recent_requests());
+        }
         Ok(Self {
-            screen_manager: ScreenManager::new(
-                terminal.size()?,
-                colors,
-                collections,
-                config,
-                dry_run,
-            )?,
+            screen_manager: ScreenManager::new(size, colors, collections, config.clone(), dry_run)?,
             event_pool: EventPool::new(60f64, 30f64),
             should_quit: false,
+            dry_run,
+            inline,
+            startup_collection: None,
+            startup_scratch: false,
+            startup_empty: None,
+            toast_duration: Duration::from_millis(config.toast_duration_ms),
+            config,
             terminal,
         })
     }
 
+    /// displays an error on the error popup, useful to report problems that
+    /// happened before the app started running
+    pub fn display_error(&mut self, message: String) {
+        self.screen_manager.handle_command(Command::Error(message));
+    }
+
+    /// opens the given collection when the app starts running, skipping the
+    /// collection list
+    pub fn start_on_collection(&mut self, collection: Collection) {
+        self.startup_collection = Some(collection);
+    }
+
+    /// opens a scratch request when the app starts running, nothing done on
+    /// it is ever saved to disk
+    pub fn start_on_scratch(&mut self) {
+        self.startup_scratch = true;
+    }
+
+    /// there are no collections to show, `behavior` tells whether a starter
+    /// collection is offered or created when the app starts running
+    pub fn start_without_collections(&mut self, behavior: EmptyCollections) {
+        self.startup_empty = Some(behavior);
+    }
+
+    /// hands the terminal over to the pager of the user to show `content`,
+    /// taking it back and redrawing everything once the pager exits
+    fn open_pager(&mut self, content: &str) -> anyhow::Result<()> {
+        self.event_pool.stop();
+        shutdown(self.inline)?;
+
+        let result = run_pager(content);
+
+        startup(self.inline)?;
+        self.terminal.clear()?;
+        self.event_pool.start();
+
+        if let Err(e) = result {
+            toast::show(e.to_string());
+        }
+
+        Ok(())
+    }
+
+    /// reads the config again and hands it to every screen, along with the
+    /// network settings. Settings only read when the app starts are listed on
+    /// the toast, as they need a restart to apply
+    fn reload_config(&mut self) {
+        let config = Rc::new(hac_config::load_config());
+        apply_network_settings(&config);
+        self.toast_duration = Duration::from_millis(config.toast_duration_ms);
+
+        let needs_restart = [
+            (
+                "max_concurrent_requests",
+                self.config.max_concurrent_requests != config.max_concurrent_requests,
+            ),
+            ("inline", self.config.inline != config.inline),
+            (
+                "max_collections_loaded",
+                self.config.max_collections_loaded != config.max_collections_loaded,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(name, changed)| changed.then_some(name))
+        .collect::<Vec<_>>();
+
+        self.screen_manager.reload_config(config.clone());
+        self.config = config;
+        match needs_restart.is_empty() {
+            true => toast::show("config reloaded"),
+            false => toast::show(format!(
+                "config reloaded, restart to apply {}",
+                needs_restart.join(", ")
+            )),
+        }
+    }
+
     /// this is the main method which starts the event loop task, listen for events and commands
     /// to pass them down the chain, and render the terminal screen
     pub async fn run(&mut self) -> anyhow::Result<()> {
         let (command_tx, mut command_rx) = mpsc::unbounded_channel();
         self.event_pool.start();
 
-        startup()?;
+        startup(self.inline)?;
 
         self.screen_manager
             .register_command_handler(command_tx.clone())?;
 
+        if let Some(collection) = self.startup_collection.take() {
+            command_tx
+                .send(Command::SelectCollection(collection))
+                .expect("failed to send command through channel");
+        }
+
+        match self.startup_empty.take() {
+            Some(EmptyCollections::Prompt) => self.screen_manager.offer_starter_collection(),
+            Some(EmptyCollections::Create) => self.screen_manager.create_starter_collection(),
+            Some(EmptyCollections::Ignore) | None => {}
+        }
+
+        if self.startup_scratch {
+            command_tx
+                .send(Command::OpenScratch(
+                    hac_core::collection::collection::scratch_collection(),
+                ))
+                .expect("failed to send command through channel");
+        }
+
         loop {
             {
                 while let Ok(command) = command_rx.try_recv() {
                     match command {
-                        Command::Quit => self.should_quit = true,
+                        Command::Quit => {
+                            self.screen_manager.save_session();
+                            self.should_quit = true;
+                        }
+                        Command::SelectCollection(ref collection)
+                        | Command::CreateCollection(ref collection) => {
+                            if !self.dry_run {
+                                hac_config::save_last_collection(&collection.path);
+                            }
+                            self.screen_manager.handle_command(command);
+                        }
+                        Command::OpenPager(content) => self.open_pager(&content)?,
+                        Command::ReloadConfig => self.reload_config(),
                         _ => self.screen_manager.handle_command(command),
                     }
                 }
@@ -61,7 +205,10 @@ impl<'app> App<'app> {
 
             if let Some(event) = self.event_pool.next().await {
                 match event {
-                    Event::Tick => self.screen_manager.handle_tick()?,
+                    Event::Tick => {
+                        toast::expire(self.toast_duration);
+                        self.screen_manager.handle_tick()?
+                    }
                     Event::Resize(new_size) => self.screen_manager.resize(new_size),
                     Event::Render => {
                         self.terminal.draw(|f| {
@@ -90,28 +237,82 @@ impl<'app> App<'app> {
             }
         }
 
-        shutdown()?;
+        if self.inline {
+            // leave the last frame on the screen and the prompt right below it
+            let area = self.terminal.get_frame().size();
+            self.terminal
+                .set_cursor(0, area.bottom().saturating_sub(1))?;
+        }
+        shutdown(self.inline)?;
         Ok(())
     }
 }
 
+/// applies the settings of `config` used when sending requests, like the
+/// proxy and whether redirects are followed
+pub fn apply_network_settings(config: &hac_config::Config) {
+    hac_core::net::set_response_indent(config.response_indent);
+    hac_core::net::set_follow_redirects(config.follow_redirects);
+    hac_core::net::set_client_settings(ClientSettings {
+        proxy: config.proxy.clone(),
+        accept_invalid_certs: config.accept_invalid_certs.then_some(true),
+        ca_certificate: config.ca_certificate.clone(),
+        compression: config.compression,
+        ..Default::default()
+    });
+    hac_core::net::set_request_log(config.request_log.clone(), config.request_log_redact);
+}
+
+/// writes `content` into a temporary file and opens it with `$PAGER`, or
+/// `less` when it isn't set, waiting until the pager exits
+fn run_pager(content: &str) -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join(format!("hac-{}.txt", uuid::Uuid::new_v4()));
+    std::fs::write(&path, content)?;
+
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| String::from("less"));
+    // pagers are usually configured along with their arguments, eg: `less -R`
+    let mut args = pager.split_whitespace();
+    let program = args.next().unwrap_or("less");
+    let status = std::process::Command::new(program)
+        .args(args)
+        .arg(&path)
+        .status();
+    _ = std::fs::remove_file(&path);
+
+    match status {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => anyhow::bail!("{program} exited with {status}"),
+        Err(e) => anyhow::bail!("failed to run {program}: {e}"),
+    }
+}
+
 /// before initializing the app, we must setup the terminal to enable all the features
-/// we need, such as raw mode and entering the alternate screen
-fn startup() -> anyhow::Result<()> {
+/// we need, such as raw mode and entering the alternate screen, which is skipped when
+/// running inline
+fn startup(inline: bool) -> anyhow::Result<()> {
     crossterm::terminal::enable_raw_mode()?;
-    crossterm::execute!(std::io::stdout(), crossterm::terminal::EnterAlternateScreen)?;
+    if !inline {
+        crossterm::execute!(std::io::stdout(), crossterm::terminal::EnterAlternateScreen)?;
+    }
 
-    std::panic::set_hook(Box::new(|info| {
+    std::panic::set_hook(Box::new(move |info| {
         tracing::error!("{info:?}");
-        _ = shutdown();
+        _ = shutdown(inline);
     }));
     Ok(())
 }
 
 /// before shutting down we must reverse the changes we made to the users terminal, allowing
 /// them have a usable terminal
-fn shutdown() -> anyhow::Result<()> {
+fn shutdown(inline: bool) -> anyhow::Result<()> {
     crossterm::terminal::disable_raw_mode()?;
-    crossterm::execute!(std::io::stdout(), crossterm::terminal::LeaveAlternateScreen)?;
+    if !inline {
+        crossterm::execute!(std::io::stdout(), crossterm::terminal::LeaveAlternateScreen)?;
+    }
+    // the cursor might have been hidden by the last frame
+    crossterm::execute!(std::io::stdout(), crossterm::cursor::Show)?;
+    if inline {
+        println!();
+    }
     Ok(())
 }
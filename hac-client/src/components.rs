@@ -1,3 +1,2 @@
 pub mod component_styles;
-pub mod key_value_pair;
 pub mod list_item;
@@ -1,8 +1,6 @@
 #[derive(Debug, Clone, Copy)]
 pub enum ComponentBorder {
     All,
-    Below,
-    None,
 }
 
 #[derive(Debug, Clone, Copy)]
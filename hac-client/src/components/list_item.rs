@@ -23,14 +23,10 @@ where
 {
     let block = Block::default();
     let block = block.borders(match border_kind {
-        ComponentBorder::None => Borders::NONE,
         ComponentBorder::All => Borders::ALL,
-        ComponentBorder::Below => Borders::BOTTOM,
     });
     let block = block.border_style(match border_kind {
-        ComponentBorder::None => Style::default(),
         ComponentBorder::All => Style::default().fg(color_from_focus(focus, colors)),
-        ComponentBorder::Below => Style::default().fg(color_from_focus(focus, colors)),
     });
 
     let style = Style::default();
@@ -19,6 +19,7 @@ pub struct EventPool {
     event_tx: tokio::sync::mpsc::UnboundedSender<Event>,
     frame_rate: f64,
     tick_rate: f64,
+    task: Option<tokio::task::JoinHandle<()>>,
 }
 
 impl EventPool {
@@ -30,6 +31,7 @@ impl EventPool {
             event_tx,
             frame_rate,
             tick_rate,
+            task: None,
         }
     }
 
@@ -39,7 +41,7 @@ impl EventPool {
         let tick_delay = std::time::Duration::from_secs_f64(1.0.div(self.tick_rate));
 
         let event_tx = self.event_tx.clone();
-        tokio::spawn(async move {
+        self.task = Some(tokio::spawn(async move {
             let mut reader = crossterm::event::EventStream::new();
             let mut render_interval = tokio::time::interval(render_delay);
             let mut tick_interval = tokio::time::interval(tick_delay);
@@ -71,7 +73,16 @@ impl EventPool {
                     },
                 }
             }
-        });
+        }));
+    }
+
+    /// stops reading events from the terminal, so another process can take
+    /// over it until the pool is started again
+    #[cfg_attr(test, mutants::skip)]
+    pub fn stop(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
     }
 
     #[cfg_attr(test, mutants::skip)]
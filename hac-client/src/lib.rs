@@ -3,5 +3,7 @@ mod ascii;
 mod components;
 pub mod event_pool;
 pub mod pages;
+mod recent;
 pub mod screen_manager;
+mod toast;
 pub mod utils;
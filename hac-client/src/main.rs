@@ -1,6 +1,14 @@
-use hac_cli::RuntimeBehavior;
+use hac_cli::{ImportConflictArg, OpenApiSource, RuntimeBehavior};
 use hac_client::app;
+use hac_config::{ImportConflict, Startup};
 use hac_core::collection::collection;
+use hac_core::collection::types::BodyType;
+use hac_core::net::RequestContext;
+
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::{Arc, RwLock};
 
 fn setup_tracing() -> anyhow::Result<tracing_appender::non_blocking::WorkerGuard> {
     let (data_dir, logfile) = hac_config::log_file();
@@ -17,9 +25,218 @@ fn setup_tracing() -> anyhow::Result<tracing_appender::non_blocking::WorkerGuard
     Ok(guard)
 }
 
+/// imports a har file into a new collection, resolving name clashes with the
+/// existing collections through `conflict_behavior`
+async fn import_har(
+    path: &Path,
+    on_conflict: Option<ImportConflictArg>,
+    config: &hac_config::Config,
+) -> anyhow::Result<()> {
+    let content = std::fs::read_to_string(path)?;
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| String::from("har import"));
+
+    let import = hac_core::collection::har::collection_from_har(&content, &name)?;
+    save_import(
+        import.collection,
+        import.imported,
+        import.skipped,
+        on_conflict,
+        config,
+    )
+    .await
+}
+
+/// imports the operations of an OpenAPI spec into a new collection, the spec
+/// is downloaded first when it is served from an url
+async fn import_openapi(
+    source: OpenApiSource,
+    on_conflict: Option<ImportConflictArg>,
+    config: &hac_config::Config,
+) -> anyhow::Result<()> {
+    let (content, name) = match source {
+        OpenApiSource::File(path) => {
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| String::from("openapi import"));
+            (std::fs::read_to_string(path)?, name)
+        }
+        OpenApiSource::Url { url, token } => {
+            let name = reqwest::Url::parse(&url)?
+                .host_str()
+                .map(String::from)
+                .unwrap_or_else(|| String::from("openapi import"));
+            let content =
+                hac_core::net::request_client::fetch_document(&url, token.as_deref()).await?;
+            (content, name)
+        }
+    };
+
+    let import = hac_core::collection::openapi::collection_from_openapi(&content, &name)?;
+    save_import(
+        import.collection,
+        import.imported,
+        import.skipped,
+        on_conflict,
+        config,
+    )
+    .await
+}
+
+/// writes an imported collection to the collections directory, resolving name
+/// clashes with the existing collections through `conflict_behavior`
+async fn save_import(
+    collection: hac_core::collection::Collection,
+    imported: usize,
+    skipped: usize,
+    on_conflict: Option<ImportConflictArg>,
+    config: &hac_config::Config,
+) -> anyhow::Result<()> {
+    let (existing, _) = collection::get_collections_from_config()?;
+    let behavior = match hac_core::collection::import::find_clash(&collection, &existing) {
+        Some(clash) => conflict_behavior(&clash.info.name, on_conflict, config)?,
+        None => config.on_import_conflict,
+    };
+    let collection =
+        hac_core::collection::import::resolve_conflict(collection, &existing, behavior);
+    let collection_name = collection.info.name.clone();
+    let collection_path = collection.path.clone();
+    hac_core::fs::sync_collection(collection, false)
+        .await
+        .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+    println!(
+        "imported {} requests into collection {} at {}",
+        imported,
+        collection_name,
+        collection_path.to_string_lossy()
+    );
+    if skipped > 0 {
+        println!("skipped {skipped} requests with unsupported methods");
+    }
+
+    Ok(())
+}
+
+/// how an import that clashes with the collection named `name` is saved. The
+/// behavior given through --on-conflict wins, otherwise the user is asked when
+/// running on a terminal, where the configured behavior is the default answer
+fn conflict_behavior(
+    name: &str,
+    on_conflict: Option<ImportConflictArg>,
+    config: &hac_config::Config,
+) -> anyhow::Result<ImportConflict> {
+    let default = config.on_import_conflict;
+    match on_conflict {
+        Some(ImportConflictArg::Overwrite) => return Ok(ImportConflict::Overwrite),
+        Some(ImportConflictArg::Merge) => return Ok(ImportConflict::Merge),
+        Some(ImportConflictArg::Suffix) => return Ok(ImportConflict::Suffix),
+        Some(ImportConflictArg::Prompt) => {}
+        None if !std::io::stdin().is_terminal() => return Ok(default),
+        None => {}
+    }
+
+    let default_answer = match default {
+        ImportConflict::Overwrite => "o",
+        ImportConflict::Merge => "m",
+        ImportConflict::Suffix => "s",
+    };
+    loop {
+        print!(
+            "a collection named {name} already exists, (o)verwrite it, (m)erge into it \
+             or keep both with a (s)uffix? [{default_answer}] "
+        );
+        std::io::stdout().flush()?;
+
+        let mut answer = String::new();
+        if std::io::stdin().read_line(&mut answer)? == 0 {
+            return Ok(default);
+        }
+        match answer.trim().to_lowercase().as_str() {
+            "" => return Ok(default),
+            "o" | "overwrite" => return Ok(ImportConflict::Overwrite),
+            "m" | "merge" => return Ok(ImportConflict::Merge),
+            "s" | "suffix" => return Ok(ImportConflict::Suffix),
+            _ => println!("please answer o, m or s"),
+        }
+    }
+}
+
+/// sends a single request of a collection and prints its response, the
+/// variables of the request are resolved first against `env` layered over
+/// `env_file` or the env file of the collection. When given, `body` replaces
+/// the body of the request, being read from stdin when it is `-`
+async fn run_request(
+    collection_name: &str,
+    request_name: &str,
+    env: Option<&str>,
+    env_file: Option<&Path>,
+    body: Option<&str>,
+    config: &hac_config::Config,
+) -> anyhow::Result<()> {
+    let (collections, _) = collection::get_collections_from_config()?;
+    let collection = collections
+        .iter()
+        .find(|collection| collection.info.name.eq(collection_name))
+        .ok_or_else(|| anyhow::anyhow!("no collection named {collection_name}"))?;
+    let request = collection
+        .requests
+        .as_ref()
+        .and_then(|requests| {
+            collection::find_request_by_name(&requests.read().unwrap(), request_name)
+        })
+        .ok_or_else(|| {
+            anyhow::anyhow!("no request named {request_name} on collection {collection_name}")
+        })?;
+
+    let mut request = request.read().unwrap().clone();
+    if let Some(body) = body {
+        let body = match body {
+            "-" => std::io::read_to_string(std::io::stdin())?,
+            body => body.to_string(),
+        };
+        request.body = Some(body);
+        // the body given replaces the one of the file, any other body type
+        // still tells how the given body is sent
+        if matches!(request.body_type, None | Some(BodyType::File(_))) {
+            request.body_type = Some(BodyType::Json);
+        }
+    }
+    let context =
+        RequestContext::from_collection(collection, env, env_file, config.max_variable_depth)
+            .map_err(anyhow::Error::msg)?;
+
+    let (response_tx, mut response_rx) = tokio::sync::mpsc::unbounded_channel();
+    hac_core::net::handle_request(&Arc::new(RwLock::new(request)), &context, response_tx);
+
+    while let Some(response) = response_rx.recv().await {
+        if response.is_downloading() {
+            continue;
+        }
+        if response.is_error {
+            anyhow::bail!(response
+                .cause
+                .unwrap_or_else(|| String::from("unknown error")));
+        }
+
+        if let Some(status) = response.status {
+            eprintln!("{status} in {}ms", response.duration.as_millis());
+        }
+        if let Some(body) = response.body {
+            println!("{body}");
+        }
+        break;
+    }
+
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let runtime_behavior = hac_cli::Cli::parse_args();
+    let (runtime_behavior, runtime_options) = hac_cli::Cli::parse_args();
 
     match runtime_behavior {
         RuntimeBehavior::PrintConfigPath => hac_cli::Cli::print_config_path(
@@ -39,12 +256,75 @@ async fn main() -> anyhow::Result<()> {
 
     let _guard = setup_tracing()?;
     hac_config::get_or_create_data_dir();
-    let config = hac_config::load_config();
+    // the screens of the app share the config, and swap it when it is reloaded
+    let config = Rc::new(hac_config::load_config());
+
+    if let RuntimeBehavior::ImportHar(path) = runtime_behavior {
+        return import_har(&path, runtime_options.on_conflict, &config).await;
+    }
+
+    if let RuntimeBehavior::ImportOpenApi(source) = runtime_behavior {
+        return import_openapi(source, runtime_options.on_conflict, &config).await;
+    }
 
-    let colors = hac_colors::Colors::default();
-    let mut collections = collection::get_collections_from_config()?;
-    collections.sort_by_key(|key| key.info.name.clone());
-    let mut app = app::App::new(&colors, collections, &config, dry_run)?;
+    if let RuntimeBehavior::RunRequest {
+        collection,
+        request,
+        env,
+        env_file,
+        body,
+    } = &runtime_behavior
+    {
+        app::apply_network_settings(&config);
+        return run_request(
+            collection,
+            request,
+            env.as_deref(),
+            env_file.as_deref(),
+            body.as_deref(),
+            &config,
+        )
+        .await;
+    }
+
+    hac_core::net::set_max_concurrent_requests(config.max_concurrent_requests);
+    app::apply_network_settings(&config);
+
+    let colors = hac_colors::load_colors(hac_config::get_colors_path());
+    let (collections, errors) =
+        collection::get_capped_collections_from_config(config.max_collections_loaded)?;
+    let is_first_run = collections.is_empty() && errors.is_empty();
+    // the last collection might not have been read when loading is capped, so
+    // it is always read from disk
+    let last_collection = match config.startup {
+        Startup::List => None,
+        Startup::Last => match hac_config::load_last_collection() {
+            Some(path)
+                if collections
+                    .iter()
+                    .any(|collection| collection.path.eq(&path)) =>
+            {
+                collection::load_collection(path).await.ok()
+            }
+            _ => None,
+        },
+    };
+    let inline = runtime_options.inline || config.inline;
+    let mut app = app::App::new(&colors, collections, config.clone(), dry_run, inline)?;
+    match last_collection {
+        _ if runtime_options.scratch => app.start_on_scratch(),
+        Some(collection) => app.start_on_collection(collection),
+        None if is_first_run => app.start_without_collections(config.on_empty_collections),
+        None => {}
+    }
+    if !errors.is_empty() {
+        let message = errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        app.display_error(message);
+    }
     app.run().await?;
 
     Ok(())
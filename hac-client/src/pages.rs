@@ -3,9 +3,11 @@ pub mod collection_viewer;
 pub mod confirm_popup;
 pub mod error_popup;
 pub mod input;
+pub mod list_overlay;
 pub mod overlay;
 mod spinner;
 pub mod terminal_too_small;
+pub mod text_overlay;
 mod under_construction;
 
 use crate::event_pool::Event;
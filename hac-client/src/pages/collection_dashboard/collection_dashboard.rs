@@ -5,9 +5,11 @@ use crate::pages::collection_dashboard::new_collection_form::{
     FormFocus, FormState, NewCollectionForm,
 };
 use crate::pages::confirm_popup::ConfirmPopup;
-use crate::pages::error_popup::ErrorPopup;
+use crate::pages::error_popup::{ErrorPopup, ErrorPopupState};
 use crate::pages::overlay::{draw_overlay, make_overlay};
+use crate::pages::spinner::Spinner;
 use crate::pages::{Eventful, Renderable};
+use crate::toast;
 
 use std::ops::{Add, Div, Not, Sub};
 
@@ -44,7 +46,11 @@ pub struct CollectionDashboard<'a> {
     pane_focus: PaneFocus,
     pub command_sender: Option<UnboundedSender<Command>>,
     error_message: String,
+    error_popup_state: ErrorPopupState,
     dry_run: bool,
+    /// name of the collection being loaded in the background after being
+    /// selected, the dashboard ignores input until it is opened
+    loading: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -55,6 +61,8 @@ enum PaneFocus {
     Prompt,
     Help,
     Filter,
+    /// asking whether the starter collection should be created
+    Starter,
 }
 
 impl<'a> CollectionDashboard<'a> {
@@ -80,14 +88,73 @@ impl<'a> CollectionDashboard<'a> {
             filter: String::new(),
             command_sender: None,
             error_message: String::default(),
+            error_popup_state: ErrorPopupState::default(),
             pane_focus: PaneFocus::List,
             dry_run,
+            loading: None,
         })
     }
 
     pub fn display_error(&mut self, message: String) {
+        self.loading = None;
         self.pane_focus = PaneFocus::Error;
         self.error_message = message;
+        self.error_popup_state.reset();
+    }
+
+    /// asks whether a starter collection with an example request should be
+    /// created, used when there are no collections at all
+    pub fn offer_starter_collection(&mut self) {
+        self.pane_focus = PaneFocus::Starter;
+    }
+
+    /// creates the starter collection on a background task, which sends the
+    /// command to open it when done
+    pub fn create_starter_collection(&mut self) {
+        let Some(sender) = self.command_sender.clone() else {
+            return;
+        };
+        let dry_run = self.dry_run;
+        tokio::spawn(async move {
+            let command = match hac_core::fs::create_starter_collection(dry_run).await {
+                Ok(collection) => Command::CreateCollection(collection),
+                Err(e) => Command::Error(e.to_string()),
+            };
+            if sender.send(command).is_err() {
+                tracing::error!("failed to send command through channel");
+            }
+        });
+    }
+
+    /// called once the collection being loaded is opened
+    pub fn finish_loading(&mut self) {
+        self.loading = None;
+    }
+
+    /// reads the selected collection from disk on a background task, which
+    /// sends the command to open it when done. Collections that were never
+    /// written to disk are opened right away
+    fn load_collection(&mut self, collection: &Collection) -> Option<Command> {
+        let Some(sender) = self.command_sender.clone() else {
+            return Some(Command::SelectCollection(collection.clone()));
+        };
+        if !collection.path.is_file() {
+            return Some(Command::SelectCollection(collection.clone()));
+        }
+
+        self.loading = Some(collection.info.name.clone());
+        let path = collection.path.clone();
+        tokio::spawn(async move {
+            let command = match hac_core::collection::collection::load_collection(path).await {
+                Ok(collection) => Command::SelectCollection(collection),
+                Err(e) => Command::Error(e.to_string()),
+            };
+            if sender.send(command).is_err() {
+                tracing::error!("failed to send command through channel");
+            }
+        });
+
+        None
     }
 
     fn filter_list(&mut self) {
@@ -132,7 +199,7 @@ impl<'a> CollectionDashboard<'a> {
     fn handle_list_key_event(&mut self, key_event: KeyEvent) -> anyhow::Result<Option<Command>> {
         match key_event.code {
             KeyCode::Enter => {
-                return Ok(self
+                let collection = self
                     .list_state
                     .items
                     .is_empty()
@@ -145,10 +212,11 @@ impl<'a> CollectionDashboard<'a> {
                                 "user should never be allowed to select a non existing collection",
                             )
                     })
-                    .map(|collection| {
-                        tracing::debug!("selected collection: {}", collection.info.name);
-                        Command::SelectCollection(collection.clone())
-                    }));
+                    .cloned();
+                return Ok(collection.and_then(|collection| {
+                    tracing::debug!("selected collection: {}", collection.info.name);
+                    self.load_collection(&collection)
+                }));
             }
             KeyCode::Char('d') => {
                 if self.list_state.selected().is_some() {
@@ -158,6 +226,11 @@ impl<'a> CollectionDashboard<'a> {
             KeyCode::Char('n') | KeyCode::Char('c') => {
                 self.pane_focus = PaneFocus::Form;
             }
+            KeyCode::Char('s') => {
+                return Ok(Some(Command::OpenScratch(
+                    hac_core::collection::collection::scratch_collection(),
+                )));
+            }
             KeyCode::Char('h') | KeyCode::Left => {
                 if !self.list_state.items.is_empty() {
                     self.list_state.select(
@@ -296,14 +369,13 @@ impl<'a> CollectionDashboard<'a> {
                     .expect("should never attempt to delete a non existing item");
                 let path = collection.path.clone();
 
-                if !self.dry_run {
-                    tokio::spawn(async move {
-                        tracing::debug!("attempting to delete collection: {:?}", path);
-                        hac_core::fs::delete_collection(&path)
-                            .await
-                            .expect("failed to delete collection from filesystem");
-                    });
-                }
+                let dry_run = self.dry_run;
+                tokio::spawn(async move {
+                    tracing::debug!("attempting to delete collection: {:?}", path);
+                    hac_core::fs::delete_collection(&path, dry_run)
+                        .await
+                        .expect("failed to delete collection from filesystem");
+                });
 
                 self.collections.remove(selected);
                 self.list_state.set_items(self.collections.clone());
@@ -319,6 +391,19 @@ impl<'a> CollectionDashboard<'a> {
         Ok(None)
     }
 
+    fn handle_starter_prompt_key_event(&mut self, key_event: KeyEvent) -> Option<Command> {
+        match key_event.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                self.pane_focus = PaneFocus::List;
+                self.create_starter_collection();
+            }
+            KeyCode::Char('n') | KeyCode::Esc => self.pane_focus = PaneFocus::List,
+            _ => {}
+        }
+
+        None
+    }
+
     fn handle_error_popup_key_event(
         &mut self,
         key_event: KeyEvent,
@@ -327,6 +412,8 @@ impl<'a> CollectionDashboard<'a> {
             KeyCode::Char('o') | KeyCode::Esc | KeyCode::Enter => {
                 self.pane_focus = PaneFocus::List;
             }
+            KeyCode::Char('j') | KeyCode::Down => self.error_popup_state.scroll_down(),
+            KeyCode::Char('k') | KeyCode::Up => self.error_popup_state.scroll_up(),
             _ => {}
         };
 
@@ -334,6 +421,21 @@ impl<'a> CollectionDashboard<'a> {
     }
 
     fn draw_hint_text(&self, frame: &mut Frame) {
+        if let Some(name) = self.loading.as_ref() {
+            let spinner = Spinner::default()
+                .with_label(format!("loading {name}").fg(self.colors.bright.black))
+                .with_style(Style::default().fg(self.colors.normal.red))
+                .into_centered_line();
+            frame.render_widget(spinner, self.layout.hint_pane);
+            return;
+        }
+
+        if let Some(message) = toast::current() {
+            let message = message.fg(self.colors.normal.green).into_centered_line();
+            frame.render_widget(message, self.layout.hint_pane);
+            return;
+        }
+
         let hint =
             "[h/j/k/l to move] [n -> new] [enter -> select item] [? -> help] [<C-c> -> quit]"
                 .fg(self.colors.normal.magenta)
@@ -366,6 +468,10 @@ impl<'a> CollectionDashboard<'a> {
                 "n/c".fg(self.colors.bright.magenta),
                 "         - creates a new collection".into(),
             ]),
+            Line::from(vec![
+                "s".fg(self.colors.bright.magenta),
+                "           - opens a scratch request".into(),
+            ]),
             Line::from(vec![
                 "d".fg(self.colors.bright.magenta),
                 "           - deletes the selected collection".into(),
@@ -461,9 +567,9 @@ impl<'a> CollectionDashboard<'a> {
         frame.render_widget(Block::default().bg(self.colors.primary.background), size);
     }
 
-    fn draw_error_popup(&self, frame: &mut Frame) {
+    fn draw_error_popup(&mut self, frame: &mut Frame) {
         let popup = ErrorPopup::new(self.error_message.clone(), self.colors);
-        popup.render(self.layout.error_popup, frame.buffer_mut());
+        frame.render_stateful_widget(popup, self.layout.error_popup, &mut self.error_popup_state);
     }
 
     fn draw_form_popup(&mut self, size: Rect, frame: &mut Frame) {
@@ -500,6 +606,14 @@ impl<'a> CollectionDashboard<'a> {
         confirm_popup.render(self.layout.confirm_popup, frame.buffer_mut());
     }
 
+    fn draw_starter_prompt(&self, frame: &mut Frame) {
+        let confirm_popup = ConfirmPopup::new(
+            String::from("No collections yet, create one with an example request to get started?"),
+            self.colors,
+        );
+        confirm_popup.render(self.layout.confirm_popup, frame.buffer_mut());
+    }
+
     fn draw_title(&self, frame: &mut Frame) -> anyhow::Result<()> {
         let title = BigText::builder()
             .pixel_size(PixelSize::Quadrant)
@@ -535,6 +649,7 @@ impl Renderable for CollectionDashboard<'_> {
             PaneFocus::Filter => self.draw_filter_prompt(frame),
             PaneFocus::Help => self.draw_help_popup(frame),
             PaneFocus::Prompt => self.draw_delete_prompt(frame),
+            PaneFocus::Starter => self.draw_starter_prompt(frame),
             PaneFocus::List => self.draw_hint_text(frame),
         }
 
@@ -559,12 +674,17 @@ impl Eventful for CollectionDashboard<'_> {
             return Ok(Some(Command::Quit));
         };
 
+        if self.loading.is_some() {
+            return Ok(None);
+        }
+
         match self.pane_focus {
             PaneFocus::List => self.handle_list_key_event(key_event),
             PaneFocus::Form => self.handle_form_key_event(key_event),
             PaneFocus::Error => self.handle_error_popup_key_event(key_event),
             PaneFocus::Prompt => self.handle_confirm_popup_key_event(key_event),
             PaneFocus::Filter => self.handle_filter_key_event(key_event),
+            PaneFocus::Starter => Ok(self.handle_starter_prompt_key_event(key_event)),
             PaneFocus::Help => {
                 self.pane_focus = PaneFocus::List;
                 Ok(None)
@@ -593,7 +713,7 @@ fn build_layout(size: Rect) -> DashboardLayout {
         size.width.div(2).saturating_sub(25),
         size.height.div(2).saturating_sub(7),
         50,
-        14,
+        15,
     );
     let confirm_popup = Rect::new(
         size.width.div(4),
@@ -672,7 +792,7 @@ mod tests {
             collections_pane: Rect::new(1, 6, 79, 17),
             hint_pane: Rect::new(1, 23, 79, 1),
             title_pane: Rect::new(1, 1, 79, 5),
-            help_popup: Rect::new(14, 5, 50, 14),
+            help_popup: Rect::new(14, 5, 50, 15),
             confirm_popup: Rect::new(19, 8, 39, 8),
             form_popup: Rect::new(19, 5, 39, 14),
             error_popup: Rect::new(19, 2, 39, 20),
@@ -736,6 +856,27 @@ mod tests {
         assert_eq!(dashboard.list_state.selected(), None);
     }
 
+    #[test]
+    fn test_declining_starter_collection() {
+        let size = Rect::new(0, 0, 80, 24);
+        let colors = hac_colors::Colors::default();
+        let mut dashboard = CollectionDashboard::new(size, &colors, vec![], false).unwrap();
+
+        dashboard.offer_starter_collection();
+        assert_eq!(dashboard.pane_focus, PaneFocus::Starter);
+
+        feed_keys(
+            &mut dashboard,
+            &[
+                KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE),
+                KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE),
+            ],
+        );
+
+        assert_eq!(dashboard.pane_focus, PaneFocus::List);
+        assert!(dashboard.collections.is_empty());
+    }
+
     #[test]
     fn test_filtering_list() {
         let size = Rect::new(0, 0, 80, 24);
@@ -1004,7 +1145,7 @@ mod tests {
             collections_pane: Rect::new(1, 6, 79, 17),
             hint_pane: Rect::new(1, 23, 79, 1),
             title_pane: Rect::new(1, 1, 79, 5),
-            help_popup: Rect::new(14, 5, 50, 14),
+            help_popup: Rect::new(14, 5, 50, 15),
             confirm_popup: Rect::new(19, 8, 39, 8),
             form_popup: Rect::new(19, 5, 39, 14),
             error_popup: Rect::new(19, 2, 39, 20),
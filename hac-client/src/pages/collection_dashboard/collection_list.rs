@@ -187,7 +187,7 @@ mod tests {
                 description: None,
             },
             path: "any_path".into(),
-            requests: None,
+            ..Default::default()
         }
     }
 
@@ -227,7 +227,7 @@ mod tests {
                 description: None,
             },
             path: "any_path".into(),
-            requests: None,
+            ..Default::default()
         }];
         let state = CollectionListState::new(collections.clone());
 
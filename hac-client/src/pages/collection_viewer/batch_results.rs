@@ -0,0 +1,272 @@
+use hac_core::collection::types::Request;
+use hac_core::net::request_manager::{RequestContext, Response};
+
+use crate::pages::collection_viewer::sidebar::colored_method;
+use crate::pages::overlay::make_overlay;
+use crate::pages::{Eventful, Renderable};
+
+use std::ops::{Add, Div, Mul};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Rect;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph};
+use ratatui::Frame;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchResultsEvent {
+    /// user closed the results, requests still running keep going
+    Close,
+}
+
+#[derive(Debug)]
+enum BatchStatus {
+    Pending,
+    Done {
+        status: Option<u16>,
+        duration: Duration,
+        cause: Option<String>,
+    },
+}
+
+#[derive(Debug)]
+struct BatchEntry {
+    request: Arc<RwLock<Request>>,
+    /// `None` until the request is dispatched
+    response_rx: Option<UnboundedReceiver<Response>>,
+    status: BatchStatus,
+}
+
+impl BatchEntry {
+    /// requests fail when they error or respond with an unexpected status
+    fn has_failed(&self) -> bool {
+        match self.status {
+            BatchStatus::Pending => false,
+            BatchStatus::Done {
+                status: Some(status),
+                ..
+            } => !self.request.read().unwrap().is_success(status),
+            BatchStatus::Done { .. } => true,
+        }
+    }
+}
+
+/// sends a list of requests at once and lists the status and time of each of
+/// them as they respond
+#[derive(Debug)]
+pub struct BatchResults<'br> {
+    colors: &'br hac_colors::Colors,
+    entries: Vec<BatchEntry>,
+    scroll: usize,
+    /// base url and variables of the environment that was active when the
+    /// batch started
+    context: RequestContext,
+    /// whether every request waits for the previous one to respond before
+    /// being sent, used when the collection defines a run order
+    sequential: bool,
+}
+
+impl<'br> BatchResults<'br> {
+    /// dispatches every request through the net layer, which respects the
+    /// configured amount of concurrent requests. `sequential` batches only
+    /// send a request after the previous one responded
+    pub fn send(
+        colors: &'br hac_colors::Colors,
+        requests: Vec<Arc<RwLock<Request>>>,
+        context: RequestContext,
+        sequential: bool,
+    ) -> Self {
+        let entries = requests
+            .into_iter()
+            .map(|request| BatchEntry {
+                request,
+                response_rx: None,
+                status: BatchStatus::Pending,
+            })
+            .collect();
+
+        let mut batch = BatchResults {
+            colors,
+            entries,
+            scroll: 0,
+            context,
+            sequential,
+        };
+        match sequential {
+            true => batch.dispatch(0),
+            false => (0..batch.entries.len()).for_each(|idx| batch.dispatch(idx)),
+        }
+
+        batch
+    }
+
+    fn dispatch(&mut self, idx: usize) {
+        let Some(entry) = self.entries.get_mut(idx) else {
+            return;
+        };
+        let (response_tx, response_rx) = unbounded_channel();
+        hac_core::net::handle_request(&entry.request, &self.context, response_tx);
+        entry.response_rx = Some(response_rx);
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.entries
+            .iter()
+            .all(|entry| matches!(entry.status, BatchStatus::Done { .. }))
+    }
+
+    /// collects the responses that arrived since the last call, returning the
+    /// id and status of every request that finished
+    pub fn drain(&mut self) -> Vec<(String, Option<u16>)> {
+        let mut finished = vec![];
+
+        for entry in self.entries.iter_mut() {
+            let Some(response_rx) = entry.response_rx.as_mut() else {
+                continue;
+            };
+            while let Ok(response) = response_rx.try_recv() {
+                // downloads report progress until the last chunk is written
+                if response.is_downloading() {
+                    continue;
+                }
+
+                let status = response.status.map(|status| status.as_u16());
+                entry.status = BatchStatus::Done {
+                    status,
+                    duration: response.duration,
+                    cause: response.cause,
+                };
+                finished.push((entry.request.read().unwrap().id.clone(), status));
+            }
+        }
+
+        if self.sequential && !finished.is_empty() {
+            if let Some(idx) = self
+                .entries
+                .iter()
+                .position(|entry| entry.response_rx.is_none())
+            {
+                self.dispatch(idx);
+            }
+        }
+
+        finished
+    }
+
+    fn build_entry(&self, entry: &BatchEntry) -> Line<'static> {
+        let request = entry.request.read().unwrap();
+        let mut spans = vec![
+            colored_method(request.method.clone(), self.colors),
+            Span::from(format!(" {} ", request.name)).fg(self.colors.normal.white),
+        ];
+
+        match &entry.status {
+            BatchStatus::Pending => spans.push("pending".fg(self.colors.bright.black)),
+            BatchStatus::Done {
+                status: Some(status),
+                duration,
+                ..
+            } => {
+                let color = match request.is_success(*status) {
+                    true => self.colors.normal.green,
+                    false => self.colors.normal.red,
+                };
+                spans.push(status.to_string().fg(color).bold());
+                spans.push(format!(" {}ms", duration.as_millis()).fg(self.colors.bright.black));
+            }
+            BatchStatus::Done { cause, .. } => spans.push(
+                format!("error: {}", cause.as_deref().unwrap_or("unknown"))
+                    .fg(self.colors.normal.red),
+            ),
+        }
+
+        Line::from(spans)
+    }
+}
+
+impl Renderable for BatchResults<'_> {
+    fn draw(&mut self, frame: &mut Frame, _: Rect) -> anyhow::Result<()> {
+        make_overlay(self.colors, self.colors.normal.black, 0.1, frame);
+
+        let size = frame.size();
+        let size = Rect::new(
+            size.width.div(6),
+            size.height.div(8),
+            size.width.div(6).mul(4),
+            size.height.div(4).mul(3),
+        );
+
+        let done = self
+            .entries
+            .iter()
+            .filter(|entry| matches!(entry.status, BatchStatus::Done { .. }))
+            .count();
+        let failed = self
+            .entries
+            .iter()
+            .filter(|entry| entry.has_failed())
+            .count();
+
+        let mut lines = self
+            .entries
+            .iter()
+            .map(|entry| self.build_entry(entry))
+            .collect::<Vec<_>>();
+        lines.push(Line::from(""));
+        lines.push(Line::from(
+            "(j/k) scroll  (esc) close".fg(self.colors.bright.black),
+        ));
+
+        // 2 lines for the borders
+        let visible_lines = usize::from(size.height.saturating_sub(2));
+        self.scroll = self.scroll.min(lines.len().saturating_sub(visible_lines));
+
+        let block = Block::default()
+            .title(format!(
+                "Sent requests ({done}/{}, {failed} failed)",
+                self.entries.len()
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.colors.normal.red))
+            .bg(self.colors.primary.background)
+            .padding(Padding::horizontal(1));
+
+        frame.render_widget(Clear, size);
+        frame.render_widget(
+            Paragraph::new(
+                lines
+                    .into_iter()
+                    .skip(self.scroll)
+                    .take(visible_lines)
+                    .collect::<Vec<_>>(),
+            )
+            .block(block),
+            size,
+        );
+
+        Ok(())
+    }
+}
+
+impl Eventful for BatchResults<'_> {
+    type Result = BatchResultsEvent;
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> anyhow::Result<Option<Self::Result>> {
+        if let (KeyCode::Char('c'), KeyModifiers::CONTROL) = (key_event.code, key_event.modifiers) {
+            return Ok(Some(BatchResultsEvent::Close));
+        }
+
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q') => return Ok(Some(BatchResultsEvent::Close)),
+            KeyCode::Char('j') | KeyCode::Down => self.scroll = self.scroll.add(1),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            _ => {}
+        }
+
+        Ok(None)
+    }
+}
@@ -0,0 +1,137 @@
+use hac_core::collection::Collection;
+
+use crate::pages::overlay::make_overlay;
+use crate::pages::{Eventful, Renderable};
+
+use std::ops::{Add, Div, Sub};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Rect;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph};
+use ratatui::Frame;
+
+#[derive(Debug)]
+pub enum CollectionPickerEvent {
+    /// user picked a collection, holds the picked collection
+    Confirm(Box<Collection>),
+    /// user closed the picker without choosing anything
+    Cancel,
+}
+
+/// list of collections to choose from, used to pick which collection gets
+/// merged into the one being viewed
+#[derive(Debug)]
+pub struct CollectionPicker<'cp> {
+    colors: &'cp hac_colors::Colors,
+    collections: Vec<Collection>,
+    selected: usize,
+}
+
+impl<'cp> CollectionPicker<'cp> {
+    pub fn new(colors: &'cp hac_colors::Colors, collections: Vec<Collection>) -> Self {
+        CollectionPicker {
+            colors,
+            collections,
+            selected: 0,
+        }
+    }
+
+    fn build_entry(&self, collection: &Collection, selected: bool, width: usize) -> Line<'_> {
+        let name = &collection.info.name;
+        let padding = width.saturating_sub(name.chars().count().add(1));
+
+        let bg = if selected {
+            self.colors.primary.hover
+        } else {
+            self.colors.primary.background
+        };
+
+        Line::from(vec![
+            Span::from(format!(" {name}"))
+                .fg(self.colors.normal.white)
+                .bg(bg),
+            Span::from(" ".repeat(padding)).bg(bg),
+        ])
+    }
+}
+
+impl Renderable for CollectionPicker<'_> {
+    fn draw(&mut self, frame: &mut Frame, _: Rect) -> anyhow::Result<()> {
+        make_overlay(self.colors, self.colors.normal.black, 0.1, frame);
+
+        let size = frame.size();
+        let height = (self.collections.len() as u16)
+            .add(4)
+            .min(size.height.sub(size.height.div(6)));
+        let size = Rect::new(
+            size.width.div(4),
+            size.height.div(6),
+            size.width.div(2),
+            height,
+        );
+
+        let block = Block::default()
+            .title("Merge collection")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.colors.normal.red))
+            .bg(self.colors.primary.background)
+            .padding(Padding::horizontal(1));
+        let inner = block.inner(size);
+
+        let mut lines = vec![
+            Line::from("pick the collection to merge into this one".fg(self.colors.bright.black)),
+            Line::from(""),
+        ];
+
+        let amount_on_view = inner.height.sub(2.min(inner.height)) as usize;
+        let scroll = self
+            .selected
+            .saturating_sub(amount_on_view.saturating_sub(1));
+        self.collections
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(amount_on_view)
+            .for_each(|(idx, collection)| {
+                lines.push(self.build_entry(collection, idx.eq(&self.selected), inner.width.into()))
+            });
+
+        frame.render_widget(Clear, size);
+        frame.render_widget(Paragraph::new(lines).block(block), size);
+
+        Ok(())
+    }
+}
+
+impl Eventful for CollectionPicker<'_> {
+    type Result = CollectionPickerEvent;
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> anyhow::Result<Option<Self::Result>> {
+        if let (KeyCode::Char('c'), KeyModifiers::CONTROL) = (key_event.code, key_event.modifiers) {
+            return Ok(Some(CollectionPickerEvent::Cancel));
+        }
+
+        match key_event.code {
+            KeyCode::Esc => return Ok(Some(CollectionPickerEvent::Cancel)),
+            KeyCode::Enter => {
+                if self.selected < self.collections.len() {
+                    let collection = self.collections.swap_remove(self.selected);
+                    return Ok(Some(CollectionPickerEvent::Confirm(Box::new(collection))));
+                }
+            }
+            KeyCode::Down | KeyCode::Tab | KeyCode::Char('j') => {
+                if !self.collections.is_empty() {
+                    self.selected = self.selected.add(1).min(self.collections.len().sub(1));
+                }
+            }
+            KeyCode::Up | KeyCode::BackTab | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1)
+            }
+            _ => {}
+        }
+
+        Ok(None)
+    }
+}
@@ -1,11 +1,14 @@
-use hac_core::collection::types::{Request, RequestKind};
+use hac_core::collection::types::{
+    ClientSettings, Request, RequestDefaults, RequestKind, TrailingSlash,
+};
 use hac_core::collection::Collection;
 
 use crate::pages::collection_viewer::collection_viewer::CollectionViewerOverlay;
 use crate::pages::collection_viewer::collection_viewer::PaneFocus;
+use crate::pages::collection_viewer::request_editor::ReqEditorTabs;
 
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::rc::Rc;
 use std::sync::{Arc, RwLock};
 
@@ -19,8 +22,28 @@ pub struct CollectionState {
     focused_pane: PaneFocus,
     has_pending_request: bool,
     overlay_stack: Vec<CollectionViewerOverlay>,
+    /// last tab viewed on the request editor for each request, keyed by the
+    /// request id
+    editor_tabs: HashMap<String, ReqEditorTabs>,
+    /// status codes of the latest responses of each request during this
+    /// session, keyed by the request id. `None` represents a request that
+    /// failed without a response
+    status_history: Rc<RefCell<StatusHistory>>,
+    /// name of the environment of the collection currently in use
+    active_environment: Option<String>,
+    /// fingerprint of what each request sent last time, keyed by the request
+    /// id, used to tell whether it was edited since
+    sent_fingerprints: HashMap<String, String>,
+    /// requests edited since they were last sent, so their responses are
+    /// outdated
+    edited_requests: HashSet<String>,
 }
 
+pub type StatusHistory = HashMap<String, VecDeque<Option<u16>>>;
+
+/// how many status codes are kept for each request on the status history
+pub const STATUS_HISTORY_LEN: usize = 8;
+
 #[derive(Debug, Default)]
 pub struct CollectionStore {
     state: Option<Rc<RefCell<CollectionState>>>,
@@ -37,6 +60,22 @@ pub enum CollectionStoreAction {
     SetFocusedPane(PaneFocus),
     SetSelectedPane(Option<PaneFocus>),
     SetPendingRequest(bool),
+    SetRequestEditorTab(String, ReqEditorTabs),
+    /// records how long the request with the given id took to respond, these
+    /// are stored on the request itself so they are persisted with the
+    /// collection
+    RecordResponseTime(String, std::time::Duration),
+    /// replaces the template new requests of the collection are created from
+    SetRequestDefaults(Option<RequestDefaults>),
+    SetTrailingSlash(TrailingSlash),
+    /// appends the status of a response to the history of the request with
+    /// the given id, dropping the oldest entry when the history is full
+    RecordStatus(String, Option<u16>),
+    SetActiveEnvironment(Option<String>),
+    /// stores the fingerprint of what the request with the given id sent,
+    /// clearing its edited mark
+    RecordSentRequest(String, String),
+    SetRequestEdited(String, bool),
 }
 
 impl CollectionStore {
@@ -65,6 +104,11 @@ impl CollectionStore {
             selected_pane: None,
             has_pending_request: false,
             overlay_stack: vec![],
+            editor_tabs: HashMap::default(),
+            status_history: Rc::new(RefCell::new(HashMap::default())),
+            active_environment: None,
+            sent_fingerprints: HashMap::default(),
+            edited_requests: HashSet::default(),
         };
 
         self.state = Some(Rc::new(RefCell::new(state)));
@@ -107,16 +151,114 @@ impl CollectionStore {
                 CollectionStoreAction::SetPendingRequest(is_pending) => {
                     state.borrow_mut().has_pending_request = is_pending;
                 }
+                CollectionStoreAction::SetRequestEditorTab(request_id, tab) => {
+                    state.borrow_mut().editor_tabs.insert(request_id, tab);
+                }
+                CollectionStoreAction::SetRequestDefaults(defaults) => {
+                    state.borrow().collection.borrow_mut().request_defaults = defaults;
+                }
+                CollectionStoreAction::SetTrailingSlash(behavior) => {
+                    state.borrow().collection.borrow_mut().trailing_slash = behavior;
+                }
+                CollectionStoreAction::RecordSentRequest(request_id, fingerprint) => {
+                    let mut state = state.borrow_mut();
+                    state.edited_requests.remove(&request_id);
+                    state.sent_fingerprints.insert(request_id, fingerprint);
+                }
+                CollectionStoreAction::SetRequestEdited(request_id, true) => {
+                    state.borrow_mut().edited_requests.insert(request_id);
+                }
+                CollectionStoreAction::SetRequestEdited(request_id, false) => {
+                    state.borrow_mut().edited_requests.remove(&request_id);
+                }
+                CollectionStoreAction::SetActiveEnvironment(environment) => {
+                    state.borrow_mut().active_environment = environment
+                }
+                CollectionStoreAction::RecordStatus(request_id, status) => {
+                    let state = state.borrow();
+                    let mut history = state.status_history.borrow_mut();
+                    let statuses = history.entry(request_id).or_default();
+                    if statuses.len().ge(&STATUS_HISTORY_LEN) {
+                        statuses.pop_front();
+                    }
+                    statuses.push_back(status);
+                }
+                CollectionStoreAction::RecordResponseTime(request_id, duration) => {
+                    if let Some(request) = self.find_request(&request_id) {
+                        request
+                            .write()
+                            .unwrap()
+                            .timings
+                            .get_or_insert_with(Default::default)
+                            .record(duration);
+                    }
+                }
             }
         }
     }
 
+    pub fn get_request_defaults(&self) -> Option<RequestDefaults> {
+        self.state
+            .as_ref()
+            .and_then(|state| state.borrow().collection.borrow().request_defaults.clone())
+    }
+
+    pub fn get_trailing_slash(&self) -> TrailingSlash {
+        self.state
+            .as_ref()
+            .map(|state| state.borrow().collection.borrow().trailing_slash)
+            .unwrap_or_default()
+    }
+
+    pub fn get_client_settings(&self) -> ClientSettings {
+        self.state
+            .as_ref()
+            .map(|state| state.borrow().collection.borrow().client.clone())
+            .unwrap_or_default()
+    }
+
+    /// base url of the active environment, relative uris are sent against it
+    pub fn get_base_url(&self) -> Option<String> {
+        self.state.as_ref().and_then(|state| {
+            let state = state.borrow();
+            let collection = state.collection.borrow();
+            collection
+                .base_url(state.active_environment.as_deref())
+                .map(String::from)
+        })
+    }
+
+    pub fn get_sent_fingerprint(&self, request_id: &str) -> Option<String> {
+        self.state
+            .as_ref()
+            .and_then(|state| state.borrow().sent_fingerprints.get(request_id).cloned())
+    }
+
+    /// whether the request was edited after it was last sent
+    pub fn is_request_edited(&self, request_id: &str) -> bool {
+        self.state
+            .as_ref()
+            .is_some_and(|state| state.borrow().edited_requests.contains(request_id))
+    }
+
+    pub fn get_active_environment(&self) -> Option<String> {
+        self.state
+            .as_ref()
+            .and_then(|state| state.borrow().active_environment.clone())
+    }
+
     pub fn get_selected_request(&self) -> Option<Arc<RwLock<Request>>> {
         self.state
             .as_ref()
             .and_then(|state| state.borrow().selected_request.clone())
     }
 
+    pub fn get_request_editor_tab(&self, request_id: &str) -> Option<ReqEditorTabs> {
+        self.state
+            .as_ref()
+            .and_then(|state| state.borrow().editor_tabs.get(request_id).cloned())
+    }
+
     pub fn get_focused_pane(&self) -> PaneFocus {
         self.state
             .as_ref()
@@ -149,6 +291,12 @@ impl CollectionStore {
             .map(|state| state.borrow().dirs_expanded.clone())
     }
 
+    pub fn get_status_history(&self) -> Option<Rc<RefCell<StatusHistory>>> {
+        self.state
+            .as_ref()
+            .map(|state| state.borrow().status_history.clone())
+    }
+
     pub fn push_overlay(&mut self, overlay: CollectionViewerOverlay) {
         if let Some(state) = self.state.as_mut() {
             state.borrow_mut().overlay_stack.push(overlay)
@@ -249,12 +397,118 @@ impl CollectionStore {
         };
     }
 
-    pub fn find_hovered_request(&mut self) -> RequestKind {
-        get_request_by_id(
-            &self.get_requests().as_ref().unwrap().read().unwrap(),
-            &self.get_dirs_expanded().unwrap().borrow(),
-            self.get_hovered_request().as_ref().unwrap(),
-        )
+    /// the hovered item on the tree, `None` when nothing is hovered, which is
+    /// always the case on collections without requests
+    pub fn find_hovered_request(&mut self) -> Option<RequestKind> {
+        let requests = self.get_requests()?;
+        let hovered = self.get_hovered_request()?;
+        let dirs_expanded = self.get_dirs_expanded()?;
+        let request =
+            get_request_by_id(&requests.read().unwrap(), &dirs_expanded.borrow(), &hovered);
+        request
+    }
+
+    /// every enabled request currently shown on the sidebar, in the order they
+    /// appear, requests inside collapsed directories are left out
+    pub fn visible_requests(&mut self) -> Vec<Arc<RwLock<Request>>> {
+        fn walk(
+            tree: &[RequestKind],
+            dirs_expanded: &HashMap<String, bool>,
+            visible: &mut Vec<Arc<RwLock<Request>>>,
+        ) {
+            for node in tree {
+                match node {
+                    RequestKind::Single(req) if req.read().unwrap().enabled => {
+                        visible.push(req.clone())
+                    }
+                    RequestKind::Single(_) => {}
+                    RequestKind::Nested(dir) => {
+                        if dirs_expanded.get(&dir.id).copied().unwrap_or(false) {
+                            walk(&dir.requests.read().unwrap(), dirs_expanded, visible);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut visible = vec![];
+        if let (Some(requests), Some(dirs_expanded)) =
+            (self.get_requests(), self.get_dirs_expanded())
+        {
+            walk(
+                &requests.read().unwrap(),
+                &dirs_expanded.borrow(),
+                &mut visible,
+            );
+        }
+        visible
+    }
+
+    /// expands every directory above the item with the given id, so it is
+    /// visible on the tree. Returns whether the item is on the collection
+    pub fn expand_ancestors(&mut self, item_id: &str) -> bool {
+        let Some(requests) = self.get_requests() else {
+            return false;
+        };
+        let Some(parents) = find_parent_dirs(&requests.read().unwrap(), item_id) else {
+            return false;
+        };
+
+        if let Some(dirs_expanded) = self.get_dirs_expanded() {
+            let mut dirs_expanded = dirs_expanded.borrow_mut();
+            parents.into_iter().for_each(|dir_id| {
+                dirs_expanded.insert(dir_id, true);
+            });
+        }
+        true
+    }
+
+    /// hovers the item with the given id. When `expand` is set every
+    /// directory above it is expanded so it is visible on the tree, otherwise
+    /// the outermost collapsed directory holding it is hovered instead. Ids
+    /// that are no longer on the collection are ignored, keeping the current
+    /// hover
+    pub fn restore_hovered_request(&mut self, item_id: String, expand: bool) {
+        if expand {
+            if self.expand_ancestors(&item_id) {
+                self.dispatch(CollectionStoreAction::SetHoveredRequest(Some(item_id)));
+            }
+            return;
+        }
+
+        let (Some(requests), Some(dirs_expanded)) = (self.get_requests(), self.get_dirs_expanded())
+        else {
+            return;
+        };
+        let Some(parents) = find_parent_dirs(&requests.read().unwrap(), &item_id) else {
+            return;
+        };
+        let collapsed = parents.into_iter().find(|dir_id| {
+            !dirs_expanded
+                .borrow()
+                .get(dir_id)
+                .copied()
+                .unwrap_or_default()
+        });
+
+        let hovered = collapsed.unwrap_or(item_id);
+        self.dispatch(CollectionStoreAction::SetHoveredRequest(Some(hovered)));
+    }
+
+    /// the request with the given id, wherever it is on the tree, even
+    /// inside collapsed directories
+    pub fn find_request(&self, request_id: &str) -> Option<Arc<RwLock<Request>>> {
+        fn find(tree: &[RequestKind], id: &str) -> Option<Arc<RwLock<Request>>> {
+            tree.iter().find_map(|node| match node {
+                RequestKind::Single(req) if req.read().unwrap().id.eq(id) => Some(req.clone()),
+                RequestKind::Single(_) => None,
+                RequestKind::Nested(dir) => find(&dir.requests.read().unwrap(), id),
+            })
+        }
+
+        let requests = self.get_requests()?;
+        let request = find(&requests.read().unwrap(), request_id);
+        request
     }
 
     pub fn remove_item(&mut self, item_id: String) {
@@ -347,7 +601,7 @@ fn get_request_by_id(
     tree: &[RequestKind],
     dirs_expanded: &HashMap<String, bool>,
     id: &str,
-) -> RequestKind {
+) -> Option<RequestKind> {
     let mut found = false;
     let mut path = vec![];
 
@@ -365,7 +619,25 @@ fn get_request_by_id(
     }
 
     path.pop()
-        .expect("attempting to find an unexisting request")
+}
+
+/// ids of the directories leading to the item with the given id, from the
+/// outermost to the innermost, or `None` when the item is not on the tree
+fn find_parent_dirs(tree: &[RequestKind], id: &str) -> Option<Vec<String>> {
+    for node in tree {
+        if node.get_id().eq(id) {
+            return Some(vec![]);
+        }
+
+        if let RequestKind::Nested(dir) = node {
+            if let Some(mut parents) = find_parent_dirs(&dir.requests.read().unwrap(), id) {
+                parents.insert(0, dir.id.clone());
+                return Some(parents);
+            }
+        }
+    }
+
+    None
 }
 
 fn find_next_entry(
@@ -395,28 +667,20 @@ mod tests {
     fn create_root_one() -> RequestKind {
         RequestKind::Single(Arc::new(RwLock::new(Request {
             id: "root".to_string(),
-            method: RequestMethod::Get,
             name: "Root1".to_string(),
-            auth_method: None,
-            parent: None,
-            headers: None,
             uri: "/root1".to_string(),
-            body_type: None,
-            body: None,
+            ..Default::default()
         })))
     }
 
     fn create_child_one() -> RequestKind {
         RequestKind::Single(Arc::new(RwLock::new(Request {
             id: "child_one".to_string(),
-            auth_method: None,
             parent: Some(String::from("dir")),
             method: RequestMethod::Post,
             name: "Child1".to_string(),
             uri: "/nested1/child1".to_string(),
-            headers: None,
-            body_type: None,
-            body: None,
+            ..Default::default()
         })))
     }
 
@@ -424,13 +688,10 @@ mod tests {
         RequestKind::Single(Arc::new(RwLock::new(Request {
             id: "child_two".to_string(),
             method: RequestMethod::Put,
-            auth_method: None,
             name: "Child2".to_string(),
-            headers: None,
             parent: Some(String::from("dir")),
             uri: "/nested1/child2".to_string(),
-            body_type: None,
-            body: None,
+            ..Default::default()
         })))
     }
 
@@ -439,12 +700,8 @@ mod tests {
             id: "not_used".to_string(),
             method: RequestMethod::Put,
             name: "NotUsed".to_string(),
-            parent: None,
-            auth_method: None,
-            headers: None,
             uri: "/not/used".to_string(),
-            body_type: None,
-            body: None,
+            ..Default::default()
         })))
     }
 
@@ -464,13 +721,9 @@ mod tests {
         RequestKind::Single(Arc::new(RwLock::new(Request {
             id: "root_two".to_string(),
             method: RequestMethod::Delete,
-            auth_method: None,
-            headers: None,
-            parent: None,
             name: "Root2".to_string(),
             uri: "/root2".to_string(),
-            body_type: None,
-            body: None,
+            ..Default::default()
         })))
     }
 
@@ -556,4 +809,271 @@ mod tests {
 
         assert!(next.is_none());
     }
+
+    #[test]
+    fn test_remembers_request_editor_tab() {
+        let mut store = CollectionStore::default();
+        store.set_state(Collection {
+            info: hac_core::collection::types::Info {
+                name: String::from("any_name"),
+                description: None,
+            },
+            requests: Some(Arc::new(RwLock::new(create_test_tree()))),
+            path: "any_path".into(),
+            ..Default::default()
+        });
+
+        assert!(store.get_request_editor_tab("root").is_none());
+
+        store.dispatch(CollectionStoreAction::SetRequestEditorTab(
+            String::from("root"),
+            ReqEditorTabs::Auth,
+        ));
+
+        assert_eq!(
+            store.get_request_editor_tab("root"),
+            Some(ReqEditorTabs::Auth)
+        );
+        assert!(store.get_request_editor_tab("child_one").is_none());
+    }
+
+    #[test]
+    fn test_records_response_times_on_the_request_that_responded() {
+        let mut store = CollectionStore::default();
+        store.set_state(Collection {
+            info: hac_core::collection::types::Info {
+                name: String::from("any_name"),
+                description: None,
+            },
+            requests: Some(Arc::new(RwLock::new(create_test_tree()))),
+            path: "any_path".into(),
+            ..Default::default()
+        });
+
+        let selected = store.get_selected_request().unwrap();
+        let selected_id = selected.read().unwrap().id.clone();
+
+        store.dispatch(CollectionStoreAction::RecordResponseTime(
+            selected_id.clone(),
+            std::time::Duration::from_millis(300),
+        ));
+        store.dispatch(CollectionStoreAction::RecordResponseTime(
+            selected_id,
+            std::time::Duration::from_millis(500),
+        ));
+        store.dispatch(CollectionStoreAction::RecordResponseTime(
+            String::from("child_one"),
+            std::time::Duration::from_millis(100),
+        ));
+
+        let timings = selected.read().unwrap().timings.clone().unwrap();
+        let other = store.find_request("child_one").unwrap();
+        let other = other.read().unwrap().timings.clone().unwrap();
+        assert_eq!(timings.runs, 2);
+        assert_eq!(timings.average_ms(), 400);
+        assert_eq!(other.runs, 1);
+    }
+
+    #[test]
+    fn test_sets_and_clears_request_defaults() {
+        let mut store = CollectionStore::default();
+        store.set_state(Collection {
+            info: hac_core::collection::types::Info {
+                name: String::from("any_name"),
+                description: None,
+            },
+            requests: Some(Arc::new(RwLock::new(create_test_tree()))),
+            path: "any_path".into(),
+            ..Default::default()
+        });
+
+        let defaults = RequestDefaults {
+            headers: Some(vec![hac_core::collection::types::HeaderMap {
+                pair: (String::from("Accept"), String::from("application/json")),
+                enabled: true,
+            }]),
+            auth_method: Some(hac_core::collection::types::AuthMethod::Bearer),
+        };
+
+        store.dispatch(CollectionStoreAction::SetRequestDefaults(Some(
+            defaults.clone(),
+        )));
+        assert_eq!(store.get_request_defaults(), Some(defaults));
+
+        store.dispatch(CollectionStoreAction::SetRequestDefaults(None));
+        assert!(store.get_request_defaults().is_none());
+    }
+
+    #[test]
+    fn test_keeps_only_latest_statuses() {
+        let mut store = CollectionStore::default();
+        store.set_state(Collection {
+            info: hac_core::collection::types::Info {
+                name: String::from("any_name"),
+                description: None,
+            },
+            requests: Some(Arc::new(RwLock::new(create_test_tree()))),
+            path: "any_path".into(),
+            ..Default::default()
+        });
+
+        store.dispatch(CollectionStoreAction::RecordStatus(
+            String::from("root"),
+            None,
+        ));
+        for _ in 0..STATUS_HISTORY_LEN {
+            store.dispatch(CollectionStoreAction::RecordStatus(
+                String::from("root"),
+                Some(200),
+            ));
+        }
+        store.dispatch(CollectionStoreAction::RecordStatus(
+            String::from("root"),
+            Some(500),
+        ));
+
+        let history = store.get_status_history().unwrap();
+        let history = history.borrow();
+        let statuses = history.get("root").unwrap();
+        assert_eq!(statuses.len(), STATUS_HISTORY_LEN);
+        assert!(!statuses.contains(&None));
+        assert_eq!(statuses.back(), Some(&Some(500)));
+        assert!(!history.contains_key("child_one"));
+    }
+
+    #[test]
+    fn test_visible_requests() {
+        let mut store = CollectionStore::default();
+        store.set_state(Collection {
+            info: hac_core::collection::types::Info {
+                name: String::from("any_name"),
+                description: None,
+            },
+            requests: Some(Arc::new(RwLock::new(create_test_tree()))),
+            path: "any_path".into(),
+            ..Default::default()
+        });
+
+        let ids = |store: &mut CollectionStore| {
+            store
+                .visible_requests()
+                .iter()
+                .map(|req| req.read().unwrap().id.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&mut store), vec!["root", "root_two"]);
+
+        store.dispatch(CollectionStoreAction::ToggleDirectory(String::from("dir")));
+        if let Some(RequestKind::Single(req)) = store.get_requests().unwrap().read().unwrap().last()
+        {
+            req.write().unwrap().enabled = false;
+        }
+        assert_eq!(ids(&mut store), vec!["root", "child_one", "child_two"]);
+    }
+
+    #[test]
+    fn test_restore_hovered_request() {
+        let mut store = CollectionStore::default();
+        store.set_state(Collection {
+            info: hac_core::collection::types::Info {
+                name: String::from("any_name"),
+                description: None,
+            },
+            requests: Some(Arc::new(RwLock::new(create_test_tree()))),
+            path: "any_path".into(),
+            ..Default::default()
+        });
+
+        store.restore_hovered_request(String::from("missing"), true);
+        assert_eq!(store.get_hovered_request(), Some(String::from("root")));
+
+        store.restore_hovered_request(String::from("child_two"), true);
+        assert_eq!(store.get_hovered_request(), Some(String::from("child_two")));
+        assert_eq!(
+            store.get_dirs_expanded().unwrap().borrow().get("dir"),
+            Some(&true)
+        );
+        assert!(store
+            .find_hovered_request()
+            .is_some_and(|req| req.get_id().eq("child_two")));
+    }
+
+    #[test]
+    fn test_restore_hovered_request_without_expanding() {
+        let mut store = CollectionStore::default();
+        store.set_state(Collection {
+            info: hac_core::collection::types::Info {
+                name: String::from("any_name"),
+                description: None,
+            },
+            requests: Some(Arc::new(RwLock::new(create_test_tree()))),
+            path: "any_path".into(),
+            ..Default::default()
+        });
+
+        store.restore_hovered_request(String::from("child_two"), false);
+        assert_eq!(store.get_hovered_request(), Some(String::from("dir")));
+        assert_ne!(
+            store.get_dirs_expanded().unwrap().borrow().get("dir"),
+            Some(&true)
+        );
+        assert!(store.find_request("child_two").is_some_and(|req| req
+            .read()
+            .unwrap()
+            .id
+            .eq("child_two")));
+    }
+
+    #[test]
+    fn test_empty_collection_has_nothing_to_hover() {
+        let mut store = CollectionStore::default();
+        store.set_state(Collection {
+            info: hac_core::collection::types::Info {
+                name: String::from("any_name"),
+                description: None,
+            },
+            path: "any_path".into(),
+            ..Default::default()
+        });
+
+        store.dispatch(CollectionStoreAction::HoverNext);
+        store.dispatch(CollectionStoreAction::HoverPrev);
+
+        assert!(store.get_hovered_request().is_none());
+        assert!(store.get_selected_request().is_none());
+        assert!(store.find_hovered_request().is_none());
+    }
+
+    #[test]
+    fn test_sending_clears_the_edited_mark() {
+        let mut store = CollectionStore::default();
+        store.set_state(Collection {
+            info: hac_core::collection::types::Info {
+                name: String::from("any_name"),
+                description: None,
+            },
+            path: "any_path".into(),
+            ..Default::default()
+        });
+
+        store.dispatch(CollectionStoreAction::RecordSentRequest(
+            String::from("root"),
+            String::from("first"),
+        ));
+        store.dispatch(CollectionStoreAction::SetRequestEdited(
+            String::from("root"),
+            true,
+        ));
+        assert!(store.is_request_edited("root"));
+
+        store.dispatch(CollectionStoreAction::RecordSentRequest(
+            String::from("root"),
+            String::from("second"),
+        ));
+        assert!(!store.is_request_edited("root"));
+        assert_eq!(
+            store.get_sent_fingerprint("root"),
+            Some(String::from("second"))
+        );
+    }
 }
@@ -1,23 +1,52 @@
+use hac_config::{
+    Compression, CtrlCBehavior, MissingCollectionBehavior, StatusBarItem, UnsavedEditsBehavior,
+};
 use hac_core::collection::types::*;
 use hac_core::command::Command;
-use hac_core::net::request_manager::Response;
+use hac_core::net::request_client::redirect_request;
+use hac_core::net::request_manager::{RequestContext, Response};
+use hac_core::net::request_strategies::raw_strategy::split_head;
+use hac_core::net::substitute_variables;
+use hac_core::net::uri::normalize_trailing_slash;
+use hac_core::text_object::{TextObject, Write};
 
+use crate::pages::collection_viewer::batch_results::{BatchResults, BatchResultsEvent};
+use crate::pages::collection_viewer::collection_picker::{CollectionPicker, CollectionPickerEvent};
 use crate::pages::collection_viewer::collection_store::{CollectionStore, CollectionStoreAction};
+use crate::pages::collection_viewer::command_palette::{
+    CommandPalette, CommandPaletteEvent, PaletteAction,
+};
+use crate::pages::collection_viewer::environment_picker::{
+    EnvironmentEntry, EnvironmentPicker, EnvironmentPickerEvent,
+};
+use crate::pages::collection_viewer::recent_requests::{RecentRequests, RecentRequestsEvent};
 use crate::pages::collection_viewer::request_editor::{RequestEditor, RequestEditorEvent};
-use crate::pages::collection_viewer::request_uri::{RequestUri, RequestUriEvent};
+use crate::pages::collection_viewer::request_uri::{uri_pane_height, RequestUri, RequestUriEvent};
+use crate::pages::collection_viewer::resolved_request::{ResolvedRequest, ResolvedRequestEvent};
 use crate::pages::collection_viewer::response_viewer::{ResponseViewer, ResponseViewerEvent};
+use crate::pages::collection_viewer::run_order::{RunOrder, RunOrderEvent};
 use crate::pages::collection_viewer::sidebar::{self, Sidebar, SidebarEvent};
+use crate::pages::confirm_popup::ConfirmPopup;
+use crate::pages::error_popup::{ErrorPopup, ErrorPopupState};
 use crate::pages::{Eventful, Renderable};
+use crate::recent;
+use crate::toast;
+use crate::utils::{copy_to_clipboard, ReadableByteSize};
 
 use std::cell::RefCell;
-use std::collections::HashMap;
-use std::ops::{Add, Div};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::ops::{Div, Sub};
+use std::path::PathBuf;
 use std::rc::Rc;
+use std::sync::{Arc, RwLock};
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::Stylize;
-use ratatui::widgets::{Block, Clear};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Clear, Widget};
 use ratatui::Frame;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 
@@ -29,8 +58,14 @@ pub struct ExplorerLayout {
     pub req_editor: Rect,
     pub response_preview: Rect,
     pub create_req_form: Rect,
+    pub confirm_popup: Rect,
+    pub error_popup: Rect,
 }
 
+/// amount of editors of previously selected requests kept around to be
+/// reused when going back to them
+const MAX_CACHED_EDITORS: usize = 16;
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum CollectionViewerOverlay {
     None,
@@ -41,9 +76,54 @@ pub enum CollectionViewerOverlay {
     CreateDirectory,
     HeadersHelp,
     HeadersDelete,
+    /// read only list of the headers that will be sent with the request
+    EffectiveHeaders,
     ChangeAuthMethod,
     HeadersForm(usize, bool),
     DeleteSidebarItem(String),
+    MissingCollection,
+    CommandPalette,
+    /// the body of the request about to be sent is bigger than the configured
+    /// `max_request_body_bytes`, holds the size of the body
+    LargeBodyWarning(u64),
+    CollectionSettings,
+    /// schema inferred from the body of the current response
+    ResponseSchema,
+    /// an action failed, holds the message shown to the user
+    Error(String),
+    /// picking another collection to merge into the current one
+    MergeCollection,
+    /// status and time of every request sent through "send visible requests"
+    BatchResults,
+    /// field by field diff between two requests of the collection
+    RequestDiff,
+    /// requests sent recently on any collection
+    RecentRequests,
+    /// order in which the requests run when sending the collection
+    RunOrder,
+    /// selected request with every variable resolved
+    ResolvedRequest,
+    /// picking the active environment
+    EnvironmentPicker,
+    /// the body of the selected request has unsaved edits and the user is
+    /// leaving it, holds where the user is going
+    UnsavedEdits(LeaveTarget),
+}
+
+/// where the user goes once the unsaved edits of the selected request are
+/// handled
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum LeaveTarget {
+    /// another request of the collection, holds its id
+    Request(String),
+    /// back to the list of collections
+    Back,
+    /// a request of another collection, opened by the screen manager
+    OtherCollection {
+        path: PathBuf,
+        request_id: String,
+        send: bool,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -72,20 +152,53 @@ impl PaneFocus {
             PaneFocus::Preview => PaneFocus::Editor,
         }
     }
+
+    /// jumps between the request editor and the response preview, any other pane
+    /// goes to the editor, as that is where the edit-send-read loop starts
+    fn toggle_editor_preview(&self) -> Self {
+        match self {
+            PaneFocus::Editor => PaneFocus::Preview,
+            _ => PaneFocus::Editor,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct CollectionViewer<'cv> {
     response_viewer: ResponseViewer<'cv>,
     request_editor: RequestEditor<'cv>,
+    /// editors of the requests that were selected last, keyed by the request
+    /// id and the most recently left at the back, so going back to a request
+    /// doesn't rebuild its editor from scratch
+    request_editors: VecDeque<(String, RequestEditor<'cv>)>,
     request_uri: RequestUri<'cv>,
     sidebar: Sidebar<'cv>,
+    command_palette: CommandPalette<'cv>,
+    /// only present while picking a collection to merge
+    collection_picker: Option<CollectionPicker<'cv>>,
+    environment_picker: Option<EnvironmentPicker<'cv>>,
+    /// only present while the recently sent requests are listed
+    recent_requests: Option<RecentRequests<'cv>>,
+    /// only present while setting the run order of the collection
+    run_order: Option<RunOrder<'cv>>,
+    /// only present while previewing the resolved request
+    resolved_request: Option<ResolvedRequest<'cv>>,
+    error_popup_state: ErrorPopupState,
+    /// latest batch of requests sent at once, kept after the results are
+    /// closed until every response arrives
+    batch_results: Option<BatchResults<'cv>>,
 
     colors: &'cv hac_colors::Colors,
-    config: &'cv hac_config::Config,
+    config: Rc<hac_config::Config>,
     layout: ExplorerLayout,
     global_command_sender: Option<UnboundedSender<Command>>,
     collection_sync_timer: std::time::Instant,
+    /// when the view was last saved to the session file
+    session_save_timer: std::time::Instant,
+    /// the session as it was last saved or restored
+    saved_session: Option<hac_config::SessionState>,
+    /// what the edited mark of the selected request was last computed from
+    fingerprint_cache: Option<FingerprintCache>,
     collection_store: Rc<RefCell<CollectionStore>>,
 
     responses_map: HashMap<String, Rc<RefCell<Response>>>,
@@ -93,6 +206,11 @@ pub struct CollectionViewer<'cv> {
     request_tx: UnboundedSender<Response>,
 
     dry_run: bool,
+    /// set when the user chooses to discard a collection whose file was removed
+    /// from disk, we stop writing it back so the file stays deleted
+    sync_disabled: bool,
+    /// id of the request whose next response is saved as a sample
+    pending_sample: Option<String>,
 }
 
 impl<'cv> CollectionViewer<'cv> {
@@ -100,65 +218,170 @@ impl<'cv> CollectionViewer<'cv> {
         size: Rect,
         collection_store: Rc<RefCell<CollectionStore>>,
         colors: &'cv hac_colors::Colors,
-        config: &'cv hac_config::Config,
+        config: Rc<hac_config::Config>,
         dry_run: bool,
     ) -> Self {
-        let layout = build_layout(size);
+        let layout = build_layout(size, config.wrap_uri);
         let (request_tx, response_rx) = unbounded_channel::<Response>();
 
-        let sidebar = sidebar::Sidebar::new(colors, collection_store.clone());
+        let sidebar = sidebar::Sidebar::new(colors, config.clone(), collection_store.clone());
 
-        let request_editor =
-            RequestEditor::new(colors, config, collection_store.clone(), layout.req_editor);
+        let request_editor = RequestEditor::new(
+            colors,
+            config.clone(),
+            collection_store.clone(),
+            layout.req_editor,
+        );
 
         let response_viewer = ResponseViewer::new(
             colors,
+            config.clone(),
             collection_store.clone(),
             None,
             layout.response_preview,
         );
 
-        let request_uri = RequestUri::new(colors, collection_store.clone(), layout.req_uri);
+        let request_uri = RequestUri::new(
+            colors,
+            config.clone(),
+            collection_store.clone(),
+            layout.req_uri,
+        );
 
         CollectionViewer {
             request_editor,
+            request_editors: VecDeque::default(),
             response_viewer,
             sidebar,
             request_uri,
+            command_palette: CommandPalette::new(colors),
+            collection_picker: None,
+            environment_picker: None,
+            recent_requests: None,
+            run_order: None,
+            resolved_request: None,
+            error_popup_state: ErrorPopupState::default(),
+            batch_results: None,
             colors,
             layout,
             config,
             global_command_sender: None,
             collection_sync_timer: std::time::Instant::now(),
+            session_save_timer: std::time::Instant::now(),
+            saved_session: None,
+            fingerprint_cache: None,
             responses_map: HashMap::default(),
             response_rx,
             request_tx,
             dry_run,
+            sync_disabled: false,
+            pending_sample: None,
             collection_store,
         }
     }
 
-    fn rebuild_everything(&mut self) {
-        self.sidebar = sidebar::Sidebar::new(self.colors, self.collection_store.clone());
-        self.request_editor = RequestEditor::new(
+    fn rebuild_everything(&mut self, prev_request: Option<Arc<RwLock<Request>>>) {
+        self.sidebar = sidebar::Sidebar::new(
             self.colors,
-            self.config,
+            self.config.clone(),
             self.collection_store.clone(),
-            self.layout.req_editor,
         );
+        self.swap_request_editor(prev_request);
         self.response_viewer = ResponseViewer::new(
             self.colors,
+            self.config.clone(),
             self.collection_store.clone(),
             None,
             self.layout.response_preview,
         );
         self.request_uri = RequestUri::new(
             self.colors,
+            self.config.clone(),
             self.collection_store.clone(),
             self.layout.req_uri,
         );
     }
 
+    /// swaps the configuration the viewer was built with, rebuilding every pane
+    /// so the state they derived from the old configuration is refreshed
+    pub fn reload_config(&mut self, config: Rc<hac_config::Config>, size: Rect) {
+        let selected_request = self.collection_store.borrow().get_selected_request();
+        if let Some(request) = selected_request.as_ref() {
+            update_request_body(request, self.request_editor.body_to_store());
+        }
+
+        self.layout = build_layout(size, config.wrap_uri);
+        self.config = config;
+        self.request_editors.clear();
+        self.request_editor = RequestEditor::new(
+            self.colors,
+            self.config.clone(),
+            self.collection_store.clone(),
+            self.layout.req_editor,
+        );
+        self.rebuild_everything(selected_request.clone());
+
+        let response = selected_request
+            .and_then(|req| self.responses_map.get(&req.read().unwrap().id).cloned());
+        self.response_viewer.update(response);
+    }
+
+    /// drops the cached editor of `request_id`, which would otherwise bring back
+    /// the request as it was before being changed from outside of the editor
+    fn forget_request_editor(&mut self, request_id: &str) {
+        self.request_editors.retain(|(id, _)| id.ne(request_id));
+    }
+
+    // building an editor means running a full highlight pass over the request body,
+    // which gets noticeable when rapidly switching between requests on a big collection.
+    // Instead, we keep the editor of the request we are leaving around, and reuse it
+    // when that request gets selected again
+    fn swap_request_editor(&mut self, prev_request: Option<Arc<RwLock<Request>>>) {
+        let next_id = self
+            .collection_store
+            .borrow()
+            .get_selected_request()
+            .map(|req| req.read().unwrap().id.clone());
+        let prev_id = prev_request
+            .as_ref()
+            .map(|req| req.read().unwrap().id.clone());
+
+        if next_id.is_some() && next_id.eq(&prev_id) {
+            return;
+        }
+
+        let cached = next_id.as_ref().and_then(|next_id| {
+            self.request_editors
+                .iter()
+                .position(|(id, _)| id.eq(next_id))
+        });
+        let next_editor = match cached.and_then(|idx| self.request_editors.remove(idx)) {
+            Some((_, mut editor)) => {
+                editor.resize(self.layout.req_editor);
+                editor
+            }
+            None => RequestEditor::new(
+                self.colors,
+                self.config.clone(),
+                self.collection_store.clone(),
+                self.layout.req_editor,
+            ),
+        };
+        let prev_editor = std::mem::replace(&mut self.request_editor, next_editor);
+
+        // when nothing is selected after a rebuild, the previous request was deleted,
+        // so its editor is not worth keeping
+        if let (Some(request), Some(prev_id), Some(_)) = (prev_request, prev_id, next_id) {
+            update_request_body(&request, prev_editor.body_to_store());
+            self.request_editors.push_back((prev_id, prev_editor));
+            // every editor keeps its highlighted body around, so only the ones
+            // left last are kept
+            if self.request_editors.len().gt(&MAX_CACHED_EDITORS) {
+                self.request_editors.pop_front();
+            }
+        }
+    }
+
     fn focus_next(&mut self) {
         let next_pane = self.collection_store.borrow().get_focused_pane().next();
         self.update_focus(next_pane);
@@ -169,22 +392,63 @@ impl<'cv> CollectionViewer<'cv> {
         self.update_focus(prev_pane);
     }
 
+    /// moves focus between the editor and the preview, keeping the new pane
+    /// selected when the previous one was
+    fn toggle_editor_preview(&mut self) {
+        let store = self.collection_store.borrow();
+        let next_pane = store.get_focused_pane().toggle_editor_preview();
+        let was_selected = store.get_selected_pane().is_some();
+        drop(store);
+
+        self.update_focus(next_pane);
+        if was_selected {
+            self.update_selection(Some(next_pane));
+        }
+    }
+
     // collect all pending responses from the channel. Here, I don't see a way we
     // may have more than one response on this channel at any point, but it shouldn't matter
     // if we have, so we can drain all the responses and update accordingly
     fn drain_responses_channel(&mut self) {
         while let Ok(res) = self.response_rx.try_recv() {
+            // responses arrive for the request that was sent, which may no
+            // longer be the selected one
+            let request_id = res.request_id.clone().unwrap_or_default();
             let res = Rc::new(RefCell::new(res));
-            self.collection_store
+            self.responses_map
+                .insert(request_id.clone(), Rc::clone(&res));
+            let is_downloading = res.borrow().is_downloading();
+            if !is_downloading && !res.borrow().is_error {
+                self.collection_store.borrow_mut().dispatch(
+                    CollectionStoreAction::RecordResponseTime(
+                        request_id.clone(),
+                        res.borrow().duration,
+                    ),
+                );
+            }
+            if !is_downloading {
+                self.maybe_save_sample(&res.borrow());
+                self.maybe_save_response(&res.borrow());
+                let status = res.borrow().status.map(|status| status.as_u16());
+                self.collection_store
+                    .borrow_mut()
+                    .dispatch(CollectionStoreAction::RecordStatus(
+                        request_id.clone(),
+                        status,
+                    ));
+                self.sidebar.rebuild_tree_view();
+            }
+            let is_selected = self
+                .collection_store
                 .borrow()
                 .get_selected_request()
-                .as_ref()
-                .and_then(|req| {
-                    self.responses_map
-                        .insert(req.read().unwrap().id.to_string(), Rc::clone(&res))
-                });
-            self.response_viewer.update(Some(Rc::clone(&res)));
-            self.response_rx.is_empty().then(|| {
+                .is_some_and(|req| req.read().unwrap().id.eq(&request_id));
+            if is_selected {
+                self.response_viewer.update(Some(Rc::clone(&res)));
+            }
+            // downloads keep sending progress until they are done, so the request is
+            // still pending until we get the last one
+            (self.response_rx.is_empty() && !is_downloading).then(|| {
                 self.collection_store
                     .borrow_mut()
                     .dispatch(CollectionStoreAction::SetPendingRequest(false));
@@ -192,13 +456,168 @@ impl<'cv> CollectionViewer<'cv> {
         }
     }
 
-    fn sync_collection_changes(&mut self) {
-        let sender = self
-            .global_command_sender
+    /// saves the response as a sample of the request it was sent from when it
+    /// was sent through `C-s`, failed requests are not saved
+    fn maybe_save_sample(&mut self, response: &Response) {
+        if self.pending_sample.ne(&response.request_id) {
+            return;
+        }
+        let Some(request_id) = self.pending_sample.take() else {
+            return;
+        };
+        if response.is_error {
+            return;
+        }
+        let Some(request) = self.collection_store.borrow().find_request(&request_id) else {
+            return;
+        };
+
+        let sample = build_sample(response);
+        request.write().unwrap().samples.push(sample);
+        self.sync_collection_changes();
+        toast::show("saved response as a sample");
+    }
+
+    /// writes the body of a successful response to the file configured on
+    /// the request it was sent from, if any
+    fn maybe_save_response(&self, response: &Response) {
+        if self.dry_run || response.is_error {
+            return;
+        }
+        if !response.status.is_some_and(|status| status.is_success()) {
+            return;
+        }
+        let store = self.collection_store.borrow();
+        let (Some(request), Some(collection)) = (
+            response
+                .request_id
+                .as_ref()
+                .and_then(|id| store.find_request(id)),
+            store.get_collection(),
+        ) else {
+            return;
+        };
+        drop(store);
+        let dir = collection
+            .borrow()
+            .path
+            .parent()
+            .map(std::path::Path::to_path_buf)
+            .unwrap_or_default();
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let Some(path) = request.read().unwrap().response_save_path(&dir, timestamp) else {
+            return;
+        };
+
+        let body = response.body.clone().unwrap_or_default();
+        tokio::spawn(async move {
+            if let Some(parent) = path
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+            {
+                if let Err(e) = tokio::fs::create_dir_all(parent).await {
+                    return toast::show(format!("failed to save response: {e}"));
+                }
+            }
+            match tokio::fs::write(&path, body).await {
+                Ok(_) => toast::show(format!("response saved to {}", path.to_string_lossy())),
+                Err(e) => toast::show(format!("failed to save response: {e}")),
+            }
+        });
+    }
+
+    /// saves what is on view, like the hovered request and the active
+    /// environment, so opening the collection again restores it. Only the
+    /// hovered request is kept unless `restore_session` is enabled, and
+    /// nothing is written when the view didn't change since the last save
+    pub fn save_session(&mut self) {
+        if self.dry_run {
+            return;
+        }
+
+        let store = self.collection_store.borrow();
+        let Some(collection) = store.get_collection() else {
+            return;
+        };
+        let session = match self.config.restore_session {
+            true => hac_config::SessionState {
+                hovered_request: store.get_hovered_request(),
+                active_environment: store.get_active_environment(),
+                sidebar_scroll: self.sidebar.scroll(),
+            },
+            false => hac_config::SessionState {
+                hovered_request: store.get_hovered_request(),
+                ..Default::default()
+            },
+        };
+        if self
+            .saved_session
             .as_ref()
-            .expect("should have a sender at this point")
-            .clone();
+            .is_some_and(|saved| saved.eq(&session))
+        {
+            return;
+        }
+
+        hac_config::save_session(&collection.borrow().path, &session);
+        drop(store);
+        self.saved_session = Some(session);
+    }
+
+    /// starts the sidebar from where it was scrolled to on the last session,
+    /// the rest of the session lives on the store. The session is what is
+    /// saved on disk, so it is only written again once the view changes
+    pub fn restore_session(&mut self, session: hac_config::SessionState) {
+        self.sidebar.set_scroll(session.sidebar_scroll);
+        self.saved_session = Some(session);
+    }
+
+    fn sync_collection_changes(&mut self) {
+        self.collection_sync_timer = std::time::Instant::now();
+
+        if self.dry_run || self.sync_disabled {
+            return;
+        }
+
+        let collection = self.collect_changes();
+
+        if !collection.path.exists() {
+            match self.config.on_missing_collection {
+                MissingCollectionBehavior::Recreate => {
+                    tracing::warn!(
+                        "collection file {:?} was removed, recreating it",
+                        collection.path
+                    );
+                }
+                MissingCollectionBehavior::Discard => {
+                    tracing::warn!(
+                        "collection file {:?} was removed, discarding changes",
+                        collection.path
+                    );
+                    self.sync_disabled = true;
+                    return;
+                }
+                MissingCollectionBehavior::Ask => {
+                    let mut store = self.collection_store.borrow_mut();
+                    if store
+                        .peek_overlay()
+                        .ne(&CollectionViewerOverlay::MissingCollection)
+                    {
+                        store.push_overlay(CollectionViewerOverlay::MissingCollection);
+                    }
+                    return;
+                }
+            }
+        }
+
+        self.write_collection(collection);
+    }
 
+    /// builds a copy of the collection including the changes that live only on
+    /// the editors, like the body of the selected request
+    fn collect_changes(&mut self) -> Collection {
         let mut collection = self
             .collection_store
             .borrow()
@@ -209,13 +628,7 @@ impl<'cv> CollectionViewer<'cv> {
             .clone();
         if let Some(request) = self.collection_store.borrow().get_selected_request() {
             let request = request.clone();
-            let body = self.request_editor.body().to_string();
-            // this is not the best idea for when we start implementing other kinds of
-            // body types like GraphQL
-            if !body.is_empty() {
-                request.write().unwrap().body = Some(body);
-                request.write().unwrap().body_type = Some(BodyType::Json)
-            }
+            update_request_body(&request, self.request_editor.body_to_store());
 
             // we might later on decide to keep track of the actual dir/request index
             // so we dont have to go over all the possible requests, this might be a
@@ -245,14 +658,19 @@ impl<'cv> CollectionViewer<'cv> {
                 });
         }
 
-        self.collection_sync_timer = std::time::Instant::now();
+        collection
+    }
 
-        if self.dry_run {
-            return;
-        }
+    fn write_collection(&self, collection: Collection) {
+        let sender = self
+            .global_command_sender
+            .as_ref()
+            .expect("should have a sender at this point")
+            .clone();
 
+        let dry_run = self.dry_run;
         tokio::spawn(async move {
-            match hac_core::fs::sync_collection(collection).await {
+            match hac_core::fs::sync_collection(collection, dry_run).await {
                 Ok(_) => {}
                 Err(e) => {
                     if sender.send(Command::Error(e.to_string())).is_err() {
@@ -264,131 +682,1300 @@ impl<'cv> CollectionViewer<'cv> {
         });
     }
 
-    fn update_selection(&mut self, pane_to_select: Option<PaneFocus>) {
-        self.collection_store
-            .borrow_mut()
-            .dispatch(CollectionStoreAction::SetSelectedPane(pane_to_select));
+    fn draw_missing_collection_prompt(&self, frame: &mut Frame) {
+        let collection_name = self
+            .collection_store
+            .borrow()
+            .get_collection()
+            .map(|collection| collection.borrow().info.name.clone())
+            .unwrap_or_default();
+
+        let confirm_popup = ConfirmPopup::new(
+            format!(
+                "The file for collection {} was removed. Recreate it from the current state?",
+                collection_name
+            ),
+            self.colors,
+        );
+        confirm_popup.render(self.layout.confirm_popup, frame.buffer_mut());
     }
 
-    fn update_focus(&mut self, pane_to_focus: PaneFocus) {
+    /// rebuilds the view after another request was selected, unless the body
+    /// of `prev_request` has unsaved edits, which are handled as configured
+    /// on `on_unsaved_edits` before leaving it
+    fn change_selected_request(&mut self, prev_request: Option<Arc<RwLock<Request>>>) {
+        let next_request = self.collection_store.borrow().get_selected_request();
+        let id = |request: &Option<Arc<RwLock<Request>>>| {
+            request
+                .as_ref()
+                .map(|request| request.read().unwrap().id.clone())
+        };
+        let (prev_id, next_id) = (id(&prev_request), id(&next_request));
+        let Some(next_id) = next_id.filter(|next_id| {
+            prev_id.as_ref().is_some_and(|prev_id| prev_id.ne(next_id))
+                && self.request_editor.has_unsaved_edits()
+        }) else {
+            return self.rebuild_everything(prev_request);
+        };
+
+        // the edits are handled while their request is still the selected one
         self.collection_store
             .borrow_mut()
-            .dispatch(CollectionStoreAction::SetFocusedPane(pane_to_focus));
+            .dispatch(CollectionStoreAction::SetSelectedRequest(prev_request));
+        self.leave_selected_request(LeaveTarget::Request(next_id));
     }
-}
-
-impl Renderable for CollectionViewer<'_> {
-    #[tracing::instrument(skip_all)]
-    fn draw(&mut self, frame: &mut Frame, size: Rect) -> anyhow::Result<()> {
-        // we redraw the background to prevent weird "transparent" spots when popups are
-        // cleared from the buffer
-        frame.render_widget(Clear, size);
-        frame.render_widget(Block::default().bg(self.colors.primary.background), size);
-
-        self.drain_responses_channel();
 
-        self.sidebar.draw(frame, self.layout.sidebar)?;
-        self.response_viewer
-            .draw(frame, self.layout.response_preview)?;
-        self.request_editor.draw(frame, self.layout.req_editor)?;
-        self.request_uri.draw(frame, self.layout.req_uri)?;
+    /// goes to `target`, handling the unsaved edits of the body of the
+    /// selected request as configured on `on_unsaved_edits` first. Returns
+    /// the command to send when going to `target` needs the screen manager
+    fn leave_selected_request(&mut self, target: LeaveTarget) -> Option<Command> {
+        if !self.request_editor.has_unsaved_edits() {
+            return self.go_to(target);
+        }
 
-        let overlay = self.collection_store.borrow().peek_overlay();
-        match overlay {
-            CollectionViewerOverlay::CreateRequest => {
-                self.sidebar.draw_overlay(frame, overlay)?;
-            }
-            CollectionViewerOverlay::CreateDirectory => {
-                self.sidebar.draw_overlay(frame, overlay)?;
-            }
-            CollectionViewerOverlay::SelectParentDir => {
-                self.sidebar.draw_overlay(frame, overlay)?;
-            }
-            CollectionViewerOverlay::EditRequest => {
-                self.sidebar.draw_overlay(frame, overlay)?;
-            }
-            CollectionViewerOverlay::EditDirectory => {
-                self.sidebar.draw_overlay(frame, overlay)?;
-            }
-            CollectionViewerOverlay::DeleteSidebarItem(_) => {
-                self.sidebar.draw_overlay(frame, overlay)?;
-            }
-            CollectionViewerOverlay::HeadersHelp => {
-                self.request_editor.draw_overlay(frame, overlay)?;
+        match self.config.on_unsaved_edits {
+            UnsavedEditsBehavior::Ask => {
+                self.collection_store
+                    .borrow_mut()
+                    .push_overlay(CollectionViewerOverlay::UnsavedEdits(target));
+                None
             }
-            CollectionViewerOverlay::HeadersDelete => {
-                self.request_editor.draw_overlay(frame, overlay)?;
+            UnsavedEditsBehavior::Save => {
+                self.save_edits();
+                self.go_to(target)
             }
-            CollectionViewerOverlay::HeadersForm(_, _) => {
-                self.request_editor.draw_overlay(frame, overlay)?;
+            UnsavedEditsBehavior::Discard => {
+                self.discard_edits();
+                self.go_to(target)
             }
-            CollectionViewerOverlay::ChangeAuthMethod => {
-                self.request_editor.draw_overlay(frame, overlay)?;
+        }
+    }
+
+    fn go_to(&mut self, target: LeaveTarget) -> Option<Command> {
+        match target {
+            LeaveTarget::Request(request_id) => {
+                let request = self.collection_store.borrow().find_request(&request_id);
+                self.select_request(request);
+                None
             }
-            CollectionViewerOverlay::None => {}
+            LeaveTarget::Back => Some(Command::Back),
+            LeaveTarget::OtherCollection {
+                path,
+                request_id,
+                send,
+            } => Some(Command::OpenRequest {
+                path,
+                request_id,
+                send,
+            }),
         }
+    }
 
-        if self
-            .collection_store
-            .borrow()
-            .get_selected_pane()
-            .as_ref()
-            .is_some_and(|pane| pane.eq(&PaneFocus::Editor))
-        {
-            self.request_editor.maybe_draw_cursor(frame);
+    fn select_request(&mut self, request: Option<Arc<RwLock<Request>>>) {
+        let prev_request = self.collection_store.borrow().get_selected_request();
+        self.collection_store
+            .borrow_mut()
+            .dispatch(CollectionStoreAction::SetSelectedRequest(request));
+        self.rebuild_everything(prev_request);
+    }
+
+    /// writes the collection to disk, edits of the body are no longer at
+    /// risk of being lost
+    fn save_edits(&mut self) {
+        self.sync_collection_changes();
+        self.request_editor.mark_saved();
+    }
+
+    /// puts back the body the selected request had before the edits
+    fn discard_edits(&mut self) {
+        let Some(request) = self.collection_store.borrow().get_selected_request() else {
+            return;
+        };
+        let saved_body = self.request_editor.saved_body().to_string();
+        let mut request = request.write().unwrap();
+        if !matches!(request.body_type, Some(BodyType::File(_))) {
+            request.body = (!saved_body.is_empty()).then_some(saved_body);
         }
+        drop(request);
 
-        if self
+        self.rebuild_request_editor();
+        self.sync_collection_changes();
+    }
+
+    fn draw_unsaved_edits_prompt(&self, frame: &mut Frame) {
+        let request_name = self
             .collection_store
             .borrow()
-            .get_selected_pane()
-            .as_ref()
-            .is_some_and(|pane| pane.eq(&PaneFocus::ReqUri))
-        {
-            if let Some(request) = self
-                .collection_store
-                .borrow()
-                .get_selected_request()
-                .as_ref()
-            {
-                frame.set_cursor(
-                    self.layout
-                        .req_uri
-                        .x
-                        .add(request.read().unwrap().uri.chars().count() as u16)
-                        .add(1),
-                    self.layout.req_uri.y.add(1),
-                )
+            .get_selected_request()
+            .map(|request| request.read().unwrap().name.clone())
+            .unwrap_or_default();
+
+        let confirm_popup = ConfirmPopup::new(
+            format!(
+                "The body of {request_name} has unsaved edits. Save them before leaving? \
+                 Saying no discards them, esc stays on the request"
+            ),
+            self.colors,
+        );
+        confirm_popup.render(self.layout.confirm_popup, frame.buffer_mut());
+    }
+
+    fn handle_unsaved_edits_key_event(
+        &mut self,
+        key_event: KeyEvent,
+        target: LeaveTarget,
+    ) -> Option<Command> {
+        match key_event.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                self.collection_store.borrow_mut().pop_overlay();
+                self.save_edits();
             }
+            KeyCode::Char('n') => {
+                self.collection_store.borrow_mut().pop_overlay();
+                self.discard_edits();
+            }
+            KeyCode::Esc => {
+                self.collection_store.borrow_mut().pop_overlay();
+                return None;
+            }
+            _ => return None,
         }
 
-        Ok(())
+        self.go_to(target)
     }
 
-    fn handle_tick(&mut self) -> anyhow::Result<()> {
-        if self.collection_sync_timer.elapsed().as_secs().ge(&5) {
-            self.sync_collection_changes();
+    fn handle_missing_collection_key_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                self.collection_store.borrow_mut().pop_overlay();
+                let collection = self.collect_changes();
+                self.collection_sync_timer = std::time::Instant::now();
+                self.write_collection(collection);
+            }
+            KeyCode::Char('n') | KeyCode::Esc => {
+                self.collection_store.borrow_mut().pop_overlay();
+                self.sync_disabled = true;
+            }
+            _ => {}
         }
-        Ok(())
     }
 
-    fn register_command_handler(&mut self, sender: UnboundedSender<Command>) -> anyhow::Result<()> {
-        self.global_command_sender = Some(sender);
-        Ok(())
+    /// sends the selected request, unless its body is bigger than the configured
+    /// limit, in which case we ask for confirmation first
+    fn maybe_send_request(&mut self) {
+        let Some(request) = self.collection_store.borrow().get_selected_request() else {
+            return;
+        };
+        update_request_body(&request, self.request_editor.body_to_store());
+
+        let body_size = match &*request.read().unwrap() {
+            // GET requests are sent without a body, so there is nothing to check
+            Request {
+                method: RequestMethod::Get,
+                ..
+            } => 0,
+            Request {
+                body_type: Some(BodyType::File(path)),
+                ..
+            } => std::fs::metadata(path)
+                .map(|metadata| metadata.len())
+                .unwrap_or_default(),
+            Request { body, .. } => body.as_ref().map(|b| b.len() as u64).unwrap_or_default(),
+        };
+        let max_body_size = self.config.max_request_body_bytes;
+
+        if max_body_size.gt(&0) && body_size.gt(&max_body_size) {
+            self.collection_store
+                .borrow_mut()
+                .push_overlay(CollectionViewerOverlay::LargeBodyWarning(body_size));
+            return;
+        }
+
+        self.send_request(&request);
     }
 
-    fn resize(&mut self, new_size: Rect) {
-        let new_layout = build_layout(new_size);
-        self.request_editor.resize(new_layout.req_editor);
-        self.response_viewer.resize(new_layout.response_preview);
-        self.layout = new_layout;
+    /// sends `request` with the variables of the collection and of the active
+    /// environment, its response arrives through `request_tx`
+    fn send_request(&mut self, request: &Arc<RwLock<Request>>) {
+        let Some(context) = self.request_context() else {
+            self.pending_sample = None;
+            self.collection_store
+                .borrow_mut()
+                .dispatch(CollectionStoreAction::SetPendingRequest(false));
+            return;
+        };
+        self.record_recent_request(&request.read().unwrap());
+        self.record_sent_request(&request.read().unwrap());
+        hac_core::net::handle_request(request, &context, self.request_tx.clone());
     }
-}
 
-impl Eventful for CollectionViewer<'_> {
-    type Result = Command;
+    /// what requests of the collection are sent with, like the base url and
+    /// the variables of the active environment. Variables that can't be
+    /// loaded, like from a missing env file, are shown on the error popup
+    fn request_context(&mut self) -> Option<RequestContext> {
+        let store = self.collection_store.borrow();
+        let collection = store.get_collection()?;
+        let environment = store.get_active_environment();
+        drop(store);
+
+        let context = RequestContext::from_collection(
+            &collection.borrow(),
+            environment.as_deref(),
+            None,
+            self.config.max_variable_depth,
+        );
+        match context {
+            Ok(context) => Some(context),
+            Err(e) => {
+                self.show_error(e);
+                None
+            }
+        }
+    }
+
+    /// remembers what `request` is sending, so later edits can mark its
+    /// response as outdated
+    fn record_sent_request(&self, request: &Request) {
+        let environment = self.collection_store.borrow().get_active_environment();
+        let fingerprint = request_fingerprint(
+            &serialize_for_fingerprint(request, environment.as_deref()),
+            &self.request_editor.body_to_store(),
+        );
+        self.collection_store
+            .borrow_mut()
+            .dispatch(CollectionStoreAction::RecordSentRequest(
+                request.id.clone(),
+                fingerprint,
+            ));
+    }
+
+    /// marks the selected request as edited when it would send something
+    /// different from what it sent last time, or with another environment.
+    /// Nothing is computed when neither changed since the last time, and the
+    /// request is only serialized again when more than its body changed
+    fn refresh_edited_mark(&mut self) {
+        let Some(request) = self.collection_store.borrow().get_selected_request() else {
+            return;
+        };
+        let request = request.read().unwrap();
+        let Some(sent) = self
+            .collection_store
+            .borrow()
+            .get_sent_fingerprint(&request.id)
+        else {
+            return;
+        };
+
+        let environment = self.collection_store.borrow().get_active_environment();
+        let body = self.request_editor.body();
+        let serialized = match self.fingerprint_cache.take() {
+            Some(cache) if cache.request.eq(&request) && cache.environment.eq(&environment) => {
+                if cache.body.eq(body) {
+                    self.fingerprint_cache = Some(cache);
+                    return;
+                }
+                cache.serialized
+            }
+            _ => serialize_for_fingerprint(&request, environment.as_deref()),
+        };
+
+        let fingerprint = request_fingerprint(&serialized, &self.request_editor.body_to_store());
+        self.collection_store
+            .borrow_mut()
+            .dispatch(CollectionStoreAction::SetRequestEdited(
+                request.id.clone(),
+                fingerprint.ne(&sent),
+            ));
+        self.fingerprint_cache = Some(FingerprintCache {
+            request: request.clone(),
+            environment,
+            serialized,
+            body: body.clone(),
+        });
+    }
+
+    /// adds `request` to the top of the recently sent requests, requests of
+    /// scratch collections are left out as there is no way to get back to them
+    fn record_recent_request(&self, request: &Request) {
+        let Some(collection) = self.collection_store.borrow().get_collection() else {
+            return;
+        };
+        let collection = collection.borrow();
+        if collection.path.as_os_str().is_empty() {
+            return;
+        }
+
+        let recent = recent::record(hac_config::RecentRequest {
+            collection_path: collection.path.clone(),
+            collection_name: collection.info.name.clone(),
+            request_id: request.id.clone(),
+            request_name: request.name.clone(),
+            method: request.method.to_string(),
+        });
+        if self.config.persist_recent_requests && !self.dry_run {
+            hac_config::save_recent_requests(&recent);
+        }
+    }
+
+    /// selects the request with `request_id`, revealing it on the sidebar, and
+    /// sends it right away when `send` is set
+    pub fn open_request(&mut self, request_id: &str, send: bool) {
+        let prev_request = self.collection_store.borrow().get_selected_request();
+        let mut store = self.collection_store.borrow_mut();
+        store.restore_hovered_request(request_id.to_string(), self.config.expand_selected_request);
+        let Some(request) = store.find_request(request_id) else {
+            drop(store);
+            return self.show_error("the request no longer exists on its collection");
+        };
+        store.dispatch(CollectionStoreAction::SetSelectedRequest(Some(request)));
+        drop(store);
+        self.change_selected_request(prev_request);
+
+        // the request is not selected yet while the user decides what to do
+        // with the edits of the previous one
+        let is_selected = self
+            .collection_store
+            .borrow()
+            .get_selected_request()
+            .is_some_and(|request| request.read().unwrap().id.eq(request_id));
+        if send && is_selected && !self.collection_store.borrow().has_pending_request() {
+            self.collection_store
+                .borrow_mut()
+                .dispatch(CollectionStoreAction::SetPendingRequest(true));
+            self.maybe_send_request();
+        }
+    }
+
+    fn open_recent_requests(&mut self) {
+        let requests = recent::entries();
+        if requests.is_empty() {
+            return self.show_error("no request was sent recently");
+        }
+
+        self.recent_requests = Some(RecentRequests::new(self.colors, requests));
+        self.collection_store
+            .borrow_mut()
+            .push_overlay(CollectionViewerOverlay::RecentRequests);
+    }
+
+    /// lists every request of the collection in the order they run, so the
+    /// user can rearrange them
+    fn open_run_order(&mut self) {
+        let Some(collection) = self.collection_store.borrow().get_collection() else {
+            return;
+        };
+        let collection = collection.borrow();
+        let requests = collection
+            .requests
+            .as_ref()
+            .map(|requests| {
+                hac_core::collection::collection::flatten_requests(&requests.read().unwrap())
+            })
+            .unwrap_or_default();
+        if requests.is_empty() {
+            drop(collection);
+            return self.show_error("the collection has no requests to order");
+        }
+
+        let requests =
+            hac_core::collection::collection::apply_run_order(requests, &collection.run_order);
+        drop(collection);
+        self.run_order = Some(RunOrder::new(self.colors, requests));
+        self.collection_store
+            .borrow_mut()
+            .push_overlay(CollectionViewerOverlay::RunOrder);
+    }
+
+    /// resolves the selected request against the variables of the collection
+    /// and of the active environment, after applying the base url. Failures
+    /// are shown on the error popup, where `missing` explains that a request
+    /// has to be selected
+    fn resolve_selected_request<T>(
+        &mut self,
+        missing: &str,
+        resolve: impl FnOnce(
+            &Request,
+            &HashMap<String, String>,
+            usize,
+            TrailingSlash,
+        ) -> Result<T, hac_core::variables::VariableError>,
+    ) -> Option<T> {
+        let Some(request) = self.collection_store.borrow().get_selected_request() else {
+            self.show_error(missing);
+            return None;
+        };
+        let context = self.request_context()?;
+        let mut request = request.read().unwrap().clone();
+        request.uri =
+            hac_core::net::uri::resolve_base_url(&request.uri, context.base_url.as_deref());
+
+        match resolve(
+            &request,
+            &context.variables,
+            context.max_variable_depth,
+            context.trailing_slash,
+        ) {
+            Ok(resolved) => Some(resolved),
+            Err(e) => {
+                self.show_error(e.to_string());
+                None
+            }
+        }
+    }
+
+    /// previews the selected request with its variables resolved against the
+    /// variables of the collection and of the active environment
+    fn open_resolved_request(&mut self) {
+        let Some(text) = self.resolve_selected_request(
+            "select a request to see how it will be sent",
+            hac_core::export::to_resolved_text,
+        ) else {
+            return;
+        };
+
+        self.resolved_request = Some(ResolvedRequest::new(self.colors, text));
+        self.collection_store
+            .borrow_mut()
+            .push_overlay(CollectionViewerOverlay::ResolvedRequest);
+    }
+
+    /// copies the url the selected request will be sent to, with the base url
+    /// and every variable resolved
+    fn copy_resolved_url(&mut self) -> anyhow::Result<()> {
+        if let Some(url) = self.resolve_selected_request(
+            "select a request to copy its url",
+            hac_core::export::to_resolved_url,
+        ) {
+            copy_to_clipboard(&url)?;
+            toast::show("copied to clipboard");
+        }
+
+        Ok(())
+    }
+
+    /// sends a `HEAD` request to the base url of the active environment, with
+    /// its variables resolved, and shows whether it was reachable on a toast.
+    /// Nothing is added to the collection
+    fn test_connection(&mut self) {
+        let Some(context) = self.request_context() else {
+            return;
+        };
+        let Some(base_url) = context.base_url else {
+            return self.show_error(
+                "set a base_url variable on the collection or the environment to test it",
+            );
+        };
+        let settings = context
+            .client
+            .or(&hac_core::net::request_client::global_client_settings());
+        let url = match hac_core::variables::resolve(
+            &base_url,
+            &context.variables,
+            context.max_variable_depth,
+        ) {
+            Ok(url) => url,
+            Err(e) => return self.show_error(e.to_string()),
+        };
+
+        toast::show(format!("testing connection to {url}"));
+        tokio::spawn(async move {
+            match hac_core::net::request_client::check_connection(&url, &settings).await {
+                Ok((status, duration)) => toast::show(format!(
+                    "{url} is reachable, responded {status} in {} ms",
+                    duration.as_millis()
+                )),
+                Err(cause) => toast::show(format!("{url} is unreachable: {cause}")),
+            }
+        });
+    }
+
+    /// makes the environment after the active one active, going back to no
+    /// environment after the last one
+    fn switch_environment(&mut self) {
+        let Some(collection) = self.collection_store.borrow().get_collection() else {
+            return;
+        };
+        if collection.borrow().environments.is_empty() {
+            return self.show_error("the collection has no environments");
+        }
+
+        let active = self.collection_store.borrow().get_active_environment();
+        let next = {
+            let collection = collection.borrow();
+            let mut names = collection.environments.keys();
+            match active {
+                Some(active) => names.skip_while(|name| active.ne(*name)).nth(1).cloned(),
+                None => names.next().cloned(),
+            }
+        };
+
+        self.set_active_environment(next);
+    }
+
+    fn set_active_environment(&mut self, environment: Option<String>) {
+        toast::show(match environment.as_ref() {
+            Some(name) => format!("using environment {name}"),
+            None => String::from("not using any environment"),
+        });
+        self.collection_store
+            .borrow_mut()
+            .dispatch(CollectionStoreAction::SetActiveEnvironment(environment));
+    }
+
+    /// lists the environments of the collection along with their base urls,
+    /// so one can be made active
+    fn open_environment_picker(&mut self) {
+        let store = self.collection_store.borrow();
+        let Some(collection) = store.get_collection() else {
+            return;
+        };
+        let active = store.get_active_environment();
+        drop(store);
+
+        // base urls are shown as they are sent, falling back to how they are
+        // written when their variables can't be resolved
+        let collection = collection.borrow();
+        let environments = collection
+            .environments
+            .keys()
+            .map(|name| EnvironmentEntry {
+                name: name.clone(),
+                base_url: collection.base_url(Some(name)).map(|base_url| {
+                    collection
+                        .variables(Some(name), None)
+                        .ok()
+                        .and_then(|variables| {
+                            hac_core::variables::resolve(
+                                base_url,
+                                &variables,
+                                self.config.max_variable_depth,
+                            )
+                            .ok()
+                        })
+                        .unwrap_or_else(|| base_url.to_string())
+                }),
+            })
+            .collect::<Vec<_>>();
+        drop(collection);
+        if environments.is_empty() {
+            return self.show_error("the collection has no environments");
+        }
+
+        self.environment_picker = Some(EnvironmentPicker::new(
+            self.colors,
+            environments,
+            active.as_deref(),
+        ));
+        self.collection_store
+            .borrow_mut()
+            .push_overlay(CollectionViewerOverlay::EnvironmentPicker);
+    }
+
+    /// toggles whether the selected request sends the `ETag` of its last
+    /// response, letting the server answer with `304 Not Modified`
+    fn toggle_etag_cache(&mut self) {
+        let Some(request) = self.collection_store.borrow().get_selected_request() else {
+            return self.show_error("select a request to toggle its ETag caching");
+        };
+
+        let enabled = {
+            let mut request = request.write().unwrap();
+            request.etag_cache = !request.etag_cache;
+            request.etag_cache
+        };
+        self.sync_collection_changes();
+        toast::show(match enabled {
+            true => "sending If-None-Match with the last ETag",
+            false => "ETag caching disabled",
+        });
+    }
+
+    /// cycles the selected request between falling back to the compression of
+    /// its collection or the config, advertising compressed encodings and
+    /// asking for an uncompressed response
+    fn toggle_compression(&mut self) {
+        let Some(request) = self.collection_store.borrow().get_selected_request() else {
+            return self.show_error("select a request to change its compression");
+        };
+
+        let compression = {
+            let mut request = request.write().unwrap();
+            request.client.compression = match request.client.compression {
+                None => Some(Compression::Compressed),
+                Some(Compression::Compressed) => Some(Compression::Identity),
+                Some(Compression::Identity) => None,
+            };
+            request.client.compression
+        };
+        self.sync_collection_changes();
+        toast::show(match compression {
+            Some(compression) => {
+                format!("sending Accept-Encoding: {}", compression.accept_encoding())
+            }
+            None => String::from("using the compression of the collection"),
+        });
+    }
+
+    /// switches the selected request between its regular form and a raw HTTP
+    /// request written on the body editor, which is sent verbatim. Switching
+    /// to raw starts from the request as it is, and switching back keeps only
+    /// the body of the raw request
+    fn toggle_raw_mode(&mut self) {
+        let Some(request) = self.collection_store.borrow().get_selected_request() else {
+            return self.show_error("select a request to write it as raw HTTP");
+        };
+        update_request_body(&request, self.request_editor.body_to_store());
+
+        let body_type = request.read().unwrap().body_type.clone();
+        let raw = match body_type {
+            Some(BodyType::File(_)) => {
+                return self.show_error("bodies read from files can't be written as raw HTTP");
+            }
+            Some(BodyType::Raw) => {
+                let mut request = request.write().unwrap();
+                let body = request
+                    .body
+                    .as_deref()
+                    .and_then(|raw| split_head(raw).1)
+                    .map(|body| body.to_string())
+                    .filter(|body| !body.trim().is_empty());
+                request.body_type = body
+                    .as_ref()
+                    .map(|_| BodyType::for_content_type(request.active_content_type()));
+                request.body = body;
+                false
+            }
+            _ => {
+                // the raw request is written as it is sent, so its host and
+                // content length match the resolved uri and body
+                let Some(text) = self.resolve_selected_request(
+                    "select a request to write it as raw HTTP",
+                    |request, variables, max_depth, trailing_slash| {
+                        let mut request = request.clone();
+                        substitute_variables(&mut request, variables, max_depth)?;
+                        request.uri = normalize_trailing_slash(&request.uri, trailing_slash);
+                        Ok(hac_core::export::to_raw_request(&request))
+                    },
+                ) else {
+                    return;
+                };
+                let mut request = request.write().unwrap();
+                request.body = Some(text);
+                request.body_type = Some(BodyType::Raw);
+                true
+            }
+        };
+
+        self.rebuild_request_editor();
+        self.sync_collection_changes();
+        toast::show(match raw {
+            true => "the body editor now holds the raw request",
+            false => "back to a regular request",
+        });
+    }
+
+    /// sends the selected request with its body for the next content type,
+    /// the body on the editor is kept under its own content type so switching
+    /// back restores it
+    fn switch_body(&mut self) {
+        let Some(request) = self.collection_store.borrow().get_selected_request() else {
+            return self.show_error("select a request to switch its body");
+        };
+        update_request_body(&request, self.request_editor.body_to_store());
+
+        let mut request = request.write().unwrap();
+        if matches!(request.body_type, Some(BodyType::File(_) | BodyType::Raw)) {
+            drop(request);
+            return self.show_error("bodies read from files or raw requests can't be switched");
+        }
+        let content_type = request.next_content_type();
+        request.switch_body(&content_type);
+        request.body_type = request
+            .body
+            .as_ref()
+            .map(|_| BodyType::for_content_type(&content_type));
+        drop(request);
+
+        self.rebuild_request_editor();
+        self.sync_collection_changes();
+        toast::show(format!("sending the {content_type} body"));
+    }
+
+    fn draw_status_bar(&self, frame: &mut Frame) {
+        let store = self.collection_store.borrow();
+        let collection_name = store
+            .get_collection()
+            .map(|collection| collection.borrow().info.name.clone())
+            .unwrap_or_default();
+        let environment = store.get_active_environment();
+        let base_url = store.get_base_url();
+        drop(store);
+
+        let colors = &self.colors.status_bar;
+        let spans = self
+            .config
+            .status_bar
+            .iter()
+            .filter_map(|item| match item {
+                StatusBarItem::Collection => Some(collection_name.clone().fg(colors.collection)),
+                StatusBarItem::Environment => Some(match (&environment, &base_url) {
+                    (Some(name), Some(base_url)) => {
+                        format!("env: {name} ({base_url})").fg(colors.environment)
+                    }
+                    (Some(name), None) => format!("env: {name}").fg(colors.environment),
+                    (None, Some(base_url)) => {
+                        format!("no environment ({base_url})").fg(colors.foreground)
+                    }
+                    (None, None) => "no environment".fg(colors.foreground),
+                }),
+                StatusBarItem::DryRun => self.dry_run.then(|| "DRY RUN".fg(colors.dry_run).bold()),
+            })
+            .fold(vec![], |mut spans: Vec<Span>, span| {
+                if !spans.is_empty() {
+                    spans.push(" │ ".fg(colors.foreground));
+                }
+                spans.push(span);
+                spans
+            });
+
+        frame.render_widget(
+            Line::from(spans).bg(colors.background),
+            self.layout.hint_pane,
+        );
+    }
+
+    fn save_run_order(&mut self, order: Vec<String>) {
+        let Some(collection) = self.collection_store.borrow().get_collection() else {
+            return;
+        };
+        let message = match order.is_empty() {
+            true => "requests now run in the order of the sidebar",
+            false => "run order saved",
+        };
+        collection.borrow_mut().run_order = order;
+        self.sync_collection_changes();
+        toast::show(message);
+    }
+
+    /// goes to the picked recent request, requests of other collections are
+    /// opened by the screen manager, as it owns the collection being viewed
+    fn open_recent_request(
+        &mut self,
+        request: hac_config::RecentRequest,
+        send: bool,
+    ) -> Option<Command> {
+        let is_current = self
+            .collection_store
+            .borrow()
+            .get_collection()
+            .is_some_and(|collection| collection.borrow().path.eq(&request.collection_path));
+
+        if is_current {
+            self.open_request(&request.request_id, send);
+            return None;
+        }
+
+        self.leave_selected_request(LeaveTarget::OtherCollection {
+            path: request.collection_path,
+            request_id: request.request_id,
+            send,
+        })
+    }
+
+    /// sends a copy of the selected request to where the redirect response
+    /// points, the copy is never stored so the request itself is unchanged
+    fn follow_redirect(&mut self, status: u16, url: &str, location: &str) {
+        let Some(request) = self.collection_store.borrow().get_selected_request() else {
+            return;
+        };
+        let redirect = redirect_request(&request.read().unwrap(), url, status, location);
+        let Some(redirect) = redirect else {
+            self.show_error(format!("invalid redirect location: {location}"));
+            return;
+        };
+
+        toast::show(format!("following redirect to {}", redirect.uri));
+        let Some(context) = self.request_context() else {
+            return;
+        };
+        self.collection_store
+            .borrow_mut()
+            .dispatch(CollectionStoreAction::SetPendingRequest(true));
+        // the location is already a full url, normalized by the server
+        let context = RequestContext {
+            base_url: None,
+            trailing_slash: TrailingSlash::Keep,
+            ..context
+        };
+        hac_core::net::handle_request(
+            &Arc::new(RwLock::new(redirect)),
+            &context,
+            self.request_tx.clone(),
+        );
+    }
+
+    fn draw_large_body_warning(&self, frame: &mut Frame, body_size: u64) {
+        let confirm_popup = ConfirmPopup::new(
+            format!(
+                "The request body has {}, which is above the limit of {}. Send it anyway?",
+                ReadableByteSize(body_size),
+                ReadableByteSize(self.config.max_request_body_bytes),
+            ),
+            self.colors,
+        );
+        confirm_popup.render(self.layout.confirm_popup, frame.buffer_mut());
+    }
+
+    fn handle_large_body_warning_key_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                let mut store = self.collection_store.borrow_mut();
+                store.pop_overlay();
+                if let Some(request) = store.get_selected_request() {
+                    drop(store);
+                    self.send_request(&request);
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Esc => {
+                self.pending_sample = None;
+                let mut store = self.collection_store.borrow_mut();
+                store.pop_overlay();
+                store.dispatch(CollectionStoreAction::SetPendingRequest(false));
+            }
+            _ => {}
+        }
+    }
+
+    fn run_palette_action(&mut self, action: PaletteAction) -> anyhow::Result<Option<Command>> {
+        match action {
+            PaletteAction::Quit => return Ok(Some(Command::Quit)),
+            PaletteAction::ReloadConfig => return Ok(Some(Command::ReloadConfig)),
+            PaletteAction::SyncCollection => {
+                self.save_edits();
+                toast::show("collection saved");
+            }
+            PaletteAction::ToggleEditorPreview => self.toggle_editor_preview(),
+            PaletteAction::BodyToQuery => self.convert_body_to_query(),
+            PaletteAction::QueryToBody => self.convert_query_to_body(),
+            PaletteAction::MergeCollection => self.open_collection_picker(),
+            PaletteAction::RecentRequests => self.open_recent_requests(),
+            PaletteAction::RunOrder => self.open_run_order(),
+            PaletteAction::ResolvedRequest => self.open_resolved_request(),
+            PaletteAction::SwitchEnvironment => self.switch_environment(),
+            PaletteAction::PickEnvironment => self.open_environment_picker(),
+            PaletteAction::TestConnection => self.test_connection(),
+            PaletteAction::ToggleEtagCache => self.toggle_etag_cache(),
+            PaletteAction::ToggleCompression => self.toggle_compression(),
+            PaletteAction::SwitchBody => self.switch_body(),
+            PaletteAction::ToggleRawMode => self.toggle_raw_mode(),
+            PaletteAction::SendVisible => self.send_visible_requests(),
+            _ => {
+                let Some((pane, key_event)) = action.target() else {
+                    return Ok(None);
+                };
+
+                self.update_focus(pane);
+                self.update_selection(Some(pane));
+
+                if let Some(key_event) = key_event {
+                    return self.handle_key_event(key_event);
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// moves the parameters of a flat json body to the query string of the
+    /// selected request, leaving it without a body
+    fn convert_body_to_query(&mut self) {
+        let Some(request) = self.collection_store.borrow().get_selected_request() else {
+            return;
+        };
+        if let Some(BodyType::File(_)) = request.read().unwrap().body_type {
+            return self.show_error("bodies read from files can't be converted");
+        }
+
+        let body = self.request_editor.body().to_string();
+        if body.trim().is_empty() {
+            return self.show_error("the request has no body to convert");
+        }
+        let params = match hac_core::query::json_to_query(&body) {
+            Ok(params) => params,
+            Err(message) => return self.show_error(message),
+        };
+
+        let mut request = request.write().unwrap();
+        request.uri = hac_core::query::append_query(&request.uri, &params);
+        request.body = None;
+        request.body_type = None;
+        drop(request);
+
+        self.rebuild_request_editor();
+        self.sync_collection_changes();
+    }
+
+    /// moves the query parameters of the selected request to a json body
+    fn convert_query_to_body(&mut self) {
+        let Some(request) = self.collection_store.borrow().get_selected_request() else {
+            return;
+        };
+        let has_body = request.read().unwrap().body_type.is_some()
+            || !self.request_editor.body().to_string().trim().is_empty();
+        if has_body {
+            return self.show_error("the request already has a body");
+        }
+
+        let (base, params) = hac_core::query::split_query(&request.read().unwrap().uri);
+        if params.is_empty() {
+            return self.show_error("the uri has no query parameters to convert");
+        }
+
+        let mut request = request.write().unwrap();
+        request.uri = base;
+        request.body = Some(hac_core::query::query_to_json(&params));
+        request.body_type = Some(BodyType::Json);
+        drop(request);
+
+        self.rebuild_request_editor();
+        self.sync_collection_changes();
+    }
+
+    /// recreates the editor of the selected request, used when its body is
+    /// changed from outside of the editor
+    fn rebuild_request_editor(&mut self) {
+        let selected_id = self
+            .collection_store
+            .borrow()
+            .get_selected_request()
+            .map(|req| req.read().unwrap().id.clone());
+        if let Some(selected_id) = selected_id {
+            self.forget_request_editor(&selected_id);
+        }
+        self.request_editor = RequestEditor::new(
+            self.colors,
+            self.config.clone(),
+            self.collection_store.clone(),
+            self.layout.req_editor,
+        );
+    }
+
+    /// sends every enabled request shown on the sidebar and opens the list of
+    /// their results. While a batch is still running its results are shown
+    /// again instead of sending a new one
+    fn send_visible_requests(&mut self) {
+        if self
+            .batch_results
+            .as_ref()
+            .is_some_and(|batch| !batch.is_finished())
+        {
+            self.collection_store
+                .borrow_mut()
+                .push_overlay(CollectionViewerOverlay::BatchResults);
+            return;
+        }
+
+        let Some(context) = self.request_context() else {
+            return;
+        };
+        let mut store = self.collection_store.borrow_mut();
+        let requests = store.visible_requests();
+        if requests.is_empty() {
+            drop(store);
+            return self.show_error("there are no enabled requests on the sidebar to send");
+        }
+
+        let run_order = store
+            .get_collection()
+            .map(|collection| collection.borrow().run_order.clone())
+            .unwrap_or_default();
+        let requests = hac_core::collection::collection::apply_run_order(requests, &run_order);
+
+        self.batch_results = Some(BatchResults::send(
+            self.colors,
+            requests,
+            context,
+            !run_order.is_empty(),
+        ));
+        store.push_overlay(CollectionViewerOverlay::BatchResults);
+    }
+
+    /// records the status of the requests of the running batch that finished
+    fn drain_batch_results(&mut self) {
+        let Some(batch) = self.batch_results.as_mut() else {
+            return;
+        };
+
+        let finished = batch.drain();
+        if finished.is_empty() {
+            return;
+        }
+
+        let mut store = self.collection_store.borrow_mut();
+        for (request_id, status) in finished {
+            store.dispatch(CollectionStoreAction::RecordStatus(request_id, status));
+        }
+        drop(store);
+        self.sidebar.rebuild_tree_view();
+    }
+
+    /// lists every other collection on disk so the user can pick one to merge
+    /// into the current collection
+    fn open_collection_picker(&mut self) {
+        let current_path = self
+            .collection_store
+            .borrow()
+            .get_collection()
+            .map(|collection| collection.borrow().path.clone());
+        let collections = match hac_core::collection::collection::get_collections_from_config() {
+            Ok((collections, _)) => collections,
+            Err(e) => return self.show_error(format!("failed to load collections: {e}")),
+        };
+        let collections = collections
+            .into_iter()
+            .filter(|collection| current_path.as_ref().ne(&Some(&collection.path)))
+            .collect::<Vec<_>>();
+
+        if collections.is_empty() {
+            return self.show_error("there are no other collections to merge");
+        }
+
+        self.collection_picker = Some(CollectionPicker::new(self.colors, collections));
+        self.collection_store
+            .borrow_mut()
+            .push_overlay(CollectionViewerOverlay::MergeCollection);
+    }
+
+    /// copies every request and directory of `source` into the current
+    /// collection, suffixing the names that clash, and saves the result
+    fn merge_collection(&mut self, source: &Collection) {
+        {
+            let store = self.collection_store.borrow();
+            let Some(collection) = store.get_collection() else {
+                return;
+            };
+            let mut collection = collection.borrow_mut();
+            let requests = collection
+                .requests
+                .get_or_insert(Arc::new(RwLock::new(vec![])));
+            hac_core::collection::import::append_collection(&mut requests.write().unwrap(), source);
+        }
+
+        self.sidebar.rebuild_tree_view();
+        self.sync_collection_changes();
+        toast::show(format!("merged {} into this collection", source.info.name));
+    }
+
+    fn show_error(&mut self, message: impl Into<String>) {
+        self.error_popup_state.reset();
+        self.collection_store
+            .borrow_mut()
+            .push_overlay(CollectionViewerOverlay::Error(message.into()));
+    }
+
+    fn update_selection(&mut self, pane_to_select: Option<PaneFocus>) {
+        self.collection_store
+            .borrow_mut()
+            .dispatch(CollectionStoreAction::SetSelectedPane(pane_to_select));
+    }
+
+    fn update_focus(&mut self, pane_to_focus: PaneFocus) {
+        self.collection_store
+            .borrow_mut()
+            .dispatch(CollectionStoreAction::SetFocusedPane(pane_to_focus));
+    }
+
+    /// handles a key event on whichever pane or overlay has focus
+    fn dispatch_key_event(&mut self, key_event: KeyEvent) -> anyhow::Result<Option<Command>> {
+        // when `C-c` is configured to go back it is handled exactly as `Esc`, so
+        // every pane and popup goes back the same way they do with `Esc`
+        let key_event = match (self.config.ctrl_c, key_event.code, key_event.modifiers) {
+            (CtrlCBehavior::Back, KeyCode::Char('c'), KeyModifiers::CONTROL) => {
+                KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)
+            }
+            _ => key_event,
+        };
+
+        if self
+            .collection_store
+            .borrow()
+            .peek_overlay()
+            .eq(&CollectionViewerOverlay::MissingCollection)
+        {
+            self.handle_missing_collection_key_event(key_event);
+            return Ok(None);
+        }
+
+        let overlay = self.collection_store.borrow().peek_overlay();
+        if let CollectionViewerOverlay::Error(_) = overlay {
+            match key_event.code {
+                KeyCode::Char('o') | KeyCode::Esc | KeyCode::Enter => {
+                    self.collection_store.borrow_mut().pop_overlay();
+                }
+                KeyCode::Char('j') | KeyCode::Down => self.error_popup_state.scroll_down(),
+                KeyCode::Char('k') | KeyCode::Up => self.error_popup_state.scroll_up(),
+                _ => {}
+            }
+            return Ok(None);
+        }
+
+        if let CollectionViewerOverlay::LargeBodyWarning(_) = overlay {
+            self.handle_large_body_warning_key_event(key_event);
+            return Ok(None);
+        }
+
+        if let CollectionViewerOverlay::UnsavedEdits(target) = overlay {
+            return Ok(self.handle_unsaved_edits_key_event(key_event, target));
+        }
+
+        if overlay.eq(&CollectionViewerOverlay::CommandPalette) {
+            return match self.command_palette.handle_key_event(key_event)? {
+                Some(CommandPaletteEvent::Run(action)) => {
+                    self.collection_store.borrow_mut().pop_overlay();
+                    self.run_palette_action(action)
+                }
+                Some(CommandPaletteEvent::Cancel) => {
+                    self.collection_store.borrow_mut().pop_overlay();
+                    Ok(None)
+                }
+                None => Ok(None),
+            };
+        }
+
+        if overlay.eq(&CollectionViewerOverlay::MergeCollection) {
+            let event = match self.collection_picker.as_mut() {
+                Some(picker) => picker.handle_key_event(key_event)?,
+                None => Some(CollectionPickerEvent::Cancel),
+            };
+            match event {
+                Some(CollectionPickerEvent::Confirm(source)) => {
+                    self.collection_store.borrow_mut().pop_overlay();
+                    self.collection_picker = None;
+                    self.merge_collection(&source);
+                }
+                Some(CollectionPickerEvent::Cancel) => {
+                    self.collection_store.borrow_mut().pop_overlay();
+                    self.collection_picker = None;
+                }
+                None => {}
+            }
+            return Ok(None);
+        }
+
+        if overlay.eq(&CollectionViewerOverlay::EnvironmentPicker) {
+            let event = match self.environment_picker.as_mut() {
+                Some(picker) => picker.handle_key_event(key_event)?,
+                None => Some(EnvironmentPickerEvent::Cancel),
+            };
+            match event {
+                Some(EnvironmentPickerEvent::Confirm(environment)) => {
+                    self.collection_store.borrow_mut().pop_overlay();
+                    self.environment_picker = None;
+                    self.set_active_environment(environment);
+                }
+                Some(EnvironmentPickerEvent::Cancel) => {
+                    self.collection_store.borrow_mut().pop_overlay();
+                    self.environment_picker = None;
+                }
+                None => {}
+            }
+            return Ok(None);
+        }
+
+        if overlay.eq(&CollectionViewerOverlay::RecentRequests) {
+            let event = match self.recent_requests.as_mut() {
+                Some(recent) => recent.handle_key_event(key_event)?,
+                None => Some(RecentRequestsEvent::Cancel),
+            };
+            return match event {
+                Some(RecentRequestsEvent::Open { request, send }) => {
+                    self.collection_store.borrow_mut().pop_overlay();
+                    self.recent_requests = None;
+                    Ok(self.open_recent_request(request, send))
+                }
+                Some(RecentRequestsEvent::Cancel) => {
+                    self.collection_store.borrow_mut().pop_overlay();
+                    self.recent_requests = None;
+                    Ok(None)
+                }
+                None => Ok(None),
+            };
+        }
+
+        if overlay.eq(&CollectionViewerOverlay::ResolvedRequest) {
+            let event = match self.resolved_request.as_mut() {
+                Some(resolved) => resolved.handle_key_event(key_event)?,
+                None => Some(ResolvedRequestEvent::Close),
+            };
+            match event {
+                Some(ResolvedRequestEvent::Copy(text)) => {
+                    copy_to_clipboard(&text)?;
+                    toast::show("copied to clipboard");
+                }
+                Some(ResolvedRequestEvent::Close) => {
+                    self.collection_store.borrow_mut().pop_overlay();
+                    self.resolved_request = None;
+                }
+                None => {}
+            }
+            return Ok(None);
+        }
+
+        if overlay.eq(&CollectionViewerOverlay::RunOrder) {
+            let event = match self.run_order.as_mut() {
+                Some(run_order) => run_order.handle_key_event(key_event)?,
+                None => Some(RunOrderEvent::Cancel),
+            };
+            match event {
+                Some(RunOrderEvent::Save(order)) => {
+                    self.collection_store.borrow_mut().pop_overlay();
+                    self.run_order = None;
+                    self.save_run_order(order);
+                }
+                Some(RunOrderEvent::Cancel) => {
+                    self.collection_store.borrow_mut().pop_overlay();
+                    self.run_order = None;
+                }
+                None => {}
+            }
+            return Ok(None);
+        }
+
+        if overlay.eq(&CollectionViewerOverlay::BatchResults) {
+            let event = match self.batch_results.as_mut() {
+                Some(batch) => batch.handle_key_event(key_event)?,
+                None => Some(BatchResultsEvent::Close),
+            };
+            if let Some(BatchResultsEvent::Close) = event {
+                self.collection_store.borrow_mut().pop_overlay();
+            }
+            return Ok(None);
+        }
+
+        // most terminals can't tell `C-S-p` apart from `C-p`, so we accept both
+        if let (KeyCode::Char('p') | KeyCode::Char('P'), true, CollectionViewerOverlay::None) = (
+            key_event.code,
+            key_event.modifiers.contains(KeyModifiers::CONTROL),
+            &overlay,
+        ) {
+            self.command_palette = CommandPalette::new(self.colors);
+            self.collection_store
+                .borrow_mut()
+                .push_overlay(CollectionViewerOverlay::CommandPalette);
+            return Ok(None);
+        }
+
+        // `C-Tab` only reaches us on terminals with enhanced keyboard support, so
+        // `C-t` does the same everywhere else
+        if let (KeyCode::Tab | KeyCode::Char('t'), true, CollectionViewerOverlay::None) = (
+            key_event.code,
+            key_event.modifiers.contains(KeyModifiers::CONTROL),
+            &overlay,
+        ) {
+            self.toggle_editor_preview();
+            return Ok(None);
+        }
 
-    fn handle_key_event(&mut self, key_event: KeyEvent) -> anyhow::Result<Option<Command>> {
         if let (
             None,
             KeyEvent {
@@ -427,6 +2014,9 @@ impl Eventful for CollectionViewer<'_> {
                     let curr_pane = self.collection_store.borrow().get_focused_pane();
                     self.update_selection(Some(curr_pane));
                 }
+                KeyCode::Esc if overlay.eq(&CollectionViewerOverlay::None) => {
+                    return Ok(self.leave_selected_request(LeaveTarget::Back));
+                }
                 _ => {}
             }
             return Ok(None);
@@ -435,52 +2025,72 @@ impl Eventful for CollectionViewer<'_> {
         let selected_pane = self.collection_store.borrow().get_selected_pane();
         if let Some(curr_pane) = selected_pane {
             match curr_pane {
-                PaneFocus::Sidebar => match self.sidebar.handle_key_event(key_event)? {
-                    Some(SidebarEvent::CreateRequest) => self
-                        .collection_store
-                        .borrow_mut()
-                        .push_overlay(CollectionViewerOverlay::CreateRequest),
-                    Some(SidebarEvent::EditRequest) => self
-                        .collection_store
-                        .borrow_mut()
-                        .push_overlay(CollectionViewerOverlay::EditRequest),
-                    Some(SidebarEvent::EditDirectory) => self
-                        .collection_store
-                        .borrow_mut()
-                        .push_overlay(CollectionViewerOverlay::EditDirectory),
-                    Some(SidebarEvent::CreateDirectory) => self
-                        .collection_store
-                        .borrow_mut()
-                        .push_overlay(CollectionViewerOverlay::CreateDirectory),
-                    Some(SidebarEvent::DeleteItem(item_id)) => self
-                        .collection_store
-                        .borrow_mut()
-                        .push_overlay(CollectionViewerOverlay::DeleteSidebarItem(item_id)),
-                    Some(SidebarEvent::RemoveSelection) => self.update_selection(None),
-                    Some(SidebarEvent::SelectNext) => {
-                        self.update_selection(None);
-                        self.focus_next();
-                    }
-                    Some(SidebarEvent::SelectPrev) => {
-                        self.update_selection(None);
-                        self.focus_prev();
+                PaneFocus::Sidebar => {
+                    let prev_request = self.collection_store.borrow().get_selected_request();
+                    match self.sidebar.handle_key_event(key_event)? {
+                        Some(SidebarEvent::CreateRequest) => self
+                            .collection_store
+                            .borrow_mut()
+                            .push_overlay(CollectionViewerOverlay::CreateRequest),
+                        Some(SidebarEvent::EditRequest) => self
+                            .collection_store
+                            .borrow_mut()
+                            .push_overlay(CollectionViewerOverlay::EditRequest),
+                        Some(SidebarEvent::EditDirectory) => self
+                            .collection_store
+                            .borrow_mut()
+                            .push_overlay(CollectionViewerOverlay::EditDirectory),
+                        Some(SidebarEvent::CreateDirectory) => self
+                            .collection_store
+                            .borrow_mut()
+                            .push_overlay(CollectionViewerOverlay::CreateDirectory),
+                        Some(SidebarEvent::DeleteItem(item_id)) => self
+                            .collection_store
+                            .borrow_mut()
+                            .push_overlay(CollectionViewerOverlay::DeleteSidebarItem(item_id)),
+                        Some(SidebarEvent::CollectionSettings) => self
+                            .collection_store
+                            .borrow_mut()
+                            .push_overlay(CollectionViewerOverlay::CollectionSettings),
+                        Some(SidebarEvent::SendRequest) => self.maybe_send_request(),
+                        Some(SidebarEvent::EditBody) => {
+                            self.update_focus(PaneFocus::Editor);
+                            self.update_selection(Some(PaneFocus::Editor));
+                        }
+                        Some(SidebarEvent::RemoveSelection) => self.update_selection(None),
+                        Some(SidebarEvent::SelectNext) => {
+                            self.update_selection(None);
+                            self.focus_next();
+                        }
+                        Some(SidebarEvent::SelectPrev) => {
+                            self.update_selection(None);
+                            self.focus_prev();
+                        }
+                        Some(SidebarEvent::SyncCollection) => self.save_edits(),
+                        Some(SidebarEvent::RequestChanged(request_id)) => {
+                            self.forget_request_editor(&request_id);
+                            self.save_edits();
+                        }
+                        Some(SidebarEvent::Quit) => return Ok(Some(Command::Quit)),
+                        Some(SidebarEvent::RebuildView) => {
+                            self.change_selected_request(prev_request)
+                        }
+                        // when theres no event we do nothing
+                        None => {}
                     }
-                    Some(SidebarEvent::SyncCollection) => self.sync_collection_changes(),
-                    Some(SidebarEvent::Quit) => return Ok(Some(Command::Quit)),
-                    Some(SidebarEvent::RebuildView) => self.rebuild_everything(),
-                    // when theres no event we do nothing
-                    None => {}
-                },
+                }
                 PaneFocus::ReqUri => match self.request_uri.handle_key_event(key_event)? {
                     Some(RequestUriEvent::Quit) => return Ok(Some(Command::Quit)),
-                    Some(RequestUriEvent::SendRequest) => hac_core::net::handle_request(
-                        self.collection_store
+                    Some(RequestUriEvent::SendRequest) => self.maybe_send_request(),
+                    Some(RequestUriEvent::SendAndSaveSample) => {
+                        self.pending_sample = self
+                            .collection_store
                             .borrow()
                             .get_selected_request()
-                            .as_ref()
-                            .unwrap(),
-                        self.request_tx.clone(),
-                    ),
+                            .map(|req| req.read().unwrap().id.clone());
+                        self.maybe_send_request();
+                    }
+                    Some(RequestUriEvent::CopyResolvedUrl) => self.copy_resolved_url()?,
                     Some(RequestUriEvent::RemoveSelection) => self.update_selection(None),
                     Some(RequestUriEvent::SelectNext) => {
                         self.update_selection(None);
@@ -496,6 +2106,12 @@ impl Eventful for CollectionViewer<'_> {
                 PaneFocus::Preview => match self.response_viewer.handle_key_event(key_event)? {
                     Some(ResponseViewerEvent::RemoveSelection) => self.update_selection(None),
                     Some(ResponseViewerEvent::Quit) => return Ok(Some(Command::Quit)),
+                    Some(ResponseViewerEvent::FollowRedirect(status, url, location)) => {
+                        self.follow_redirect(status, &url, &location)
+                    }
+                    Some(ResponseViewerEvent::OpenPager(body)) => {
+                        return Ok(Some(Command::OpenPager(body)))
+                    }
                     // when theres no event we do nothing
                     None => {}
                 },
@@ -512,7 +2128,265 @@ impl Eventful for CollectionViewer<'_> {
     }
 }
 
-pub fn build_layout(size: Rect) -> ExplorerLayout {
+impl Renderable for CollectionViewer<'_> {
+    #[tracing::instrument(skip_all)]
+    fn draw(&mut self, frame: &mut Frame, size: Rect) -> anyhow::Result<()> {
+        // we redraw the background to prevent weird "transparent" spots when popups are
+        // cleared from the buffer
+        frame.render_widget(Clear, size);
+        frame.render_widget(Block::default().bg(self.colors.primary.background), size);
+
+        self.drain_responses_channel();
+        self.drain_batch_results();
+
+        self.sidebar.draw(frame, self.layout.sidebar)?;
+        self.response_viewer
+            .draw(frame, self.layout.response_preview)?;
+        self.request_editor.draw(frame, self.layout.req_editor)?;
+        self.request_uri.draw(frame, self.layout.req_uri)?;
+        match toast::current() {
+            Some(message) => frame.render_widget(
+                Line::from(message.fg(self.colors.normal.green)).centered(),
+                self.layout.hint_pane,
+            ),
+            None if !self.config.status_bar.is_empty() => self.draw_status_bar(frame),
+            None => {}
+        }
+
+        let overlay = self.collection_store.borrow().peek_overlay();
+        match overlay {
+            CollectionViewerOverlay::CreateRequest => {
+                self.sidebar.draw_overlay(frame, overlay)?;
+            }
+            CollectionViewerOverlay::CreateDirectory => {
+                self.sidebar.draw_overlay(frame, overlay)?;
+            }
+            CollectionViewerOverlay::SelectParentDir => {
+                self.sidebar.draw_overlay(frame, overlay)?;
+            }
+            CollectionViewerOverlay::EditRequest => {
+                self.sidebar.draw_overlay(frame, overlay)?;
+            }
+            CollectionViewerOverlay::EditDirectory => {
+                self.sidebar.draw_overlay(frame, overlay)?;
+            }
+            CollectionViewerOverlay::DeleteSidebarItem(_) => {
+                self.sidebar.draw_overlay(frame, overlay)?;
+            }
+            CollectionViewerOverlay::CollectionSettings => {
+                self.sidebar.draw_overlay(frame, overlay)?;
+            }
+            CollectionViewerOverlay::RequestDiff => {
+                self.sidebar.draw_overlay(frame, overlay)?;
+            }
+            CollectionViewerOverlay::ResponseSchema => {
+                self.response_viewer.draw_overlay(frame, overlay);
+            }
+            CollectionViewerOverlay::Error(message) => {
+                let popup = ErrorPopup::new(message, self.colors);
+                frame.render_stateful_widget(
+                    popup,
+                    self.layout.error_popup,
+                    &mut self.error_popup_state,
+                );
+            }
+            CollectionViewerOverlay::HeadersHelp => {
+                self.request_editor.draw_overlay(frame, overlay)?;
+            }
+            CollectionViewerOverlay::HeadersDelete => {
+                self.request_editor.draw_overlay(frame, overlay)?;
+            }
+            CollectionViewerOverlay::EffectiveHeaders => {
+                self.request_editor.draw_overlay(frame, overlay)?;
+            }
+            CollectionViewerOverlay::HeadersForm(_, _) => {
+                self.request_editor.draw_overlay(frame, overlay)?;
+            }
+            CollectionViewerOverlay::ChangeAuthMethod => {
+                self.request_editor.draw_overlay(frame, overlay)?;
+            }
+            CollectionViewerOverlay::MissingCollection => {
+                self.draw_missing_collection_prompt(frame);
+            }
+            CollectionViewerOverlay::UnsavedEdits(_) => {
+                self.draw_unsaved_edits_prompt(frame);
+            }
+            CollectionViewerOverlay::CommandPalette => {
+                self.command_palette.draw(frame, frame.size())?;
+            }
+            CollectionViewerOverlay::MergeCollection => {
+                if let Some(picker) = self.collection_picker.as_mut() {
+                    picker.draw(frame, frame.size())?;
+                }
+            }
+            CollectionViewerOverlay::EnvironmentPicker => {
+                if let Some(picker) = self.environment_picker.as_mut() {
+                    picker.draw(frame, frame.size())?;
+                }
+            }
+            CollectionViewerOverlay::RecentRequests => {
+                if let Some(recent) = self.recent_requests.as_mut() {
+                    recent.draw(frame, frame.size())?;
+                }
+            }
+            CollectionViewerOverlay::ResolvedRequest => {
+                if let Some(resolved) = self.resolved_request.as_mut() {
+                    resolved.draw(frame, frame.size())?;
+                }
+            }
+            CollectionViewerOverlay::RunOrder => {
+                if let Some(run_order) = self.run_order.as_mut() {
+                    run_order.draw(frame, frame.size())?;
+                }
+            }
+            CollectionViewerOverlay::BatchResults => {
+                if let Some(batch) = self.batch_results.as_mut() {
+                    batch.draw(frame, frame.size())?;
+                }
+            }
+            CollectionViewerOverlay::LargeBodyWarning(body_size) => {
+                self.draw_large_body_warning(frame, body_size);
+            }
+            CollectionViewerOverlay::None => {}
+        }
+
+        if self
+            .collection_store
+            .borrow()
+            .get_selected_pane()
+            .as_ref()
+            .is_some_and(|pane| pane.eq(&PaneFocus::Editor))
+        {
+            self.request_editor.maybe_draw_cursor(frame);
+        }
+
+        Ok(())
+    }
+
+    fn handle_tick(&mut self) -> anyhow::Result<()> {
+        if self.collection_sync_timer.elapsed().as_secs().ge(&5) {
+            self.sync_collection_changes();
+        }
+
+        let autosave = self.config.session_autosave_secs;
+        if autosave.gt(&0) && self.session_save_timer.elapsed().as_secs().ge(&autosave) {
+            self.session_save_timer = std::time::Instant::now();
+            self.save_session();
+        }
+        Ok(())
+    }
+
+    fn register_command_handler(&mut self, sender: UnboundedSender<Command>) -> anyhow::Result<()> {
+        self.global_command_sender = Some(sender);
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: Rect) {
+        let new_layout = build_layout(new_size, self.config.wrap_uri);
+        self.request_uri.resize(new_layout.req_uri);
+        self.request_editor.resize(new_layout.req_editor);
+        self.response_viewer.resize(new_layout.response_preview);
+        self.layout = new_layout;
+    }
+}
+
+impl Eventful for CollectionViewer<'_> {
+    type Result = Command;
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> anyhow::Result<Option<Command>> {
+        let command = self.dispatch_key_event(key_event)?;
+        // any key may have changed the selected request or the environment it
+        // is sent with, like editing it on a form or picking another environment
+        self.refresh_edited_mark();
+        Ok(command)
+    }
+}
+
+/// builds a sample out of a response, named after its status and the time it
+/// was saved, eg: `200 1718000000000`
+fn build_sample(response: &Response) -> ResponseSample {
+    let saved_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    let status = response.status.map(|status| status.as_u16());
+    let headers = response
+        .headers
+        .as_ref()
+        .map(|headers| {
+            headers
+                .iter()
+                .map(|(name, value)| {
+                    let value = String::from_utf8_lossy(value.as_bytes()).to_string();
+                    (name.to_string(), value)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    ResponseSample {
+        name: match status {
+            Some(status) => format!("{status} {saved_at}"),
+            None => saved_at.to_string(),
+        },
+        status,
+        headers,
+        body: response.body.clone(),
+        saved_at,
+    }
+}
+
+/// the inputs the edited mark was last computed from, along with `request`
+/// serialized by `serialize_for_fingerprint`
+#[derive(Debug)]
+struct FingerprintCache {
+    request: Request,
+    environment: Option<String>,
+    serialized: String,
+    body: TextObject<Write>,
+}
+
+/// everything a request sends but its body, along with the `environment` it
+/// is sent with. The body lives on the editor until the request is sent, so
+/// it is left out, and so are samples and timings, as they come from responses
+fn serialize_for_fingerprint(request: &Request, environment: Option<&str>) -> String {
+    let request = Request {
+        body: None,
+        samples: vec![],
+        timings: None,
+        ..request.clone()
+    };
+    serde_json::to_string(&(request, environment)).unwrap_or_default()
+}
+
+/// hash of a request serialized by `serialize_for_fingerprint` and the `body`
+/// on the editor
+fn request_fingerprint(serialized: &str, body: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    serialized.hash(&mut hasher);
+    body.hash(&mut hasher);
+    format!("{:x}", hasher.finish())
+}
+
+fn update_request_body(request: &Arc<RwLock<Request>>, body: String) {
+    // this is not the best idea for when we start implementing other kinds of
+    // body types like GraphQL
+    let mut request = request.write().unwrap();
+    match request.body_type {
+        // bodies from files are never written back from the editor
+        Some(BodyType::File(_)) => {}
+        // raw requests keep whatever was written, even an empty request
+        Some(BodyType::Raw) => request.body = Some(body),
+        // bodies typed on the editor are json, unless the request says otherwise
+        _ if !body.is_empty() => {
+            request.body = Some(body);
+            request.body_type.get_or_insert(BodyType::Json);
+        }
+        _ => {}
+    }
+}
+
+pub fn build_layout(size: Rect, wrap_uri: bool) -> ExplorerLayout {
     let [top_pane, hint_pane] = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Fill(1), Constraint::Length(1)])
@@ -520,12 +2394,18 @@ pub fn build_layout(size: Rect) -> ExplorerLayout {
 
     let [sidebar, right_pane] = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Length(30), Constraint::Fill(1)])
+        .constraints([
+            Constraint::Length(sidebar::SIDEBAR_WIDTH),
+            Constraint::Fill(1),
+        ])
         .areas(top_pane);
 
     let [req_uri, req_builder] = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Fill(1)])
+        .constraints([
+            Constraint::Length(uri_pane_height(wrap_uri)),
+            Constraint::Fill(1),
+        ])
         .areas(right_pane);
 
     let [req_editor, response_preview] = if size.width < 120 {
@@ -547,6 +2427,21 @@ pub fn build_layout(size: Rect) -> ExplorerLayout {
         11,
     );
 
+    let confirm_popup = Rect::new(
+        size.width.div(4),
+        size.height.div(2).saturating_sub(4),
+        size.width.div(2),
+        8,
+    );
+
+    // errors can get long, the popup shrinks to fit shorter ones
+    let error_popup = Rect::new(
+        size.width.div(4),
+        size.height.div(6),
+        size.width.div(2),
+        size.height.sub(size.height.div(3)),
+    );
+
     ExplorerLayout {
         hint_pane,
         sidebar,
@@ -554,5 +2449,7 @@ pub fn build_layout(size: Rect) -> ExplorerLayout {
         req_editor,
         response_preview,
         create_req_form,
+        confirm_popup,
+        error_popup,
     }
 }
@@ -0,0 +1,504 @@
+use crate::pages::collection_viewer::collection_viewer::PaneFocus;
+use crate::pages::overlay::make_overlay;
+use crate::pages::{Eventful, Renderable};
+
+use std::ops::{Add, Div, Sub};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Rect;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph};
+use ratatui::Frame;
+
+/// every action the command palette knows how to run, each of them maps to a
+/// keybinding that already exists on one of the panes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteAction {
+    SendRequest,
+    SendAndSaveSample,
+    CopyResolvedUrl,
+    SendVisible,
+    CreateRequest,
+    CreateDirectory,
+    EditItem,
+    DeleteItem,
+    CollectionSettings,
+    CopyAsFetch,
+    CompareRequests,
+    ToggleRequestEnabled,
+    SyncCollection,
+    ReloadConfig,
+    CopyJqAssertion,
+    CopyPytestAssertion,
+    ToggleOutline,
+    ToggleRawBody,
+    SortResponseKeys,
+    SelectResponse,
+    ResponseSchema,
+    OpenInPager,
+    BodyToQuery,
+    QueryToBody,
+    MergeCollection,
+    RecentRequests,
+    RunOrder,
+    ResolvedRequest,
+    SwitchEnvironment,
+    PickEnvironment,
+    TestConnection,
+    ToggleEtagCache,
+    ToggleCompression,
+    ToggleRawMode,
+    SwitchBody,
+    FocusSidebar,
+    FocusUri,
+    FocusEditor,
+    FocusPreview,
+    ToggleEditorPreview,
+    Quit,
+}
+
+impl PaletteAction {
+    pub const ALL: [PaletteAction; 41] = [
+        PaletteAction::SendRequest,
+        PaletteAction::SendAndSaveSample,
+        PaletteAction::CopyResolvedUrl,
+        PaletteAction::SendVisible,
+        PaletteAction::CreateRequest,
+        PaletteAction::CreateDirectory,
+        PaletteAction::EditItem,
+        PaletteAction::DeleteItem,
+        PaletteAction::CollectionSettings,
+        PaletteAction::CopyAsFetch,
+        PaletteAction::CompareRequests,
+        PaletteAction::ToggleRequestEnabled,
+        PaletteAction::SyncCollection,
+        PaletteAction::ReloadConfig,
+        PaletteAction::CopyJqAssertion,
+        PaletteAction::CopyPytestAssertion,
+        PaletteAction::ToggleOutline,
+        PaletteAction::ToggleRawBody,
+        PaletteAction::SortResponseKeys,
+        PaletteAction::SelectResponse,
+        PaletteAction::ResponseSchema,
+        PaletteAction::OpenInPager,
+        PaletteAction::BodyToQuery,
+        PaletteAction::QueryToBody,
+        PaletteAction::MergeCollection,
+        PaletteAction::RecentRequests,
+        PaletteAction::RunOrder,
+        PaletteAction::ResolvedRequest,
+        PaletteAction::SwitchEnvironment,
+        PaletteAction::PickEnvironment,
+        PaletteAction::TestConnection,
+        PaletteAction::ToggleEtagCache,
+        PaletteAction::ToggleCompression,
+        PaletteAction::ToggleRawMode,
+        PaletteAction::SwitchBody,
+        PaletteAction::FocusSidebar,
+        PaletteAction::FocusUri,
+        PaletteAction::FocusEditor,
+        PaletteAction::FocusPreview,
+        PaletteAction::ToggleEditorPreview,
+        PaletteAction::Quit,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PaletteAction::SendRequest => "Send request",
+            PaletteAction::SendAndSaveSample => "Send request and save response as sample",
+            PaletteAction::CopyResolvedUrl => "Copy request url with variables resolved",
+            PaletteAction::SendVisible => "Send every request visible on the sidebar",
+            PaletteAction::CreateRequest => "New request",
+            PaletteAction::CreateDirectory => "New directory",
+            PaletteAction::EditItem => "Edit hovered item",
+            PaletteAction::DeleteItem => "Delete hovered item",
+            PaletteAction::CollectionSettings => "Collection settings",
+            PaletteAction::CopyAsFetch => "Copy hovered request as fetch()",
+            PaletteAction::CompareRequests => "Compare hovered request with another",
+            PaletteAction::ToggleRequestEnabled => "Enable or disable hovered request",
+            PaletteAction::SyncCollection => "Save collection",
+            PaletteAction::ReloadConfig => "Reload config",
+            PaletteAction::CopyJqAssertion => "Copy jq assertion",
+            PaletteAction::CopyPytestAssertion => "Copy pytest assertion",
+            PaletteAction::ToggleOutline => "Toggle json outline",
+            PaletteAction::ToggleRawBody => "Toggle between pretty and raw body",
+            PaletteAction::SortResponseKeys => "Toggle sorting the json keys of the response",
+            PaletteAction::SelectResponse => "Select response text to copy",
+            PaletteAction::ResponseSchema => "Infer response schema",
+            PaletteAction::OpenInPager => "Open response body in pager",
+            PaletteAction::BodyToQuery => "Convert body to query parameters",
+            PaletteAction::QueryToBody => "Convert query parameters to body",
+            PaletteAction::MergeCollection => "Merge another collection into this one",
+            PaletteAction::RecentRequests => "Recently sent requests",
+            PaletteAction::RunOrder => "Set the run order of the collection",
+            PaletteAction::ResolvedRequest => "Preview request with variables resolved",
+            PaletteAction::SwitchEnvironment => "Switch to the next environment",
+            PaletteAction::PickEnvironment => "Pick the environment to use",
+            PaletteAction::TestConnection => "Check whether the base url is reachable",
+            PaletteAction::ToggleEtagCache => "Enable or disable ETag caching of the request",
+            PaletteAction::ToggleCompression => "Switch the compression the request asks for",
+            PaletteAction::ToggleRawMode => "Write the request as raw HTTP",
+            PaletteAction::SwitchBody => "Send the body of the next content type",
+            PaletteAction::FocusSidebar => "Focus sidebar",
+            PaletteAction::FocusUri => "Focus request uri",
+            PaletteAction::FocusEditor => "Focus request editor",
+            PaletteAction::FocusPreview => "Focus response preview",
+            PaletteAction::ToggleEditorPreview => "Toggle between editor and response",
+            PaletteAction::Quit => "Quit",
+        }
+    }
+
+    /// the keys that trigger the same action outside of the palette, displayed
+    /// next to each entry so users can learn them over time
+    pub fn hint(&self) -> &'static str {
+        match self {
+            PaletteAction::SendRequest => "u <enter>",
+            PaletteAction::SendAndSaveSample => "u C-s",
+            PaletteAction::CopyResolvedUrl => "u C-y",
+            PaletteAction::SendVisible => "",
+            PaletteAction::CreateRequest => "r n",
+            PaletteAction::CreateDirectory => "r d",
+            PaletteAction::EditItem => "r e",
+            PaletteAction::DeleteItem => "r D",
+            PaletteAction::CollectionSettings => "r S",
+            PaletteAction::CopyAsFetch => "r y",
+            PaletteAction::CompareRequests => "r c",
+            PaletteAction::ToggleRequestEnabled => "r x",
+            PaletteAction::SyncCollection => "",
+            PaletteAction::ReloadConfig => "",
+            PaletteAction::CopyJqAssertion => "p y",
+            PaletteAction::CopyPytestAssertion => "p Y",
+            PaletteAction::ToggleOutline => "p o",
+            PaletteAction::ToggleRawBody => "p R",
+            PaletteAction::SortResponseKeys => "p K",
+            PaletteAction::SelectResponse => "p v",
+            PaletteAction::ResponseSchema => "p s",
+            PaletteAction::OpenInPager => "p P",
+            PaletteAction::BodyToQuery => "",
+            PaletteAction::QueryToBody => "",
+            PaletteAction::MergeCollection => "",
+            PaletteAction::RecentRequests => "",
+            PaletteAction::RunOrder => "",
+            PaletteAction::ResolvedRequest => "",
+            PaletteAction::SwitchEnvironment => "",
+            PaletteAction::PickEnvironment => "",
+            PaletteAction::TestConnection => "",
+            PaletteAction::ToggleEtagCache => "",
+            PaletteAction::ToggleCompression => "",
+            PaletteAction::ToggleRawMode => "",
+            PaletteAction::SwitchBody => "",
+            PaletteAction::FocusSidebar => "r",
+            PaletteAction::FocusUri => "u",
+            PaletteAction::FocusEditor => "e",
+            PaletteAction::FocusPreview => "p",
+            PaletteAction::ToggleEditorPreview => "C-t",
+            PaletteAction::Quit => "C-c",
+        }
+    }
+
+    /// the pane that handles this action and the key event it would receive
+    /// when the user presses the keybinding directly, actions that don't live
+    /// on a pane return `None`
+    pub fn target(&self) -> Option<(PaneFocus, Option<KeyEvent>)> {
+        let key = |code| Some(KeyEvent::new(code, KeyModifiers::NONE));
+
+        match self {
+            PaletteAction::SendRequest => Some((PaneFocus::ReqUri, key(KeyCode::Enter))),
+            PaletteAction::SendAndSaveSample => Some((
+                PaneFocus::ReqUri,
+                Some(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)),
+            )),
+            PaletteAction::CopyResolvedUrl => Some((
+                PaneFocus::ReqUri,
+                Some(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::CONTROL)),
+            )),
+            PaletteAction::CreateRequest => Some((PaneFocus::Sidebar, key(KeyCode::Char('n')))),
+            PaletteAction::CreateDirectory => Some((PaneFocus::Sidebar, key(KeyCode::Char('d')))),
+            PaletteAction::EditItem => Some((PaneFocus::Sidebar, key(KeyCode::Char('e')))),
+            PaletteAction::DeleteItem => Some((PaneFocus::Sidebar, key(KeyCode::Char('D')))),
+            PaletteAction::CollectionSettings => {
+                Some((PaneFocus::Sidebar, key(KeyCode::Char('S'))))
+            }
+            PaletteAction::CopyAsFetch => Some((PaneFocus::Sidebar, key(KeyCode::Char('y')))),
+            PaletteAction::CompareRequests => Some((PaneFocus::Sidebar, key(KeyCode::Char('c')))),
+            PaletteAction::ToggleRequestEnabled => {
+                Some((PaneFocus::Sidebar, key(KeyCode::Char('x'))))
+            }
+            PaletteAction::CopyJqAssertion => Some((PaneFocus::Preview, key(KeyCode::Char('y')))),
+            PaletteAction::CopyPytestAssertion => {
+                Some((PaneFocus::Preview, key(KeyCode::Char('Y'))))
+            }
+            PaletteAction::ToggleOutline => Some((PaneFocus::Preview, key(KeyCode::Char('o')))),
+            PaletteAction::ToggleRawBody => Some((PaneFocus::Preview, key(KeyCode::Char('R')))),
+            PaletteAction::SortResponseKeys => Some((PaneFocus::Preview, key(KeyCode::Char('K')))),
+            PaletteAction::SelectResponse => Some((PaneFocus::Preview, key(KeyCode::Char('v')))),
+            PaletteAction::ResponseSchema => Some((PaneFocus::Preview, key(KeyCode::Char('s')))),
+            PaletteAction::OpenInPager => Some((PaneFocus::Preview, key(KeyCode::Char('P')))),
+            PaletteAction::FocusSidebar => Some((PaneFocus::Sidebar, None)),
+            PaletteAction::FocusUri => Some((PaneFocus::ReqUri, None)),
+            PaletteAction::FocusEditor => Some((PaneFocus::Editor, None)),
+            PaletteAction::FocusPreview => Some((PaneFocus::Preview, None)),
+            PaletteAction::SyncCollection
+            | PaletteAction::ReloadConfig
+            | PaletteAction::ToggleEditorPreview
+            | PaletteAction::BodyToQuery
+            | PaletteAction::QueryToBody
+            | PaletteAction::MergeCollection
+            | PaletteAction::RecentRequests
+            | PaletteAction::RunOrder
+            | PaletteAction::ResolvedRequest
+            | PaletteAction::SwitchEnvironment
+            | PaletteAction::PickEnvironment
+            | PaletteAction::TestConnection
+            | PaletteAction::ToggleEtagCache
+            | PaletteAction::ToggleCompression
+            | PaletteAction::ToggleRawMode
+            | PaletteAction::SwitchBody
+            | PaletteAction::SendVisible
+            | PaletteAction::Quit => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandPaletteEvent {
+    /// user chose an action from the list, the parent is responsible for
+    /// running it and closing the palette
+    Run(PaletteAction),
+    /// user closed the palette without choosing anything
+    Cancel,
+}
+
+#[derive(Debug)]
+pub struct CommandPalette<'cp> {
+    colors: &'cp hac_colors::Colors,
+    query: String,
+    matches: Vec<PaletteAction>,
+    selected: usize,
+}
+
+impl<'cp> CommandPalette<'cp> {
+    pub fn new(colors: &'cp hac_colors::Colors) -> Self {
+        CommandPalette {
+            colors,
+            query: String::default(),
+            matches: PaletteAction::ALL.to_vec(),
+            selected: 0,
+        }
+    }
+
+    fn update_matches(&mut self) {
+        let mut scored = PaletteAction::ALL
+            .iter()
+            .filter_map(|action| fuzzy_score(&self.query, action.label()).map(|s| (s, *action)))
+            .collect::<Vec<_>>();
+        // sort_by is stable, so entries with the same score keep their order
+        scored.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        self.matches = scored.into_iter().map(|(_, action)| action).collect();
+        self.selected = 0;
+    }
+
+    fn build_entry(&self, action: &PaletteAction, selected: bool, width: usize) -> Line<'_> {
+        let label = action.label();
+        let hint = action.hint();
+        let padding = width.saturating_sub(label.len().add(hint.len()).add(2));
+
+        let bg = if selected {
+            self.colors.primary.hover
+        } else {
+            self.colors.primary.background
+        };
+
+        Line::from(vec![
+            Span::from(format!(" {label}"))
+                .fg(self.colors.normal.white)
+                .bg(bg),
+            Span::from(" ".repeat(padding)).bg(bg),
+            Span::from(format!("{hint} "))
+                .fg(self.colors.bright.black)
+                .bg(bg),
+        ])
+    }
+}
+
+impl Renderable for CommandPalette<'_> {
+    fn draw(&mut self, frame: &mut Frame, _: Rect) -> anyhow::Result<()> {
+        make_overlay(self.colors, self.colors.normal.black, 0.1, frame);
+
+        let size = frame.size();
+        let height = (PaletteAction::ALL.len() as u16)
+            .add(4)
+            .min(size.height.sub(size.height.div(6)));
+        let size = Rect::new(
+            size.width.div(4),
+            size.height.div(6),
+            size.width.div(2),
+            height,
+        );
+
+        let block = Block::default()
+            .title("Command palette")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.colors.normal.red))
+            .bg(self.colors.primary.background)
+            .padding(Padding::horizontal(1));
+        let inner = block.inner(size);
+
+        let mut lines = vec![
+            Line::from(vec![
+                "> ".fg(self.colors.normal.red),
+                self.query.clone().fg(self.colors.normal.white),
+            ]),
+            Line::from(""),
+        ];
+
+        if self.matches.is_empty() {
+            lines.push(Line::from(
+                "no matching commands".fg(self.colors.bright.black),
+            ));
+        }
+
+        let amount_on_view = inner.height.sub(2.min(inner.height)) as usize;
+        let scroll = self
+            .selected
+            .saturating_sub(amount_on_view.saturating_sub(1));
+        self.matches
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(amount_on_view)
+            .for_each(|(idx, action)| {
+                lines.push(self.build_entry(action, idx.eq(&self.selected), inner.width.into()))
+            });
+
+        frame.render_widget(Clear, size);
+        frame.render_widget(Paragraph::new(lines).block(block), size);
+        frame.set_cursor(
+            inner.x.add(2).add(self.query.chars().count() as u16),
+            inner.y,
+        );
+
+        Ok(())
+    }
+}
+
+impl Eventful for CommandPalette<'_> {
+    type Result = CommandPaletteEvent;
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> anyhow::Result<Option<Self::Result>> {
+        if let (KeyCode::Char('c'), KeyModifiers::CONTROL) = (key_event.code, key_event.modifiers) {
+            return Ok(Some(CommandPaletteEvent::Cancel));
+        }
+
+        match key_event.code {
+            KeyCode::Esc => return Ok(Some(CommandPaletteEvent::Cancel)),
+            KeyCode::Enter => {
+                if let Some(action) = self.matches.get(self.selected) {
+                    return Ok(Some(CommandPaletteEvent::Run(*action)));
+                }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                if !self.matches.is_empty() {
+                    self.selected = self.selected.add(1).min(self.matches.len().sub(1));
+                }
+            }
+            KeyCode::Up | KeyCode::BackTab => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Char('n') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                if !self.matches.is_empty() {
+                    self.selected = self.selected.add(1).min(self.matches.len().sub(1));
+                }
+            }
+            KeyCode::Char('p') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.selected = self.selected.saturating_sub(1)
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.update_matches();
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.update_matches();
+            }
+            _ => {}
+        }
+
+        Ok(None)
+    }
+}
+
+/// scores how well `query` matches `target` as a case insensitive subsequence,
+/// returning `None` when some character of the query is missing. Consecutive
+/// matches and matches at the start of a word are rewarded, so "nr" ranks "New
+/// request" above entries that just happen to contain both letters
+fn fuzzy_score(query: &str, target: &str) -> Option<i32> {
+    let mut score = 0;
+    let mut target_chars = target.chars().enumerate().peekable();
+    let mut prev_match: Option<usize> = None;
+    let mut prev_char = ' ';
+
+    for query_char in query.chars().map(|c| c.to_ascii_lowercase()) {
+        if query_char.eq(&' ') {
+            continue;
+        }
+
+        loop {
+            let (idx, target_char) = target_chars.next()?;
+            let is_word_start = prev_char.eq(&' ');
+            prev_char = target_char;
+
+            if target_char.to_ascii_lowercase().ne(&query_char) {
+                continue;
+            }
+
+            score += 1;
+            if is_word_start {
+                score += 3;
+            }
+            if prev_match.is_some_and(|prev| prev.add(1).eq(&idx)) {
+                score += 2;
+            }
+            prev_match = Some(idx);
+            break;
+        }
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score_matches_subsequences() {
+        assert!(fuzzy_score("", "New request").is_some());
+        assert!(fuzzy_score("nreq", "New request").is_some());
+        assert!(fuzzy_score("NEW", "New request").is_some());
+        assert!(fuzzy_score("qn", "New request").is_none());
+        assert!(fuzzy_score("xyz", "New request").is_none());
+    }
+
+    #[test]
+    fn test_filtering_ranks_better_matches_first() {
+        let colors = hac_colors::Colors::default();
+        let mut palette = CommandPalette::new(&colors);
+
+        "nr".chars().for_each(|c| {
+            palette
+                .handle_key_event(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+                .unwrap();
+        });
+
+        assert_eq!(palette.matches.first(), Some(&PaletteAction::CreateRequest));
+
+        let event = palette
+            .handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+            .unwrap();
+        assert_eq!(
+            event,
+            Some(CommandPaletteEvent::Run(PaletteAction::CreateRequest))
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use crate::pages::list_overlay::ListOverlay;
+use crate::pages::{Eventful, Renderable};
+
+use std::ops::Add;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::{Line, Span};
+use ratatui::Frame;
+
+#[derive(Debug, PartialEq)]
+pub enum EnvironmentPickerEvent {
+    /// user picked an environment, `None` when picking to use no environment
+    Confirm(Option<String>),
+    /// user closed the picker without choosing anything
+    Cancel,
+}
+
+/// an environment listed on the picker, along with its base url
+#[derive(Debug)]
+pub struct EnvironmentEntry {
+    pub name: String,
+    pub base_url: Option<String>,
+}
+
+/// list of the environments of the collection to choose the active one from,
+/// the first entry stands for using no environment
+#[derive(Debug)]
+pub struct EnvironmentPicker<'ep> {
+    colors: &'ep hac_colors::Colors,
+    list: ListOverlay<'ep, Option<EnvironmentEntry>>,
+}
+
+impl<'ep> EnvironmentPicker<'ep> {
+    pub fn new(
+        colors: &'ep hac_colors::Colors,
+        environments: Vec<EnvironmentEntry>,
+        active: Option<&str>,
+    ) -> Self {
+        let selected = active
+            .and_then(|active| environments.iter().position(|env| env.name.eq(active)))
+            .map(|idx| idx.add(1))
+            .unwrap_or_default();
+
+        let entries = std::iter::once(None)
+            .chain(environments.into_iter().map(Some))
+            .collect();
+        let mut list = ListOverlay::new(
+            colors,
+            "Environment",
+            "relative uris are sent against the base url of the environment",
+            entries,
+        );
+        list.select(selected);
+
+        EnvironmentPicker { colors, list }
+    }
+
+    fn build_entry(&self, env: &Option<EnvironmentEntry>, width: usize) -> Line<'static> {
+        let (name, base_url) = match env {
+            Some(env) => (
+                env.name.as_str(),
+                env.base_url.as_deref().unwrap_or_default(),
+            ),
+            None => ("no environment", ""),
+        };
+        let padding =
+            width.saturating_sub(name.chars().count().add(base_url.chars().count()).add(2));
+
+        Line::from(vec![
+            Span::from(format!(" {name}")).fg(self.colors.normal.white),
+            Span::from(" ".repeat(padding)),
+            Span::from(format!("{base_url} ")).fg(self.colors.bright.black),
+        ])
+    }
+}
+
+impl Renderable for EnvironmentPicker<'_> {
+    fn draw(&mut self, frame: &mut Frame, _: Rect) -> anyhow::Result<()> {
+        self.list
+            .draw(frame, |_, env, width| self.build_entry(env, width));
+
+        Ok(())
+    }
+}
+
+impl Eventful for EnvironmentPicker<'_> {
+    type Result = EnvironmentPickerEvent;
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> anyhow::Result<Option<Self::Result>> {
+        if let (KeyCode::Char('c'), KeyModifiers::CONTROL) = (key_event.code, key_event.modifiers) {
+            return Ok(Some(EnvironmentPickerEvent::Cancel));
+        }
+        if self.list.handle_navigation(key_event) {
+            return Ok(None);
+        }
+
+        match key_event.code {
+            KeyCode::Esc => Ok(Some(EnvironmentPickerEvent::Cancel)),
+            KeyCode::Enter => {
+                let name = self.list.items()[self.list.selected()]
+                    .as_ref()
+                    .map(|env| env.name.clone());
+                Ok(Some(EnvironmentPickerEvent::Confirm(name)))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_picking_starts_at_the_active_environment() {
+        let colors = hac_colors::Colors::default();
+        let environments = ["dev", "prod"]
+            .map(|name| EnvironmentEntry {
+                name: name.to_string(),
+                base_url: None,
+            })
+            .into();
+        let mut picker = EnvironmentPicker::new(&colors, environments, Some("dev"));
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        picker.handle_key_event(key(KeyCode::Char('j'))).unwrap();
+        picker.handle_key_event(key(KeyCode::Char('j'))).unwrap();
+        assert_eq!(
+            picker.handle_key_event(key(KeyCode::Enter)).unwrap(),
+            Some(EnvironmentPickerEvent::Confirm(Some(String::from("prod"))))
+        );
+
+        picker.handle_key_event(key(KeyCode::Up)).unwrap();
+        picker.handle_key_event(key(KeyCode::Up)).unwrap();
+        assert_eq!(
+            picker.handle_key_event(key(KeyCode::Enter)).unwrap(),
+            Some(EnvironmentPickerEvent::Confirm(None))
+        );
+    }
+}
@@ -1,9 +1,16 @@
+mod batch_results;
+mod collection_picker;
 pub mod collection_store;
 #[allow(clippy::module_inception)]
 pub mod collection_viewer;
+mod command_palette;
+mod environment_picker;
+mod recent_requests;
 mod request_editor;
 mod request_uri;
-mod response_viewer;
-mod sidebar;
+mod resolved_request;
+pub mod response_viewer;
+mod run_order;
+pub mod sidebar;
 
 pub use collection_viewer::CollectionViewer;
@@ -0,0 +1,98 @@
+use hac_config::RecentRequest;
+
+use crate::pages::list_overlay::ListOverlay;
+use crate::pages::{Eventful, Renderable};
+
+use std::ops::Add;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::{Line, Span};
+use ratatui::Frame;
+
+#[derive(Debug)]
+pub enum RecentRequestsEvent {
+    /// user picked a request to go to, `send` is set when it should also be
+    /// sent right away
+    Open { request: RecentRequest, send: bool },
+    /// user closed the list without choosing anything
+    Cancel,
+}
+
+/// list of the requests sent recently on any collection, used to quickly get
+/// back to one of them
+#[derive(Debug)]
+pub struct RecentRequests<'rr> {
+    colors: &'rr hac_colors::Colors,
+    list: ListOverlay<'rr, RecentRequest>,
+}
+
+impl<'rr> RecentRequests<'rr> {
+    pub fn new(colors: &'rr hac_colors::Colors, requests: Vec<RecentRequest>) -> Self {
+        RecentRequests {
+            colors,
+            list: ListOverlay::new(
+                colors,
+                "Recently sent",
+                "[Open: Enter] [Open and send: s] [Close: Esc]",
+                requests,
+            ),
+        }
+    }
+
+    fn build_entry(&self, request: &RecentRequest, width: usize) -> Line<'static> {
+        let method = format!(" {:<7}", request.method);
+        let name = format!("{} ", request.request_name);
+        let collection = format!("{} ", request.collection_name);
+        let padding = width.saturating_sub(
+            method
+                .chars()
+                .count()
+                .add(name.chars().count())
+                .add(collection.chars().count()),
+        );
+
+        Line::from(vec![
+            Span::from(method).fg(self.colors.normal.red),
+            Span::from(name).fg(self.colors.normal.white),
+            Span::from(" ".repeat(padding)),
+            Span::from(collection).fg(self.colors.bright.black),
+        ])
+    }
+
+    fn take_selected(&mut self, send: bool) -> Option<RecentRequestsEvent> {
+        self.list
+            .take_selected()
+            .map(|request| RecentRequestsEvent::Open { request, send })
+    }
+}
+
+impl Renderable for RecentRequests<'_> {
+    fn draw(&mut self, frame: &mut Frame, _: Rect) -> anyhow::Result<()> {
+        self.list
+            .draw(frame, |_, request, width| self.build_entry(request, width));
+
+        Ok(())
+    }
+}
+
+impl Eventful for RecentRequests<'_> {
+    type Result = RecentRequestsEvent;
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> anyhow::Result<Option<Self::Result>> {
+        if let (KeyCode::Char('c'), KeyModifiers::CONTROL) = (key_event.code, key_event.modifiers) {
+            return Ok(Some(RecentRequestsEvent::Cancel));
+        }
+        if self.list.handle_navigation(key_event) {
+            return Ok(None);
+        }
+
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q') => Ok(Some(RecentRequestsEvent::Cancel)),
+            KeyCode::Enter => Ok(self.take_selected(false)),
+            KeyCode::Char('s') => Ok(self.take_selected(true)),
+            _ => Ok(None),
+        }
+    }
+}
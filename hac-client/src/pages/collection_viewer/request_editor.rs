@@ -1,19 +1,24 @@
 mod auth_editor;
 mod body_editor;
+mod file_body_preview;
 mod headers_editor;
+mod settings_editor;
 
 use auth_editor::{AuthEditor, AuthEditorEvent};
 use body_editor::{BodyEditor, BodyEditorEvent};
+use file_body_preview::FileBodyPreview;
 use hac_config::EditorMode;
-use hac_core::collection::types::{Request, RequestMethod};
+use hac_core::collection::types::{BodyType, Request, RequestMethod};
 use hac_core::text_object::{TextObject, Write};
 use headers_editor::{HeadersEditor, HeadersEditorEvent};
+use settings_editor::{SettingsEditor, SettingsEditorEvent};
 
-use crate::pages::collection_viewer::collection_store::CollectionStore;
+use crate::pages::collection_viewer::collection_store::{CollectionStore, CollectionStoreAction};
 use crate::pages::collection_viewer::collection_viewer::{CollectionViewerOverlay, PaneFocus};
 use crate::pages::under_construction::UnderConstruction;
 use crate::pages::Eventful;
 use crate::pages::Renderable;
+use crate::utils::build_tabs;
 
 use std::cell::RefCell;
 use std::fmt::Display;
@@ -24,7 +29,7 @@ use std::sync::{Arc, RwLock};
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Style, Stylize};
-use ratatui::widgets::{Block, Borders, Tabs};
+use ratatui::widgets::{Block, Borders};
 use ratatui::Frame;
 
 /// set of possible events the edtior can send to the parent
@@ -45,15 +50,17 @@ pub enum ReqEditorTabs {
     Headers,
     Query,
     Auth,
+    Settings,
 }
 
 impl ReqEditorTabs {
     pub fn prev(&self) -> Self {
         match self {
-            ReqEditorTabs::Body => ReqEditorTabs::Auth,
+            ReqEditorTabs::Body => ReqEditorTabs::Settings,
             ReqEditorTabs::Headers => ReqEditorTabs::Body,
             ReqEditorTabs::Query => ReqEditorTabs::Headers,
             ReqEditorTabs::Auth => ReqEditorTabs::Query,
+            ReqEditorTabs::Settings => ReqEditorTabs::Auth,
         }
     }
 
@@ -62,7 +69,8 @@ impl ReqEditorTabs {
             ReqEditorTabs::Body => ReqEditorTabs::Headers,
             ReqEditorTabs::Headers => ReqEditorTabs::Query,
             ReqEditorTabs::Query => ReqEditorTabs::Auth,
-            ReqEditorTabs::Auth => ReqEditorTabs::Body,
+            ReqEditorTabs::Auth => ReqEditorTabs::Settings,
+            ReqEditorTabs::Settings => ReqEditorTabs::Body,
         }
     }
 }
@@ -80,6 +88,7 @@ impl Display for ReqEditorTabs {
             ReqEditorTabs::Headers => f.write_str("Headers"),
             ReqEditorTabs::Query => f.write_str("Query"),
             ReqEditorTabs::Auth => f.write_str("Auth"),
+            ReqEditorTabs::Settings => f.write_str("Settings"),
         }
     }
 }
@@ -93,10 +102,15 @@ impl AsRef<ReqEditorTabs> for ReqEditorTabs {
 #[derive(Debug)]
 pub struct RequestEditor<'re> {
     colors: &'re hac_colors::Colors,
+    config: Rc<hac_config::Config>,
     collection_store: Rc<RefCell<CollectionStore>>,
     body_editor: BodyEditor<'re>,
+    /// present when the body of the request is read from a file, replacing the
+    /// body editor on the body tab
+    file_body: Option<FileBodyPreview<'re>>,
     headers_editor: HeadersEditor<'re>,
     auth_editor: AuthEditor<'re>,
+    settings_editor: SettingsEditor<'re>,
     layout: ReqEditorLayout,
     curr_tab: ReqEditorTabs,
 }
@@ -104,23 +118,36 @@ pub struct RequestEditor<'re> {
 impl<'re> RequestEditor<'re> {
     pub fn new(
         colors: &'re hac_colors::Colors,
-        config: &'re hac_config::Config,
+        config: Rc<hac_config::Config>,
         collection_store: Rc<RefCell<CollectionStore>>,
         size: Rect,
     ) -> Self {
-        let curr_tab = collection_store
-            .borrow()
-            .get_selected_request()
+        let store = collection_store.borrow();
+        let selected_request = store.get_selected_request();
+        let has_no_body = selected_request.as_ref().is_some_and(request_has_no_body);
+        let stored_tab = selected_request
             .as_ref()
-            .map(request_has_no_body)
-            .unwrap_or(false)
-            .then_some(ReqEditorTabs::Headers)
-            .unwrap_or_default();
+            .and_then(|req| store.get_request_editor_tab(&req.read().unwrap().id));
+        // the last tab viewed on this request wins, unless its the body tab on
+        // a request that can't have a body
+        let curr_tab = match stored_tab {
+            Some(ReqEditorTabs::Body) | None if has_no_body => ReqEditorTabs::Headers,
+            Some(tab) => tab,
+            None => ReqEditorTabs::default(),
+        };
+        let file_body = selected_request.as_ref().and_then(|req| {
+            match req.read().unwrap().body_type.as_ref() {
+                Some(BodyType::File(path)) => Some(FileBodyPreview::new(colors, path.clone())),
+                _ => None,
+            }
+        });
+        drop(store);
 
         let layout = build_layout(size);
 
         Self {
             colors,
+            config: config.clone(),
             body_editor: BodyEditor::new(
                 colors,
                 config,
@@ -133,14 +160,30 @@ impl<'re> RequestEditor<'re> {
                 layout.content_pane,
             ),
             auth_editor: AuthEditor::new(colors, collection_store.clone()),
+            settings_editor: SettingsEditor::new(colors, collection_store.clone()),
+            file_body,
             layout,
             curr_tab,
             collection_store,
         }
     }
 
+    /// changes the current tab and remembers it for the selected request, so we
+    /// can restore it next time the request is opened
+    fn set_curr_tab(&mut self, tab: ReqEditorTabs) {
+        let mut store = self.collection_store.borrow_mut();
+        if let Some(request) = store.get_selected_request() {
+            let request_id = request.read().unwrap().id.clone();
+            store.dispatch(CollectionStoreAction::SetRequestEditorTab(
+                request_id,
+                tab.clone(),
+            ));
+        }
+        self.curr_tab = tab;
+    }
+
     pub fn maybe_draw_cursor(&self, frame: &mut Frame) {
-        if self.curr_tab.eq(&ReqEditorTabs::Body) {
+        if self.curr_tab.eq(&ReqEditorTabs::Body) && self.file_body.is_none() {
             self.body_editor.draw_cursor(frame);
         }
     }
@@ -149,6 +192,22 @@ impl<'re> RequestEditor<'re> {
         self.body_editor.body()
     }
 
+    pub fn body_to_store(&self) -> String {
+        self.body_editor.body_to_store()
+    }
+
+    pub fn has_unsaved_edits(&self) -> bool {
+        self.body_editor.has_unsaved_edits()
+    }
+
+    pub fn saved_body(&self) -> &str {
+        self.body_editor.saved_body()
+    }
+
+    pub fn mark_saved(&mut self) {
+        self.body_editor.mark_saved()
+    }
+
     pub fn resize(&mut self, new_size: Rect) {
         self.layout = build_layout(new_size);
         self.headers_editor.resize(self.layout.content_pane);
@@ -157,35 +216,30 @@ impl<'re> RequestEditor<'re> {
 
     fn draw_current_tab(&mut self, frame: &mut Frame, size: Rect) -> anyhow::Result<()> {
         match self.curr_tab {
-            ReqEditorTabs::Body => self.body_editor.draw(frame, size)?,
+            ReqEditorTabs::Body => match self.file_body.as_mut() {
+                Some(file_body) => file_body.draw(frame, size)?,
+                None => self.body_editor.draw(frame, size)?,
+            },
             ReqEditorTabs::Headers => self.headers_editor.draw(frame, size)?,
             ReqEditorTabs::Query => UnderConstruction::new(self.colors).draw(frame, size)?,
             ReqEditorTabs::Auth => self.auth_editor.draw(frame, size)?,
+            ReqEditorTabs::Settings => self.settings_editor.draw(frame, size)?,
         }
 
         Ok(())
     }
 
     fn draw_tabs(&self, frame: &mut Frame, size: Rect) {
-        let tabs = vec!["Body", "Headers", "Query", "Auth"];
+        let tabs = vec!["Body", "Headers", "Query", "Auth", "Settings"];
         let active = match self.curr_tab {
             ReqEditorTabs::Body => 0,
             ReqEditorTabs::Headers => 1,
             ReqEditorTabs::Query => 2,
             ReqEditorTabs::Auth => 3,
+            ReqEditorTabs::Settings => 4,
         };
 
-        frame.render_widget(
-            Tabs::new(tabs)
-                .style(Style::default().fg(self.colors.bright.black))
-                .select(active)
-                .highlight_style(
-                    Style::default()
-                        .fg(self.colors.normal.white)
-                        .bg(self.colors.normal.blue),
-                ),
-            size,
-        );
+        frame.render_widget(build_tabs(tabs, active, self.colors, &self.config), size);
     }
 
     fn draw_container(&self, size: Rect, frame: &mut Frame) {
@@ -222,6 +276,8 @@ impl<'re> RequestEditor<'re> {
             ReqEditorTabs::Headers => self.headers_editor.draw_overlay(frame, overlay),
             ReqEditorTabs::Query => todo!(),
             ReqEditorTabs::Auth => self.auth_editor.draw_overlay(frame, overlay),
+            // the settings are edited in place, so the tab has no overlays
+            ReqEditorTabs::Settings => Ok(()),
         }
     }
 }
@@ -255,10 +311,17 @@ impl Eventful for RequestEditor<'_> {
             {
                 return Ok(None);
             }
-            if !store.has_overlay() {
-                self.curr_tab = self.curr_tab.next();
+            if self.curr_tab.eq(&ReqEditorTabs::Auth) && self.auth_editor.is_editing() {
+                return Ok(None);
+            }
+            if self.curr_tab.eq(&ReqEditorTabs::Settings) && self.settings_editor.is_editing() {
+                return Ok(None);
             }
+            let has_overlay = store.has_overlay();
             drop(store);
+            if !has_overlay {
+                self.set_curr_tab(self.curr_tab.next());
+            }
         }
 
         if let KeyCode::BackTab = key_event.code {
@@ -268,12 +331,27 @@ impl Eventful for RequestEditor<'_> {
             {
                 return Ok(None);
             }
-            if !store.has_overlay() {
-                self.curr_tab = self.curr_tab.prev();
+            if self.curr_tab.eq(&ReqEditorTabs::Auth) && self.auth_editor.is_editing() {
+                return Ok(None);
+            }
+            if self.curr_tab.eq(&ReqEditorTabs::Settings) && self.settings_editor.is_editing() {
+                return Ok(None);
+            }
+            let has_overlay = store.has_overlay();
+            drop(store);
+            if !has_overlay {
+                self.set_curr_tab(self.curr_tab.prev());
             }
         }
 
         match self.curr_tab {
+            // file bodies are edited outside of hac, so the only thing to do here
+            // is leaving the editor
+            ReqEditorTabs::Body if self.file_body.is_some() => {
+                if let KeyCode::Esc = key_event.code {
+                    return Ok(Some(RequestEditorEvent::RemoveSelection));
+                }
+            }
             ReqEditorTabs::Body => match self.body_editor.handle_key_event(key_event)? {
                 Some(BodyEditorEvent::RemoveSelection) => {
                     return Ok(Some(RequestEditorEvent::RemoveSelection))
@@ -294,9 +372,19 @@ impl Eventful for RequestEditor<'_> {
                     let mut store = self.collection_store.borrow_mut();
                     store.push_overlay(CollectionViewerOverlay::ChangeAuthMethod);
                 }
+                Some(AuthEditorEvent::RemoveSelection) => {
+                    return Ok(Some(RequestEditorEvent::RemoveSelection))
+                }
                 Some(AuthEditorEvent::Quit) => return Ok(Some(RequestEditorEvent::Quit)),
                 None => {}
             },
+            ReqEditorTabs::Settings => match self.settings_editor.handle_key_event(key_event)? {
+                Some(SettingsEditorEvent::RemoveSelection) => {
+                    return Ok(Some(RequestEditorEvent::RemoveSelection))
+                }
+                Some(SettingsEditorEvent::Quit) => return Ok(Some(RequestEditorEvent::Quit)),
+                None => {}
+            },
         }
 
         Ok(None)
@@ -8,22 +8,41 @@ use std::ops::{Add, Sub};
 use std::rc::Rc;
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use hac_core::collection::types::AuthMethod;
+use hac_core::collection::types::{AuthMethod, OAuth2ClientCredentials};
 use ratatui::layout::Rect;
 use ratatui::style::Stylize;
+use ratatui::text::Line;
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
 
 pub enum AuthEditorEvent {
     ChangeAuthMethod,
+    RemoveSelection,
     Quit,
 }
 
+/// labels of the editable fields of the oauth2 client credentials flow, in the
+/// order they are displayed
+const OAUTH2_FIELDS: [&str; 4] = ["Token url", "Client id", "Client secret", "Scopes"];
+
+fn oauth2_field(credentials: &mut OAuth2ClientCredentials, idx: usize) -> &mut String {
+    match idx {
+        0 => &mut credentials.token_url,
+        1 => &mut credentials.client_id,
+        2 => &mut credentials.client_secret,
+        _ => &mut credentials.scopes,
+    }
+}
+
 #[derive(Debug)]
 pub struct AuthEditor<'ae> {
     colors: &'ae hac_colors::colors::Colors,
     collection_store: Rc<RefCell<CollectionStore>>,
     auth_kind_prompt: AuthKindPrompt<'ae>,
+    selected_field: usize,
+    /// wether the selected field is being edited, while editing every key is
+    /// inserted on the field
+    editing: bool,
 }
 
 impl<'ae> AuthEditor<'ae> {
@@ -32,9 +51,74 @@ impl<'ae> AuthEditor<'ae> {
         collection_store: Rc<RefCell<CollectionStore>>,
     ) -> Self {
         AuthEditor {
-            auth_kind_prompt: AuthKindPrompt::new(colors, collection_store.clone()),
+            auth_kind_prompt: AuthKindPrompt::new(colors),
             colors,
             collection_store,
+            selected_field: 0,
+            editing: false,
+        }
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.editing
+    }
+
+    fn draw_oauth2_fields(
+        &self,
+        frame: &mut Frame,
+        size: Rect,
+        credentials: &OAuth2ClientCredentials,
+    ) {
+        let mut credentials = credentials.clone();
+        let label_width = OAUTH2_FIELDS
+            .iter()
+            .map(|label| label.len())
+            .max()
+            .unwrap_or_default();
+
+        let lines = OAUTH2_FIELDS
+            .iter()
+            .enumerate()
+            .map(|(idx, label)| {
+                let value = oauth2_field(&mut credentials, idx).clone();
+                // secrets are hidden unless they come from the environment
+                let value = match (idx, value.starts_with("env:")) {
+                    (2, false) => "*".repeat(value.chars().count()),
+                    _ => value,
+                };
+                let chevron = match self.selected_field.eq(&idx) {
+                    true => "> ",
+                    false => "  ",
+                };
+                let value_color = match self.selected_field.eq(&idx) && self.editing {
+                    true => self.colors.normal.yellow,
+                    false => self.colors.normal.white,
+                };
+
+                Line::from(vec![
+                    chevron.fg(self.colors.normal.red),
+                    format!("{label:label_width$}  ").fg(self.colors.bright.black),
+                    value.fg(value_color),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        let size = Rect::new(
+            size.x.add(1),
+            size.y,
+            size.width.saturating_sub(1),
+            size.height,
+        );
+        frame.render_widget(Paragraph::new(lines), size);
+
+        if self.editing {
+            let value_len = oauth2_field(&mut credentials, self.selected_field)
+                .chars()
+                .count();
+            frame.set_cursor(
+                size.x.add((label_width + 4 + value_len) as u16),
+                size.y.add(self.selected_field as u16),
+            );
         }
     }
 
@@ -47,6 +131,7 @@ impl<'ae> AuthEditor<'ae> {
         let hint_size = self.get_hint_size(frame);
         let hint = if has_auth {
             match hint_size.width {
+                _ if self.editing => "[Enter/Esc: Stop editing]",
                 w if w.le(&100) => "[e: Change method] [Tab: Change focus] [?: Help]",
                 _ => "[e: Change method] [Tab: Change focus] [?: Help]",
             }
@@ -64,11 +149,8 @@ impl<'ae> AuthEditor<'ae> {
         frame: &mut Frame,
         overlay: CollectionViewerOverlay,
     ) -> anyhow::Result<()> {
-        match overlay {
-            CollectionViewerOverlay::ChangeAuthMethod => {
-                self.auth_kind_prompt.draw(frame, frame.size())?;
-            }
-            _ => {}
+        if let CollectionViewerOverlay::ChangeAuthMethod = overlay {
+            self.auth_kind_prompt.draw(frame, frame.size())?;
         }
         Ok(())
     }
@@ -102,6 +184,11 @@ impl Renderable for AuthEditor<'_> {
             return Ok(());
         }
 
+        if let Some(AuthMethod::OAuth2ClientCredentials(credentials)) = request.auth_method.as_ref()
+        {
+            self.draw_oauth2_fields(frame, size, credentials);
+        }
+
         Ok(())
     }
 }
@@ -130,6 +217,7 @@ impl Eventful for AuthEditor<'_> {
                 }
                 Some(AuthKindPromptEvent::Confirm(auth_kind)) => {
                     request.auth_method = Some(auth_kind);
+                    self.selected_field = 0;
                     store.pop_overlay();
                 }
                 None => (),
@@ -138,11 +226,38 @@ impl Eventful for AuthEditor<'_> {
             return Ok(None);
         }
 
-        match key_event.code {
-            KeyCode::Char('n') => return Ok(Some(AuthEditorEvent::ChangeAuthMethod)),
-            _ => {}
+        if let Some(AuthMethod::OAuth2ClientCredentials(credentials)) = request.auth_method.as_mut()
+        {
+            let field = oauth2_field(credentials, self.selected_field);
+            match (self.editing, key_event.code) {
+                (true, KeyCode::Enter | KeyCode::Esc) => self.editing = false,
+                (true, KeyCode::Char(c)) => field.push(c),
+                (true, KeyCode::Backspace) => _ = field.pop(),
+                (true, _) => {}
+                (false, KeyCode::Char('j') | KeyCode::Down) => {
+                    self.selected_field = self.selected_field.add(1).min(OAUTH2_FIELDS.len() - 1);
+                }
+                (false, KeyCode::Char('k') | KeyCode::Up) => {
+                    self.selected_field = self.selected_field.saturating_sub(1);
+                }
+                (false, KeyCode::Enter | KeyCode::Char('i')) => self.editing = true,
+                _ => {}
+            }
+
+            if self.editing
+                || !matches!(
+                    key_event.code,
+                    KeyCode::Char('n') | KeyCode::Char('e') | KeyCode::Esc
+                )
+            {
+                return Ok(None);
+            }
         }
 
-        Ok(None)
+        match key_event.code {
+            KeyCode::Char('n') | KeyCode::Char('e') => Ok(Some(AuthEditorEvent::ChangeAuthMethod)),
+            KeyCode::Esc => Ok(Some(AuthEditorEvent::RemoveSelection)),
+            _ => Ok(None),
+        }
     }
 }
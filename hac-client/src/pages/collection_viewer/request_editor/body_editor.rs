@@ -1,18 +1,23 @@
 use hac_config::{Action, EditorMode, KeyAction};
-use hac_core::syntax::highlighter::HIGHLIGHTER;
+use hac_core::collection::types::RequestMethod;
+use hac_core::net::response_decoders::pretty_print_json;
+use hac_core::syntax::highlighter::{Highlighter, HIGHLIGHTER};
 use hac_core::text_object::{cursor::Cursor, TextObject, Write};
 
 use crate::pages::{collection_viewer::collection_store::CollectionStore, Eventful, Renderable};
-use crate::utils::build_syntax_highlighted_lines;
+use crate::toast;
+use crate::utils::{build_syntax_highlighted_lines_with_tokens, draw_vertical_scrollbar};
 
+use std::borrow::Cow;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::ops::{Add, Div, Mul, Sub};
 use std::rc::Rc;
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::Stylize;
+use ratatui::style::{Style, Stylize};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 use ratatui::Frame;
@@ -33,7 +38,7 @@ pub struct BodyEditor<'be> {
     row_scroll: usize,
     col_scroll: usize,
     colors: &'be hac_colors::Colors,
-    config: &'be hac_config::Config,
+    config: Rc<hac_config::Config>,
 
     size: Rect,
 
@@ -43,24 +48,40 @@ pub struct BodyEditor<'be> {
     /// Only KeyAction::Complex are stored here as any other kind of key action can be acted upon
     /// instantly
     keymap_buffer: Option<KeyAction>,
-    _collection_store: Rc<RefCell<CollectionStore>>,
+    collection_store: Rc<RefCell<CollectionStore>>,
+    /// when the body was pretty printed on open, holds the pretty printed body
+    /// and the body as it was stored, so an untouched body is saved as it was
+    prettified: Option<(String, String)>,
+    /// the body as it was when the editor was opened or last saved, edits
+    /// made since could be lost when leaving the request
+    saved_body: String,
+    /// the text of the editor when it was opened or last saved, comparing
+    /// against it is cheap, so the body is only converted to what is stored
+    /// when the text changed
+    saved_text: TextObject<Write>,
 }
 
 impl<'be> BodyEditor<'be> {
     pub fn new(
         colors: &'be hac_colors::Colors,
-        config: &'be hac_config::Config,
+        config: Rc<hac_config::Config>,
         collection_store: Rc<RefCell<CollectionStore>>,
         size: Rect,
     ) -> Self {
-        let (body, tree) = make_body(&collection_store);
+        let (body, tree, prettified) = make_body(&collection_store, config.prettify_on_open);
         let content = body.to_string();
-        let styled_display = build_syntax_highlighted_lines(&content, tree.as_ref(), colors);
+        let styled_display = build_syntax_highlighted_lines_with_tokens(
+            &content,
+            tree.as_ref(),
+            colors,
+            &body_tokens(colors, &collection_store),
+        );
 
-        Self {
+        let mut body_editor = Self {
             body,
             tree,
-            _collection_store: collection_store,
+            collection_store,
+            prettified,
             styled_display,
             cursor: Cursor::default(),
             editor_mode: EditorMode::Normal,
@@ -70,6 +91,52 @@ impl<'be> BodyEditor<'be> {
             colors,
             config,
             keymap_buffer: None,
+            saved_body: String::default(),
+            saved_text: TextObject::default(),
+        };
+        body_editor.mark_saved();
+        body_editor.highlight_matching_bracket();
+
+        body_editor
+    }
+
+    fn rebuild_styled_display(&mut self) {
+        let content = self.body.to_string();
+        self.tree = HIGHLIGHTER.write().unwrap().parse(&content);
+        self.styled_display = build_syntax_highlighted_lines_with_tokens(
+            &content,
+            self.tree.as_ref(),
+            self.colors,
+            &body_tokens(self.colors, &self.collection_store),
+        );
+        self.highlight_matching_bracket();
+    }
+
+    /// when the cursor is over a bracket or a brace, highlights it along with
+    /// its pair on the styled display
+    fn highlight_matching_bracket(&mut self) {
+        let Some(tree) = self.tree.as_ref() else {
+            return;
+        };
+        let Some(cursor_byte_idx) = self.body.cursor_byte_idx(&self.cursor) else {
+            return;
+        };
+        let Some((opening, closing)) = Highlighter::find_matching_bracket(tree, cursor_byte_idx)
+        else {
+            return;
+        };
+
+        let style = Style::default()
+            .bg(self.colors.primary.hover)
+            .fg(self.colors.normal.yellow)
+            .bold();
+
+        for byte_idx in [opening, closing] {
+            if let Some((col, row)) = self.body.byte_to_position(byte_idx) {
+                if let Some(line) = self.styled_display.get_mut(row) {
+                    *line = restyle_char(line, col, style);
+                }
+            }
         }
     }
 
@@ -81,6 +148,30 @@ impl<'be> BodyEditor<'be> {
         &self.body
     }
 
+    /// the body that should be stored on the request, which is the body as it
+    /// was before being pretty printed on open, unless it was edited since
+    pub fn body_to_store(&self) -> String {
+        let body = self.body.to_string();
+        match self.prettified.as_ref() {
+            Some((pretty, original)) if pretty.eq(&body) => original.clone(),
+            _ => body,
+        }
+    }
+
+    /// whether the body changed since the editor was opened or last saved
+    pub fn has_unsaved_edits(&self) -> bool {
+        self.body.ne(&self.saved_text) && self.body_to_store().ne(&self.saved_body)
+    }
+
+    pub fn saved_body(&self) -> &str {
+        &self.saved_body
+    }
+
+    pub fn mark_saved(&mut self) {
+        self.saved_body = self.body_to_store();
+        self.saved_text = self.body.clone();
+    }
+
     pub fn draw_cursor(&self, frame: &mut Frame) {
         // the editor status bar occupies 1 row, so we have to subtract it to prevent the
         // cursor from going out of the intended spacing, we also subtract the bottom border.
@@ -195,6 +286,9 @@ impl<'be> BodyEditor<'be> {
             Action::JumpToClosing => self.jump_to_opposing_token(),
             Action::JumpToEmptyLineBelow => self.jump_to_empty_line_below(),
             Action::JumpToEmptyLineAbove => self.jump_to_empty_line_above(),
+            Action::EscapeJson => self.escape_body(),
+            Action::UnescapeJson => self.unescape_body(),
+            Action::SortJsonKeys => self.sort_body_keys(),
             Action::Undo => {}
             Action::FindNext => {}
             Action::FindPrevious => {}
@@ -203,37 +297,37 @@ impl<'be> BodyEditor<'be> {
     }
 
     fn maybe_scroll_view(&mut self) {
-        self.cursor
-            .row()
-            .saturating_sub(self.row_scroll)
-            .gt(&self.size.height.sub(2).into())
-            .then(|| self.row_scroll = self.cursor.row().sub(self.size.height.sub(2) as usize));
+        // last row and column offsets that are visible inside the pane
+        let max_row = usize::from(self.size.height.saturating_sub(2));
+        let max_col = usize::from(self.size.width.saturating_sub(1));
+        // scrolloff can't be bigger than half of the pane, otherwise the cursor
+        // would never be allowed to rest anywhere
+        let row_off = self.config.scrolloff.min(max_row.div(2));
+        let col_off = self.config.scrolloff.min(max_col.div(2));
+        // there is nothing to show below the last line, so the context below
+        // the cursor shrinks as it gets closer to the end of the body
+        let row_off_below = row_off.min(
+            self.body
+                .len_lines()
+                .saturating_sub(1)
+                .saturating_sub(self.cursor.row()),
+        );
 
-        self.cursor
-            .row()
-            .saturating_sub(self.row_scroll)
-            .eq(&0)
-            .then(|| {
-                self.row_scroll = self
-                    .row_scroll
-                    .saturating_sub(self.row_scroll.saturating_sub(self.cursor.row()))
-            });
+        if self.cursor.row().add(row_off_below) > self.row_scroll.add(max_row) {
+            self.row_scroll = self.cursor.row().add(row_off_below).sub(max_row);
+        }
 
-        self.cursor
-            .col()
-            .saturating_sub(self.col_scroll)
-            .eq(&0)
-            .then(|| {
-                self.col_scroll = self
-                    .col_scroll
-                    .saturating_sub(self.col_scroll.saturating_sub(self.cursor.col()))
-            });
+        if self.cursor.row() < self.row_scroll.add(row_off) {
+            self.row_scroll = self.cursor.row().saturating_sub(row_off);
+        }
 
-        self.cursor
-            .col()
-            .saturating_sub(self.col_scroll)
-            .gt(&self.size.width.sub(1).into())
-            .then(|| self.col_scroll = self.cursor.col().sub(self.size.width.sub(1) as usize));
+        if self.cursor.col() < self.col_scroll.add(col_off) {
+            self.col_scroll = self.cursor.col().saturating_sub(col_off);
+        }
+
+        if self.cursor.col().add(col_off) > self.col_scroll.add(max_col) {
+            self.col_scroll = self.cursor.col().add(col_off).sub(max_col);
+        }
     }
 
     fn jump_to_empty_line_below(&mut self) {
@@ -361,6 +455,33 @@ impl<'be> BodyEditor<'be> {
         self.cursor.maybe_snap_to_col(current_line_len);
     }
 
+    /// the editor has no selection, so escaping works on the whole body,
+    /// turning it into a json string ready to be embedded into another body
+    fn escape_body(&mut self) {
+        let escaped = hac_core::escape::escape_json(&self.body.to_string());
+        self.replace_body(&escaped);
+    }
+
+    fn unescape_body(&mut self) {
+        match hac_core::escape::unescape_json(&self.body.to_string()) {
+            Ok(unescaped) => self.replace_body(&unescaped),
+            Err(message) => toast::show(message),
+        }
+    }
+
+    fn sort_body_keys(&mut self) {
+        match hac_core::json::sort_json_keys(&self.body.to_string()) {
+            Ok(sorted) => self.replace_body(&sorted),
+            Err(message) => toast::show(message),
+        }
+    }
+
+    fn replace_body(&mut self, content: &str) {
+        self.body = TextObject::from(content).with_write();
+        self.cursor = Cursor::default();
+        self.move_to_top();
+    }
+
     fn move_to_top(&mut self) {
         self.cursor.move_to_row(0);
         self.maybe_scroll_view();
@@ -479,8 +600,18 @@ impl<'be> BodyEditor<'be> {
     }
 
     fn insert_newline(&mut self) {
+        let indentation = match self.config.auto_indent {
+            true => self.body.indentation_for_newline(&self.cursor),
+            false => String::new(),
+        };
+
         self.body.insert_newline(&self.cursor);
         self.cursor.move_to_newline_start();
+
+        indentation.chars().for_each(|c| {
+            self.body.insert_char(c, &self.cursor);
+            self.cursor.move_right(1);
+        });
     }
 
     fn erase_previous_char(&mut self) {
@@ -525,6 +656,20 @@ impl Renderable for BodyEditor<'_> {
             .collect::<Vec<Line>>();
 
         frame.render_widget(Paragraph::new(lines_in_view), request_pane);
+        draw_vertical_scrollbar(
+            frame,
+            self.colors,
+            self.styled_display.len(),
+            self.row_scroll,
+            request_pane.height.into(),
+            // drawn over the right border of the editor so it never hides the body
+            Rect::new(
+                request_pane.x,
+                request_pane.y,
+                request_pane.width.add(1),
+                request_pane.height,
+            ),
+        );
         Ok(())
     }
 
@@ -556,12 +701,7 @@ impl Eventful for BodyEditor<'_> {
                 _ => self.keymap_buffer = None,
             }
 
-            self.tree = HIGHLIGHTER.write().unwrap().parse(&self.body.to_string());
-            self.styled_display = build_syntax_highlighted_lines(
-                &self.body.to_string(),
-                self.tree.as_ref(),
-                self.colors,
-            );
+            self.rebuild_styled_display();
             return Ok(None);
         }
 
@@ -575,8 +715,9 @@ impl Eventful for BodyEditor<'_> {
             return Ok(Some(BodyEditorEvent::Quit));
         };
 
+        let config = self.config.clone();
         match self.editor_mode {
-            EditorMode::Normal => match self.config.editor_keys.normal.get(&key_str) {
+            EditorMode::Normal => match config.editor_keys.normal.get(&key_str) {
                 Some(KeyAction::Simple(action)) => self.handle_action(action),
                 Some(KeyAction::Multiple(actions)) => {
                     actions.iter().for_each(|a| self.handle_action(a))
@@ -584,7 +725,7 @@ impl Eventful for BodyEditor<'_> {
                 Some(key_action) => self.keymap_buffer = Some(key_action.clone()),
                 None => {}
             },
-            EditorMode::Insert => match self.config.editor_keys.insert.get(&key_str) {
+            EditorMode::Insert => match config.editor_keys.insert.get(&key_str) {
                 Some(KeyAction::Simple(action)) => self.handle_action(action),
                 Some(KeyAction::Multiple(actions)) => {
                     actions.iter().for_each(|a| self.handle_action(a))
@@ -598,9 +739,7 @@ impl Eventful for BodyEditor<'_> {
             },
         }
 
-        self.tree = HIGHLIGHTER.write().unwrap().parse(&self.body.to_string());
-        self.styled_display =
-            build_syntax_highlighted_lines(&self.body.to_string(), self.tree.as_ref(), self.colors);
+        self.rebuild_styled_display();
 
         Ok(None)
     }
@@ -653,19 +792,84 @@ fn get_visible_spans(line: &Line<'static>, scroll: usize) -> Line<'static> {
     Line::from(new_spans)
 }
 
-fn make_body(collection_store: &Rc<RefCell<CollectionStore>>) -> (TextObject<Write>, Option<Tree>) {
-    let (body, tree) = if let Some(request) = collection_store.borrow().get_selected_request() {
-        if let Some(body) = request.read().unwrap().body.as_ref() {
-            let mut highlighter = HIGHLIGHTER.write().unwrap();
-            let tree = highlighter.parse(body);
+/// applies `style` to the character at `col`, splitting the span that contains
+/// it when needed
+fn restyle_char(line: &Line<'static>, col: usize, style: Style) -> Line<'static> {
+    let mut remaining = col;
+    let mut new_spans = vec![];
 
-            (TextObject::from(body).with_write(), tree)
-        } else {
-            Default::default()
+    for span in line.spans.iter() {
+        let span_len = span.content.chars().count();
+        if remaining >= span_len {
+            remaining -= span_len;
+            new_spans.push(span.clone());
+            continue;
         }
-    } else {
-        Default::default()
+
+        let before = span.content.chars().take(remaining).collect::<String>();
+        let target = span
+            .content
+            .chars()
+            .skip(remaining)
+            .take(1)
+            .collect::<String>();
+        let after = span
+            .content
+            .chars()
+            .skip(remaining.add(1))
+            .collect::<String>();
+
+        (!before.is_empty()).then(|| new_spans.push(Span::styled(before, span.style)));
+        new_spans.push(Span::styled(target, span.style.patch(style)));
+        (!after.is_empty()).then(|| new_spans.push(Span::styled(after, span.style)));
+        // we already found the character, so we use `usize::MAX` to copy every
+        // other span as is
+        remaining = usize::MAX;
+    }
+
+    Line::from(new_spans)
+}
+
+/// tokens used to highlight the body, PATCH requests mark `null` values with a
+/// warning color, as they delete the field when sent as a JSON Merge Patch
+fn body_tokens<'a>(
+    colors: &'a hac_colors::Colors,
+    collection_store: &Rc<RefCell<CollectionStore>>,
+) -> Cow<'a, HashMap<String, Style>> {
+    let is_patch = collection_store
+        .borrow()
+        .get_selected_request()
+        .is_some_and(|req| req.read().unwrap().method.eq(&RequestMethod::Patch));
+
+    match (is_patch, colors.tokens.get("merge_patch.delete")) {
+        (true, Some(style)) => {
+            let mut tokens = colors.tokens.clone();
+            tokens.insert("constant.builtin".into(), *style);
+            Cow::Owned(tokens)
+        }
+        _ => Cow::Borrowed(&colors.tokens),
+    }
+}
+
+fn make_body(
+    collection_store: &Rc<RefCell<CollectionStore>>,
+    prettify: bool,
+) -> (TextObject<Write>, Option<Tree>, Option<(String, String)>) {
+    let Some(request) = collection_store.borrow().get_selected_request() else {
+        return Default::default();
     };
+    let Some(body) = request.read().unwrap().body.clone() else {
+        return Default::default();
+    };
+
+    let prettified = (prettify && serde_json::from_str::<serde_json::Value>(&body).is_ok())
+        .then(|| (pretty_print_json(&body), body.clone()))
+        .filter(|(pretty, original)| pretty.ne(original));
+    let body = prettified
+        .as_ref()
+        .map(|(pretty, _)| pretty.clone())
+        .unwrap_or(body);
 
-    (body, tree)
+    let tree = HIGHLIGHTER.write().unwrap().parse(&body);
+    (TextObject::from(&body).with_write(), tree, prettified)
 }
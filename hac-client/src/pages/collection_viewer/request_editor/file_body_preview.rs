@@ -0,0 +1,77 @@
+use crate::pages::Renderable;
+
+use std::io::BufRead;
+use std::ops::Add;
+
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+/// we only read enough of the file to fill the editor, as these files are
+/// usually the big payloads users don't want in the editor to begin with
+const MAX_PREVIEW_LINES: usize = 200;
+
+/// displayed on the body tab when the request body is read from a file, shows
+/// where the body comes from and the first lines of the file
+#[derive(Debug)]
+pub struct FileBodyPreview<'fbp> {
+    colors: &'fbp hac_colors::Colors,
+    path: String,
+    preview: Result<Vec<String>, String>,
+}
+
+impl<'fbp> FileBodyPreview<'fbp> {
+    pub fn new(colors: &'fbp hac_colors::Colors, path: String) -> Self {
+        let preview = read_preview(&path);
+        FileBodyPreview {
+            colors,
+            path,
+            preview,
+        }
+    }
+}
+
+impl Renderable for FileBodyPreview<'_> {
+    fn draw(&mut self, frame: &mut Frame, size: Rect) -> anyhow::Result<()> {
+        let mut lines = vec![
+            Line::from(vec![
+                "Body from file: ".fg(self.colors.bright.black),
+                self.path.clone().fg(self.colors.normal.yellow),
+            ]),
+            Line::from(""),
+        ];
+
+        match &self.preview {
+            Ok(preview) => lines.extend(
+                preview
+                    .iter()
+                    .take(size.height.into())
+                    .map(|line| Line::from(line.clone().fg(self.colors.normal.white))),
+            ),
+            Err(e) => lines.push(Line::from(e.clone().fg(self.colors.normal.red))),
+        }
+
+        let size = Rect::new(
+            size.x.add(1),
+            size.y,
+            size.width.saturating_sub(1),
+            size.height,
+        );
+        frame.render_widget(Paragraph::new(lines), size);
+
+        Ok(())
+    }
+}
+
+fn read_preview(path: &str) -> Result<Vec<String>, String> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| format!("could not read the body file, it won't be sent: {e}"))?;
+
+    std::io::BufReader::new(file)
+        .lines()
+        .take(MAX_PREVIEW_LINES)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("could not read the body file, it won't be sent: {e}"))
+}
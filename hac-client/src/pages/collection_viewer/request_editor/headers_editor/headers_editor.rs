@@ -8,12 +8,13 @@ use std::{cell::RefCell, ops::Add, rc::Rc};
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use hac_core::collection::types::HeaderMap;
+use hac_core::net::request_client::effective_headers;
 use rand::Rng;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Style, Stylize};
 use ratatui::text::Line;
 use ratatui::widgets::{
-    Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+    Block, Borders, Clear, Padding, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
 };
 use ratatui::Frame;
 
@@ -147,6 +148,11 @@ impl<'he> HeadersEditor<'he> {
                 format!("- select header for editing{}", " ".repeat(13))
                     .fg(self.colors.normal.yellow),
             ],
+            [
+                format!("v{}", " ".repeat(11)).fg(self.colors.normal.red),
+                format!("- shows the headers that will be sent{}", " ".repeat(3))
+                    .fg(self.colors.normal.yellow),
+            ],
             [
                 format!("?{}", " ".repeat(11)).fg(self.colors.normal.red),
                 format!("- shows this help message{}", " ".repeat(15))
@@ -183,7 +189,7 @@ impl<'he> HeadersEditor<'he> {
         let components = logo
             .iter()
             .map(|line| Line::from(line.fg(self.colors.normal.red)))
-            .chain(std::iter::repeat(Line::from("")).take(2))
+            .chain(std::iter::repeat_n(Line::from(""), 2))
             .chain(lines)
             .collect::<Vec<_>>();
 
@@ -202,6 +208,65 @@ impl<'he> HeadersEditor<'he> {
         frame.render_widget(Paragraph::new(hint), hint_size);
     }
 
+    fn draw_effective_headers_overlay(&self, frame: &mut Frame) {
+        make_overlay(self.colors, self.colors.normal.black, 0.1, frame);
+
+        let headers = self
+            .collection_store
+            .borrow()
+            .get_selected_request()
+            .map(|request| effective_headers(&request.read().unwrap()))
+            .unwrap_or_default();
+
+        let mut lines = headers
+            .into_iter()
+            .map(|(name, value)| {
+                Line::from(vec![
+                    format!("{name}: ").fg(self.colors.normal.yellow),
+                    value.fg(self.colors.normal.white),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        if lines.is_empty() {
+            lines.push(Line::from(
+                "No headers will be sent".fg(self.colors.bright.black),
+            ));
+        }
+
+        let size = frame.size();
+        let height = (lines.len() as u16)
+            .add(2)
+            .min(size.height.saturating_sub(4));
+        let popup_size = Rect::new(
+            size.width.div(4),
+            size.height.div(2).saturating_sub(height.div(2)),
+            size.width.div(2),
+            height,
+        );
+
+        let hint_size = Rect::new(
+            popup_size.x,
+            popup_size.y.add(popup_size.height).add(1),
+            popup_size.width,
+            1,
+        );
+
+        let hint = Line::from("press any key to close this dialog")
+            .fg(self.colors.bright.black)
+            .centered();
+
+        let block = Block::default()
+            .title("Effective headers")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.colors.bright.black))
+            .padding(Padding::horizontal(1));
+
+        frame.render_widget(Clear, popup_size);
+        frame.render_widget(Paragraph::new(lines).block(block), popup_size);
+        frame.render_widget(Paragraph::new(hint), hint_size);
+    }
+
     pub fn draw_empty_message(&self, frame: &mut Frame) {
         let size = self.layout.content_size;
         let no_headers = "No headers method".fg(self.colors.bright.black);
@@ -223,6 +288,7 @@ impl<'he> HeadersEditor<'he> {
     ) -> anyhow::Result<()> {
         match overlay {
             CollectionViewerOverlay::HeadersHelp => self.draw_help_overlay(frame),
+            CollectionViewerOverlay::EffectiveHeaders => self.draw_effective_headers_overlay(frame),
             CollectionViewerOverlay::HeadersDelete => {
                 self.delete_prompt.draw(frame, frame.size())?;
             }
@@ -330,7 +396,9 @@ impl Eventful for HeadersEditor<'_> {
     fn handle_key_event(&mut self, key_event: KeyEvent) -> anyhow::Result<Option<Self::Result>> {
         let overlay = self.collection_store.borrow().peek_overlay();
 
-        if let CollectionViewerOverlay::HeadersHelp = overlay {
+        if let CollectionViewerOverlay::HeadersHelp | CollectionViewerOverlay::EffectiveHeaders =
+            overlay
+        {
             self.collection_store.borrow_mut().pop_overlay();
             return Ok(None);
         }
@@ -415,6 +483,12 @@ impl Eventful for HeadersEditor<'_> {
                     store.push_overlay(CollectionViewerOverlay::HeadersHelp);
                 };
             }
+            KeyCode::Char('v') => {
+                drop(request);
+                self.collection_store
+                    .borrow_mut()
+                    .push_overlay(CollectionViewerOverlay::EffectiveHeaders);
+            }
             KeyCode::Char(' ') => {
                 if total_headers.eq(&0) {
                     return Ok(None);
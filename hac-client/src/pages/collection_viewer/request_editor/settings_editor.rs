@@ -0,0 +1,388 @@
+use crate::pages::collection_viewer::collection_store::CollectionStore;
+use crate::pages::{Eventful, Renderable};
+
+use std::cell::RefCell;
+use std::net::IpAddr;
+use std::ops::{Add, Sub};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use hac_config::Compression;
+use hac_core::collection::types::ClientSettings;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+pub enum SettingsEditorEvent {
+    RemoveSelection,
+    Quit,
+}
+
+/// client options of a request, in the order they are displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SettingsField {
+    Proxy,
+    AcceptInvalidCerts,
+    CaCertificate,
+    Compression,
+    TitleCaseHeaders,
+    LocalAddress,
+    ConnectTimeout,
+}
+
+/// how an option is edited
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldKind {
+    /// typed as free text
+    Text,
+    /// typed, only accepting digits
+    Number,
+    /// cycled through its values instead of being typed
+    Toggle,
+}
+
+impl SettingsField {
+    const ALL: [SettingsField; 7] = [
+        SettingsField::Proxy,
+        SettingsField::AcceptInvalidCerts,
+        SettingsField::CaCertificate,
+        SettingsField::Compression,
+        SettingsField::TitleCaseHeaders,
+        SettingsField::LocalAddress,
+        SettingsField::ConnectTimeout,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            SettingsField::Proxy => "Proxy",
+            SettingsField::AcceptInvalidCerts => "Accept invalid certs",
+            SettingsField::CaCertificate => "CA certificate",
+            SettingsField::Compression => "Compression",
+            SettingsField::TitleCaseHeaders => "Title case headers",
+            SettingsField::LocalAddress => "Local address",
+            SettingsField::ConnectTimeout => "Connect timeout (ms)",
+        }
+    }
+
+    fn kind(&self) -> FieldKind {
+        match self {
+            SettingsField::AcceptInvalidCerts
+            | SettingsField::Compression
+            | SettingsField::TitleCaseHeaders => FieldKind::Toggle,
+            SettingsField::ConnectTimeout => FieldKind::Number,
+            SettingsField::Proxy | SettingsField::CaCertificate | SettingsField::LocalAddress => {
+                FieldKind::Text
+            }
+        }
+    }
+
+    /// the value of the option as displayed, `None` when the option is unset
+    /// and falls back to the collection or the config
+    fn value(&self, settings: &ClientSettings) -> Option<String> {
+        match self {
+            SettingsField::Proxy => settings.proxy.clone(),
+            SettingsField::AcceptInvalidCerts => settings
+                .accept_invalid_certs
+                .map(|accept| accept.to_string()),
+            SettingsField::CaCertificate => settings
+                .ca_certificate
+                .as_ref()
+                .map(|path| path.display().to_string()),
+            SettingsField::Compression => {
+                settings.compression.map(|compression| match compression {
+                    Compression::Compressed => String::from("compressed"),
+                    Compression::Identity => String::from("identity"),
+                })
+            }
+            SettingsField::TitleCaseHeaders => {
+                settings.title_case_headers.map(|title| title.to_string())
+            }
+            SettingsField::LocalAddress => settings.local_address.clone(),
+            SettingsField::ConnectTimeout => {
+                settings.connect_timeout.map(|timeout| timeout.to_string())
+            }
+        }
+    }
+
+    /// sets a typed option from its text, empty text unsets the option. Text
+    /// that isn't valid for the option gives back why
+    fn set_text(&self, settings: &mut ClientSettings, value: &str) -> Result<(), String> {
+        let value = Some(value.trim()).filter(|value| !value.is_empty());
+        match self {
+            SettingsField::Proxy => settings.proxy = value.map(String::from),
+            SettingsField::CaCertificate => settings.ca_certificate = value.map(PathBuf::from),
+            SettingsField::LocalAddress => {
+                if let Some(address) = value {
+                    address
+                        .parse::<IpAddr>()
+                        .map_err(|_| format!("{address} is not an ip address"))?;
+                }
+                settings.local_address = value.map(String::from);
+            }
+            SettingsField::ConnectTimeout => {
+                settings.connect_timeout = value.and_then(|value| value.parse().ok())
+            }
+            SettingsField::AcceptInvalidCerts
+            | SettingsField::Compression
+            | SettingsField::TitleCaseHeaders => {}
+        }
+        Ok(())
+    }
+
+    /// cycles an option through unset and each of its values
+    fn toggle(&self, settings: &mut ClientSettings) {
+        let cycle = |value: Option<bool>| match value {
+            None => Some(true),
+            Some(true) => Some(false),
+            Some(false) => None,
+        };
+
+        match self {
+            SettingsField::AcceptInvalidCerts => {
+                settings.accept_invalid_certs = cycle(settings.accept_invalid_certs)
+            }
+            SettingsField::Compression => {
+                settings.compression = match settings.compression {
+                    None => Some(Compression::Compressed),
+                    Some(Compression::Compressed) => Some(Compression::Identity),
+                    Some(Compression::Identity) => None,
+                }
+            }
+            SettingsField::TitleCaseHeaders => {
+                settings.title_case_headers = cycle(settings.title_case_headers)
+            }
+            SettingsField::Proxy
+            | SettingsField::CaCertificate
+            | SettingsField::LocalAddress
+            | SettingsField::ConnectTimeout => {}
+        }
+    }
+
+    /// unsets the option, so it falls back to the collection or the config
+    fn clear(&self, settings: &mut ClientSettings) {
+        match self {
+            SettingsField::Proxy => settings.proxy = None,
+            SettingsField::AcceptInvalidCerts => settings.accept_invalid_certs = None,
+            SettingsField::CaCertificate => settings.ca_certificate = None,
+            SettingsField::Compression => settings.compression = None,
+            SettingsField::TitleCaseHeaders => settings.title_case_headers = None,
+            SettingsField::LocalAddress => settings.local_address = None,
+            SettingsField::ConnectTimeout => settings.connect_timeout = None,
+        }
+    }
+}
+
+/// tab with the client options of the request, like its proxy or the local
+/// address it is sent from. Unset options fall back to the ones of the
+/// collection and of the config
+#[derive(Debug)]
+pub struct SettingsEditor<'se> {
+    colors: &'se hac_colors::colors::Colors,
+    collection_store: Rc<RefCell<CollectionStore>>,
+    selected_field: usize,
+    /// text of the selected field while it is being edited, it is only stored
+    /// on the request once the edit is confirmed
+    editing: Option<String>,
+    /// why the last edit couldn't be confirmed, shown until the next key
+    error: Option<String>,
+}
+
+impl<'se> SettingsEditor<'se> {
+    pub fn new(
+        colors: &'se hac_colors::colors::Colors,
+        collection_store: Rc<RefCell<CollectionStore>>,
+    ) -> Self {
+        SettingsEditor {
+            colors,
+            collection_store,
+            selected_field: 0,
+            editing: None,
+            error: None,
+        }
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.editing.is_some()
+    }
+
+    fn selected_field(&self) -> SettingsField {
+        SettingsField::ALL[self.selected_field]
+    }
+
+    fn get_hint_size(&self, frame: &mut Frame) -> Rect {
+        let size = frame.size();
+        Rect::new(0, size.height.sub(1), size.width, 1)
+    }
+
+    fn draw_hint(&self, frame: &mut Frame) {
+        let hint_size = self.get_hint_size(frame);
+        let hint = match (self.error.as_ref(), self.editing.as_ref()) {
+            (Some(error), _) => error.clone().fg(self.colors.normal.red),
+            (None, Some(_)) => "[Enter: Confirm] [Esc: Cancel]".fg(self.colors.bright.black),
+            (None, None) => "[Enter: Edit] [d: Use collection value] [Tab: Change focus]"
+                .fg(self.colors.bright.black),
+        };
+        frame.render_widget(Paragraph::new(Line::from(hint)).centered(), hint_size);
+    }
+}
+
+impl Renderable for SettingsEditor<'_> {
+    fn draw(&mut self, frame: &mut Frame, size: Rect) -> anyhow::Result<()> {
+        let store = self.collection_store.borrow();
+        let Some(request) = store.get_selected_request() else {
+            return Ok(());
+        };
+        let settings = request.read().unwrap().client.clone();
+        drop(store);
+
+        self.draw_hint(frame);
+
+        let label_width = SettingsField::ALL
+            .iter()
+            .map(|field| field.label().len())
+            .max()
+            .unwrap_or_default();
+
+        let mut lines = vec![
+            Line::from("unset options use the ones of the collection".fg(self.colors.bright.black)),
+            Line::from(""),
+        ];
+        lines.extend(SettingsField::ALL.iter().enumerate().map(|(idx, field)| {
+            let is_selected = self.selected_field.eq(&idx);
+            let chevron = match is_selected {
+                true => "> ",
+                false => "  ",
+            };
+            let value = match (is_selected, self.editing.as_ref()) {
+                (true, Some(text)) => text.clone().fg(self.colors.normal.yellow),
+                _ => match field.value(&settings) {
+                    Some(value) => value.fg(self.colors.normal.white),
+                    None => "inherited".fg(self.colors.bright.black).italic(),
+                },
+            };
+
+            Line::from(vec![
+                chevron.fg(self.colors.normal.red),
+                format!("{:label_width$}  ", field.label()).fg(self.colors.bright.black),
+                value,
+            ])
+        }));
+
+        let size = Rect::new(
+            size.x.add(1),
+            size.y,
+            size.width.saturating_sub(1),
+            size.height,
+        );
+        frame.render_widget(Paragraph::new(lines), size);
+
+        if let Some(text) = self.editing.as_ref() {
+            frame.set_cursor(
+                size.x.add((label_width + 4 + text.chars().count()) as u16),
+                size.y.add(self.selected_field.add(2) as u16),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Eventful for SettingsEditor<'_> {
+    type Result = SettingsEditorEvent;
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> anyhow::Result<Option<Self::Result>> {
+        if let (KeyCode::Char('c'), KeyModifiers::CONTROL) = (key_event.code, key_event.modifiers) {
+            return Ok(Some(SettingsEditorEvent::Quit));
+        }
+
+        let Some(request) = self.collection_store.borrow().get_selected_request() else {
+            return Ok(None);
+        };
+        let mut request = request.write().unwrap();
+        let field = self.selected_field();
+        self.error = None;
+
+        if let Some(text) = self.editing.as_mut() {
+            match key_event.code {
+                // invalid text stays on the field, so it can be fixed
+                KeyCode::Enter => match field.set_text(&mut request.client, text) {
+                    Ok(()) => self.editing = None,
+                    Err(error) => self.error = Some(error),
+                },
+                KeyCode::Esc => self.editing = None,
+                KeyCode::Char(c) if field.kind().eq(&FieldKind::Number) && !c.is_ascii_digit() => {}
+                KeyCode::Char(c) => text.push(c),
+                KeyCode::Backspace => _ = text.pop(),
+                _ => {}
+            }
+            return Ok(None);
+        }
+
+        match key_event.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected_field = self.selected_field.add(1).min(SettingsField::ALL.len() - 1);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected_field = self.selected_field.saturating_sub(1);
+            }
+            KeyCode::Enter | KeyCode::Char('i') if field.kind().eq(&FieldKind::Toggle) => {
+                field.toggle(&mut request.client);
+            }
+            KeyCode::Enter | KeyCode::Char('i') => {
+                self.editing = Some(field.value(&request.client).unwrap_or_default());
+            }
+            KeyCode::Char('d') => field.clear(&mut request.client),
+            KeyCode::Esc => return Ok(Some(SettingsEditorEvent::RemoveSelection)),
+            _ => {}
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options_are_unset_when_empty() {
+        let mut settings = ClientSettings::default();
+
+        SettingsField::ConnectTimeout
+            .set_text(&mut settings, "250")
+            .unwrap();
+        SettingsField::LocalAddress
+            .set_text(&mut settings, " 10.0.0.2 ")
+            .unwrap();
+        assert_eq!(settings.connect_timeout, Some(250));
+        assert_eq!(settings.local_address.as_deref(), Some("10.0.0.2"));
+
+        SettingsField::ConnectTimeout
+            .set_text(&mut settings, "")
+            .unwrap();
+        assert_eq!(settings.connect_timeout, None);
+        SettingsField::LocalAddress.clear(&mut settings);
+        assert!(settings.is_empty());
+
+        let field = SettingsField::TitleCaseHeaders;
+        field.toggle(&mut settings);
+        assert_eq!(field.value(&settings).as_deref(), Some("true"));
+        field.toggle(&mut settings);
+        field.toggle(&mut settings);
+        assert_eq!(field.value(&settings), None);
+    }
+
+    #[test]
+    fn test_invalid_local_address_is_rejected() {
+        let mut settings = ClientSettings::default();
+
+        assert_eq!(
+            SettingsField::LocalAddress.set_text(&mut settings, "10.0.0"),
+            Err(String::from("10.0.0 is not an ip address"))
+        );
+        assert_eq!(settings.local_address, None);
+    }
+}
@@ -1,26 +1,49 @@
+use hac_core::net::uri::normalize_trailing_slash;
+
 use crate::pages::collection_viewer::collection_store::CollectionStore;
 use crate::pages::collection_viewer::collection_store::CollectionStoreAction;
 use crate::pages::collection_viewer::collection_viewer::PaneFocus;
 use crate::pages::{Eventful, Renderable};
 
 use std::cell::RefCell;
+use std::ops::{Add, Div, Rem};
 use std::rc::Rc;
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use ratatui::layout::Rect;
+use ratatui::layout::{Alignment, Rect};
 use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::block::{Position, Title};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
 
+/// amount of lines a long uri can span when `wrap_uri` is enabled, after that
+/// we scroll vertically to keep the cursor visible
+const WRAPPED_URI_LINES: u16 = 3;
+
+/// height of the pane, including its borders
+pub fn uri_pane_height(wrap_uri: bool) -> u16 {
+    match wrap_uri {
+        true => WRAPPED_URI_LINES.add(2),
+        false => 3,
+    }
+}
+
 /// Set of events RequestUri can send back to the caller when handling key_events
 #[derive(Debug)]
 pub enum RequestUriEvent {
     /// user pressed `Enter` while request uri was selected, so we bubble
     /// the SendRequest event for the parent to handle
     SendRequest,
+    /// user pressed `C-s`, the request is sent the same way as `SendRequest`
+    /// and its response is saved as a sample of the request
+    SendAndSaveSample,
     /// user pressed `Esc` while request uri was selected, so we bubble
     /// the event up for the parent to handle
     RemoveSelection,
+    /// user pressed `C-y`, the parent copies the url of the request with its
+    /// variables resolved
+    CopyResolvedUrl,
     /// requests the parent to select the next pane
     SelectNext,
     /// requests the parent to select the previous pane
@@ -32,6 +55,7 @@ pub enum RequestUriEvent {
 #[derive(Debug)]
 pub struct RequestUri<'ru> {
     colors: &'ru hac_colors::Colors,
+    config: Rc<hac_config::Config>,
     collection_store: Rc<RefCell<CollectionStore>>,
     size: Rect,
 }
@@ -39,11 +63,13 @@ pub struct RequestUri<'ru> {
 impl<'ru> RequestUri<'ru> {
     pub fn new(
         colors: &'ru hac_colors::Colors,
+        config: Rc<hac_config::Config>,
         collection_store: Rc<RefCell<CollectionStore>>,
         size: Rect,
     ) -> Self {
         Self {
             colors,
+            config,
             collection_store,
             size,
         }
@@ -73,23 +99,55 @@ impl<'a> Renderable for RequestUri<'a> {
             (false, _) => Style::default().fg(self.colors.bright.black),
         };
 
-        let uri = self
-            .collection_store
-            .borrow()
-            .get_selected_request()
+        let request = self.collection_store.borrow().get_selected_request();
+        let uri = request
             .as_ref()
             .map(|req| req.read().unwrap().uri.to_string())
             .unwrap_or_default();
 
+        // while editing, we scroll the uri so the cursor is always visible
+        let viewport = build_viewport(
+            &uri,
+            size.width.saturating_sub(2),
+            size.height.saturating_sub(2),
+            self.config.wrap_uri,
+            is_selected,
+        );
+
+        if is_selected && request.is_some() {
+            frame.set_cursor(
+                size.x.add(viewport.cursor.0).add(1),
+                size.y.add(viewport.cursor.1).add(1),
+            );
+        }
+
+        let effective_uri =
+            normalize_trailing_slash(&uri, self.collection_store.borrow().get_trailing_slash());
+
+        let lines = viewport
+            .lines
+            .into_iter()
+            .map(Line::from)
+            .collect::<Vec<_>>();
+
         frame.render_widget(
-            Paragraph::new(uri).fg(self.colors.normal.white).block(
+            Paragraph::new(lines).fg(self.colors.normal.white).block(
                 Block::default()
                     .borders(Borders::ALL)
                     .border_style(block_border)
                     .title(vec![
                         "U".fg(self.colors.normal.red).bold(),
                         "ri".fg(self.colors.bright.black),
-                    ]),
+                    ])
+                    .title(
+                        // only shown when the uri sent differs from the one written
+                        Title::from(match effective_uri.ne(&uri) {
+                            true => format!(" sends {effective_uri} "),
+                            false => String::new(),
+                        })
+                        .position(Position::Bottom)
+                        .alignment(Alignment::Right),
+                    ),
             ),
             size,
         );
@@ -98,6 +156,60 @@ impl<'a> Renderable for RequestUri<'a> {
     }
 }
 
+/// the part of the uri that fits on the pane, and the cursor position relative
+/// to the inner area of the pane
+#[derive(Debug, PartialEq)]
+struct UriViewport {
+    lines: Vec<String>,
+    cursor: (u16, u16),
+}
+
+fn build_viewport(
+    uri: &str,
+    width: u16,
+    height: u16,
+    wrap: bool,
+    follow_cursor: bool,
+) -> UriViewport {
+    let chars = uri.chars().collect::<Vec<_>>();
+    let len = chars.len();
+    let width = usize::from(width.max(1));
+    let height = usize::from(height.max(1));
+
+    if wrap {
+        let cursor_row = len.div(width);
+        let scroll = match follow_cursor {
+            true => cursor_row.saturating_sub(height - 1),
+            false => 0,
+        };
+        let lines = chars
+            .chunks(width)
+            .skip(scroll)
+            .take(height)
+            .map(String::from_iter)
+            .collect();
+
+        return UriViewport {
+            lines,
+            cursor: (
+                len.rem(width) as u16,
+                cursor_row.saturating_sub(scroll) as u16,
+            ),
+        };
+    }
+
+    // the cursor sits after the last character, so it needs a column of its own
+    let scroll = match follow_cursor {
+        true => len.add(1).saturating_sub(width),
+        false => 0,
+    };
+
+    UriViewport {
+        lines: vec![chars.iter().skip(scroll).take(width).collect()],
+        cursor: (len.saturating_sub(scroll) as u16, 0),
+    }
+}
+
 impl Eventful for RequestUri<'_> {
     type Result = RequestUriEvent;
 
@@ -117,6 +229,23 @@ impl Eventful for RequestUri<'_> {
             return Ok(Some(RequestUriEvent::Quit));
         }
 
+        if let (KeyCode::Char('s'), KeyModifiers::CONTROL) = (key_event.code, key_event.modifiers) {
+            let mut store = self.collection_store.borrow_mut();
+            if store
+                .get_selected_request()
+                .as_ref()
+                .is_some_and(|_| !store.has_pending_request())
+            {
+                store.dispatch(CollectionStoreAction::SetPendingRequest(true));
+                return Ok(Some(RequestUriEvent::SendAndSaveSample));
+            }
+            return Ok(None);
+        }
+
+        if let (KeyCode::Char('y'), KeyModifiers::CONTROL) = (key_event.code, key_event.modifiers) {
+            return Ok(Some(RequestUriEvent::CopyResolvedUrl));
+        }
+
         match key_event.code {
             KeyCode::Esc => return Ok(Some(RequestUriEvent::RemoveSelection)),
             KeyCode::Tab => return Ok(Some(RequestUriEvent::SelectNext)),
@@ -158,3 +287,46 @@ impl Eventful for RequestUri<'_> {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrolls_long_uri_to_keep_cursor_visible() {
+        let uri = "https://example.com/users?page=2";
+
+        let viewport = build_viewport(uri, 10, 1, false, true);
+
+        assert_eq!(viewport.lines, vec![String::from("rs?page=2")]);
+        assert_eq!(viewport.cursor, (9, 0));
+
+        let viewport = build_viewport(uri, 10, 1, false, false);
+
+        assert_eq!(viewport.lines, vec![String::from("https://ex")]);
+    }
+
+    #[test]
+    fn test_wraps_long_uri() {
+        let uri = "https://example.com/users?page=2";
+
+        let viewport = build_viewport(uri, 10, 3, true, false);
+
+        assert_eq!(
+            viewport.lines,
+            vec![
+                String::from("https://ex"),
+                String::from("ample.com/"),
+                String::from("users?page"),
+            ]
+        );
+
+        let viewport = build_viewport(uri, 10, 2, true, true);
+
+        assert_eq!(
+            viewport.lines,
+            vec![String::from("users?page"), String::from("=2")]
+        );
+        assert_eq!(viewport.cursor, (2, 1));
+    }
+}
@@ -0,0 +1,93 @@
+use crate::pages::text_overlay::TextOverlay;
+use crate::pages::{Eventful, Renderable};
+
+use std::ops::Add;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::{Line, Span};
+use ratatui::Frame;
+
+#[derive(Debug)]
+pub enum ResolvedRequestEvent {
+    /// user asked to copy the resolved request to the clipboard
+    Copy(String),
+    /// user closed the preview
+    Close,
+}
+
+/// shows the selected request exactly as it will be sent, with its variables
+/// resolved. Placeholders that could not be resolved are highlighted
+#[derive(Debug)]
+pub struct ResolvedRequest<'rr> {
+    colors: &'rr hac_colors::Colors,
+    overlay: TextOverlay<'rr>,
+}
+
+impl<'rr> ResolvedRequest<'rr> {
+    pub fn new(colors: &'rr hac_colors::Colors, text: String) -> Self {
+        ResolvedRequest {
+            colors,
+            overlay: TextOverlay::new(
+                colors,
+                "Resolved request",
+                "[Copy: y] [Scroll: j/k] [Close: Esc] unresolved variables are underlined",
+                text,
+            ),
+        }
+    }
+}
+
+/// splits `line` around every `{{name}}` placeholder left on it, so the
+/// unresolved variables stand out
+fn build_line<'a>(line: &'a str, colors: &hac_colors::Colors) -> Line<'a> {
+    let mut spans = vec![];
+    let mut rest = line;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start..].find("}}") else {
+            break;
+        };
+        spans.push(Span::from(&rest[..start]).fg(colors.normal.white));
+        spans.push(
+            Span::from(&rest[start..start.add(len).add(2)])
+                .fg(colors.normal.red)
+                .underlined(),
+        );
+        rest = &rest[start.add(len).add(2)..];
+    }
+    spans.push(Span::from(rest).fg(colors.normal.white));
+
+    Line::from(spans)
+}
+
+impl Renderable for ResolvedRequest<'_> {
+    fn draw(&mut self, frame: &mut Frame, _: Rect) -> anyhow::Result<()> {
+        let colors = self.colors;
+        self.overlay.draw(frame, |line| build_line(line, colors));
+
+        Ok(())
+    }
+}
+
+impl Eventful for ResolvedRequest<'_> {
+    type Result = ResolvedRequestEvent;
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> anyhow::Result<Option<Self::Result>> {
+        if let (KeyCode::Char('c'), KeyModifiers::CONTROL) = (key_event.code, key_event.modifiers) {
+            return Ok(Some(ResolvedRequestEvent::Close));
+        }
+        if self.overlay.handle_scroll(key_event) {
+            return Ok(None);
+        }
+
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q') => Ok(Some(ResolvedRequestEvent::Close)),
+            KeyCode::Char('y') => Ok(Some(ResolvedRequestEvent::Copy(
+                self.overlay.text().to_string(),
+            ))),
+            _ => Ok(None),
+        }
+    }
+}
@@ -1,24 +1,33 @@
-use hac_core::net::request_manager::Response;
+use hac_core::export::{assertion_snippet, AssertionFormat};
+use hac_core::net::etag_cache;
+use hac_core::net::request_manager::{DownloadProgress, Response};
+use hac_core::schema::infer_schema_from_str;
 use hac_core::syntax::highlighter::HIGHLIGHTER;
+use hac_core::text_object::cursor::Cursor;
 
 use crate::ascii::{BIG_ERROR_ARTS, LOGO_ASCII, SMALL_ERROR_ARTS};
-use crate::pages::collection_viewer::collection_viewer::PaneFocus;
+use crate::pages::collection_viewer::collection_viewer::{CollectionViewerOverlay, PaneFocus};
+use crate::pages::overlay::make_overlay;
 use crate::pages::under_construction::UnderConstruction;
 use crate::pages::{spinner::Spinner, Eventful, Renderable};
-use crate::utils::build_syntax_highlighted_lines;
+use crate::toast;
+use crate::utils::{
+    build_syntax_highlighted_lines, build_tabs, copy_to_clipboard, headers_to_json,
+};
 
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::iter;
-use std::ops::{Add, Sub};
+use std::ops::{Add, Div, Mul, Sub};
 use std::rc::Rc;
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use rand::Rng;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::{Style, Stylize};
+use ratatui::style::{Color, Style, Stylize};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph, Scrollbar};
-use ratatui::widgets::{ScrollbarOrientation, ScrollbarState, Tabs};
+use ratatui::widgets::{ScrollbarOrientation, ScrollbarState};
 use ratatui::Frame;
 use tree_sitter::Tree;
 
@@ -28,6 +37,11 @@ use super::collection_store::CollectionStore;
 pub enum ResponseViewerEvent {
     RemoveSelection,
     Quit,
+    /// user asked to follow the redirect of the current response, holds the
+    /// status, the url that responded and the `Location` it points to
+    FollowRedirect(u16, String, String),
+    /// user asked to read the body of the current response on their pager
+    OpenPager(String),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -36,6 +50,7 @@ pub enum ResViewerTabs {
     Raw,
     Cookies,
     Headers,
+    Request,
 }
 
 impl ResViewerTabs {
@@ -44,16 +59,38 @@ impl ResViewerTabs {
             Self::Preview => ResViewerTabs::Raw,
             Self::Raw => ResViewerTabs::Headers,
             Self::Headers => ResViewerTabs::Cookies,
-            Self::Cookies => ResViewerTabs::Preview,
+            Self::Cookies => ResViewerTabs::Request,
+            Self::Request => ResViewerTabs::Preview,
         }
     }
 
     pub fn prev(tab: &ResViewerTabs) -> Self {
         match tab {
-            Self::Preview => ResViewerTabs::Cookies,
+            Self::Preview => ResViewerTabs::Request,
             Self::Raw => ResViewerTabs::Preview,
             Self::Headers => ResViewerTabs::Raw,
             Self::Cookies => ResViewerTabs::Headers,
+            Self::Request => ResViewerTabs::Cookies,
+        }
+    }
+
+    /// swaps between the pretty printed and the raw body, going to the pretty
+    /// printed body from any other tab
+    pub fn toggle_raw(tab: &ResViewerTabs) -> Self {
+        match tab {
+            Self::Preview => ResViewerTabs::Raw,
+            _ => ResViewerTabs::Preview,
+        }
+    }
+}
+
+impl From<hac_config::ResponseTab> for ResViewerTabs {
+    fn from(value: hac_config::ResponseTab) -> Self {
+        match value {
+            hac_config::ResponseTab::Pretty => ResViewerTabs::Preview,
+            hac_config::ResponseTab::Raw => ResViewerTabs::Raw,
+            hac_config::ResponseTab::Headers => ResViewerTabs::Headers,
+            hac_config::ResponseTab::Cookies => ResViewerTabs::Cookies,
         }
     }
 }
@@ -65,6 +102,7 @@ impl From<ResViewerTabs> for usize {
             ResViewerTabs::Raw => 1,
             ResViewerTabs::Headers => 2,
             ResViewerTabs::Cookies => 3,
+            ResViewerTabs::Request => 4,
         }
     }
 }
@@ -82,13 +120,26 @@ struct PreviewLayout {
     scrollbar: Rect,
 }
 
+/// a top level entry of a json response, used to build the outline navigator.
+/// Entries are either the keys of an object or the indexes of an array
+#[derive(Debug, Clone, PartialEq)]
+struct OutlineEntry {
+    label: String,
+    /// row of the pretty printed body where this entry starts
+    row: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct ResponseViewer<'a> {
     colors: &'a hac_colors::Colors,
+    config: Rc<hac_config::Config>,
     response: Option<Rc<RefCell<Response>>>,
     tree: Option<Tree>,
     lines: Vec<Line<'static>>,
     error_lines: Option<Vec<Line<'static>>>,
+    /// whether the technical detail of a failed request is shown below its
+    /// cause
+    show_error_detail: bool,
     empty_lines: Vec<Line<'static>>,
     preview_layout: PreviewLayout,
     layout: ResViewerLayout,
@@ -97,12 +148,37 @@ pub struct ResponseViewer<'a> {
     raw_scroll: usize,
     headers_scroll_y: usize,
     headers_scroll_x: usize,
+    /// indices of the truncated headers the user expanded
+    expanded_headers: HashSet<usize>,
+    /// line where each header starts on the headers tab, as of the last draw
+    header_offsets: Vec<usize>,
     pretty_scroll: usize,
+    pretty_scroll_x: usize,
+    /// width of the widest line of the pretty body, computed once per response
+    /// as bodies can be quite big
+    pretty_longest_line: usize,
+    request_scroll: usize,
+    outline: Vec<OutlineEntry>,
+    outline_selected: usize,
+    show_outline: bool,
+    /// whether the keys of json bodies are shown sorted, only the preview is
+    /// affected, the response itself is kept as received
+    sort_keys: bool,
+    /// schema inferred from the response body, only set while the schema
+    /// overlay is open
+    schema: Option<String>,
+    schema_lines: Vec<Line<'static>>,
+    schema_scroll: usize,
+    /// where a selection of the pretty body started, while selecting text the
+    /// selected region spans from here to `selection_cursor`
+    selection_anchor: Option<Cursor>,
+    selection_cursor: Cursor,
 }
 
 impl<'a> ResponseViewer<'a> {
     pub fn new(
         colors: &'a hac_colors::Colors,
+        config: Rc<hac_config::Config>,
         collection_store: Rc<RefCell<CollectionStore>>,
         response: Option<Rc<RefCell<Response>>>,
         size: Rect,
@@ -117,31 +193,48 @@ impl<'a> ResponseViewer<'a> {
             }
         });
 
-        let layout = build_layout(size);
+        let layout = build_layout(size, config.response_max_width);
         let preview_layout = build_preview_layout(layout.content_pane);
 
         let empty_lines = make_empty_ascii_art(colors);
+        let default_tab = config.default_response_tab.into();
 
         ResponseViewer {
             colors,
+            config,
             response,
             tree,
             lines: vec![],
             error_lines: None,
+            show_error_detail: false,
             empty_lines,
             preview_layout,
             layout,
-            active_tab: ResViewerTabs::Preview,
+            active_tab: default_tab,
             raw_scroll: 0,
             headers_scroll_y: 0,
             headers_scroll_x: 0,
+            expanded_headers: HashSet::new(),
+            header_offsets: vec![],
             pretty_scroll: 0,
+            pretty_scroll_x: 0,
+            pretty_longest_line: 0,
+            request_scroll: 0,
+            outline: vec![],
+            outline_selected: 0,
+            show_outline: false,
+            sort_keys: false,
+            schema: None,
+            schema_lines: vec![],
+            schema_scroll: 0,
+            selection_anchor: None,
+            selection_cursor: Cursor::default(),
             collection_store,
         }
     }
 
     pub fn resize(&mut self, new_size: Rect) {
-        self.layout = build_layout(new_size);
+        self.layout = build_layout(new_size, self.config.response_max_width);
         self.preview_layout = build_preview_layout(self.layout.content_pane);
     }
 
@@ -155,8 +248,25 @@ impl<'a> ResponseViewer<'a> {
                     .map(|body| body.to_string())
             })
             .unwrap_or_default();
+        let body_str = match self.sort_keys {
+            true => hac_core::json::sort_json_keys(&body_str).unwrap_or(body_str),
+            false => body_str,
+        };
+
+        let download_path = response.as_ref().and_then(|res| {
+            res.borrow()
+                .download
+                .as_ref()
+                .map(|download| download.path.to_string_lossy().to_string())
+        });
 
-        if body_str.len().gt(&0) {
+        if let Some(path) = download_path {
+            self.tree = None;
+            self.lines = vec![Line::from(vec![
+                "Response body saved to ".fg(self.colors.bright.black),
+                path.fg(self.colors.normal.white),
+            ])];
+        } else if body_str.len().gt(&0) {
             self.tree = HIGHLIGHTER.write().unwrap().parse(&body_str);
             self.lines = build_syntax_highlighted_lines(&body_str, self.tree.as_ref(), self.colors);
         } else {
@@ -164,6 +274,23 @@ impl<'a> ResponseViewer<'a> {
             self.lines = vec![];
         }
 
+        self.pretty_longest_line = self
+            .lines
+            .iter()
+            .map(|line| line.width())
+            .max()
+            .unwrap_or_default();
+
+        self.outline = self
+            .tree
+            .as_ref()
+            .map(|tree| build_outline(tree, &body_str))
+            .unwrap_or_default();
+        self.outline_selected = 0;
+        self.expanded_headers.clear();
+        self.selection_anchor = None;
+        self.show_error_detail = false;
+
         if let Some(res) = response.as_ref() {
             let cause: String = res
                 .borrow()
@@ -194,6 +321,16 @@ impl<'a> ResponseViewer<'a> {
             )
         };
 
+        // progress of a download is not a new response, so the user stays on
+        // the tab they were looking at
+        let continues_download = self
+            .response
+            .as_ref()
+            .is_some_and(|res| res.borrow().is_downloading());
+        if !continues_download {
+            self.active_tab = self.config.default_response_tab.into();
+        }
+
         self.empty_lines = make_empty_ascii_art(self.colors);
         self.response = response;
     }
@@ -228,14 +365,12 @@ impl<'a> ResponseViewer<'a> {
     }
 
     fn draw_tabs(&self, frame: &mut Frame, size: Rect) {
-        let tabs = Tabs::new(["Pretty", "Raw", "Headers", "Cookies"])
-            .style(Style::default().fg(self.colors.bright.black))
-            .select(self.active_tab.clone().into())
-            .highlight_style(
-                Style::default()
-                    .fg(self.colors.normal.white)
-                    .bg(self.colors.normal.blue),
-            );
+        let tabs = build_tabs(
+            ["Pretty", "Raw", "Headers", "Cookies", "Request"],
+            self.active_tab.clone().into(),
+            self.colors,
+            &self.config,
+        );
         frame.render_widget(tabs, size);
     }
 
@@ -243,8 +378,24 @@ impl<'a> ResponseViewer<'a> {
         let request_pane = self.preview_layout.content_pane;
         let center = request_pane.y.add(request_pane.height.div_ceil(2));
         let size = Rect::new(request_pane.x, center, request_pane.width, 1);
+        let is_queued = self
+            .collection_store
+            .borrow()
+            .get_selected_request()
+            .is_some_and(|req| hac_core::net::is_request_queued(&req.read().unwrap().id));
+        let download = self
+            .response
+            .as_ref()
+            .and_then(|res| res.borrow().download.clone());
+        let label = match (is_queued, download) {
+            (true, _) => String::from("Queued, waiting for other requests to finish"),
+            (false, Some(download)) => {
+                format!("Downloading {}", format_download_progress(&download))
+            }
+            (false, None) => String::from("Sending request"),
+        };
         let spinner = Spinner::default()
-            .with_label("Sending request".fg(self.colors.bright.black))
+            .with_label(label.fg(self.colors.bright.black))
             .with_style(Style::default().fg(self.colors.normal.red))
             .into_centered_line();
 
@@ -257,8 +408,24 @@ impl<'a> ResponseViewer<'a> {
     }
 
     fn draw_network_error(&self, frame: &mut Frame) {
-        if self.response.as_ref().is_some() {
+        if let Some(response) = self.response.as_ref() {
             let request_pane = self.preview_layout.content_pane;
+            let mut lines = self.error_lines.clone().unwrap_or_default();
+            match response.borrow().cause_detail.as_ref() {
+                Some(detail) if self.show_error_detail => {
+                    lines.push("".into());
+                    lines.extend(
+                        wrap_chars(detail, self.layout.content_pane.width.sub(3).into())
+                            .into_iter()
+                            .map(|chunk| Line::from(chunk.fg(self.colors.bright.black))),
+                    );
+                }
+                Some(_) => {
+                    lines.push("".into());
+                    lines.push(Line::from("[x] show details".fg(self.colors.bright.black)));
+                }
+                None => {}
+            }
 
             frame.render_widget(Clear, request_pane);
             frame.render_widget(
@@ -269,19 +436,17 @@ impl<'a> ResponseViewer<'a> {
             let center = request_pane
                 .y
                 .add(request_pane.height.div_ceil(2))
-                .sub(self.error_lines.as_ref().unwrap().len().div_ceil(2) as u16);
+                .saturating_sub(lines.len().div_ceil(2) as u16)
+                .max(request_pane.y);
 
             let size = Rect::new(
                 request_pane.x.add(1),
                 center,
                 request_pane.width,
-                self.error_lines.as_ref().unwrap().len() as u16,
+                (lines.len() as u16).min(request_pane.height),
             );
 
-            frame.render_widget(
-                Paragraph::new(self.error_lines.clone().unwrap()).fg(self.colors.bright.black),
-                size,
-            )
+            frame.render_widget(Paragraph::new(lines).fg(self.colors.bright.black), size)
         }
     }
 
@@ -326,11 +491,15 @@ impl<'a> ResponseViewer<'a> {
     }
 
     fn draw_current_tab(&mut self, frame: &mut Frame, size: Rect) -> anyhow::Result<()> {
-        if self
+        let is_error = self
             .response
             .as_ref()
-            .is_some_and(|res| res.borrow().is_error)
-        {
+            .is_some_and(|res| res.borrow().is_error);
+        // failed requests may still have been sent, so the request tab is
+        // shown even when there was an error
+        let is_request_tab = self.active_tab.eq(&ResViewerTabs::Request);
+
+        if is_error && !is_request_tab {
             self.draw_network_error(frame);
         };
 
@@ -338,16 +507,13 @@ impl<'a> ResponseViewer<'a> {
             self.draw_waiting_for_request(frame);
         }
 
-        if self
-            .response
-            .as_ref()
-            .is_some_and(|res| !res.borrow().is_error)
-        {
+        if self.response.is_some() && (!is_error || is_request_tab) {
             match self.active_tab {
                 ResViewerTabs::Preview => self.draw_pretty_response(frame, size),
                 ResViewerTabs::Raw => self.draw_raw_response(frame, size),
                 ResViewerTabs::Headers => self.draw_response_headers(frame),
                 ResViewerTabs::Cookies => UnderConstruction::new(self.colors).draw(frame, size)?,
+                ResViewerTabs::Request => self.draw_sent_request(frame, size),
             }
         }
 
@@ -362,34 +528,69 @@ impl<'a> ResponseViewer<'a> {
         if let Some(response) = self.response.as_ref() {
             if let Some(headers) = response.borrow().headers.as_ref() {
                 let mut longest_line: usize = 0;
+                let max_length = self.config.header_value_max_length;
+                // one column is taken by the padding of the block
+                let wrap_width = self.preview_layout.content_pane.width.saturating_sub(1);
 
                 let mut lines: Vec<Line> = vec![
                     Line::from("Headers".fg(self.colors.normal.red).bold()),
                     Line::from(""),
                 ];
-
-                for (name, value) in headers {
-                    if let Ok(value) = value.to_str() {
-                        let name_string = name.to_string();
-                        let aux = name_string.len().max(value.len());
-                        longest_line = aux.max(longest_line);
-                        lines.push(Line::from(
-                            name_string
-                                .chars()
-                                .skip(self.headers_scroll_x)
-                                .collect::<String>()
-                                .bold()
-                                .yellow(),
-                        ));
-                        lines.push(Line::from(
-                            value
-                                .chars()
-                                .skip(self.headers_scroll_x)
-                                .collect::<String>(),
-                        ));
-                        lines.push(Line::from(""));
+                let mut header_offsets = vec![];
+
+                // repeated headers, like `set-cookie`, are yielded once for each value
+                for (idx, (name, value)) in headers.iter().enumerate() {
+                    header_offsets.push(lines.len());
+                    let value = header_value_to_string(value);
+                    let name_string = name.to_string();
+                    let truncated = max_length.gt(&0) && value.chars().count().gt(&max_length);
+                    lines.push(Line::from(
+                        name_string
+                            .chars()
+                            .skip(self.headers_scroll_x)
+                            .collect::<String>()
+                            .bold()
+                            .yellow(),
+                    ));
+
+                    match (truncated, self.expanded_headers.contains(&idx)) {
+                        // expanded values are wrapped, so they never scroll horizontally
+                        (true, true) => {
+                            longest_line = name_string.len().max(longest_line);
+                            lines.extend(
+                                wrap_chars(&value, wrap_width.into())
+                                    .into_iter()
+                                    .map(Line::from),
+                            );
+                        }
+                        (true, false) => {
+                            let hint = "… (x to expand)";
+                            let aux = name_string.len().max(max_length.add(hint.chars().count()));
+                            longest_line = aux.max(longest_line);
+                            lines.push(Line::from(vec![
+                                value
+                                    .chars()
+                                    .take(max_length)
+                                    .skip(self.headers_scroll_x)
+                                    .collect::<String>()
+                                    .into(),
+                                hint.fg(self.colors.bright.black),
+                            ]));
+                        }
+                        (false, _) => {
+                            let aux = name_string.len().max(value.chars().count());
+                            longest_line = aux.max(longest_line);
+                            lines.push(Line::from(
+                                value
+                                    .chars()
+                                    .skip(self.headers_scroll_x)
+                                    .collect::<String>(),
+                            ));
+                        }
                     }
+                    lines.push(Line::from(""));
                 }
+                self.header_offsets = header_offsets;
 
                 if self
                     .headers_scroll_y
@@ -445,38 +646,118 @@ impl<'a> ResponseViewer<'a> {
         }
     }
 
+    /// expands or collapses the value of the header at the top of the view,
+    /// values are only truncated when longer than `header_value_max_length`
+    fn toggle_header_expansion(&mut self) {
+        let top = self
+            .headers_scroll_y
+            .max(self.header_offsets.first().copied().unwrap_or_default());
+        let Some(idx) = self
+            .header_offsets
+            .iter()
+            .rposition(|offset| offset.le(&top))
+        else {
+            return;
+        };
+
+        if !self.expanded_headers.remove(&idx) {
+            self.expanded_headers.insert(idx);
+        }
+    }
+
+    fn draw_sent_request(&mut self, frame: &mut Frame, size: Rect) {
+        let Some(response) = self.response.as_ref() else {
+            return;
+        };
+        let response = response.borrow();
+        let Some(sent) = response.sent.as_ref() else {
+            frame.render_widget(
+                Paragraph::new(Line::from("Request was never sent").centered()),
+                self.preview_layout.content_pane,
+            );
+            return;
+        };
+
+        let mut lines = vec![
+            Line::from(sent.request_line.clone().fg(self.colors.normal.red).bold()),
+            Line::from(""),
+        ];
+        for (name, value) in sent.headers.iter() {
+            lines.push(Line::from(vec![
+                name.clone().bold().yellow(),
+                ": ".into(),
+                value.clone().into(),
+            ]));
+        }
+        if let Some(body) = sent.body.as_ref() {
+            lines.push(Line::from(""));
+            lines.extend(body.lines().map(|line| Line::from(line.to_string())));
+        }
+
+        if self.request_scroll.ge(&lines.len().saturating_sub(1)) {
+            self.request_scroll = lines.len().saturating_sub(1);
+        }
+
+        self.draw_scrollbar(
+            lines.len(),
+            self.request_scroll,
+            frame,
+            self.preview_layout.scrollbar,
+        );
+
+        let lines_in_view = lines
+            .into_iter()
+            .skip(self.request_scroll)
+            .chain(iter::repeat(Line::from("~".fg(self.colors.bright.black))))
+            .take(size.height.into())
+            .collect::<Vec<_>>();
+
+        frame.render_widget(
+            Paragraph::new(lines_in_view).block(Block::default().padding(Padding::left(1))),
+            self.preview_layout.content_pane,
+        );
+    }
+
     fn draw_raw_response(&mut self, frame: &mut Frame, size: Rect) {
         if let Some(response) = self.response.as_ref() {
-            let lines = if response.borrow().body.is_some() {
-                response
-                    .borrow()
-                    .body
-                    .as_ref()
-                    .unwrap()
-                    .chars()
-                    .collect::<Vec<_>>()
-                    // accounting for the scrollbar width when splitting the lines
-                    .chunks(size.width.saturating_sub(2).into())
-                    .map(|row| Line::from(row.iter().collect::<String>()))
-                    .collect::<Vec<_>>()
-            } else {
-                vec![Line::from("No body").centered()]
-            };
+            let response = response.borrow();
+            // accounting for the scrollbar width when splitting the lines
+            let line_width = usize::from(size.width.saturating_sub(2)).max(1);
+            let total_lines = response
+                .body
+                .as_ref()
+                .map(|body| body.chars().count().div_ceil(line_width).max(1))
+                .unwrap_or(1);
+
             // allow for scrolling down until theres only one line left into view
-            if self.raw_scroll.ge(&lines.len().saturating_sub(1)) {
-                self.raw_scroll = lines.len().saturating_sub(1);
+            if self.raw_scroll.ge(&total_lines.saturating_sub(1)) {
+                self.raw_scroll = total_lines.saturating_sub(1);
             }
 
             self.draw_scrollbar(
-                lines.len(),
+                total_lines,
                 self.raw_scroll,
                 frame,
                 self.preview_layout.scrollbar,
             );
 
+            // bodies can get really big, so we only split the rows that are in view
+            // instead of allocating a line for every row of the body on every frame
+            let lines = match response.body.as_ref().filter(|body| !body.is_empty()) {
+                Some(body) => {
+                    let mut chars = body.chars().skip(self.raw_scroll.mul(line_width));
+                    iter::from_fn(|| {
+                        let row = chars.by_ref().take(line_width).collect::<String>();
+                        (!row.is_empty()).then(|| Line::from(row))
+                    })
+                    .take(size.height.into())
+                    .collect::<Vec<_>>()
+                }
+                None => vec![self.empty_body_line(response.status)],
+            };
+
             let lines_in_view = lines
                 .into_iter()
-                .skip(self.raw_scroll)
                 .chain(iter::repeat(Line::from("~".fg(self.colors.bright.black))))
                 .take(size.height.into())
                 .collect::<Vec<_>>();
@@ -520,6 +801,201 @@ impl<'a> ResponseViewer<'a> {
         frame.render_stateful_widget(scrollbar, size, &mut scrollbar_state);
     }
 
+    /// copies a test assertion snippet generated from the current response to the
+    /// clipboard, does nothing when there is no response or it is an error
+    fn copy_assertion_snippet(&self, format: AssertionFormat) -> anyhow::Result<()> {
+        let Some(response) = self.response.as_ref() else {
+            return Ok(());
+        };
+        let Some(request) = self.collection_store.borrow().get_selected_request() else {
+            return Ok(());
+        };
+
+        let response = response.borrow();
+        if response.is_error {
+            return Ok(());
+        }
+
+        let snippet = assertion_snippet(&request.read().unwrap(), &response, format);
+        copy_to_clipboard(&snippet)?;
+        toast::show("copied assertion to clipboard");
+        Ok(())
+    }
+
+    /// copies the headers of the current response as a json object to the
+    /// clipboard, does nothing when the response has no headers
+    fn copy_headers_as_json(&self) -> anyhow::Result<()> {
+        let Some(response) = self.response.as_ref() else {
+            return Ok(());
+        };
+        let response = response.borrow();
+        let Some(headers) = response.headers.as_ref() else {
+            return Ok(());
+        };
+
+        let json = serde_json::to_string_pretty(&headers_to_json(headers))?;
+        copy_to_clipboard(&json)?;
+        toast::show("copied headers as json to clipboard");
+        Ok(())
+    }
+
+    /// failed requests with technical detail show it on every tab but the
+    /// request one, where it can be expanded
+    fn shows_error_detail_toggle(&self) -> bool {
+        self.active_tab.ne(&ResViewerTabs::Request)
+            && self.response.as_ref().is_some_and(|response| {
+                let response = response.borrow();
+                response.is_error && response.cause_detail.is_some()
+            })
+    }
+
+    /// status, url and location of the current response when it is a redirect
+    /// we can follow
+    fn redirect(&self) -> Option<(u16, String, String)> {
+        let response = self.response.as_ref()?.borrow();
+        let status = response.status.filter(|status| status.is_redirection())?;
+        let location = response.headers.as_ref()?.get(reqwest::header::LOCATION)?;
+        let location = location.to_str().ok()?.to_string();
+        let url = response.sent.as_ref()?.url.clone();
+        Some((status.as_u16(), url, location))
+    }
+
+    /// infers a schema from the current response body and opens the overlay that
+    /// displays it, does nothing when the body isn't json
+    fn show_schema(&mut self) {
+        let schema = self.response.as_ref().and_then(|response| {
+            let response = response.borrow();
+            response.body.as_deref().and_then(infer_schema_from_str)
+        });
+
+        let Some(schema) = schema else {
+            return;
+        };
+
+        let tree = HIGHLIGHTER.write().unwrap().parse(&schema);
+        self.schema_lines = build_syntax_highlighted_lines(&schema, tree.as_ref(), self.colors);
+        self.schema_scroll = 0;
+        self.schema = Some(schema);
+        self.collection_store
+            .borrow_mut()
+            .push_overlay(CollectionViewerOverlay::ResponseSchema);
+    }
+
+    fn close_schema(&mut self) {
+        self.schema = None;
+        self.schema_lines = vec![];
+        self.collection_store.borrow_mut().pop_overlay();
+    }
+
+    fn handle_schema_key_event(&mut self, key_event: KeyEvent) -> anyhow::Result<()> {
+        match key_event.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.schema_scroll = self
+                    .schema_scroll
+                    .add(1)
+                    .min(self.schema_lines.len().saturating_sub(1));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.schema_scroll = self.schema_scroll.saturating_sub(1);
+            }
+            KeyCode::Char('y') => {
+                if let Some(schema) = self.schema.as_ref() {
+                    copy_to_clipboard(schema)?;
+                    toast::show("copied schema to clipboard");
+                }
+            }
+            KeyCode::Char('s') | KeyCode::Char('q') | KeyCode::Esc => self.close_schema(),
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    pub fn draw_overlay(&mut self, frame: &mut Frame, overlay: CollectionViewerOverlay) {
+        if let CollectionViewerOverlay::ResponseSchema = overlay {
+            make_overlay(self.colors, self.colors.normal.black, 0.1, frame);
+
+            let size = frame.size();
+            let size = Rect::new(
+                size.width.div(4),
+                size.height.div(8),
+                size.width.div(2),
+                size.height.mul(3).div(4),
+            );
+
+            let block = Block::default()
+                .title("Response schema")
+                .title_bottom(
+                    Line::from("(y) copy  (j/k) scroll  (esc) close").fg(self.colors.bright.black),
+                )
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.colors.bright.black))
+                .bg(self.colors.primary.background)
+                .padding(Padding::horizontal(1));
+
+            let lines = self
+                .schema_lines
+                .iter()
+                .skip(self.schema_scroll)
+                .take(size.height.saturating_sub(2).into())
+                .cloned()
+                .collect::<Vec<_>>();
+
+            frame.render_widget(Clear, size);
+            frame.render_widget(Paragraph::new(lines).block(block), size);
+        }
+    }
+
+    fn draw_outline(&self, frame: &mut Frame, size: Rect) {
+        let block = Block::default()
+            .borders(Borders::LEFT)
+            .border_style(Style::default().fg(self.colors.bright.black))
+            .padding(Padding::left(1));
+
+        let header = [
+            Line::from("Outline".fg(self.colors.normal.red).bold()),
+            Line::from(""),
+        ];
+
+        // keep the selected entry in view, accounting for the header lines
+        let visible_entries = usize::from(size.height.saturating_sub(2));
+        let skip = self.outline_selected.add(1).saturating_sub(visible_entries);
+
+        let entries = self
+            .outline
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .map(|(idx, entry)| {
+                let style = if idx.eq(&self.outline_selected) {
+                    Style::default()
+                        .fg(self.colors.normal.white)
+                        .bg(self.colors.primary.hover)
+                } else {
+                    Style::default().fg(self.colors.normal.white)
+                };
+                Line::from(entry.label.clone()).style(style)
+            });
+
+        let lines = header.into_iter().chain(entries).collect::<Vec<_>>();
+
+        frame.render_widget(Clear, size);
+        frame.render_widget(Paragraph::new(lines).block(block), size);
+    }
+
+    /// message shown in place of an empty body, errors are highlighted as
+    /// their empty body is usually what hides the reason they failed
+    fn empty_body_line(&self, status: Option<reqwest::StatusCode>) -> Line<'static> {
+        let color = match status {
+            Some(status) if status.is_client_error() || status.is_server_error() => {
+                self.colors.normal.red
+            }
+            _ => self.colors.bright.black,
+        };
+
+        Line::from(empty_body_message(status).fg(color)).centered()
+    }
+
     fn draw_pretty_response(&mut self, frame: &mut Frame, size: Rect) {
         if self.response.as_ref().is_some() {
             if self.pretty_scroll.ge(&self.lines.len().saturating_sub(1)) {
@@ -528,42 +1004,157 @@ impl<'a> ResponseViewer<'a> {
 
             self.draw_scrollbar(
                 self.lines.len(),
-                self.raw_scroll,
+                self.pretty_scroll,
                 frame,
                 self.preview_layout.scrollbar,
             );
 
+            let status = self.response.as_ref().and_then(|res| res.borrow().status);
+            let no_body = [self.empty_body_line(status)];
             let lines = if self.lines.len().gt(&0) {
-                self.lines.clone()
+                self.lines.as_slice()
             } else {
-                vec![Line::from("No body").centered()]
+                &no_body
             };
 
+            if self
+                .pretty_scroll_x
+                .ge(&self.pretty_longest_line.saturating_sub(1))
+            {
+                self.pretty_scroll_x = self.pretty_longest_line.saturating_sub(1);
+            }
+
+            let overflows =
+                self.pretty_longest_line
+                    .gt(&self.preview_layout.content_pane.width.into());
+            let [scrolled_pane, x_scrollbar_pane] =
+                build_horizontal_scrollbar(self.preview_layout.content_pane);
+            let pretty_pane = match overflows {
+                true => scrolled_pane,
+                false => self.preview_layout.content_pane,
+            };
+
+            let selection = self.selection_range();
             let lines_in_view = lines
-                .into_iter()
+                .iter()
+                .enumerate()
                 .skip(self.pretty_scroll)
+                .map(|(row, line)| match selection {
+                    Some((start, end)) if (start.0..=end.0).contains(&row) => {
+                        let from = if row.eq(&start.0) { start.1 } else { 0 };
+                        let to = if row.eq(&end.0) { end.1 } else { usize::MAX };
+                        highlight_columns(line, from, to, self.colors.primary.hover)
+                    }
+                    _ => line.clone(),
+                })
+                .map(|line| skip_columns(&line, self.pretty_scroll_x))
                 .chain(iter::repeat(Line::from("~".fg(self.colors.bright.black))))
-                .take(size.height.into())
+                .take(pretty_pane.height.min(size.height).into())
                 .collect::<Vec<_>>();
 
             let pretty_response = Paragraph::new(lines_in_view);
-            frame.render_widget(pretty_response, self.preview_layout.content_pane);
+            frame.render_widget(pretty_response, pretty_pane);
+
+            if overflows {
+                self.draw_horizontal_scrollbar(
+                    self.pretty_longest_line,
+                    self.pretty_scroll_x,
+                    frame,
+                    x_scrollbar_pane,
+                );
+            }
+
+            if self.show_outline && !self.outline.is_empty() {
+                let outline_pane = build_outline_layout(self.preview_layout.content_pane);
+                self.draw_outline(frame, outline_pane);
+            }
+        }
+    }
+
+    /// starts selecting the pretty body from the top left corner of the view
+    fn start_selection(&mut self) {
+        if self.lines.is_empty() {
+            return;
+        }
+        let mut cursor = Cursor::default();
+        cursor.move_to_row(self.pretty_scroll.min(self.lines.len().sub(1)));
+        cursor.move_to_col(self.pretty_scroll_x);
+        cursor.maybe_snap_to_col(self.lines[cursor.row()].width());
+        self.selection_anchor = Some(cursor.clone());
+        self.selection_cursor = cursor;
+    }
+
+    /// start and end, as row and column, of the selected region, in the order
+    /// they appear on the body
+    fn selection_range(&self) -> Option<((usize, usize), (usize, usize))> {
+        let anchor = self.selection_anchor.as_ref()?;
+        let anchor = (anchor.row(), anchor.col());
+        let cursor = (self.selection_cursor.row(), self.selection_cursor.col());
+        Some((anchor.min(cursor), anchor.max(cursor)))
+    }
+
+    fn handle_selection_key_event(&mut self, key_event: KeyEvent) -> anyhow::Result<()> {
+        let cursor = &mut self.selection_cursor;
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('v') => self.selection_anchor = None,
+            KeyCode::Char('y') => {
+                if let Some((start, end)) = self.selection_range() {
+                    let lines = self.lines.iter().map(line_text).collect::<Vec<_>>();
+                    copy_to_clipboard(&selected_text(&lines, start, end))?;
+                    toast::show("copied to clipboard");
+                }
+                self.selection_anchor = None;
+            }
+            KeyCode::Char('h') | KeyCode::Left => cursor.move_left(1),
+            KeyCode::Char('l') | KeyCode::Right => cursor.move_right(1),
+            KeyCode::Char('j') | KeyCode::Down => cursor.move_down(1),
+            KeyCode::Char('k') | KeyCode::Up => cursor.move_up(1),
+            KeyCode::Char('0') => cursor.move_to_line_start(),
+            KeyCode::Char('$') => cursor.move_to_col(usize::MAX),
+            _ => {}
         }
+
+        let cursor = &mut self.selection_cursor;
+        cursor.move_to_row(cursor.row().min(self.lines.len().saturating_sub(1)));
+        let line_len = self
+            .lines
+            .get(cursor.row())
+            .map(Line::width)
+            .unwrap_or_default();
+        cursor.maybe_snap_to_col(line_len);
+
+        // keeps the cursor in view while selecting past the edges of the pane
+        let height = self.preview_layout.content_pane.height.max(1) as usize;
+        let width = self.preview_layout.content_pane.width.max(1) as usize;
+        self.pretty_scroll = self
+            .pretty_scroll
+            .clamp(cursor.row().saturating_sub(height.sub(1)), cursor.row());
+        self.pretty_scroll_x = self
+            .pretty_scroll_x
+            .clamp(cursor.col().saturating_sub(width.sub(1)), cursor.col());
+
+        Ok(())
     }
 
     fn draw_summary(&self, frame: &mut Frame, size: Rect) {
         if let Some(ref response) = self.response {
+            let from_cache = etag_cache::is_served_from_cache(&response.borrow());
             let status_color = match response
                 .borrow()
                 .status
                 .map(|status| status.as_u16())
                 .unwrap_or_default()
             {
+                _ if from_cache => self.colors.normal.yellow,
                 s if s < 400 => self.colors.normal.green,
                 _ => self.colors.normal.red,
             };
 
             let status = match response.borrow().status {
+                Some(status) if from_cache && size.width.gt(&50) => {
+                    format!("{} (Not Modified, served from prior body)", status.as_str())
+                        .fg(status_color)
+                }
                 Some(status) if size.width.gt(&50) => format!(
                     "{} ({})",
                     status.as_str(),
@@ -576,20 +1167,87 @@ impl<'a> ResponseViewer<'a> {
                 None => "Error".fg(self.colors.normal.red),
             };
 
-            let mut pieces: Vec<Span> = vec![
-                "Status: ".fg(self.colors.bright.black),
-                status,
+            let mut pieces: Vec<Span> = vec!["Status: ".fg(self.colors.bright.black), status];
+
+            let expected_status = self
+                .collection_store
+                .borrow()
+                .get_selected_request()
+                .and_then(|request| request.read().unwrap().expected_status);
+            if let Some(expected) = expected_status {
+                let actual = response.borrow().status.map(|status| status.as_u16());
+                let (badge, color) = match actual {
+                    Some(status) if expected.matches(status) => {
+                        (" PASS ", self.colors.normal.green)
+                    }
+                    _ => (" FAIL ", self.colors.normal.red),
+                };
+                pieces.push(" ".into());
+                pieces.push(badge.fg(self.colors.normal.black).bg(color).bold());
+                if size.width.gt(&50) {
+                    pieces.push(format!(" expected {expected}").fg(self.colors.bright.black));
+                }
+            }
+
+            // the request was edited after this response arrived, so it may not
+            // match what the request sends now
+            let is_edited = {
+                let store = self.collection_store.borrow();
+                store
+                    .get_selected_request()
+                    .is_some_and(|request| store.is_request_edited(&request.read().unwrap().id))
+            };
+            if is_edited {
+                pieces.push(" ".into());
+                pieces.push(
+                    " STALE "
+                        .fg(self.colors.normal.black)
+                        .bg(self.colors.normal.yellow)
+                        .bold(),
+                );
+            }
+
+            if let Some(version) = response.borrow().version.filter(|_| size.width.gt(&50)) {
+                pieces.extend([
+                    " ".into(),
+                    format!("{version:?}").fg(self.colors.bright.black),
+                ]);
+            }
+
+            pieces.extend([
                 " ".into(),
                 "Time: ".fg(self.colors.bright.black),
                 format!("{}ms", response.borrow().duration.as_millis())
                     .fg(self.colors.normal.green),
                 " ".into(),
-            ];
+            ]);
 
-            if let Some(size) = response.borrow().size {
-                pieces.push("Size: ".fg(self.colors.bright.black));
-                pieces.push(format!("{} B", size).fg(self.colors.normal.green))
-            };
+            match response.borrow().download.as_ref() {
+                Some(download) => {
+                    pieces.push("Downloaded: ".fg(self.colors.bright.black));
+                    pieces.push(format_download_progress(download).fg(self.colors.normal.green))
+                }
+                None => {
+                    if let Some(size) = response.borrow().size {
+                        pieces.push("Size: ".fg(self.colors.bright.black));
+                        pieces.push(format!("{} B", size).fg(self.colors.normal.green))
+                    };
+                }
+            }
+
+            let timings = self
+                .collection_store
+                .borrow()
+                .get_selected_request()
+                .and_then(|request| request.read().unwrap().timings.clone());
+            if let Some(timings) = timings.filter(|_| size.width.gt(&50)) {
+                pieces.push(" ".into());
+                pieces.push("Avg: ".fg(self.colors.bright.black));
+                pieces.push(
+                    format!("{}ms over {} runs", timings.average_ms(), timings.runs)
+                        .fg(self.colors.normal.green),
+                );
+            }
 
             frame.render_widget(Line::from(pieces), size);
         }
@@ -617,6 +1275,43 @@ impl<'a> Eventful for ResponseViewer<'a> {
             return Ok(Some(ResponseViewerEvent::Quit));
         }
 
+        if self
+            .collection_store
+            .borrow()
+            .peek_overlay()
+            .eq(&CollectionViewerOverlay::ResponseSchema)
+        {
+            self.handle_schema_key_event(key_event)?;
+            return Ok(None);
+        }
+
+        if self.show_outline {
+            match key_event.code {
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.outline_selected = self
+                        .outline_selected
+                        .add(1)
+                        .min(self.outline.len().saturating_sub(1));
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.outline_selected = self.outline_selected.saturating_sub(1);
+                }
+                KeyCode::Enter => {
+                    if let Some(entry) = self.outline.get(self.outline_selected) {
+                        self.pretty_scroll = entry.row;
+                    }
+                }
+                KeyCode::Char('o') | KeyCode::Esc => self.show_outline = false,
+                _ => {}
+            }
+            return Ok(None);
+        }
+
+        if self.selection_anchor.is_some() {
+            self.handle_selection_key_event(key_event)?;
+            return Ok(None);
+        }
+
         if let KeyCode::Esc = key_event.code {
             return Ok(Some(ResponseViewerEvent::RemoveSelection));
         }
@@ -630,22 +1325,31 @@ impl<'a> Eventful for ResponseViewer<'a> {
         }
 
         match key_event.code {
-            KeyCode::Char('0') if self.active_tab.eq(&ResViewerTabs::Headers) => {
-                self.headers_scroll_x = 0;
-            }
-            KeyCode::Char('$') if self.active_tab.eq(&ResViewerTabs::Headers) => {
-                self.headers_scroll_x = usize::MAX;
-            }
-            KeyCode::Char('h') => {
-                if let ResViewerTabs::Headers = self.active_tab {
+            KeyCode::Char('0') => match self.active_tab {
+                ResViewerTabs::Headers => self.headers_scroll_x = 0,
+                ResViewerTabs::Preview => self.pretty_scroll_x = 0,
+                _ => {}
+            },
+            KeyCode::Char('$') => match self.active_tab {
+                ResViewerTabs::Headers => self.headers_scroll_x = usize::MAX,
+                ResViewerTabs::Preview => self.pretty_scroll_x = usize::MAX,
+                _ => {}
+            },
+            KeyCode::Char('h') => match self.active_tab {
+                ResViewerTabs::Headers => {
                     self.headers_scroll_x = self.headers_scroll_x.saturating_sub(1)
                 }
-            }
+                ResViewerTabs::Preview => {
+                    self.pretty_scroll_x = self.pretty_scroll_x.saturating_sub(1)
+                }
+                _ => {}
+            },
             KeyCode::Char('j') => match self.active_tab {
                 ResViewerTabs::Preview => self.pretty_scroll = self.pretty_scroll.add(1),
                 ResViewerTabs::Raw => self.raw_scroll = self.raw_scroll.add(1),
                 ResViewerTabs::Headers => self.headers_scroll_y = self.headers_scroll_y.add(1),
                 ResViewerTabs::Cookies => {}
+                ResViewerTabs::Request => self.request_scroll = self.request_scroll.add(1),
             },
             KeyCode::Char('k') => match self.active_tab {
                 ResViewerTabs::Preview => self.pretty_scroll = self.pretty_scroll.saturating_sub(1),
@@ -654,12 +1358,59 @@ impl<'a> Eventful for ResponseViewer<'a> {
                     self.headers_scroll_y = self.headers_scroll_y.saturating_sub(1)
                 }
                 ResViewerTabs::Cookies => {}
+                ResViewerTabs::Request => {
+                    self.request_scroll = self.request_scroll.saturating_sub(1)
+                }
             },
-            KeyCode::Char('l') => {
-                if let ResViewerTabs::Headers = self.active_tab {
-                    self.headers_scroll_x = self.headers_scroll_x.add(1)
+            KeyCode::Char('l') => match self.active_tab {
+                ResViewerTabs::Headers => self.headers_scroll_x = self.headers_scroll_x.add(1),
+                ResViewerTabs::Preview => self.pretty_scroll_x = self.pretty_scroll_x.add(1),
+                _ => {}
+            },
+            KeyCode::Char('y') => self.copy_assertion_snippet(AssertionFormat::Jq)?,
+            KeyCode::Char('Y') => self.copy_assertion_snippet(AssertionFormat::Pytest)?,
+            KeyCode::Char('s') => self.show_schema(),
+            KeyCode::Char('R') => self.active_tab = ResViewerTabs::toggle_raw(&self.active_tab),
+            KeyCode::Char('P') => {
+                let body = self.response.as_ref().and_then(|response| {
+                    let response = response.borrow();
+                    match response.pretty_body.as_ref() {
+                        Some(pretty_body) => Some(pretty_body.to_string()),
+                        None => response.body.clone(),
+                    }
+                });
+                match body {
+                    Some(body) => return Ok(Some(ResponseViewerEvent::OpenPager(body))),
+                    None => toast::show("response has no body"),
                 }
             }
+            KeyCode::Char('f') => match self.redirect() {
+                Some((status, url, location)) => {
+                    return Ok(Some(ResponseViewerEvent::FollowRedirect(
+                        status, url, location,
+                    )))
+                }
+                None => toast::show("response is not a redirect"),
+            },
+            KeyCode::Char('J') if self.active_tab.eq(&ResViewerTabs::Headers) => {
+                self.copy_headers_as_json()?
+            }
+            KeyCode::Char('x') if self.shows_error_detail_toggle() => {
+                self.show_error_detail = !self.show_error_detail
+            }
+            KeyCode::Char('x') if self.active_tab.eq(&ResViewerTabs::Headers) => {
+                self.toggle_header_expansion()
+            }
+            KeyCode::Char('K') if self.active_tab.eq(&ResViewerTabs::Preview) => {
+                self.sort_keys = !self.sort_keys;
+                self.update(self.response.clone());
+            }
+            KeyCode::Char('o') if self.active_tab.eq(&ResViewerTabs::Preview) => {
+                self.show_outline = !self.outline.is_empty();
+            }
+            KeyCode::Char('v') if self.active_tab.eq(&ResViewerTabs::Preview) => {
+                self.start_selection()
+            }
             _ => {}
         }
 
@@ -667,7 +1418,7 @@ impl<'a> Eventful for ResponseViewer<'a> {
     }
 }
 
-fn build_layout(size: Rect) -> ResViewerLayout {
+fn build_layout(size: Rect, max_width: u16) -> ResViewerLayout {
     let size = Rect::new(
         size.x.add(1),
         size.y.add(1),
@@ -685,6 +1436,19 @@ fn build_layout(size: Rect) -> ResViewerLayout {
         .direction(Direction::Vertical)
         .areas(size);
 
+    let content_pane = match max_width {
+        0 => content_pane,
+        max_width => {
+            let width = content_pane.width.min(max_width);
+            Rect::new(
+                content_pane.x.add(content_pane.width.sub(width).div(2)),
+                content_pane.y,
+                width,
+                content_pane.height,
+            )
+        }
+    };
+
     ResViewerLayout {
         tabs_pane,
         content_pane,
@@ -692,6 +1456,121 @@ fn build_layout(size: Rect) -> ResViewerLayout {
     }
 }
 
+/// drops the first `columns` characters of a styled line, keeping the style
+/// of the spans that remain
+fn skip_columns(line: &Line<'static>, columns: usize) -> Line<'static> {
+    if columns.eq(&0) {
+        return line.clone();
+    }
+
+    let mut remaining = columns;
+    let spans = line
+        .spans
+        .iter()
+        .filter_map(|span| {
+            let len = span.content.chars().count();
+            if remaining.ge(&len) {
+                remaining = remaining.sub(len);
+                return None;
+            }
+            let content = span.content.chars().skip(remaining).collect::<String>();
+            remaining = 0;
+            Some(Span::styled(content, span.style))
+        })
+        .collect::<Vec<_>>();
+
+    Line::from(spans)
+}
+
+/// gives `bg` to the columns of `line` from `from` to `to`, inclusive, keeping
+/// the style of every other column
+fn highlight_columns(line: &Line<'static>, from: usize, to: usize, bg: Color) -> Line<'static> {
+    let mut column = 0;
+    let mut spans = vec![];
+
+    for span in line.spans.iter() {
+        let chars = span.content.chars().collect::<Vec<_>>();
+        let start = column;
+        let end = column.add(chars.len());
+        column = end;
+
+        if to.lt(&start) || from.ge(&end) {
+            spans.push(span.clone());
+            continue;
+        }
+
+        let selected_start = from.max(start).sub(start);
+        let selected_end = to.add(1).min(end).sub(start);
+        let before = chars[..selected_start].iter().collect::<String>();
+        let selected = chars[selected_start..selected_end]
+            .iter()
+            .collect::<String>();
+        let after = chars[selected_end..].iter().collect::<String>();
+
+        if !before.is_empty() {
+            spans.push(Span::styled(before, span.style));
+        }
+        spans.push(Span::styled(selected, span.style.bg(bg)));
+        if !after.is_empty() {
+            spans.push(Span::styled(after, span.style));
+        }
+    }
+
+    // empty lines still show the selection passes through them
+    if line.spans.iter().all(|span| span.content.is_empty()) {
+        spans.push(Span::from(" ").bg(bg));
+    }
+
+    Line::from(spans)
+}
+
+/// describes an empty body according to the status of the response, telling
+/// apart statuses that never carry a body from successes and errors that just
+/// happened to send none
+fn empty_body_message(status: Option<reqwest::StatusCode>) -> String {
+    match status {
+        Some(status) if matches!(status.as_u16(), 204 | 304) || status.is_informational() => {
+            format!("{status} — empty body expected")
+        }
+        Some(status) if status.is_client_error() || status.is_server_error() => {
+            format!("{status} — error with no body")
+        }
+        Some(status) => format!("{status} — the server sent an empty body"),
+        None => String::from("No body"),
+    }
+}
+
+fn line_text(line: &Line<'_>) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
+/// text between `start` and `end`, both given as row and column and both
+/// included, the same way a visual selection works on the editor
+fn selected_text(lines: &[String], start: (usize, usize), end: (usize, usize)) -> String {
+    lines
+        .iter()
+        .enumerate()
+        .skip(start.0)
+        .take(end.0.add(1).saturating_sub(start.0))
+        .map(|(row, line)| {
+            let from = if row.eq(&start.0) { start.1 } else { 0 };
+            let to = if row.eq(&end.0) {
+                end.1.add(1)
+            } else {
+                usize::MAX
+            };
+            line.chars()
+                .skip(from)
+                .take(to.saturating_sub(from))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 fn build_horizontal_scrollbar(size: Rect) -> [Rect; 2] {
     let [request_pane, _, scrollbar_pane] = Layout::default()
         .direction(Direction::Vertical)
@@ -721,6 +1600,59 @@ fn build_preview_layout(size: Rect) -> PreviewLayout {
     }
 }
 
+fn build_outline_layout(size: Rect) -> Rect {
+    let [_, outline] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Fill(2), Constraint::Fill(1)])
+        .areas(size);
+
+    outline
+}
+
+/// builds a list of the top level entries of a json document, keys when the
+/// document is an object and indexes when it is an array, with the row each of
+/// them starts at
+fn build_outline(tree: &Tree, content: &str) -> Vec<OutlineEntry> {
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    let Some(value) = root.named_children(&mut cursor).next() else {
+        return vec![];
+    };
+
+    let mut cursor = value.walk();
+    match value.kind() {
+        "object" => value
+            .named_children(&mut cursor)
+            .filter(|node| node.kind().eq("pair"))
+            .filter_map(|pair| {
+                let key = pair.child_by_field_name("key")?;
+                let label = key.utf8_text(content.as_bytes()).ok()?;
+                Some(OutlineEntry {
+                    label: label.trim_matches('"').to_string(),
+                    row: pair.start_position().row,
+                })
+            })
+            .collect(),
+        "array" => value
+            .named_children(&mut cursor)
+            .filter(|node| !node.is_extra())
+            .enumerate()
+            .map(|(idx, node)| OutlineEntry {
+                label: format!("[{idx}]"),
+                row: node.start_position().row,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn format_download_progress(download: &DownloadProgress) -> String {
+    match download.total {
+        Some(total) => format!("{} / {} B", download.downloaded, total),
+        None => format!("{} B", download.downloaded),
+    }
+}
+
 fn get_error_ascii_art<R>(width: u16, rng: &mut R) -> &'static [&'static str]
 where
     R: Rng,
@@ -757,6 +1689,29 @@ fn make_empty_ascii_art(colors: &hac_colors::Colors) -> Vec<Line<'static>> {
         .collect::<Vec<_>>()
 }
 
+/// header values are not required to be valid utf-8, values that are get shown
+/// as they are, while the others have their bytes escaped, eg: `\xff`
+fn header_value_to_string(value: &reqwest::header::HeaderValue) -> String {
+    let bytes = value.as_bytes();
+    match std::str::from_utf8(bytes) {
+        Ok(value) => value.to_string(),
+        Err(_) => bytes
+            .iter()
+            .flat_map(|byte| std::ascii::escape_default(*byte))
+            .map(char::from)
+            .collect(),
+    }
+}
+
+/// splits `value` into lines of at most `width` characters
+fn wrap_chars(value: &str, width: usize) -> Vec<String> {
+    let chars = value.chars().collect::<Vec<_>>();
+    chars
+        .chunks(width.max(1))
+        .map(|chunk| chunk.iter().collect())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use rand::{rngs::StdRng, SeedableRng};
@@ -792,4 +1747,171 @@ mod tests {
 
         assert_eq!(art, expected);
     }
+
+    #[test]
+    fn test_toggle_raw_skips_other_tabs() {
+        assert_eq!(
+            ResViewerTabs::toggle_raw(&ResViewerTabs::Preview),
+            ResViewerTabs::Raw
+        );
+        assert_eq!(
+            ResViewerTabs::toggle_raw(&ResViewerTabs::Raw),
+            ResViewerTabs::Preview
+        );
+        assert_eq!(
+            ResViewerTabs::toggle_raw(&ResViewerTabs::Headers),
+            ResViewerTabs::Preview
+        );
+    }
+
+    #[test]
+    fn test_wrap_chars() {
+        assert_eq!(wrap_chars("abcdefg", 3), vec!["abc", "def", "g"]);
+        assert_eq!(wrap_chars("ação", 2), vec!["aç", "ão"]);
+        assert!(wrap_chars("", 3).is_empty());
+    }
+
+    #[test]
+    fn test_empty_body_message_depends_on_status() {
+        assert_eq!(
+            empty_body_message(Some(reqwest::StatusCode::NO_CONTENT)),
+            "204 No Content — empty body expected"
+        );
+        assert_eq!(
+            empty_body_message(Some(reqwest::StatusCode::OK)),
+            "200 OK — the server sent an empty body"
+        );
+        assert_eq!(
+            empty_body_message(Some(reqwest::StatusCode::BAD_GATEWAY)),
+            "502 Bad Gateway — error with no body"
+        );
+        assert_eq!(empty_body_message(None), "No body");
+    }
+
+    #[test]
+    fn test_format_download_progress() {
+        let mut download = DownloadProgress {
+            path: "any_path".into(),
+            downloaded: 512,
+            total: Some(1024),
+            finished: false,
+        };
+
+        assert_eq!(format_download_progress(&download), "512 / 1024 B");
+
+        download.total = None;
+
+        assert_eq!(format_download_progress(&download), "512 B");
+    }
+
+    #[test]
+    fn test_build_outline_from_object() {
+        let content =
+            "{\n  \"id\": 1,\n  \"user\": {\n    \"name\": \"hac\"\n  },\n  \"tags\": []\n}";
+        let tree = HIGHLIGHTER.write().unwrap().parse(content).unwrap();
+
+        let outline = build_outline(&tree, content);
+
+        assert_eq!(
+            outline,
+            vec![
+                OutlineEntry {
+                    label: "id".into(),
+                    row: 1
+                },
+                OutlineEntry {
+                    label: "user".into(),
+                    row: 2
+                },
+                OutlineEntry {
+                    label: "tags".into(),
+                    row: 5
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_outline_from_array() {
+        let content = "[\n  1,\n  {\n    \"id\": 2\n  }\n]";
+        let tree = HIGHLIGHTER.write().unwrap().parse(content).unwrap();
+
+        let outline = build_outline(&tree, content);
+
+        assert_eq!(
+            outline,
+            vec![
+                OutlineEntry {
+                    label: "[0]".into(),
+                    row: 1
+                },
+                OutlineEntry {
+                    label: "[1]".into(),
+                    row: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_header_values_are_never_dropped() {
+        let utf8 = reqwest::header::HeaderValue::from_bytes("café".as_bytes()).unwrap();
+        let invalid = reqwest::header::HeaderValue::from_bytes(b"a\xffb").unwrap();
+
+        assert_eq!(header_value_to_string(&utf8), "café");
+        assert_eq!(header_value_to_string(&invalid), "a\\xffb");
+    }
+
+    #[test]
+    fn test_selected_text_spans_lines() {
+        let lines = vec![
+            String::from("{"),
+            String::from("  \"id\": 1,"),
+            String::from("  \"name\": \"hac\""),
+            String::from("}"),
+        ];
+
+        assert_eq!(selected_text(&lines, (1, 2), (1, 5)), "\"id\"");
+        assert_eq!(selected_text(&lines, (1, 8), (2, 7)), "1,\n  \"name\"");
+    }
+
+    #[test]
+    fn test_highlight_columns_splits_spans() {
+        let line = Line::from(vec![Span::from("\"id\"").yellow(), Span::from(": 1").red()]);
+
+        let highlighted = highlight_columns(&line, 2, 5, Color::Blue);
+        let contents = highlighted
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect::<Vec<_>>();
+        assert_eq!(contents, vec!["\"i", "d\"", ": ", "1"]);
+        assert_eq!(
+            highlighted.spans[1].style,
+            Style::default().yellow().bg(Color::Blue)
+        );
+        assert_eq!(
+            highlighted.spans[2].style,
+            Style::default().red().bg(Color::Blue)
+        );
+    }
+
+    #[test]
+    fn test_skip_columns_keeps_styles() {
+        let line = Line::from(vec![
+            Span::from("{\"key\"").yellow(),
+            Span::from(": 1").red(),
+        ]);
+
+        let skipped = skip_columns(&line, 3);
+        assert_eq!(skipped.spans.len(), 2);
+        assert_eq!(skipped.spans[0].content, "ey\"");
+        assert_eq!(skipped.spans[0].style, Style::default().yellow());
+        assert_eq!(skipped.spans[1].content, ": 1");
+
+        let skipped = skip_columns(&line, 7);
+        assert_eq!(skipped.spans.len(), 1);
+        assert_eq!(skipped.spans[0].content, " 1");
+        assert!(skip_columns(&line, 100).spans.is_empty());
+    }
 }
@@ -0,0 +1,114 @@
+use hac_core::collection::types::Request;
+
+use crate::pages::collection_viewer::sidebar::colored_method;
+use crate::pages::list_overlay::ListOverlay;
+use crate::pages::{Eventful, Renderable};
+
+use std::ops::Add;
+use std::sync::{Arc, RwLock};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::{Line, Span};
+use ratatui::Frame;
+
+#[derive(Debug)]
+pub enum RunOrderEvent {
+    /// user confirmed the order, holds the ids of the requests in the order
+    /// they should run, an empty order runs requests as they appear on the
+    /// sidebar
+    Save(Vec<String>),
+    /// user closed the setup without changing the order
+    Cancel,
+}
+
+/// lists every request of the collection in the order they run when sending
+/// the collection, letting the user move them around
+#[derive(Debug)]
+pub struct RunOrder<'ro> {
+    colors: &'ro hac_colors::Colors,
+    list: ListOverlay<'ro, Arc<RwLock<Request>>>,
+}
+
+impl<'ro> RunOrder<'ro> {
+    pub fn new(colors: &'ro hac_colors::Colors, requests: Vec<Arc<RwLock<Request>>>) -> Self {
+        RunOrder {
+            colors,
+            list: ListOverlay::new(
+                colors,
+                "Run order",
+                "[Move: S-j/S-k] [Save: Enter] [Sidebar order: c] [Cancel: Esc]",
+                requests,
+            ),
+        }
+    }
+
+    fn build_entry(
+        &self,
+        idx: usize,
+        request: &Arc<RwLock<Request>>,
+        width: usize,
+    ) -> Line<'static> {
+        let request = request.read().unwrap();
+        let position = format!("{:>3}. ", idx.add(1));
+        let name = format!(" {}", request.name);
+        let method = colored_method(request.method.clone(), self.colors);
+        let padding = width.saturating_sub(
+            position
+                .chars()
+                .count()
+                .add(method.content.chars().count())
+                .add(name.chars().count()),
+        );
+
+        Line::from(vec![
+            Span::from(position).fg(self.colors.bright.black),
+            method,
+            Span::from(name).fg(self.colors.normal.white),
+            Span::from(" ".repeat(padding)),
+        ])
+    }
+}
+
+impl Renderable for RunOrder<'_> {
+    fn draw(&mut self, frame: &mut Frame, _: Rect) -> anyhow::Result<()> {
+        self.list.draw(frame, |idx, request, width| {
+            self.build_entry(idx, request, width)
+        });
+
+        Ok(())
+    }
+}
+
+impl Eventful for RunOrder<'_> {
+    type Result = RunOrderEvent;
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> anyhow::Result<Option<Self::Result>> {
+        if let (KeyCode::Char('c'), KeyModifiers::CONTROL) = (key_event.code, key_event.modifiers) {
+            return Ok(Some(RunOrderEvent::Cancel));
+        }
+        if self.list.handle_navigation(key_event) {
+            return Ok(None);
+        }
+
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q') => return Ok(Some(RunOrderEvent::Cancel)),
+            KeyCode::Enter => {
+                let order = self
+                    .list
+                    .items()
+                    .iter()
+                    .map(|request| request.read().unwrap().id.clone())
+                    .collect();
+                return Ok(Some(RunOrderEvent::Save(order)));
+            }
+            KeyCode::Char('c') => return Ok(Some(RunOrderEvent::Save(vec![]))),
+            KeyCode::Char('J') => self.list.move_selected(true),
+            KeyCode::Char('K') => self.list.move_selected(false),
+            _ => {}
+        }
+
+        Ok(None)
+    }
+}
@@ -1,25 +1,37 @@
+mod collection_settings;
 mod create_directory_form;
 mod create_request_form;
 mod delete_item_prompt;
 mod directory_form;
 mod edit_directory_form;
 mod edit_request_form;
+mod request_diff;
 mod request_form;
 mod select_request_parent;
 
+use hac_config::{EnterOnSelected, SidebarLabel};
 use hac_core::collection::types::{Request, RequestKind, RequestMethod};
+use hac_core::export::to_fetch;
+use hac_core::net::uri::uri_path;
 
+use super::sidebar::collection_settings::{CollectionSettings, CollectionSettingsEvent};
 use super::sidebar::delete_item_prompt::{DeleteItemPrompt, DeleteItemPromptEvent};
 use super::sidebar::directory_form::{DirectoryForm, DirectoryFormEvent};
 use super::sidebar::directory_form::{DirectoryFormCreate, DirectoryFormEdit};
+use super::sidebar::request_diff::{RequestDiff, RequestDiffEvent};
 use super::sidebar::request_form::{RequestForm, RequestFormEvent};
 use super::sidebar::request_form::{RequestFormCreate, RequestFormEdit};
-use crate::pages::collection_viewer::collection_store::{CollectionStore, CollectionStoreAction};
+use crate::pages::collection_viewer::collection_store::{
+    CollectionStore, CollectionStoreAction, StatusHistory,
+};
 use crate::pages::collection_viewer::collection_viewer::{CollectionViewerOverlay, PaneFocus};
 use crate::pages::{Eventful, Renderable};
+use crate::toast;
+use crate::utils::{copy_to_clipboard, draw_vertical_scrollbar};
 
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::ops::Add;
 use std::rc::Rc;
 use std::sync::{Arc, RwLock};
 
@@ -27,9 +39,12 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::Rect;
 use ratatui::style::{Style, Styled, Stylize};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 use ratatui::Frame;
 
+/// columns the sidebar takes on the collection viewer, borders included
+pub const SIDEBAR_WIDTH: u16 = 30;
+
 /// set of events Sidebar can emit to the caller when handling events.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum SidebarEvent {
@@ -54,12 +69,24 @@ pub enum SidebarEvent {
     SelectPrev,
     /// event to force a full rebuild of the view, when a request is deleted
     RebuildView,
-    /// this event is used when a request or directory is created, this notify the parent
+    /// this event is used when a request or directory is created or changed, this notify the parent
     /// to sync changes with the file system.
     SyncCollection,
+    /// a request was changed through its form, the parent should drop anything it built from
+    /// the request before syncing changes with the file system
+    RequestChanged(String),
     /// user pressed `DeleteItem (D)` hotkey, which should notify the caller to open the
     /// delete_item_prompt to ask the user for confirmation
     DeleteItem(String),
+    /// user pressed `Enter` on the selected request with `enter_on_selected`
+    /// set to `send`, the caller should send it
+    SendRequest,
+    /// user pressed `Enter` on the selected request with `enter_on_selected`
+    /// set to `edit`, the caller should move the selection to the editor
+    EditBody,
+    /// user pressed `CollectionSettings (S)` hotkey, which should notify the caller to
+    /// open the collection settings overlay
+    CollectionSettings,
     /// user pressed a hotkey to quit the application, so we bubble up so the caller
     /// can do a few things before bubbling the quit request further up
     Quit,
@@ -117,30 +144,47 @@ impl RequestFormVariant<'_> {
 #[derive(Debug)]
 pub struct Sidebar<'sbar> {
     colors: &'sbar hac_colors::Colors,
+    config: Rc<hac_config::Config>,
     lines: Vec<Paragraph<'static>>,
+    /// index of the hovered line on `lines`, used to keep it in view
+    hovered_line: Option<usize>,
+    /// first line shown on the pane when the tree doesn't fit on it
+    scroll: usize,
     collection_store: Rc<RefCell<CollectionStore>>,
     request_form: RequestFormVariant<'sbar>,
     directory_form: DirectoryFormVariant<'sbar>,
     delete_item_prompt: DeleteItemPrompt<'sbar>,
+    collection_settings: CollectionSettings<'sbar>,
+    /// request marked with `Compare (c)`, waiting for a second one to diff against
+    compare_from: Option<Arc<RwLock<Request>>>,
+    request_diff: Option<RequestDiff<'sbar>>,
 }
 
 impl<'sbar> Sidebar<'sbar> {
     pub fn new(
         colors: &'sbar hac_colors::Colors,
+        config: Rc<hac_config::Config>,
         collection_store: Rc<RefCell<CollectionStore>>,
     ) -> Self {
         let mut sidebar = Self {
             colors,
             request_form: RequestFormVariant::Create(RequestForm::<RequestFormCreate>::new(
                 colors,
+                &config,
                 collection_store.clone(),
             )),
             directory_form: DirectoryFormVariant::Create(
                 DirectoryForm::<DirectoryFormCreate>::new(colors, collection_store.clone()),
             ),
             delete_item_prompt: DeleteItemPrompt::new(colors, collection_store.clone()),
+            collection_settings: CollectionSettings::new(colors, collection_store.clone()),
+            compare_from: None,
+            request_diff: None,
             lines: vec![],
+            hovered_line: None,
+            scroll: 0,
             collection_store,
+            config,
         };
 
         sidebar.rebuild_tree_view();
@@ -148,6 +192,16 @@ impl<'sbar> Sidebar<'sbar> {
         sidebar
     }
 
+    /// first line shown on the pane, saved with the session of the collection
+    pub fn scroll(&self) -> usize {
+        self.scroll
+    }
+
+    /// starts the pane from `scroll`, it is clamped to the tree when drawing
+    pub fn set_scroll(&mut self, scroll: usize) {
+        self.scroll = scroll;
+    }
+
     pub fn rebuild_tree_view(&mut self) {
         let mut collection_store = self.collection_store.borrow_mut();
         self.lines = build_lines(
@@ -156,8 +210,15 @@ impl<'sbar> Sidebar<'sbar> {
             collection_store.get_selected_request(),
             collection_store.get_hovered_request(),
             collection_store.get_dirs_expanded().unwrap().clone(),
+            collection_store.get_status_history().unwrap_or_default(),
+            self.config.sidebar_label,
             self.colors,
         );
+        self.hovered_line = hovered_line_index(
+            collection_store.get_requests(),
+            collection_store.get_hovered_request(),
+            collection_store.get_dirs_expanded().unwrap().clone(),
+        );
     }
 
     pub fn draw_overlay(
@@ -184,6 +245,14 @@ impl<'sbar> Sidebar<'sbar> {
             CollectionViewerOverlay::DeleteSidebarItem(_) => {
                 self.delete_item_prompt.draw(frame, frame.size())?;
             }
+            CollectionViewerOverlay::CollectionSettings => {
+                self.collection_settings.draw(frame, frame.size())?;
+            }
+            CollectionViewerOverlay::RequestDiff => {
+                if let Some(request_diff) = self.request_diff.as_mut() {
+                    request_diff.draw(frame, frame.size())?;
+                }
+            }
             _ => {}
         };
 
@@ -222,10 +291,68 @@ impl<'sbar> Renderable for Sidebar<'sbar> {
 
         frame.render_widget(block, size);
 
-        self.lines.clone().into_iter().for_each(|req| {
-            requests_size.y += 1;
-            frame.render_widget(req, requests_size);
-        });
+        // lines are only rebuilt when the tree changes, so here we just clone the ones
+        // that actually fit on the pane instead of the whole tree on every frame
+        if self.lines.is_empty() {
+            let hint_size = Rect::new(
+                size.x.add(2),
+                size.y.add(1),
+                size.width.saturating_sub(4),
+                size.height.saturating_sub(2),
+            );
+            let hint = Paragraph::new(vec![
+                Line::from("No requests yet".fg(self.colors.normal.white).bold()),
+                Line::from(""),
+                Line::from(vec![
+                    "press ".fg(self.colors.bright.black),
+                    "n".fg(self.colors.normal.red).bold(),
+                    " to create your first request, or ".fg(self.colors.bright.black),
+                    "d".fg(self.colors.normal.red).bold(),
+                    " for a directory".fg(self.colors.bright.black),
+                ]),
+            ])
+            .wrap(Wrap { trim: true });
+            frame.render_widget(hint, hint_size);
+            return Ok(());
+        }
+
+        let visible_lines: usize = size.height.saturating_sub(2).into();
+        if let Some(hovered) = self.hovered_line {
+            if hovered.lt(&self.scroll) {
+                self.scroll = hovered;
+            }
+            if hovered.ge(&self.scroll.add(visible_lines)) {
+                self.scroll = hovered.add(1).saturating_sub(visible_lines);
+            }
+        }
+        self.scroll = self
+            .scroll
+            .min(self.lines.len().saturating_sub(visible_lines));
+
+        self.lines
+            .iter()
+            .skip(self.scroll)
+            .take(visible_lines)
+            .cloned()
+            .for_each(|req| {
+                requests_size.y += 1;
+                frame.render_widget(req, requests_size);
+            });
+
+        let scrollbar_size = Rect::new(
+            size.x,
+            size.y.add(1),
+            size.width,
+            size.height.saturating_sub(2),
+        );
+        draw_vertical_scrollbar(
+            frame,
+            self.colors,
+            self.lines.len(),
+            self.scroll,
+            visible_lines,
+            scrollbar_size,
+        );
 
         Ok(())
     }
@@ -321,9 +448,13 @@ impl<'a> Eventful for Sidebar<'a> {
                     Some(RequestFormEvent::Confirm) => {
                         let mut store = self.collection_store.borrow_mut();
                         store.pop_overlay();
+                        let request_id = store.get_hovered_request();
                         drop(store);
                         self.rebuild_tree_view();
-                        return Ok(Some(SidebarEvent::SyncCollection));
+                        return Ok(Some(match request_id {
+                            Some(request_id) => SidebarEvent::RequestChanged(request_id),
+                            None => SidebarEvent::SyncCollection,
+                        }));
                     }
                     Some(RequestFormEvent::Cancel) => {
                         let mut store = self.collection_store.borrow_mut();
@@ -363,6 +494,30 @@ impl<'a> Eventful for Sidebar<'a> {
                     None => return Ok(None),
                 }
             }
+            CollectionViewerOverlay::CollectionSettings => {
+                match self.collection_settings.handle_key_event(key_event)? {
+                    // settings stay open so the user can see the new defaults
+                    Some(CollectionSettingsEvent::Changed) => {
+                        return Ok(Some(SidebarEvent::SyncCollection))
+                    }
+                    Some(CollectionSettingsEvent::Close) => {
+                        self.collection_store.borrow_mut().pop_overlay();
+                        return Ok(None);
+                    }
+                    None => return Ok(None),
+                }
+            }
+            CollectionViewerOverlay::RequestDiff => {
+                let event = match self.request_diff.as_mut() {
+                    Some(request_diff) => request_diff.handle_key_event(key_event)?,
+                    None => Some(RequestDiffEvent::Close),
+                };
+                if let Some(RequestDiffEvent::Close) = event {
+                    self.request_diff = None;
+                    self.collection_store.borrow_mut().pop_overlay();
+                }
+                return Ok(None);
+            }
             _ => {}
         };
 
@@ -374,18 +529,30 @@ impl<'a> Eventful for Sidebar<'a> {
 
         match key_event.code {
             KeyCode::Enter => {
-                if store.get_requests().is_none() || store.get_hovered_request().is_none() {
+                let Some(request) = store.find_hovered_request() else {
                     return Ok(None);
-                }
-
-                let request = store.find_hovered_request();
+                };
                 match request {
                     RequestKind::Nested(_) => {
                         store.dispatch(CollectionStoreAction::ToggleDirectory(request.get_id()));
                     }
                     RequestKind::Single(req) => {
-                        store.dispatch(CollectionStoreAction::SetSelectedRequest(Some(req)));
-                        return Ok(Some(SidebarEvent::RebuildView));
+                        let is_selected = store
+                            .get_selected_request()
+                            .is_some_and(|selected| Arc::ptr_eq(&selected, &req));
+                        match (is_selected, self.config.enter_on_selected) {
+                            (true, EnterOnSelected::Send) => {
+                                return Ok(Some(SidebarEvent::SendRequest))
+                            }
+                            (true, EnterOnSelected::Edit) => {
+                                return Ok(Some(SidebarEvent::EditBody))
+                            }
+                            _ => {
+                                store
+                                    .dispatch(CollectionStoreAction::SetSelectedRequest(Some(req)));
+                                return Ok(Some(SidebarEvent::RebuildView));
+                            }
+                        }
                     }
                 }
             }
@@ -395,18 +562,22 @@ impl<'a> Eventful for Sidebar<'a> {
                 self.request_form =
                     RequestFormVariant::Create(RequestForm::<RequestFormCreate>::new(
                         self.colors,
+                        &self.config,
                         self.collection_store.clone(),
                     ));
                 return Ok(Some(SidebarEvent::CreateRequest));
             }
             KeyCode::Char('e') => {
-                let hovered_request = store.find_hovered_request();
+                let Some(hovered_request) = store.find_hovered_request() else {
+                    return Ok(None);
+                };
                 drop(store);
                 match hovered_request {
                     RequestKind::Single(req) => {
                         self.request_form =
                             RequestFormVariant::Edit(RequestForm::<RequestFormEdit>::new(
                                 self.colors,
+                                &self.config,
                                 self.collection_store.clone(),
                                 req.clone(),
                             ));
@@ -431,8 +602,48 @@ impl<'a> Eventful for Sidebar<'a> {
                 }
             }
             KeyCode::Char('d') => return Ok(Some(SidebarEvent::CreateDirectory)),
+            KeyCode::Char('S') => return Ok(Some(SidebarEvent::CollectionSettings)),
+            KeyCode::Char('x') => {
+                let Some(RequestKind::Single(req)) = store.find_hovered_request() else {
+                    return Ok(None);
+                };
+                let mut req = req.write().unwrap();
+                req.enabled = !req.enabled;
+                drop(req);
+                drop(store);
+                self.rebuild_tree_view();
+                return Ok(Some(SidebarEvent::SyncCollection));
+            }
+            KeyCode::Char('y') => {
+                if let Some(RequestKind::Single(req)) = store.find_hovered_request() {
+                    copy_to_clipboard(&to_fetch(&req.read().unwrap()))?;
+                    toast::show("copied request as fetch()");
+                }
+                return Ok(None);
+            }
+            KeyCode::Char('c') => {
+                let Some(RequestKind::Single(req)) = store.find_hovered_request() else {
+                    return Ok(None);
+                };
+                let Some(from) = self.compare_from.take() else {
+                    toast::show(format!(
+                        "comparing {}, press c on another request",
+                        req.read().unwrap().name
+                    ));
+                    self.compare_from = Some(req);
+                    return Ok(None);
+                };
+                self.request_diff = Some(RequestDiff::new(
+                    self.colors,
+                    &from.read().unwrap(),
+                    &req.read().unwrap(),
+                ));
+                store.push_overlay(CollectionViewerOverlay::RequestDiff);
+                return Ok(None);
+            }
             KeyCode::Esc => return Ok(Some(SidebarEvent::RemoveSelection)),
-            _ => {}
+            // nothing changed on the tree, so there is no need to rebuild it
+            _ => return Ok(None),
         }
 
         drop(store);
@@ -442,12 +653,15 @@ impl<'a> Eventful for Sidebar<'a> {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn build_lines(
     requests: Option<Arc<RwLock<Vec<RequestKind>>>>,
     level: usize,
     selected_request: Option<Arc<RwLock<Request>>>,
     hovered_request: Option<String>,
     dirs_expanded: Rc<RefCell<HashMap<String, bool>>>,
+    status_history: Rc<RefCell<StatusHistory>>,
+    label: SidebarLabel,
     colors: &hac_colors::Colors,
 ) -> Vec<Paragraph<'static>> {
     requests
@@ -488,6 +702,8 @@ pub fn build_lines(
                         selected_request.clone(),
                         hovered_request.clone(),
                         dirs_expanded.clone(),
+                        status_history.clone(),
+                        label,
                         colors,
                     )
                 } else {
@@ -516,13 +732,18 @@ pub fn build_lines(
                         .bg(colors.primary.hover),
                     (false, false) => Style::default().fg(colors.normal.white),
                 };
+                let req_style = match req.read().unwrap().enabled {
+                    true => req_style,
+                    false => req_style.fg(colors.bright.black).crossed_out(),
+                };
 
-                let line: Line<'_> = vec![
-                    Span::from(gap.clone()),
-                    colored_method(req.read().unwrap().method.clone(), colors),
-                    Span::from(format!(" {}", req.read().unwrap().name.clone())),
-                ]
-                .into();
+                let mut spans = vec![Span::from(gap.clone())];
+                spans.extend(request_label(&req.read().unwrap(), label, level, colors));
+                if let Some(statuses) = status_history.borrow().get(&req.read().unwrap().id) {
+                    spans.push(Span::from(" "));
+                    spans.extend(status_sparkline(&req.read().unwrap(), statuses, colors));
+                }
+                let line: Line<'_> = spans.into();
 
                 vec![Paragraph::new(line).set_style(req_style)]
             }
@@ -530,12 +751,119 @@ pub fn build_lines(
         .collect()
 }
 
-fn colored_method(method: RequestMethod, colors: &hac_colors::Colors) -> Span<'static> {
-    match method {
-        RequestMethod::Get => "GET   ".fg(colors.normal.green).bold(),
-        RequestMethod::Post => "POST  ".fg(colors.normal.magenta).bold(),
-        RequestMethod::Put => "PUT   ".fg(colors.normal.yellow).bold(),
-        RequestMethod::Patch => "PATCH ".fg(colors.normal.orange).bold(),
-        RequestMethod::Delete => "DELETE".fg(colors.normal.red).bold(),
+/// position of the hovered request on the lines built by `build_lines`, which
+/// only includes requests inside expanded directories
+fn hovered_line_index(
+    requests: Option<Arc<RwLock<Vec<RequestKind>>>>,
+    hovered_request: Option<String>,
+    dirs_expanded: Rc<RefCell<HashMap<String, bool>>>,
+) -> Option<usize> {
+    fn walk(
+        requests: &[RequestKind],
+        hovered: &str,
+        dirs_expanded: &HashMap<String, bool>,
+        idx: &mut usize,
+    ) -> bool {
+        for item in requests {
+            if item.get_id().eq(hovered) {
+                return true;
+            }
+            *idx += 1;
+            if let RequestKind::Nested(dir) = item {
+                let is_expanded = dirs_expanded.get(&dir.id).copied().unwrap_or(false);
+                if is_expanded && walk(&dir.requests.read().unwrap(), hovered, dirs_expanded, idx) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    let requests = requests?;
+    let hovered = hovered_request?;
+    let mut idx = 0;
+    let found = walk(
+        &requests.read().unwrap(),
+        &hovered,
+        &dirs_expanded.borrow(),
+        &mut idx,
+    );
+    found.then_some(idx)
+}
+
+/// renders each recorded status as a small block, responses the request
+/// expected are green and anything else, including failed requests, is red
+fn status_sparkline(
+    request: &Request,
+    statuses: &VecDeque<Option<u16>>,
+    colors: &hac_colors::Colors,
+) -> Vec<Span<'static>> {
+    statuses
+        .iter()
+        .map(|status| match status {
+            Some(status) if request.is_success(*status) => "▪".fg(colors.normal.green),
+            _ => "▪".fg(colors.normal.red),
+        })
+        .collect()
+}
+
+/// the method padded to the width of the longest method, so the names of the
+/// requests that follow are aligned
+/// spans labeling `request` on the sidebar. Paths are truncated from the
+/// start so the end of the endpoint stays visible
+fn request_label(
+    request: &Request,
+    label: SidebarLabel,
+    level: usize,
+    colors: &hac_colors::Colors,
+) -> Vec<Span<'static>> {
+    let method = colored_method(request.method.clone(), colors);
+    // what is left after the borders and the indentation
+    let available = (SIDEBAR_WIDTH as usize)
+        .saturating_sub(2)
+        .saturating_sub(level * 2);
+    let path_width = available.saturating_sub(method.width().add(1));
+
+    match label {
+        SidebarLabel::Name => vec![method, Span::from(format!(" {}", request.name))],
+        SidebarLabel::Path => vec![Span::from(truncate_start(
+            uri_path(&request.uri),
+            available,
+        ))],
+        SidebarLabel::MethodPath => vec![
+            method,
+            Span::from(format!(
+                " {}",
+                truncate_start(uri_path(&request.uri), path_width)
+            )),
+        ],
+    }
+}
+
+/// keeps the last `width` characters of `text`, marking that the start was
+/// cut with an ellipsis
+fn truncate_start(text: &str, width: usize) -> String {
+    let len = text.chars().count();
+    if len <= width || width == 0 {
+        return text.to_string();
     }
+
+    let tail = text.chars().skip(len.add(1).saturating_sub(width));
+    std::iter::once('…').chain(tail).collect()
+}
+
+pub fn colored_method(method: RequestMethod, colors: &hac_colors::Colors) -> Span<'static> {
+    let width = RequestMethod::iter()
+        .map(|method| method.to_string().len())
+        .max()
+        .unwrap_or_default();
+    let color = match method {
+        RequestMethod::Get => colors.methods.get,
+        RequestMethod::Post => colors.methods.post,
+        RequestMethod::Put => colors.methods.put,
+        RequestMethod::Patch => colors.methods.patch,
+        RequestMethod::Delete => colors.methods.delete,
+    };
+
+    format!("{method:<width$}").fg(color).bold()
 }
@@ -0,0 +1,176 @@
+use hac_core::collection::types::RequestDefaults;
+
+use crate::pages::collection_viewer::collection_store::{CollectionStore, CollectionStoreAction};
+use crate::pages::overlay::make_overlay;
+use crate::pages::{Eventful, Renderable};
+
+use std::cell::RefCell;
+use std::ops::Div;
+use std::rc::Rc;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Rect;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap};
+use ratatui::Frame;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionSettingsEvent {
+    /// a setting changed, so the collection has to be synced
+    Changed,
+    /// user closed the settings
+    Close,
+}
+
+/// overlay with settings that apply to the whole collection, like the template
+/// used to create new requests
+#[derive(Debug)]
+pub struct CollectionSettings<'cs> {
+    colors: &'cs hac_colors::Colors,
+    collection_store: Rc<RefCell<CollectionStore>>,
+}
+
+impl<'cs> CollectionSettings<'cs> {
+    pub fn new(
+        colors: &'cs hac_colors::Colors,
+        collection_store: Rc<RefCell<CollectionStore>>,
+    ) -> Self {
+        CollectionSettings {
+            colors,
+            collection_store,
+        }
+    }
+
+    fn build_defaults_lines(&self, defaults: Option<&RequestDefaults>) -> Vec<Line<'static>> {
+        let Some(defaults) = defaults else {
+            return vec![Line::from(
+                "New requests start empty".fg(self.colors.bright.black),
+            )];
+        };
+
+        let auth = defaults
+            .auth_method
+            .as_ref()
+            .map(|auth| auth.to_string())
+            .unwrap_or(String::from("None"));
+        let mut lines = vec![
+            Line::from(vec![
+                "Auth: ".fg(self.colors.bright.black),
+                auth.fg(self.colors.normal.white),
+            ]),
+            Line::from("Headers:".fg(self.colors.bright.black)),
+        ];
+
+        match defaults
+            .headers
+            .as_ref()
+            .filter(|headers| !headers.is_empty())
+        {
+            Some(headers) => headers.iter().for_each(|header| {
+                let color = match header.enabled {
+                    true => self.colors.normal.white,
+                    false => self.colors.bright.black,
+                };
+                lines.push(Line::from(
+                    format!("  {}: {}", header.pair.0, header.pair.1).fg(color),
+                ));
+            }),
+            None => lines.push(Line::from("  none".fg(self.colors.bright.black))),
+        }
+
+        lines
+    }
+}
+
+impl Renderable for CollectionSettings<'_> {
+    fn draw(&mut self, frame: &mut Frame, _: Rect) -> anyhow::Result<()> {
+        make_overlay(self.colors, self.colors.normal.black, 0.1, frame);
+
+        let size = frame.size();
+        let size = Rect::new(
+            size.width.div(4),
+            size.height.div(6),
+            size.width.div(2),
+            size.height.div(2),
+        );
+
+        let defaults = self.collection_store.borrow().get_request_defaults();
+        let trailing_slash = self.collection_store.borrow().get_trailing_slash();
+
+        let mut lines = vec![
+            Line::from("Request defaults".fg(self.colors.normal.yellow).bold()),
+            Line::from(""),
+        ];
+        lines.extend(self.build_defaults_lines(defaults.as_ref()));
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            "Trailing slashes: ".fg(self.colors.bright.black),
+            trailing_slash.to_string().fg(self.colors.normal.white),
+        ]));
+        lines.push(Line::from(""));
+        lines.push(Line::from(
+            "(s) use headers and auth of the selected request  (c) clear  (t) change trailing slashes  (esc) close"
+                .fg(self.colors.bright.black),
+        ));
+
+        let block = Block::default()
+            .title("Collection settings")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.colors.normal.red))
+            .bg(self.colors.primary.background)
+            .padding(Padding::horizontal(1));
+
+        frame.render_widget(Clear, size);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(block),
+            size,
+        );
+
+        Ok(())
+    }
+}
+
+impl Eventful for CollectionSettings<'_> {
+    type Result = CollectionSettingsEvent;
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> anyhow::Result<Option<Self::Result>> {
+        if let (KeyCode::Char('c'), KeyModifiers::CONTROL) = (key_event.code, key_event.modifiers) {
+            return Ok(Some(CollectionSettingsEvent::Close));
+        }
+
+        let mut store = self.collection_store.borrow_mut();
+
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q') => return Ok(Some(CollectionSettingsEvent::Close)),
+            KeyCode::Char('s') => {
+                let Some(request) = store.get_selected_request() else {
+                    return Ok(None);
+                };
+                let defaults = {
+                    let request = request.read().unwrap();
+                    RequestDefaults {
+                        headers: request.headers.clone(),
+                        auth_method: request.auth_method.clone(),
+                    }
+                };
+                store.dispatch(CollectionStoreAction::SetRequestDefaults(Some(defaults)));
+                return Ok(Some(CollectionSettingsEvent::Changed));
+            }
+            KeyCode::Char('t') => {
+                let behavior = store.get_trailing_slash().next();
+                store.dispatch(CollectionStoreAction::SetTrailingSlash(behavior));
+                return Ok(Some(CollectionSettingsEvent::Changed));
+            }
+            KeyCode::Char('c') => {
+                store.dispatch(CollectionStoreAction::SetRequestDefaults(None));
+                return Ok(Some(CollectionSettingsEvent::Changed));
+            }
+            _ => {}
+        }
+
+        Ok(None)
+    }
+}
@@ -37,9 +37,17 @@ impl<'rf> RequestFormTrait for RequestForm<'rf, RequestFormCreate> {
 impl<'rf> RequestForm<'rf, RequestFormCreate> {
     pub fn new(
         colors: &'rf hac_colors::Colors,
+        config: &hac_config::Config,
         collection_store: Rc<RefCell<CollectionStore>>,
     ) -> Self {
         let logo_idx = rand::thread_rng().gen_range(0..LOGO_ASCII.len());
+        let request_method = config.default_method.parse().unwrap_or_else(|_| {
+            tracing::warn!(
+                "invalid default_method {:?} on config, using GET",
+                config.default_method
+            );
+            RequestMethod::Get
+        });
 
         RequestForm {
             colors,
@@ -47,12 +55,19 @@ impl<'rf> RequestForm<'rf, RequestFormCreate> {
             collection_store,
             logo_idx,
             request_name: String::default(),
-            request_method: RequestMethod::Get,
+            request_method,
             parent_dir: None,
+            expected_status: String::default(),
+            invalid_expected_status: false,
+            cookies: String::default(),
+            invalid_cookies: false,
+            duplicate_name: false,
             focused_field: FormField::Name,
             marker: std::marker::PhantomData,
             request: None,
             no_available_parent_timer: None,
+            request_naming: config.request_naming,
+            on_duplicate_name: config.on_duplicate_name,
         }
     }
 }
@@ -96,6 +111,10 @@ impl Eventful for RequestForm<'_, RequestFormCreate> {
             return Ok(None);
         }
 
+        self.invalid_expected_status = false;
+        self.invalid_cookies = false;
+        self.duplicate_name = false;
+
         if let KeyCode::Tab = key_event.code {
             self.focused_field = self.focused_field.next();
             return Ok(None);
@@ -112,31 +131,53 @@ impl Eventful for RequestForm<'_, RequestFormCreate> {
         }
 
         if let KeyCode::Enter = key_event.code {
+            let Ok(expected_status) = self.parse_expected_status() else {
+                return Ok(None);
+            };
+            let Ok(cookies) = self.parse_cookies() else {
+                return Ok(None);
+            };
+            let requests = self.collection_store.borrow().get_requests();
+            if let Some(requests) = requests {
+                if self
+                    .check_duplicate_name(&requests.read().unwrap(), None)
+                    .is_err()
+                {
+                    return Ok(None);
+                }
+            }
             let store = self.collection_store.borrow_mut();
             let collection = store
                 .get_collection()
                 .expect("tried to create a request without a collection");
 
             let mut collection = collection.borrow_mut();
+            // new requests start as a copy of the collection template, and are
+            // free to diverge from it after being created
+            let defaults = collection.request_defaults.clone().unwrap_or_default();
             let requests = collection
                 .requests
                 .get_or_insert(Arc::new(RwLock::new(vec![])));
             let mut requests = requests.write().unwrap();
 
             if self.request_name.is_empty() {
-                self.request_name = String::from("unnamed request");
+                self.request_name = hac_core::collection::collection::auto_request_name(
+                    self.request_naming,
+                    &self.request_method,
+                    &requests,
+                );
             }
 
             let request = RequestKind::Single(Arc::new(RwLock::new(Request {
                 id: uuid::Uuid::new_v4().to_string(),
-                auth_method: None,
-                body: None,
-                body_type: None,
+                auth_method: defaults.auth_method,
+                expected_status,
+                cookies,
                 parent: self.parent_dir.as_ref().map(|(id, _)| id.clone()),
-                headers: None,
+                headers: defaults.headers,
                 method: self.request_method.clone(),
                 name: self.request_name.clone(),
-                uri: String::default(),
+                ..Default::default()
             })));
 
             if let Some((dir_id, _)) = self.parent_dir.as_ref() {
@@ -225,6 +266,24 @@ impl Eventful for RequestForm<'_, RequestFormCreate> {
                     store.push_overlay(CollectionViewerOverlay::SelectParentDir);
                 }
             }
+            FormField::ExpectedStatus => match key_event.code {
+                KeyCode::Char(c) => {
+                    self.expected_status.push(c);
+                }
+                KeyCode::Backspace => {
+                    self.expected_status.pop();
+                }
+                _ => {}
+            },
+            FormField::Cookies => match key_event.code {
+                KeyCode::Char(c) => {
+                    self.cookies.push(c);
+                }
+                KeyCode::Backspace => {
+                    self.cookies.pop();
+                }
+                _ => {}
+            },
         }
 
         Ok(None)
@@ -1,4 +1,5 @@
 use hac_core::collection::types::*;
+use hac_core::net::request_client::format_cookies;
 
 use super::request_form::FormField;
 use super::request_form::RequestForm;
@@ -37,12 +38,20 @@ impl<'rf> RequestFormTrait for RequestForm<'rf, RequestFormEdit> {
 impl<'rf> RequestForm<'rf, RequestFormEdit> {
     pub fn new(
         colors: &'rf hac_colors::Colors,
+        config: &hac_config::Config,
         collection_store: Rc<RefCell<CollectionStore>>,
         request: Arc<RwLock<Request>>,
     ) -> Self {
         let logo_idx = rand::thread_rng().gen_range(0..LOGO_ASCII.len());
         let request_method = request.read().unwrap().method.clone();
         let request_name = request.read().unwrap().name.clone();
+        let expected_status = request
+            .read()
+            .unwrap()
+            .expected_status
+            .map(|status| status.to_string())
+            .unwrap_or_default();
+        let cookies = format_cookies(&request.read().unwrap().cookies);
 
         let parent_dir = if request.read().unwrap().parent.is_some() {
             let store = collection_store.borrow();
@@ -76,10 +85,17 @@ impl<'rf> RequestForm<'rf, RequestFormEdit> {
             request_name,
             request_method,
             parent_dir,
+            expected_status,
+            invalid_expected_status: false,
+            cookies,
+            invalid_cookies: false,
+            duplicate_name: false,
             focused_field: FormField::Name,
             marker: std::marker::PhantomData,
             request: Some(request),
             no_available_parent_timer: None,
+            request_naming: Default::default(),
+            on_duplicate_name: config.on_duplicate_name,
         }
     }
 }
@@ -124,6 +140,10 @@ impl Eventful for RequestForm<'_, RequestFormEdit> {
             return Ok(None);
         }
 
+        self.invalid_expected_status = false;
+        self.invalid_cookies = false;
+        self.duplicate_name = false;
+
         if let KeyCode::Tab = key_event.code {
             self.focused_field = self.focused_field.next();
             return Ok(None);
@@ -135,12 +155,30 @@ impl Eventful for RequestForm<'_, RequestFormEdit> {
         }
 
         if let KeyCode::Enter = key_event.code {
+            let Ok(expected_status) = self.parse_expected_status() else {
+                return Ok(None);
+            };
+            let Ok(cookies) = self.parse_cookies() else {
+                return Ok(None);
+            };
+            let request_id = self.request.as_ref().unwrap().read().unwrap().id.clone();
+            let requests = self.collection_store.borrow().get_requests();
+            if let Some(requests) = requests {
+                if self
+                    .check_duplicate_name(&requests.read().unwrap(), Some(&request_id))
+                    .is_err()
+                {
+                    return Ok(None);
+                }
+            }
             let request = self.request.as_mut().unwrap();
             let mut request = request.write().unwrap();
 
             request.name.clone_from(&self.request_name);
             request.method.clone_from(&self.request_method);
             request.parent = self.parent_dir.as_ref().map(|(id, _)| id.clone());
+            request.expected_status = expected_status;
+            request.cookies = cookies;
             let request_id = request.id.clone();
 
             drop(request);
@@ -257,6 +295,24 @@ impl Eventful for RequestForm<'_, RequestFormEdit> {
                     store.push_overlay(CollectionViewerOverlay::SelectParentDir);
                 }
             }
+            FormField::ExpectedStatus => match key_event.code {
+                KeyCode::Char(c) => {
+                    self.expected_status.push(c);
+                }
+                KeyCode::Backspace => {
+                    self.expected_status.pop();
+                }
+                _ => {}
+            },
+            FormField::Cookies => match key_event.code {
+                KeyCode::Char(c) => {
+                    self.cookies.push(c);
+                }
+                KeyCode::Backspace => {
+                    self.cookies.pop();
+                }
+                _ => {}
+            },
         }
 
         Ok(None)
@@ -0,0 +1,138 @@
+use hac_core::collection::types::Request;
+use hac_core::diff::{diff_requests, DiffLine, DiffSection};
+
+use crate::pages::overlay::make_overlay;
+use crate::pages::{Eventful, Renderable};
+
+use std::ops::{Add, Div, Mul};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Rect;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph};
+use ratatui::Frame;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestDiffEvent {
+    /// user closed the diff
+    Close,
+}
+
+/// read only overlay comparing two requests field by field
+#[derive(Debug)]
+pub struct RequestDiff<'rd> {
+    colors: &'rd hac_colors::Colors,
+    title: String,
+    sections: Vec<DiffSection>,
+    scroll: usize,
+}
+
+impl<'rd> RequestDiff<'rd> {
+    pub fn new(colors: &'rd hac_colors::Colors, a: &Request, b: &Request) -> Self {
+        RequestDiff {
+            colors,
+            title: format!("{} -> {}", a.name, b.name),
+            sections: diff_requests(a, b),
+            scroll: 0,
+        }
+    }
+
+    fn build_lines(&self) -> Vec<Line<'static>> {
+        let mut lines = vec![];
+
+        for section in self.sections.iter() {
+            let title_color = match section.has_changes() {
+                true => self.colors.diff.changed,
+                false => self.colors.bright.black,
+            };
+            lines.push(Line::from(section.title.fg(title_color).bold()));
+
+            if section.lines.is_empty() {
+                lines.push(Line::from("  none".fg(self.colors.bright.black)));
+            }
+
+            for line in section.lines.iter() {
+                lines.push(match line {
+                    DiffLine::Same(text) => {
+                        Line::from(format!("  {text}").fg(self.colors.normal.white))
+                    }
+                    DiffLine::Removed(text) => {
+                        Line::from(format!("- {text}").fg(self.colors.diff.removed))
+                    }
+                    DiffLine::Added(text) => {
+                        Line::from(format!("+ {text}").fg(self.colors.diff.added))
+                    }
+                });
+            }
+
+            lines.push(Line::from(""));
+        }
+
+        lines.push(Line::from(
+            "(j/k) scroll  (esc) close".fg(self.colors.bright.black),
+        ));
+
+        lines
+    }
+}
+
+impl Renderable for RequestDiff<'_> {
+    fn draw(&mut self, frame: &mut Frame, _: Rect) -> anyhow::Result<()> {
+        make_overlay(self.colors, self.colors.normal.black, 0.1, frame);
+
+        let size = frame.size();
+        let size = Rect::new(
+            size.width.div(6),
+            size.height.div(8),
+            size.width.div(6).mul(4),
+            size.height.div(4).mul(3),
+        );
+
+        let lines = self.build_lines();
+        // 2 lines for the borders
+        let visible_lines = usize::from(size.height.saturating_sub(2));
+        self.scroll = self.scroll.min(lines.len().saturating_sub(visible_lines));
+
+        let block = Block::default()
+            .title(self.title.clone())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.colors.normal.red))
+            .bg(self.colors.primary.background)
+            .padding(Padding::horizontal(1));
+
+        frame.render_widget(Clear, size);
+        frame.render_widget(
+            Paragraph::new(
+                lines
+                    .into_iter()
+                    .skip(self.scroll)
+                    .take(visible_lines)
+                    .collect::<Vec<_>>(),
+            )
+            .block(block),
+            size,
+        );
+
+        Ok(())
+    }
+}
+
+impl Eventful for RequestDiff<'_> {
+    type Result = RequestDiffEvent;
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> anyhow::Result<Option<Self::Result>> {
+        if let (KeyCode::Char('c'), KeyModifiers::CONTROL) = (key_event.code, key_event.modifiers) {
+            return Ok(Some(RequestDiffEvent::Close));
+        }
+
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q') => return Ok(Some(RequestDiffEvent::Close)),
+            KeyCode::Char('j') | KeyCode::Down => self.scroll = self.scroll.add(1),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            _ => {}
+        }
+
+        Ok(None)
+    }
+}
@@ -1,4 +1,7 @@
-use hac_core::collection::types::{Request, RequestMethod};
+use hac_config::DuplicateName;
+use hac_core::collection::collection::{is_name_taken, unique_request_name};
+use hac_core::collection::types::{ExpectedStatus, Request, RequestKind, RequestMethod};
+use hac_core::net::request_client::parse_cookies;
 
 use crate::ascii::LOGO_ASCII;
 use crate::pages::collection_viewer::collection_store::CollectionStore;
@@ -8,6 +11,7 @@ use crate::pages::overlay::make_overlay;
 use crate::pages::Renderable;
 
 use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::ops::{Add, Div, Sub};
 use std::rc::Rc;
 use std::sync::{Arc, RwLock};
@@ -29,6 +33,8 @@ pub enum FormField {
     Name,
     Method,
     Parent,
+    ExpectedStatus,
+    Cookies,
 }
 
 impl FormField {
@@ -36,15 +42,19 @@ impl FormField {
         match self {
             FormField::Name => FormField::Method,
             FormField::Method => FormField::Parent,
-            FormField::Parent => FormField::Name,
+            FormField::Parent => FormField::ExpectedStatus,
+            FormField::ExpectedStatus => FormField::Cookies,
+            FormField::Cookies => FormField::Name,
         }
     }
 
     pub fn prev(&self) -> Self {
         match self {
-            FormField::Name => FormField::Parent,
+            FormField::Name => FormField::Cookies,
             FormField::Method => FormField::Name,
             FormField::Parent => FormField::Method,
+            FormField::ExpectedStatus => FormField::Parent,
+            FormField::Cookies => FormField::ExpectedStatus,
         }
     }
 }
@@ -71,6 +81,18 @@ pub struct RequestForm<'rf, State = RequestFormCreate> {
     /// lifetimes or to Rc our way to hell, along with it we also store the name
     /// for displaying purposes
     pub parent_dir: Option<(String, String)>,
+    /// expected status as typed by the user, only parsed when confirming
+    pub expected_status: String,
+    /// set when confirming with an expected status that can't be parsed, so
+    /// we can tell the user what is wrong until they type again
+    pub invalid_expected_status: bool,
+    /// cookies sent only with this request as typed by the user, eg: `a=1; b=2`
+    pub cookies: String,
+    /// set when confirming with cookies that are not `name=value` pairs
+    pub invalid_cookies: bool,
+    /// set when confirming with the name of another item of the same folder
+    /// while duplicates are configured to warn
+    pub duplicate_name: bool,
     /// which form field is currently focused, so we can direct interactions
     /// accordingly
     pub focused_field: FormField,
@@ -83,6 +105,10 @@ pub struct RequestForm<'rf, State = RequestFormCreate> {
     /// no directories on the collection, we use this timer to show a message for
     /// a short duration, alerting the user
     pub no_available_parent_timer: Option<std::time::Instant>,
+    /// scheme used to name requests created without a name
+    pub request_naming: hac_config::RequestNaming,
+    /// how names already used on the chosen folder are handled
+    pub on_duplicate_name: DuplicateName,
 
     pub marker: std::marker::PhantomData<State>,
 }
@@ -93,6 +119,70 @@ impl<'rf, State> RequestForm<'rf, State> {
         self.request_method = RequestMethod::Get;
         self.focused_field = FormField::Name;
         self.parent_dir = None;
+        self.expected_status = String::default();
+        self.invalid_expected_status = false;
+        self.cookies = String::default();
+        self.invalid_cookies = false;
+        self.duplicate_name = false;
+    }
+
+    /// parses the typed expected status, an empty field means no expectation.
+    /// When the status is invalid, the field is focused and flagged
+    pub fn parse_expected_status(&mut self) -> Result<Option<ExpectedStatus>, ()> {
+        if self.expected_status.trim().is_empty() {
+            return Ok(None);
+        }
+
+        match self.expected_status.parse() {
+            Ok(status) => Ok(Some(status)),
+            Err(_) => {
+                self.focused_field = FormField::ExpectedStatus;
+                self.invalid_expected_status = true;
+                Err(())
+            }
+        }
+    }
+
+    /// parses the typed cookies, flagging and focusing the field when any of
+    /// them is not a `name=value` pair
+    pub fn parse_cookies(&mut self) -> Result<BTreeMap<String, String>, ()> {
+        match parse_cookies(&self.cookies) {
+            Some(cookies) => Ok(cookies.into_iter().collect()),
+            None => {
+                self.focused_field = FormField::Cookies;
+                self.invalid_cookies = true;
+                Err(())
+            }
+        }
+    }
+
+    /// checks the typed name against the other items of the chosen folder,
+    /// a taken name either gets a suffix or is flagged, as configured.
+    /// `request_id` is the request being edited, if any
+    pub fn check_duplicate_name(
+        &mut self,
+        requests: &[RequestKind],
+        request_id: Option<&str>,
+    ) -> Result<(), ()> {
+        let parent = self.parent_dir.as_ref().map(|(id, _)| id.as_str());
+        if self.request_name.is_empty()
+            || !is_name_taken(requests, parent, &self.request_name, request_id)
+        {
+            return Ok(());
+        }
+
+        match self.on_duplicate_name {
+            DuplicateName::Suffix => {
+                self.request_name =
+                    unique_request_name(requests, parent, &self.request_name, request_id);
+                Ok(())
+            }
+            DuplicateName::Warn => {
+                self.focused_field = FormField::Name;
+                self.duplicate_name = true;
+                Err(())
+            }
+        }
     }
 
     pub fn set_no_parent_timer(&mut self) {
@@ -107,7 +197,7 @@ impl<'rf, State> Renderable for RequestForm<'rf, State> {
         let mut logo = LOGO_ASCII[self.logo_idx];
         let mut logo_size = logo.len() as u16;
         // adding size of the form + spacing + hint
-        let total_size = logo_size.add(11).add(2);
+        let total_size = logo_size.add(17).add(2);
 
         let size = frame.size();
         let mut size = Rect::new(
@@ -117,14 +207,14 @@ impl<'rf, State> Renderable for RequestForm<'rf, State> {
                 .saturating_sub(logo_size.div(2))
                 .saturating_sub(6),
             65,
-            logo_size.add(12),
+            logo_size.add(18),
         );
 
         if total_size.ge(&frame.size().height) {
             logo = &[];
             logo_size = 0;
-            size.height = 12;
-            size.y = frame.size().height.div(2).saturating_sub(5);
+            size.height = 18;
+            size.y = frame.size().height.div(2).saturating_sub(9);
         }
 
         if !logo.is_empty() {
@@ -138,6 +228,10 @@ impl<'rf, State> Renderable for RequestForm<'rf, State> {
         }
 
         let mut name_input = Input::new(self.colors, "Name".into());
+        let mut status_input = Input::new(self.colors, "Expected status".into())
+            .placeholder("eg: 200, 200-204 or 2xx".into());
+        let mut cookies_input = Input::new(self.colors, "Cookies".into())
+            .placeholder("eg: session=abc; theme=dark".into());
         let method_title = Paragraph::new("Method".fg(self.colors.normal.white));
         let hint =
             "[Confirm: Enter] [Cancel: Esc] [Switch: Tab] [Select: Space] [Remove Parent: <C-p>]";
@@ -148,13 +242,23 @@ impl<'rf, State> Renderable for RequestForm<'rf, State> {
             name_input.focus();
         }
 
+        if self.focused_field.eq(&FormField::ExpectedStatus) {
+            status_input.focus();
+        }
+
+        if self.focused_field.eq(&FormField::Cookies) {
+            cookies_input.focus();
+        }
+
         let name_size = Rect::new(size.x, size.y.add(logo_size).add(1), size.width, 3);
         let method_title_size = Rect::new(size.x, name_size.y.add(3), size.width, 1);
         let methods_size = Rect::new(size.x, method_title_size.y.add(1), size.width, 3);
         let parent_size = Rect::new(size.x, methods_size.y.add(3), size.width, 3);
+        let status_size = Rect::new(size.x, parent_size.y.add(3), size.width, 3);
+        let cookies_size = Rect::new(size.x, status_size.y.add(3), size.width, 3);
         let hint_size = Rect::new(
             frame.size().width.div(2).saturating_sub(hint_size.div(2)),
-            parent_size.y.add(4),
+            cookies_size.y.add(4),
             hint_size,
             1,
         );
@@ -164,21 +268,21 @@ impl<'rf, State> Renderable for RequestForm<'rf, State> {
             .constraints((0..5).map(|_| Constraint::Length(13)))
             .split(methods_size);
 
-        let parent_name = if self.parent_dir.is_none() {
+        let parent_name = if let Some((_, parent_name)) = &self.parent_dir {
             format!(
-                "{}None{}",
+                "{}{}{}",
                 " ".repeat(parent_size.width.div(2).sub(2).into()),
+                parent_name,
                 " ".repeat(parent_size.width.div(2).sub(2).into())
             )
-            .fg(self.colors.bright.black)
+            .fg(self.colors.normal.white)
         } else {
             format!(
-                "{}{}{}",
+                "{}None{}",
                 " ".repeat(parent_size.width.div(2).sub(2).into()),
-                self.parent_dir.as_ref().unwrap().1.clone(),
                 " ".repeat(parent_size.width.div(2).sub(2).into())
             )
-            .fg(self.colors.normal.white)
+            .fg(self.colors.bright.black)
         };
         let parent = Paragraph::new(parent_name).centered().block(
             Block::default()
@@ -213,6 +317,8 @@ impl<'rf, State> Renderable for RequestForm<'rf, State> {
         frame.render_stateful_widget(name_input, name_size, &mut self.request_name);
         frame.render_widget(method_title, method_title_size);
         frame.render_widget(parent, parent_size);
+        frame.render_stateful_widget(status_input, status_size, &mut self.expected_status);
+        frame.render_stateful_widget(cookies_input, cookies_size, &mut self.cookies);
 
         if self
             .no_available_parent_timer
@@ -223,6 +329,24 @@ impl<'rf, State> Renderable for RequestForm<'rf, State> {
             )
             .centered();
             frame.render_widget(warning, hint_size);
+        } else if self.invalid_expected_status {
+            let warning = Paragraph::new(
+                "Invalid expected status, use 200, 200-204 or 2xx".fg(self.colors.normal.red),
+            )
+            .centered();
+            frame.render_widget(warning, hint_size);
+        } else if self.duplicate_name {
+            let warning = Paragraph::new(
+                "Another item of this folder has the same name".fg(self.colors.normal.red),
+            )
+            .centered();
+            frame.render_widget(warning, hint_size);
+        } else if self.invalid_cookies {
+            let warning = Paragraph::new(
+                "Invalid cookies, use name=value separated by ;".fg(self.colors.normal.red),
+            )
+            .centered();
+            frame.render_widget(warning, hint_size);
         } else {
             frame.render_widget(hint, hint_size);
         }
@@ -244,6 +368,26 @@ impl<'rf, State> Renderable for RequestForm<'rf, State> {
             );
         }
 
+        if self.focused_field.eq(&FormField::ExpectedStatus) {
+            frame.set_cursor(
+                status_size
+                    .x
+                    .add(self.expected_status.chars().count() as u16)
+                    .add(1),
+                status_size.y.add(1),
+            );
+        }
+
+        if self.focused_field.eq(&FormField::Cookies) {
+            frame.set_cursor(
+                cookies_size
+                    .x
+                    .add(self.cookies.chars().count() as u16)
+                    .add(1),
+                cookies_size.y.add(1),
+            );
+        }
+
         Ok(())
     }
 }
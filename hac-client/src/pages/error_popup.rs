@@ -1,7 +1,13 @@
+use std::ops::{Add, Div};
+
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Direction, Flex, Layout, Rect};
 use ratatui::style::{Style, Stylize};
-use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph, Widget, Wrap};
+use ratatui::text::Line;
+use ratatui::widgets::{
+    Block, Borders, Clear, Padding, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+    StatefulWidget, Widget, Wrap,
+};
 
 #[derive(Debug, PartialEq)]
 pub struct ErrorPopupLayout {
@@ -9,6 +15,28 @@ pub struct ErrorPopupLayout {
     confirmation_pane: Rect,
 }
 
+/// how far the message of the error popup is scrolled, the popup is rebuilt on
+/// every frame so the page showing it holds this between frames
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ErrorPopupState {
+    scroll: usize,
+}
+
+impl ErrorPopupState {
+    pub fn scroll_down(&mut self) {
+        // clamped to the length of the message when rendering
+        self.scroll = self.scroll.add(1);
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+
+    pub fn reset(&mut self) {
+        self.scroll = 0;
+    }
+}
+
 pub struct ErrorPopup<'a> {
     message: String,
     colors: &'a hac_colors::Colors,
@@ -19,16 +47,40 @@ impl<'a> ErrorPopup<'a> {
         ErrorPopup { message, colors }
     }
 
-    fn build_popup(&self) -> (Paragraph<'_>, Paragraph<'_>) {
-        let message = Paragraph::new(self.message.clone().fg(self.colors.normal.red))
-            .wrap(Wrap { trim: true });
+    fn build_popup(&self, width: u16, overflows: bool) -> (Vec<Line<'_>>, Paragraph<'_>) {
+        let message = wrap_message(&self.message, width.into())
+            .into_iter()
+            .map(|line| Line::from(line.fg(self.colors.normal.red)))
+            .collect();
 
-        let confirmation = Paragraph::new("(O)k".fg(self.colors.normal.green).into_centered_line())
-            .wrap(Wrap { trim: true });
+        let mut confirmation = vec!["(O)k".fg(self.colors.normal.green)];
+        if overflows {
+            confirmation.push("  [j/k: Scroll]".fg(self.colors.bright.black));
+        }
+        let confirmation =
+            Paragraph::new(Line::from(confirmation).centered()).wrap(Wrap { trim: true });
 
         (message, confirmation)
     }
 
+    /// shrinks `size` to fit the message, centered on it, so short errors get a
+    /// small popup and long ones get up to the whole of `size`
+    fn fit(&self, size: &Rect) -> Rect {
+        let width = self.layout(size).message_pane.width;
+        // margins around the message, the gap and the confirmation line
+        let height = wrap_message(&self.message, width.into())
+            .len()
+            .add(6)
+            .min(size.height.into()) as u16;
+
+        Rect::new(
+            size.x,
+            size.y.add(size.height.saturating_sub(height).div(2)),
+            size.width,
+            height,
+        )
+    }
+
     fn layout(&self, size: &Rect) -> ErrorPopupLayout {
         let size = Rect::new(
             size.x + 2,
@@ -58,20 +110,82 @@ impl<'a> ErrorPopup<'a> {
     }
 }
 
-impl Widget for ErrorPopup<'_> {
-    fn render(self, size: Rect, buf: &mut Buffer)
+impl StatefulWidget for ErrorPopup<'_> {
+    type State = ErrorPopupState;
+
+    fn render(self, size: Rect, buf: &mut Buffer, state: &mut Self::State)
     where
         Self: Sized,
     {
+        let size = self.fit(&size);
         Clear.render(size, buf);
         let layout = self.layout(&size);
-        let (message, confirmation) = self.build_popup();
+        // leaving a blank line between the message and the confirmation
+        let viewport = usize::from(layout.message_pane.height.saturating_sub(1));
+        let total_lines = wrap_message(&self.message, layout.message_pane.width.into()).len();
+        let overflows = total_lines.gt(&viewport);
+        state.scroll = state.scroll.min(total_lines.saturating_sub(viewport));
+
+        let (message, confirmation) = self.build_popup(layout.message_pane.width, overflows);
         let full_block = self.build_container();
 
         full_block.render(size, buf);
-        message.render(layout.message_pane, buf);
+        Paragraph::new(
+            message
+                .into_iter()
+                .skip(state.scroll)
+                .take(viewport)
+                .collect::<Vec<_>>(),
+        )
+        .render(layout.message_pane, buf);
         confirmation.render(layout.confirmation_pane, buf);
+
+        if overflows {
+            let mut scrollbar_state = ScrollbarState::new(total_lines.saturating_sub(viewport))
+                .position(state.scroll)
+                .viewport_content_length(viewport);
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .style(Style::default().fg(self.colors.normal.red))
+                .begin_symbol(Some("↑"))
+                .end_symbol(Some("↓"))
+                .render(size, buf, &mut scrollbar_state);
+        }
+    }
+}
+
+/// breaks the lines of `message` on whitespace so none is wider than `width`,
+/// words that don't fit on a line by themselves are broken anywhere. The
+/// indentation of the lines is kept, as it means something on stack traces
+fn wrap_message(message: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = vec![];
+
+    for line in message.lines() {
+        let mut current = String::new();
+        let mut started = false;
+
+        for word in line.split(' ') {
+            let len = current.chars().count();
+            if started && len.add(1).add(word.chars().count()).gt(&width) {
+                lines.push(std::mem::take(&mut current));
+            } else if started {
+                current.push(' ');
+            }
+            current.push_str(word);
+            started = true;
+
+            while current.chars().count().gt(&width) {
+                let rest = current.chars().skip(width).collect::<String>();
+                let taken = current.chars().take(width).collect::<String>();
+                lines.push(taken);
+                current = rest;
+            }
+        }
+
+        lines.push(current);
     }
+
+    lines
 }
 
 #[cfg(test)]
@@ -83,16 +197,16 @@ mod tests {
         let colors = hac_colors::Colors::default();
         let popup = ErrorPopup::new("my error message".into(), &colors);
 
-        let (message, confirmation) = popup.build_popup();
+        let (message, confirmation) = popup.build_popup(20, false);
 
         assert_eq!(
             message,
-            Paragraph::new("my error message".fg(colors.normal.red)).wrap(Wrap { trim: true })
+            vec![Line::from("my error message".fg(colors.normal.red))]
         );
 
         assert_eq!(
             confirmation,
-            Paragraph::new("(O)k".fg(colors.normal.green).into_centered_line())
+            Paragraph::new(Line::from(vec!["(O)k".fg(colors.normal.green)]).centered())
                 .wrap(Wrap { trim: true })
         );
     }
@@ -127,4 +241,26 @@ mod tests {
 
         assert_eq!(expected, block);
     }
+
+    #[test]
+    fn test_popup_fits_the_message() {
+        let colors = hac_colors::Colors::default();
+        let rect = Rect::new(0, 0, 24, 20);
+
+        let popup = ErrorPopup::new("short".into(), &colors);
+        assert_eq!(popup.fit(&rect), Rect::new(0, 6, 24, 7));
+
+        let popup = ErrorPopup::new("a line\n".repeat(40), &colors);
+        assert_eq!(popup.fit(&rect), rect);
+    }
+
+    #[test]
+    fn test_wrap_message_keeps_indentation() {
+        assert_eq!(
+            wrap_message("failed to parse body\n  at line 3", 12),
+            vec!["failed to", "parse body", "  at line 3"]
+        );
+        assert_eq!(wrap_message("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(wrap_message("a\n\nb", 4), vec!["a", "", "b"]);
+    }
 }
@@ -0,0 +1,174 @@
+use crate::pages::overlay::make_overlay;
+
+use std::ops::{Add, Div, Sub};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Rect;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph};
+use ratatui::Frame;
+
+/// floating list of items to pick one from, shared by the pickers of the app.
+/// It keeps the selection and scroll, while the picker using it decides how
+/// each item is shown and what happens once one is picked
+#[derive(Debug)]
+pub struct ListOverlay<'lo, T> {
+    colors: &'lo hac_colors::Colors,
+    title: &'static str,
+    /// shown above the items, usually listing the available keys
+    hint: &'static str,
+    items: Vec<T>,
+    selected: usize,
+}
+
+impl<'lo, T> ListOverlay<'lo, T> {
+    pub fn new(
+        colors: &'lo hac_colors::Colors,
+        title: &'static str,
+        hint: &'static str,
+        items: Vec<T>,
+    ) -> Self {
+        ListOverlay {
+            colors,
+            title,
+            hint,
+            items,
+            selected: 0,
+        }
+    }
+
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// selects the item at `idx`, clamped to the last item
+    pub fn select(&mut self, idx: usize) {
+        self.selected = idx.min(self.items.len().saturating_sub(1));
+    }
+
+    /// removes the selected item from the list, handing it over
+    pub fn take_selected(&mut self) -> Option<T> {
+        self.selected
+            .lt(&self.items.len())
+            .then(|| self.items.swap_remove(self.selected))
+    }
+
+    /// moves the selected item one position up or down, carrying the
+    /// selection with it
+    pub fn move_selected(&mut self, down: bool) {
+        let target = match down {
+            true if self.selected.add(1).lt(&self.items.len()) => self.selected.add(1),
+            false if self.selected.gt(&0) => self.selected.sub(1),
+            _ => return,
+        };
+        self.items.swap(self.selected, target);
+        self.selected = target;
+    }
+
+    /// moves the selection on the keys every list shares, returning whether
+    /// `key_event` was one of them
+    pub fn handle_navigation(&mut self, key_event: KeyEvent) -> bool {
+        match key_event.code {
+            KeyCode::Down | KeyCode::Tab | KeyCode::Char('j') => {
+                self.select(self.selected.add(1));
+                true
+            }
+            KeyCode::Up | KeyCode::BackTab | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// draws the list over the screen, `build_entry` builds the line of an
+    /// item given its index and the available width, the selected one is
+    /// highlighted
+    pub fn draw<'a, F>(&'a self, frame: &mut Frame, build_entry: F)
+    where
+        F: Fn(usize, &'a T, usize) -> Line<'a>,
+    {
+        make_overlay(self.colors, self.colors.normal.black, 0.1, frame);
+
+        let size = frame.size();
+        let height = (self.items.len() as u16)
+            .add(4)
+            .min(size.height.sub(size.height.div(6)));
+        let size = Rect::new(
+            size.width.div(4),
+            size.height.div(6),
+            size.width.div(2),
+            height,
+        );
+
+        let block = Block::default()
+            .title(self.title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.colors.normal.red))
+            .bg(self.colors.primary.background)
+            .padding(Padding::horizontal(1));
+        let inner = block.inner(size);
+
+        let mut lines = vec![
+            Line::from(self.hint.fg(self.colors.bright.black)),
+            Line::from(""),
+        ];
+
+        let amount_on_view = inner.height.sub(2.min(inner.height)) as usize;
+        let scroll = self
+            .selected
+            .saturating_sub(amount_on_view.saturating_sub(1));
+        self.items
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(amount_on_view)
+            .for_each(|(idx, item)| {
+                let bg = match idx.eq(&self.selected) {
+                    true => self.colors.primary.hover,
+                    false => self.colors.primary.background,
+                };
+                let line = build_entry(idx, item, inner.width.into());
+                lines.push(Line::from(
+                    line.spans
+                        .into_iter()
+                        .map(|span| span.bg(bg))
+                        .collect::<Vec<_>>(),
+                ));
+            });
+
+        frame.render_widget(Clear, size);
+        frame.render_widget(Paragraph::new(lines).block(block), size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crossterm::event::KeyModifiers;
+
+    #[test]
+    fn test_selection_stays_within_the_items() {
+        let colors = hac_colors::Colors::default();
+        let mut list = ListOverlay::new(&colors, "list", "", vec!["a", "b"]);
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        assert!(list.handle_navigation(key(KeyCode::Up)));
+        assert_eq!(list.selected(), 0);
+
+        list.handle_navigation(key(KeyCode::Char('j')));
+        list.handle_navigation(key(KeyCode::Char('j')));
+        assert_eq!(list.selected(), 1);
+        assert!(!list.handle_navigation(key(KeyCode::Enter)));
+
+        list.move_selected(false);
+        assert_eq!(list.items(), ["b", "a"]);
+        assert_eq!(list.take_selected(), Some("b"));
+    }
+}
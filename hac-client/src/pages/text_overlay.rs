@@ -0,0 +1,108 @@
+use crate::pages::overlay::make_overlay;
+
+use std::ops::{Add, Div, Sub};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Rect;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph};
+use ratatui::Frame;
+
+/// floating, scrollable text shown over the current screen. The page using it
+/// decides how each line is highlighted and which keys close it
+#[derive(Debug)]
+pub struct TextOverlay<'to> {
+    colors: &'to hac_colors::Colors,
+    title: &'static str,
+    /// shown above the text, usually listing the available keys
+    hint: &'static str,
+    text: String,
+    scroll: usize,
+}
+
+impl<'to> TextOverlay<'to> {
+    pub fn new(
+        colors: &'to hac_colors::Colors,
+        title: &'static str,
+        hint: &'static str,
+        text: String,
+    ) -> Self {
+        TextOverlay {
+            colors,
+            title,
+            hint,
+            text,
+            scroll: 0,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn line_count(&self) -> usize {
+        self.text.lines().count()
+    }
+
+    /// scrolls the text on the keys every text overlay shares, returning
+    /// whether `key_event` was one of them
+    pub fn handle_scroll(&mut self, key_event: KeyEvent) -> bool {
+        match key_event.code {
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.scroll = self.scroll.add(1).min(self.line_count().saturating_sub(1));
+                true
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.scroll = self.scroll.saturating_sub(1);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// draws the text over the screen, `build_line` builds each line shown
+    pub fn draw<'a, F>(&'a mut self, frame: &mut Frame, build_line: F)
+    where
+        F: Fn(&'a str) -> Line<'a>,
+    {
+        make_overlay(self.colors, self.colors.normal.black, 0.1, frame);
+
+        let size = frame.size();
+        let height = (self.line_count() as u16)
+            .add(4)
+            .min(size.height.sub(size.height.div(6)));
+        let size = Rect::new(
+            size.width.div(6),
+            size.height.div(6),
+            size.width.sub(size.width.div(3)),
+            height,
+        );
+
+        let block = Block::default()
+            .title(self.title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.colors.normal.red))
+            .bg(self.colors.primary.background)
+            .padding(Padding::horizontal(1));
+        let inner = block.inner(size);
+
+        let amount_on_view = inner.height.sub(2.min(inner.height)) as usize;
+        self.scroll = self
+            .scroll
+            .min(self.line_count().saturating_sub(amount_on_view));
+
+        let mut lines = vec![
+            Line::from(self.hint.fg(self.colors.bright.black)),
+            Line::from(""),
+        ];
+        self.text
+            .lines()
+            .skip(self.scroll)
+            .take(amount_on_view)
+            .for_each(|line| lines.push(build_line(line)));
+
+        frame.render_widget(Clear, size);
+        frame.render_widget(Paragraph::new(lines).block(block), size);
+    }
+}
@@ -0,0 +1,83 @@
+//! requests sent recently across every collection, most recent first. Unlike
+//! the samples of a request, this list only points to the requests, so it can
+//! be used to quickly get back to a request sent on another collection.
+//!
+//! the list is shared by every screen, and may be loaded from the data dir
+//! when the app starts
+
+use hac_config::RecentRequest;
+
+use std::sync::Mutex;
+
+/// how many requests are remembered, older ones are dropped first
+pub const MAX_RECENT_REQUESTS: usize = 20;
+
+static RECENT: Mutex<Vec<RecentRequest>> = Mutex::new(Vec::new());
+
+/// replaces the list with `requests`, used to restore the list of a previous run
+pub fn load(mut requests: Vec<RecentRequest>) {
+    requests.truncate(MAX_RECENT_REQUESTS);
+    *RECENT.lock().unwrap() = requests;
+}
+
+/// moves `request` to the top of the list, returning the updated list
+pub fn record(request: RecentRequest) -> Vec<RecentRequest> {
+    let mut recent = RECENT.lock().unwrap();
+    push_recent(&mut recent, request);
+    recent.clone()
+}
+
+/// every recently sent request, most recent first
+pub fn entries() -> Vec<RecentRequest> {
+    RECENT.lock().unwrap().clone()
+}
+
+fn push_recent(recent: &mut Vec<RecentRequest>, request: RecentRequest) {
+    recent.retain(|entry| {
+        entry.collection_path.ne(&request.collection_path)
+            || entry.request_id.ne(&request.request_id)
+    });
+    recent.insert(0, request);
+    recent.truncate(MAX_RECENT_REQUESTS);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::PathBuf;
+
+    fn make_entry(collection: &str, id: &str) -> RecentRequest {
+        RecentRequest {
+            collection_path: PathBuf::from(collection),
+            collection_name: collection.to_string(),
+            request_id: id.to_string(),
+            request_name: id.to_string(),
+            method: String::from("GET"),
+        }
+    }
+
+    #[test]
+    fn test_resending_moves_the_request_to_the_top() {
+        let mut recent = vec![];
+        push_recent(&mut recent, make_entry("a.json", "1"));
+        push_recent(&mut recent, make_entry("b.json", "1"));
+        push_recent(&mut recent, make_entry("a.json", "1"));
+
+        assert_eq!(
+            recent,
+            vec![make_entry("a.json", "1"), make_entry("b.json", "1")]
+        );
+    }
+
+    #[test]
+    fn test_oldest_requests_are_dropped() {
+        let mut recent = vec![];
+        (0..MAX_RECENT_REQUESTS + 5).for_each(|id| {
+            push_recent(&mut recent, make_entry("a.json", &id.to_string()));
+        });
+
+        assert_eq!(recent.len(), MAX_RECENT_REQUESTS);
+        assert_eq!(recent[0].request_id, (MAX_RECENT_REQUESTS + 4).to_string());
+    }
+}
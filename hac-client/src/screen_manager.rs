@@ -2,11 +2,13 @@ use hac_core::{collection::Collection, command::Command};
 
 use crate::event_pool::Event;
 use crate::pages::collection_dashboard::CollectionDashboard;
-use crate::pages::collection_viewer::collection_store::CollectionStore;
+use crate::pages::collection_viewer::collection_store::{CollectionStore, CollectionStoreAction};
 use crate::pages::collection_viewer::CollectionViewer;
 use crate::pages::terminal_too_small::TerminalTooSmall;
 use crate::pages::{Eventful, Renderable};
+use crate::toast;
 
+use std::path::PathBuf;
 use std::{cell::RefCell, rc::Rc};
 
 use ratatui::{layout::Rect, Frame};
@@ -35,7 +37,7 @@ pub struct ScreenManager<'sm> {
 
     size: Rect,
     colors: &'sm hac_colors::Colors,
-    config: &'sm hac_config::Config,
+    config: Rc<hac_config::Config>,
     dry_run: bool,
 
     collection_store: Rc<RefCell<CollectionStore>>,
@@ -50,7 +52,7 @@ impl<'sm> ScreenManager<'sm> {
         size: Rect,
         colors: &'sm hac_colors::Colors,
         collections: Vec<Collection>,
-        config: &'sm hac_config::Config,
+        config: Rc<hac_config::Config>,
         dry_run: bool,
     ) -> anyhow::Result<Self> {
         Ok(Self {
@@ -68,6 +70,17 @@ impl<'sm> ScreenManager<'sm> {
         })
     }
 
+    /// asks on the collection list whether a starter collection should be
+    /// created
+    pub fn offer_starter_collection(&mut self) {
+        self.collection_list.offer_starter_collection();
+    }
+
+    /// creates the starter collection and opens it
+    pub fn create_starter_collection(&mut self) {
+        self.collection_list.create_starter_collection();
+    }
+
     fn restore_screen(&mut self) {
         std::mem::swap(&mut self.curr_screen, &mut self.prev_screen);
     }
@@ -86,31 +99,139 @@ impl<'sm> ScreenManager<'sm> {
     pub fn handle_command(&mut self, command: Command) {
         match command {
             Command::SelectCollection(collection) | Command::CreateCollection(collection) => {
-                tracing::debug!("changing to api explorer: {}", collection.info.name);
-                self.switch_screen(Screens::CollectionViewer);
-                self.collection_store.borrow_mut().set_state(collection);
-                self.collection_viewer = Some(CollectionViewer::new(
-                    self.size,
-                    self.collection_store.clone(),
-                    self.colors,
-                    self.config,
-                    self.dry_run,
-                ));
-                self.collection_viewer.as_mut().unwrap()
-                    .register_command_handler(
-                        self.sender
-                            .as_ref()
-                            .expect("attempted to register the sender on collection_viewer but it was None")
-                            .clone(),
-                    )
-                    .ok();
+                self.open_collection_viewer(collection, self.dry_run);
+            }
+            Command::OpenScratch(collection) => {
+                // scratch collections have no file backing them, so they are
+                // always opened with disk synchronization disabled
+                self.open_collection_viewer(collection, true);
             }
             Command::Error(msg) => {
                 self.collection_list.display_error(msg);
             }
+            Command::Back if self.curr_screen.eq(&Screens::CollectionViewer) => {
+                self.save_session();
+                self.switch_screen(Screens::CollectionDashboard);
+            }
+            Command::OpenRequest {
+                path,
+                request_id,
+                send,
+            } => self.open_request(path, request_id, send),
             _ => {}
         }
     }
+
+    /// swaps the configuration every screen was built with for `config`
+    pub fn reload_config(&mut self, config: Rc<hac_config::Config>) {
+        if let Some(viewer) = self.collection_viewer.as_mut() {
+            viewer.reload_config(config.clone(), self.size);
+        }
+        self.config = config;
+    }
+
+    /// persists the view of the collection being viewed, if any
+    pub fn save_session(&mut self) {
+        if let Some(viewer) = self.collection_viewer.as_mut() {
+            viewer.save_session();
+        }
+    }
+
+    /// navigates to the request with `request_id` of the collection stored at
+    /// `path`. When the collection is not the one being viewed it is loaded
+    /// in the background, and the command is sent again once it is opened
+    fn open_request(&mut self, path: PathBuf, request_id: String, send: bool) {
+        let is_open = self.curr_screen.eq(&Screens::CollectionViewer)
+            && self
+                .collection_store
+                .borrow()
+                .get_collection()
+                .is_some_and(|collection| collection.borrow().path.eq(&path));
+
+        if is_open {
+            if let Some(viewer) = self.collection_viewer.as_mut() {
+                viewer.open_request(&request_id, send);
+            }
+            return;
+        }
+
+        let sender = self
+            .sender
+            .clone()
+            .expect("attempted to open a request without a command sender");
+        tokio::spawn(async move {
+            match hac_core::collection::collection::load_collection(path.clone()).await {
+                Ok(collection) => {
+                    sender.send(Command::SelectCollection(collection)).ok();
+                    sender
+                        .send(Command::OpenRequest {
+                            path,
+                            request_id,
+                            send,
+                        })
+                        .ok();
+                }
+                Err(e) => toast::show(format!("failed to open the collection: {e}")),
+            }
+        });
+    }
+
+    fn open_collection_viewer(&mut self, collection: Collection, dry_run: bool) {
+        tracing::debug!("changing to api explorer: {}", collection.info.name);
+        self.collection_list.finish_loading();
+        self.save_session();
+        self.switch_screen(Screens::CollectionViewer);
+
+        let mut session = (!dry_run)
+            .then(|| hac_config::load_session(&collection.path))
+            .flatten()
+            .unwrap_or_default();
+        // the hovered request is always restored, the rest of the view only
+        // when asked to
+        if !self.config.restore_session {
+            session = hac_config::SessionState {
+                hovered_request: session.hovered_request,
+                ..Default::default()
+            };
+        }
+        let last_hover = session.hovered_request.clone();
+        // environments may have been removed from the collection since
+        let environment = session
+            .active_environment
+            .clone()
+            .filter(|name| collection.environments.contains_key(name));
+        let mut store = self.collection_store.borrow_mut();
+        store.set_state(collection);
+        if let Some(item_id) = last_hover {
+            store.restore_hovered_request(item_id, self.config.expand_selected_request);
+        }
+        if environment.is_some() {
+            store.dispatch(CollectionStoreAction::SetActiveEnvironment(environment));
+        }
+        drop(store);
+
+        self.collection_viewer = Some(CollectionViewer::new(
+            self.size,
+            self.collection_store.clone(),
+            self.colors,
+            self.config.clone(),
+            dry_run,
+        ));
+        self.collection_viewer
+            .as_mut()
+            .unwrap()
+            .register_command_handler(
+                self.sender
+                    .as_ref()
+                    .expect("attempted to register the sender on collection_viewer but it was None")
+                    .clone(),
+            )
+            .ok();
+        self.collection_viewer
+            .as_mut()
+            .unwrap()
+            .restore_session(session);
+    }
 }
 
 impl Renderable for ScreenManager<'_> {
@@ -228,9 +349,9 @@ mod tests {
         let colors = hac_colors::Colors::default();
         let (_guard, path) = setup_temp_collections(10);
         let collections = collection::collection::get_collections(path).unwrap();
-        let config = hac_config::load_config();
+        let config = Rc::new(hac_config::load_config());
         let mut sm =
-            ScreenManager::new(small_in_width, &colors, collections, &config, false).unwrap();
+            ScreenManager::new(small_in_width, &colors, collections, config, false).unwrap();
         let mut terminal = Terminal::new(TestBackend::new(80, 22)).unwrap();
 
         sm.draw(&mut terminal.get_frame(), small_in_width).unwrap();
@@ -247,8 +368,8 @@ mod tests {
         let colors = hac_colors::Colors::default();
         let (_guard, path) = setup_temp_collections(10);
         let collections = collection::collection::get_collections(path).unwrap();
-        let config = hac_config::load_config();
-        let mut sm = ScreenManager::new(small, &colors, collections, &config, false).unwrap();
+        let config = Rc::new(hac_config::load_config());
+        let mut sm = ScreenManager::new(small, &colors, collections, config, false).unwrap();
         let mut terminal = Terminal::new(TestBackend::new(80, 22)).unwrap();
 
         terminal.resize(small).unwrap();
@@ -269,8 +390,8 @@ mod tests {
         let colors = hac_colors::Colors::default();
         let (_guard, path) = setup_temp_collections(10);
         let collection = collection::collection::get_collections(path).unwrap();
-        let config = hac_config::load_config();
-        let mut sm = ScreenManager::new(initial, &colors, collection, &config, false).unwrap();
+        let config = Rc::new(hac_config::load_config());
+        let mut sm = ScreenManager::new(initial, &colors, collection, config, false).unwrap();
 
         sm.resize(expected);
 
@@ -287,14 +408,14 @@ mod tests {
                 description: None,
             },
             path: "any_path".into(),
-            requests: None,
+            ..Default::default()
         };
         let command = Command::SelectCollection(collection.clone());
         let (_guard, path) = setup_temp_collections(10);
         let collection = collection::collection::get_collections(path).unwrap();
-        let config = hac_config::load_config();
+        let config = Rc::new(hac_config::load_config());
         let (tx, _) = tokio::sync::mpsc::unbounded_channel::<Command>();
-        let mut sm = ScreenManager::new(initial, &colors, collection, &config, false).unwrap();
+        let mut sm = ScreenManager::new(initial, &colors, collection, config, false).unwrap();
         _ = sm.register_command_handler(tx.clone());
         assert_eq!(sm.curr_screen, Screens::CollectionDashboard);
 
@@ -302,14 +423,33 @@ mod tests {
         assert_eq!(sm.curr_screen, Screens::CollectionViewer);
     }
 
+    #[test]
+    fn test_back_from_explorer_to_dashboard() {
+        let initial = Rect::new(0, 0, 80, 22);
+        let colors = hac_colors::Colors::default();
+        let (_guard, path) = setup_temp_collections(1);
+        let collections = collection::collection::get_collections(path).unwrap();
+        let command = Command::SelectCollection(collections[0].clone());
+        let config = Rc::new(hac_config::load_config());
+        let (tx, _) = tokio::sync::mpsc::unbounded_channel::<Command>();
+        let mut sm = ScreenManager::new(initial, &colors, collections, config, true).unwrap();
+        _ = sm.register_command_handler(tx.clone());
+
+        sm.handle_command(command);
+        assert_eq!(sm.curr_screen, Screens::CollectionViewer);
+
+        sm.handle_command(Command::Back);
+        assert_eq!(sm.curr_screen, Screens::CollectionDashboard);
+    }
+
     #[test]
     fn test_register_command_sender_for_dashboard() {
         let initial = Rect::new(0, 0, 80, 22);
         let colors = hac_colors::Colors::default();
         let (_guard, path) = setup_temp_collections(10);
         let collections = collection::collection::get_collections(path).unwrap();
-        let config = hac_config::load_config();
-        let mut sm = ScreenManager::new(initial, &colors, collections, &config, false).unwrap();
+        let config = Rc::new(hac_config::load_config());
+        let mut sm = ScreenManager::new(initial, &colors, collections, config, false).unwrap();
 
         let (tx, _) = tokio::sync::mpsc::unbounded_channel::<Command>();
 
@@ -324,8 +464,8 @@ mod tests {
         let colors = hac_colors::Colors::default();
         let (_guard, path) = setup_temp_collections(10);
         let collections = collection::collection::get_collections(path).unwrap();
-        let config = hac_config::load_config();
-        let mut sm = ScreenManager::new(initial, &colors, collections, &config, false).unwrap();
+        let config = Rc::new(hac_config::load_config());
+        let mut sm = ScreenManager::new(initial, &colors, collections, config, false).unwrap();
 
         let event = Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
 
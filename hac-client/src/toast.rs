@@ -0,0 +1,59 @@
+//! short lived messages shown on the hint pane, used to give feedback on
+//! actions that have no other visible result, like copying to the clipboard.
+//!
+//! any component can show a message, and the app loop clears it on a tick
+//! once it has been visible for the configured duration
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static TOAST: Mutex<Option<Toast>> = Mutex::new(None);
+
+#[derive(Debug)]
+struct Toast {
+    message: String,
+    shown_at: Instant,
+}
+
+/// shows `message` on the hint pane, replacing any message being shown
+pub fn show(message: impl Into<String>) {
+    *TOAST.lock().unwrap() = Some(Toast {
+        message: message.into(),
+        shown_at: Instant::now(),
+    });
+}
+
+/// message currently being shown, if any
+pub fn current() -> Option<String> {
+    TOAST
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|toast| toast.message.clone())
+}
+
+/// clears the message when it was shown for longer than `duration`
+pub fn expire(duration: Duration) {
+    let mut toast = TOAST.lock().unwrap();
+    if toast
+        .as_ref()
+        .is_some_and(|toast| toast.shown_at.elapsed().ge(&duration))
+    {
+        *toast = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages_expire_after_the_duration() {
+        show("copied to clipboard");
+        expire(Duration::from_secs(60));
+        assert_eq!(current().as_deref(), Some("copied to clipboard"));
+
+        expire(Duration::ZERO);
+        assert!(current().is_none());
+    }
+}
@@ -1,30 +1,63 @@
 use hac_core::syntax::highlighter::HIGHLIGHTER;
 
-use std::ops::Add;
+use std::collections::HashMap;
+use std::io::Write;
 
-use ratatui::style::{Color, Stylize};
+use base64::Engine;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style, Stylize};
 use ratatui::text::{Line, Span};
+use ratatui::widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState, Tabs};
+use ratatui::Frame;
+use reqwest::header::{HeaderMap, HeaderValue};
 use tree_sitter::Tree;
 
 fn is_endline(c: char) -> bool {
     matches!(c, '\n' | '\r')
 }
 
+/// tab bar styled with the tab colors of the theme and the configured padding,
+/// every tab bar of the app should be built through this
+pub fn build_tabs<'a, T>(
+    titles: impl IntoIterator<Item = T>,
+    selected: usize,
+    colors: &hac_colors::Colors,
+    config: &hac_config::Config,
+) -> Tabs<'a>
+where
+    T: Into<Line<'a>>,
+{
+    let padding = " ".repeat(config.tab_padding);
+    Tabs::new(titles)
+        .style(colors.tabs.style())
+        .select(selected)
+        .highlight_style(colors.tabs.active_style())
+        .padding(padding.clone(), padding)
+}
+
 /// Builds a vector of `Lines` to be rendered with syntax highlight from treesitter
 pub fn build_syntax_highlighted_lines(
     content: &str,
     tree: Option<&Tree>,
     colors: &hac_colors::Colors,
+) -> Vec<Line<'static>> {
+    build_syntax_highlighted_lines_with_tokens(content, tree, colors, &colors.tokens)
+}
+
+/// same as `build_syntax_highlighted_lines`, but styling the captures with
+/// `tokens` instead of the tokens of the colorscheme
+pub fn build_syntax_highlighted_lines_with_tokens(
+    content: &str,
+    tree: Option<&Tree>,
+    colors: &hac_colors::Colors,
+    tokens: &HashMap<String, Style>,
 ) -> Vec<Line<'static>> {
     // we collect every line into this vector, and return it at the end
     let mut styled_lines: Vec<Line> = vec![];
 
     // `HIGHLIGHTER` returns a vector of `ColorInfo`, which contains information about
     // which kind of token that is, and the style to apply to it
-    let mut highlights = HIGHLIGHTER
-        .read()
-        .unwrap()
-        .apply(content, tree, &colors.tokens);
+    let mut highlights = HIGHLIGHTER.read().unwrap().apply(content, tree, tokens);
 
     // these are helper variables to collect each line into styled spans based on the
     // token it contains
@@ -40,7 +73,11 @@ pub fn build_syntax_highlighted_lines(
     // to be rendered to the terminal
     let mut skip_next = false;
 
-    for (i, c) in content.chars().enumerate() {
+    // peeking the next char through the iterator keeps this linear, looking it up on
+    // `content` would walk the whole string again on every line break
+    let mut chars = content.chars().enumerate().peekable();
+
+    while let Some((i, c)) = chars.next() {
         if skip_next {
             skip_next = false;
             continue;
@@ -73,10 +110,9 @@ pub fn build_syntax_highlighted_lines(
                 current_line.clear();
                 current_capture = highlights.pop_front();
 
-                content
-                    .chars()
-                    .nth(i.add(1))
-                    .and_then(|next| is_endline(next).then(|| skip_next = true));
+                chars
+                    .peek()
+                    .and_then(|(_, next)| is_endline(*next).then(|| skip_next = true));
 
                 continue;
             }
@@ -99,10 +135,9 @@ pub fn build_syntax_highlighted_lines(
                 current_token.clear();
                 current_line.clear();
 
-                content
-                    .chars()
-                    .nth(i.add(1))
-                    .and_then(|next| is_endline(next).then(|| skip_next = true));
+                chars
+                    .peek()
+                    .and_then(|(_, next)| is_endline(*next).then(|| skip_next = true));
 
                 continue;
             }
@@ -128,10 +163,9 @@ pub fn build_syntax_highlighted_lines(
             current_token.clear();
             current_line.clear();
 
-            content
-                .chars()
-                .nth(i.add(1))
-                .and_then(|next| is_endline(next).then(|| skip_next = true));
+            chars
+                .peek()
+                .and_then(|(_, next)| is_endline(*next).then(|| skip_next = true));
 
             continue;
         }
@@ -145,6 +179,92 @@ pub fn build_syntax_highlighted_lines(
     styled_lines
 }
 
+/// copies the given content to the system clipboard through the terminal, by
+/// using the `OSC 52` escape sequence.
+///
+/// this works over ssh and without any clipboard provider installed, as long as
+/// the terminal emulator supports it
+pub fn copy_to_clipboard(content: &str) -> anyhow::Result<()> {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(content);
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{encoded}\x07")?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// serializes response headers into a json object, headers that appear more
+/// than once become an array with every value. Values that are not valid
+/// UTF-8 are converted lossily
+pub fn headers_to_json(headers: &HeaderMap<HeaderValue>) -> serde_json::Value {
+    let object = headers
+        .keys()
+        .map(|name| {
+            let mut values = headers
+                .get_all(name)
+                .iter()
+                .map(|value| serde_json::Value::from(String::from_utf8_lossy(value.as_bytes())))
+                .collect::<Vec<_>>();
+            let value = match values.len() {
+                1 => values.remove(0),
+                _ => serde_json::Value::Array(values),
+            };
+            (name.to_string(), value)
+        })
+        .collect::<serde_json::Map<_, _>>();
+
+    serde_json::Value::Object(object)
+}
+
+/// draws a vertical scrollbar on `size` for panes showing `viewport` lines out
+/// of `total_lines`, nothing is drawn when all the lines fit on the pane
+pub fn draw_vertical_scrollbar(
+    frame: &mut Frame,
+    colors: &hac_colors::Colors,
+    total_lines: usize,
+    scroll: usize,
+    viewport: usize,
+    size: Rect,
+) {
+    if total_lines.le(&viewport) {
+        return;
+    }
+
+    let mut scrollbar_state = ScrollbarState::new(total_lines.saturating_sub(viewport))
+        .position(scroll)
+        .viewport_content_length(viewport);
+
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .style(Style::default().fg(colors.normal.red))
+        .begin_symbol(Some("↑"))
+        .end_symbol(Some("↓"));
+
+    frame.render_stateful_widget(scrollbar, size, &mut scrollbar_state);
+}
+
+/// wrapper around an amount of bytes that displays it using the biggest unit
+/// that keeps the value above 1, eg: `1536` is displayed as `1.5 KB`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadableByteSize(pub u64);
+
+impl std::fmt::Display for ReadableByteSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+
+        let mut size = self.0 as f64;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+
+        write!(f, "{:.1} {}", size, UNITS[unit])
+    }
+}
+
 /// will try to apply a blending using multiply to two colors, based on a given alpha.
 ///
 /// It will apply the background over the foreground so we get a middleground color. This
@@ -221,3 +341,33 @@ fn ansi_to_rgb(val: u8) -> Option<(u8, u8, u8)> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readable_byte_size() {
+        assert_eq!(ReadableByteSize(512).to_string(), "512 B");
+        assert_eq!(ReadableByteSize(1536).to_string(), "1.5 KB");
+        assert_eq!(ReadableByteSize(5 * 1024 * 1024).to_string(), "5.0 MB");
+    }
+
+    #[test]
+    fn test_headers_to_json() {
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", HeaderValue::from_static("text/plain"));
+        headers.append("set-cookie", HeaderValue::from_static("a=1"));
+        headers.append("set-cookie", HeaderValue::from_static("b=2"));
+        headers.insert("x-raw", HeaderValue::from_bytes(b"caf\xe9").unwrap());
+
+        assert_eq!(
+            headers_to_json(&headers),
+            serde_json::json!({
+                "content-type": "text/plain",
+                "set-cookie": ["a=1", "b=2"],
+                "x-raw": "caf\u{fffd}",
+            })
+        );
+    }
+}
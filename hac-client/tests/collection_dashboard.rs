@@ -220,26 +220,26 @@ fn test_draw_error() {
 
     let expected = [
         "                                                                                ",
-        "  ▟▀▙     ▝█       ┌─────────────────────────────────────┐     ▟   ▀            ",
-        "  ▜▙  ▟▀▙  █  ▟▀▙ ▟│                                     │▟▀▙ ▝█▀ ▝█  ▟▀▙ █▀▙   ",
-        "  ▄▝█ █▀▀  █  █▀▀ █│ any_error_message                   │█ ▄  █▗  █  █ █ █ █   ",
-        "  ▝▀▘ ▝▀▘ ▝▀▘ ▝▀▘ ▝│                                     │▝▀▘  ▝▘ ▝▀▘ ▝▀▘ ▀ ▀   ",
-        "                   │                                     │                      ",
-        "                   │                                     │                      ",
-        "                   │                                     │                      ",
-        "                   │                                     │                      ",
+        "  ▟▀▙     ▝█           ▟                      ▝█  ▝█           ▟   ▀            ",
+        "  ▜▙  ▟▀▙  █  ▟▀▙ ▟▀▙ ▝█▀     ▝▀▙     ▟▀▙ ▟▀▙  █   █  ▟▀▙ ▟▀▙ ▝█▀ ▝█  ▟▀▙ █▀▙   ",
+        "  ▄▝█ █▀▀  █  █▀▀ █ ▄  █▗     ▟▀█     █ ▄ █ █  █   █  █▀▀ █ ▄  █▗  █  █ █ █ █   ",
+        "  ▝▀▘ ▝▀▘ ▝▀▘ ▝▀▘ ▝▀▘  ▝▘     ▝▀▝▘    ▝▀▘ ▝▀▘ ▝▀▘ ▝▀▘ ▝▀▘ ▝▀▘  ▝▘ ▝▀▘ ▝▀▘ ▀ ▀   ",
+        "                                                                                ",
+        "                                                                                ",
+        "                   ┌─────────────────────────────────────┐                      ",
         "                   │                                     │                      ",
+        "                   │ any_error_message                   │                      ",
         "            █▖▐▌   │                                     │                      ",
-        "            █▜▟▌▟▀▙│                                     │▙ █▀▙ ▟▀▀             ",
+        "            █▜▟▌▟▀▙│                (O)k                 │▙ █▀▙ ▟▀▀             ",
         "            █ ▜▌█ █│                                     │█ █ █ ▝▀▙             ",
-        "            ▀ ▝▘▝▀▘│                                     │▘ ▀ ▀ ▀▀▘             ",
-        "                   │                                     │                      ",
-        "                   │                                     │                      ",
-        "                   │                                     │                      ",
-        "                   │                                     │                      ",
-        "                   │                (O)k                 │                      ",
-        "                   │                                     │                      ",
-        "                   └─────────────────────────────────────┘                      ",
+        "            ▀ ▝▘▝▀▘└─────────────────────────────────────┘▘ ▀ ▀ ▀▀▘             ",
+        "                                                                                ",
+        "                                                                                ",
+        "                                                                                ",
+        "                                                                                ",
+        "                                                                                ",
+        "                                                                                ",
+        "                                                                                ",
         "                                                                                ",
     ];
 
@@ -275,10 +275,11 @@ fn test_draw_help() {
         "                k/<up>      - select item above                                 ",
         "                l/<right>   - select right item                                 ",
         "                n/c         - creates a new collection                          ",
-        "            █▖▐▌d           - deletes the selected collection                   ",
-        "            █▜▟▌?           - toggle this help window█  ▟▀▙ █▀▙ ▟▀▀             ",
-        "            █ ▜▌enter       - select item under cursor  █ █ █ █ ▝▀▙             ",
-        "            ▀ ▝▘/           - enter filter mode  ▝▘ ▝▀▘ ▝▀▘ ▀ ▀ ▀▀▘             ",
+        "            █▖▐▌s           - opens a scratch request▀                          ",
+        "            █▜▟▌d           - deletes the selected collection▀▙ ▟▀▀             ",
+        "            █ ▜▌?           - toggle this help window█  █ █ █ █ ▝▀▙             ",
+        "            ▀ ▝▘enter       - select item under cursor▘ ▝▀▘ ▀ ▀ ▀▀▘             ",
+        "                /           - enter filter mode                                 ",
         "                <C-c>       - quits the application                             ",
         "                                                                                ",
         "                           press any key to go back                             ",
@@ -286,7 +287,6 @@ fn test_draw_help() {
         "                                                                                ",
         "                                                                                ",
         "                                                                                ",
-        "                                                                                ",
     ];
 
     feed_keys(
@@ -1,12 +1,17 @@
 use std::collections::HashMap;
 
 use ratatui::style::{Color, Style};
+use serde::Deserialize;
 
 #[derive(Debug, PartialEq)]
 pub struct Colors {
     pub primary: PrimaryColors,
     pub normal: NormalColors,
     pub bright: BrightColors,
+    pub tabs: TabColors,
+    pub methods: MethodColors,
+    pub diff: DiffColors,
+    pub status_bar: StatusBarColors,
     pub tokens: HashMap<String, Style>,
 }
 
@@ -16,11 +21,89 @@ impl Default for Colors {
             primary: Default::default(),
             normal: Default::default(),
             bright: Default::default(),
-            tokens: token_highlight(),
+            tabs: Default::default(),
+            methods: Default::default(),
+            diff: Default::default(),
+            status_bar: Default::default(),
+            tokens: token_highlight(&BrightColors::default()),
         }
     }
 }
 
+/// colors set on the colors file of the user, grouped as on [`Colors`] and
+/// written as names or hex values, like `red` or `#c4746e`. Colors not on
+/// the file keep their default value
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ColorsFile {
+    primary: HashMap<String, String>,
+    normal: HashMap<String, String>,
+    bright: HashMap<String, String>,
+    tabs: HashMap<String, String>,
+    methods: HashMap<String, String>,
+    diff: HashMap<String, String>,
+    status_bar: HashMap<String, String>,
+}
+
+impl Colors {
+    /// builds the colors from the contents of a colors file, every color set
+    /// on it replaces the default one
+    pub fn from_toml(content: &str) -> anyhow::Result<Colors> {
+        let file = toml::from_str::<ColorsFile>(content)?;
+        let mut colors = Colors::default();
+
+        apply_overrides("primary", &mut colors.primary, &file.primary)?;
+        apply_overrides("normal", &mut colors.normal, &file.normal)?;
+        apply_overrides("bright", &mut colors.bright, &file.bright)?;
+        apply_overrides("tabs", &mut colors.tabs, &file.tabs)?;
+        apply_overrides("methods", &mut colors.methods, &file.methods)?;
+        apply_overrides("diff", &mut colors.diff, &file.diff)?;
+        apply_overrides("status_bar", &mut colors.status_bar, &file.status_bar)?;
+        colors.tokens = token_highlight(&colors.bright);
+
+        Ok(colors)
+    }
+}
+
+/// loads the colors from the colors file at `path`, falling back to the
+/// default colors when there is no such file or it is invalid
+pub fn load_colors<P>(path: Option<P>) -> Colors
+where
+    P: AsRef<std::path::Path>,
+{
+    let Some(content) = path.and_then(|path| std::fs::read_to_string(path).ok()) else {
+        return Colors::default();
+    };
+
+    Colors::from_toml(&content).unwrap_or_else(|e| {
+        tracing::warn!("failed to load the colors file, using the default colors: {e}");
+        Colors::default()
+    })
+}
+
+/// a group of colors that can be set on the colors file
+trait Palette {
+    fn entries(&mut self) -> Vec<(&'static str, &mut Color)>;
+}
+
+fn apply_overrides<P: Palette>(
+    group: &str,
+    palette: &mut P,
+    overrides: &HashMap<String, String>,
+) -> anyhow::Result<()> {
+    for (name, value) in overrides {
+        let mut entries = palette.entries();
+        let Some((_, color)) = entries.iter_mut().find(|(entry, _)| entry.eq(name)) else {
+            anyhow::bail!("unknown color {group}.{name}");
+        };
+        **color = value
+            .parse()
+            .map_err(|_| anyhow::anyhow!("invalid value for {group}.{name}: {value}"))?;
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, PartialEq)]
 pub struct PrimaryColors {
     pub foreground: Color,
@@ -53,9 +136,143 @@ pub struct BrightColors {
     pub white: Color,
 }
 
-fn token_highlight() -> HashMap<String, Style> {
+/// colors of the tab bars, like the ones on the request editor and on the
+/// response viewer
+#[derive(Debug, PartialEq)]
+pub struct TabColors {
+    pub foreground: Color,
+    pub active_foreground: Color,
+    pub active_background: Color,
+}
+
+impl TabColors {
+    pub fn style(&self) -> Style {
+        Style::default().fg(self.foreground)
+    }
+
+    pub fn active_style(&self) -> Style {
+        Style::default()
+            .fg(self.active_foreground)
+            .bg(self.active_background)
+    }
+}
+
+/// colors each http method is displayed with, like on the sidebar
+#[derive(Debug, PartialEq)]
+pub struct MethodColors {
+    pub get: Color,
+    pub post: Color,
+    pub put: Color,
+    pub patch: Color,
+    pub delete: Color,
+}
+
+/// colors of what was added, removed or changed, shared by every view that
+/// compares two things, like the request diff or merge patch bodies
+#[derive(Debug, PartialEq)]
+pub struct DiffColors {
+    pub added: Color,
+    pub removed: Color,
+    pub changed: Color,
+}
+
+/// colors of the status bar at the bottom of the collection viewer
+#[derive(Debug, PartialEq)]
+pub struct StatusBarColors {
+    pub foreground: Color,
+    pub background: Color,
+    pub collection: Color,
+    pub environment: Color,
+    pub dry_run: Color,
+}
+
+impl Palette for PrimaryColors {
+    fn entries(&mut self) -> Vec<(&'static str, &mut Color)> {
+        vec![
+            ("foreground", &mut self.foreground),
+            ("background", &mut self.background),
+            ("accent", &mut self.accent),
+            ("hover", &mut self.hover),
+        ]
+    }
+}
+
+impl Palette for NormalColors {
+    fn entries(&mut self) -> Vec<(&'static str, &mut Color)> {
+        vec![
+            ("black", &mut self.black),
+            ("red", &mut self.red),
+            ("green", &mut self.green),
+            ("yellow", &mut self.yellow),
+            ("blue", &mut self.blue),
+            ("magenta", &mut self.magenta),
+            ("orange", &mut self.orange),
+            ("white", &mut self.white),
+        ]
+    }
+}
+
+impl Palette for BrightColors {
+    fn entries(&mut self) -> Vec<(&'static str, &mut Color)> {
+        vec![
+            ("black", &mut self.black),
+            ("red", &mut self.red),
+            ("green", &mut self.green),
+            ("yellow", &mut self.yellow),
+            ("blue", &mut self.blue),
+            ("magenta", &mut self.magenta),
+            ("orange", &mut self.orange),
+            ("white", &mut self.white),
+        ]
+    }
+}
+
+impl Palette for TabColors {
+    fn entries(&mut self) -> Vec<(&'static str, &mut Color)> {
+        vec![
+            ("foreground", &mut self.foreground),
+            ("active_foreground", &mut self.active_foreground),
+            ("active_background", &mut self.active_background),
+        ]
+    }
+}
+
+impl Palette for MethodColors {
+    fn entries(&mut self) -> Vec<(&'static str, &mut Color)> {
+        vec![
+            ("get", &mut self.get),
+            ("post", &mut self.post),
+            ("put", &mut self.put),
+            ("patch", &mut self.patch),
+            ("delete", &mut self.delete),
+        ]
+    }
+}
+
+impl Palette for DiffColors {
+    fn entries(&mut self) -> Vec<(&'static str, &mut Color)> {
+        vec![
+            ("added", &mut self.added),
+            ("removed", &mut self.removed),
+            ("changed", &mut self.changed),
+        ]
+    }
+}
+
+impl Palette for StatusBarColors {
+    fn entries(&mut self) -> Vec<(&'static str, &mut Color)> {
+        vec![
+            ("foreground", &mut self.foreground),
+            ("background", &mut self.background),
+            ("collection", &mut self.collection),
+            ("environment", &mut self.environment),
+            ("dry_run", &mut self.dry_run),
+        ]
+    }
+}
+
+fn token_highlight(colors: &BrightColors) -> HashMap<String, Style> {
     let mut tokens = HashMap::new();
-    let colors = BrightColors::default();
 
     tokens.insert("conceal".into(), Style::new().fg(colors.red));
     tokens.insert("boolean".into(), Style::new().fg(colors.red));
@@ -70,6 +287,11 @@ fn token_highlight() -> HashMap<String, Style> {
         Style::new().fg(colors.magenta),
     );
     tokens.insert("string".into(), Style::new().fg(colors.green));
+    // `null` values on PATCH bodies, which delete the field on JSON Merge Patch
+    tokens.insert(
+        "merge_patch.delete".into(),
+        Style::new().fg(colors.black).bg(colors.yellow),
+    );
 
     tokens
 }
@@ -85,6 +307,53 @@ impl Default for PrimaryColors {
     }
 }
 
+impl Default for TabColors {
+    fn default() -> Self {
+        TabColors {
+            foreground: BrightColors::default().black,
+            active_foreground: NormalColors::default().white,
+            active_background: NormalColors::default().blue,
+        }
+    }
+}
+
+impl Default for MethodColors {
+    fn default() -> Self {
+        let colors = NormalColors::default();
+        MethodColors {
+            get: colors.green,
+            post: colors.magenta,
+            put: colors.yellow,
+            patch: colors.orange,
+            delete: colors.red,
+        }
+    }
+}
+
+impl Default for DiffColors {
+    fn default() -> Self {
+        let colors = NormalColors::default();
+        DiffColors {
+            added: colors.green,
+            removed: colors.red,
+            changed: colors.yellow,
+        }
+    }
+}
+
+impl Default for StatusBarColors {
+    fn default() -> Self {
+        let colors = NormalColors::default();
+        StatusBarColors {
+            foreground: BrightColors::default().black,
+            background: PrimaryColors::default().hover,
+            collection: colors.white,
+            environment: colors.green,
+            dry_run: colors.yellow,
+        }
+    }
+}
+
 impl Default for NormalColors {
     fn default() -> Self {
         NormalColors {
@@ -114,3 +383,37 @@ impl Default for BrightColors {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colors_file_overrides_every_group() {
+        let colors = Colors::from_toml(
+            r##"
+            [methods]
+            get = "#010203"
+
+            [diff]
+            removed = "blue"
+
+            [status_bar]
+            dry_run = "#ff0000"
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(colors.methods.get, Color::Rgb(0x01, 0x02, 0x03));
+        assert_eq!(colors.diff.removed, Color::Blue);
+        assert_eq!(colors.status_bar.dry_run, Color::Rgb(0xff, 0x00, 0x00));
+        assert_eq!(colors.methods.post, MethodColors::default().post);
+        assert_eq!(colors.normal, NormalColors::default());
+    }
+
+    #[test]
+    fn test_unknown_or_invalid_colors_are_rejected() {
+        assert!(Colors::from_toml("[methods]\nconnect = \"red\"").is_err());
+        assert!(Colors::from_toml("[diff]\nadded = \"not a color\"").is_err());
+    }
+}
@@ -1,3 +1,3 @@
 pub mod colors;
 
-pub use colors::Colors;
+pub use colors::{load_colors, Colors};
@@ -1,5 +1,7 @@
 use crate::default_config::DEFAULT_CONFIG;
-use crate::{EditorMode, APP_NAME, CONFIG_ENV_VAR, CONFIG_FILE, XDG_DEFAULTS, XDG_ENV_VARS};
+use crate::{
+    EditorMode, APP_NAME, COLORS_FILE, CONFIG_ENV_VAR, CONFIG_FILE, XDG_DEFAULTS, XDG_ENV_VARS,
+};
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -47,11 +49,407 @@ pub enum Action {
     JumpToClosing,
     JumpToEmptyLineBelow,
     JumpToEmptyLineAbove,
+    /// replaces the body with a json string holding it
+    EscapeJson,
+    /// replaces a body holding a json string with the contents of the string
+    UnescapeJson,
+    /// sorts the keys of every object of a json body
+    SortJsonKeys,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub editor_keys: Keys,
+    /// maximum amount of requests that can be in flight at the same time,
+    /// requests sent after the limit is reached are queued
+    #[serde(default = "default_max_concurrent_requests")]
+    pub max_concurrent_requests: usize,
+    /// what to do when the file of the collection being edited is removed
+    /// while hac is running
+    #[serde(default)]
+    pub on_missing_collection: MissingCollectionBehavior,
+    /// request bodies bigger than this amount of bytes require confirmation
+    /// before being sent, `0` disables the warning
+    #[serde(default)]
+    pub max_request_body_bytes: u64,
+    /// method selected by default when creating new requests, eg: "POST"
+    #[serde(default = "default_method")]
+    pub default_method: String,
+    /// when inserting a new line on the editor, keep the indentation of the
+    /// current line, adding a level after an opening brace or bracket
+    #[serde(default = "default_auto_indent")]
+    pub auto_indent: bool,
+    /// wraps long uris over a few lines instead of scrolling them horizontally
+    #[serde(default)]
+    pub wrap_uri: bool,
+    /// which screen is shown when hac starts
+    #[serde(default)]
+    pub startup: Startup,
+    /// how requests created without a name are named
+    #[serde(default)]
+    pub request_naming: RequestNaming,
+    /// what to do when an imported collection has the same name as an
+    /// existing one, the default answer when asking on a terminal
+    #[serde(default)]
+    pub on_import_conflict: ImportConflict,
+    /// amount of spaces used to indent pretty printed responses, independent
+    /// of the indentation of the request editor
+    #[serde(default = "default_response_indent")]
+    pub response_indent: usize,
+    /// renders on the current screen instead of the alternate screen, the same
+    /// as running with `--inline`
+    #[serde(default)]
+    pub inline: bool,
+    /// how many levels of variables referencing other variables are resolved
+    /// before giving up, guards against variables nested too deep
+    #[serde(default = "default_max_variable_depth")]
+    pub max_variable_depth: usize,
+    /// minimum amount of lines and columns kept visible around the cursor of
+    /// the body editor when scrolling, like vim's `scrolloff`
+    #[serde(default = "default_scrolloff")]
+    pub scrolloff: usize,
+    /// how long, in milliseconds, short messages like "copied to clipboard"
+    /// stay on the hint bar before being dismissed
+    #[serde(default = "default_toast_duration_ms")]
+    pub toast_duration_ms: u64,
+    /// maximum width of the response content, the content is centered on
+    /// the pane when it is wider than this. `0` means no limit
+    #[serde(default)]
+    pub response_max_width: u16,
+    /// what pressing `Enter` on the sidebar does when the hovered request is
+    /// already the selected one
+    #[serde(default)]
+    pub enter_on_selected: EnterOnSelected,
+    /// tab the response viewer shows when it opens and when a new response
+    /// arrives
+    #[serde(default)]
+    pub default_response_tab: ResponseTab,
+    /// pretty prints valid json bodies when a request is opened on the editor,
+    /// the stored body is kept as is until the body is edited
+    #[serde(default)]
+    pub prettify_on_open: bool,
+    /// amount of spaces on each side of the titles of tab bars
+    #[serde(default = "default_tab_padding")]
+    pub tab_padding: usize,
+    /// whether redirect responses are followed automatically, when disabled
+    /// the redirect is shown and can be followed one hop at a time
+    #[serde(default = "default_follow_redirects")]
+    pub follow_redirects: bool,
+    /// what `C-c` does, either quitting from anywhere or going back one level
+    /// like `Esc` does
+    #[serde(default)]
+    pub ctrl_c: CtrlCBehavior,
+    /// whether the list of recently sent requests is kept between runs, when
+    /// disabled the list only holds requests sent on the current session
+    #[serde(default = "default_persist_recent_requests")]
+    pub persist_recent_requests: bool,
+    /// what to do when a request is created or renamed with the name of
+    /// another item on the same folder
+    #[serde(default)]
+    pub on_duplicate_name: DuplicateName,
+    /// header values longer than this are truncated on the headers tab until
+    /// expanded, 0 never truncates
+    #[serde(default = "default_header_value_max_length")]
+    pub header_value_max_length: usize,
+    /// file every sent request and its response are appended to, one json
+    /// entry per line. Nothing is logged when unset
+    #[serde(default)]
+    pub request_log: Option<PathBuf>,
+    /// whether the values of headers and query parameters that usually hold
+    /// credentials, like `Authorization`, are hidden on the request log
+    #[serde(default = "default_request_log_redact")]
+    pub request_log_redact: bool,
+    /// what happens when hac starts without any collection, like on the first
+    /// run
+    #[serde(default)]
+    pub on_empty_collections: EmptyCollections,
+    /// amount of collections fully read when hac starts, the others are
+    /// listed by their file name and only read when opened. Every collection
+    /// is read when unset
+    #[serde(default)]
+    pub max_collections_loaded: Option<usize>,
+    /// what the status bar at the bottom of the collection viewer shows, in
+    /// order. An empty list hides the status bar
+    #[serde(default = "default_status_bar")]
+    pub status_bar: Vec<StatusBarItem>,
+    /// what requests are labeled with on the sidebar
+    #[serde(default)]
+    pub sidebar_label: SidebarLabel,
+    /// url of the proxy requests go through, collections and requests can
+    /// set their own. `none` ignores the proxy of the environment
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// accepts invalid TLS certificates, like self signed ones
+    #[serde(default)]
+    pub accept_invalid_certs: bool,
+    /// pem file with a certificate trusted on top of the ones of the system
+    #[serde(default)]
+    pub ca_certificate: Option<PathBuf>,
+    /// whether the directories holding a request are expanded when it gets
+    /// selected from somewhere other than the sidebar, like the recent
+    /// requests list or the hover restored from the last session
+    #[serde(default = "default_expand_selected_request")]
+    pub expand_selected_request: bool,
+    /// what to do with unsaved edits of the request body when selecting
+    /// another request
+    #[serde(default)]
+    pub on_unsaved_edits: UnsavedEditsBehavior,
+    /// what requests advertise on `Accept-Encoding`, collections and requests
+    /// can set their own. When unset, the header is only sent if a request
+    /// sets it itself
+    #[serde(default)]
+    pub compression: Option<Compression>,
+    /// whether opening a collection restores the view it was left with, like
+    /// the hovered request, the active environment and the sidebar scroll
+    #[serde(default)]
+    pub restore_session: bool,
+    /// how often, in seconds, the view of the open collection is saved, so it
+    /// survives a crash. 0 only saves it when quitting or leaving the
+    /// collection
+    #[serde(default = "default_session_autosave_secs")]
+    pub session_autosave_secs: u64,
+}
+
+/// ways of resolving an imported collection that clashes with an existing one
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportConflict {
+    /// replace the existing collection with the imported one
+    Overwrite,
+    /// combine directories and requests with the same name, keeping the ones
+    /// that only exist on the existing collection
+    Merge,
+    /// keep both, adding a numeric suffix to the name of the imported one
+    #[default]
+    Suffix,
+}
+
+/// what to do when there are no collections when hac starts
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EmptyCollections {
+    /// ask whether a starter collection should be created
+    #[default]
+    Prompt,
+    /// create the starter collection without asking
+    Create,
+    /// show the empty list
+    Ignore,
+}
+
+/// ways of handling a request named like another item of its folder
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateName {
+    /// keep the name, adding a numeric suffix to it
+    #[default]
+    Suffix,
+    /// refuse the name, asking for a different one
+    Warn,
+}
+
+/// naming schemes for requests created without a name
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RequestNaming {
+    /// "New GET 3", counting the requests of the collection with the same
+    /// method
+    #[default]
+    Method,
+    /// "Unnamed Request 1718000000000", using the creation time in millis
+    Timestamp,
+}
+
+/// screens hac can start on
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Startup {
+    /// the list of collections
+    #[default]
+    List,
+    /// the last collection that was opened, falling back to the list when
+    /// there is none
+    Last,
+}
+
+/// possible behaviors when a collection file disappears from the file system
+/// while the collection is open
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MissingCollectionBehavior {
+    /// prompt the user to either recreate the file or discard it
+    #[default]
+    Ask,
+    /// silently recreate the file from the in-memory collection
+    Recreate,
+    /// stop syncing the collection, leaving the file deleted
+    Discard,
+}
+
+/// possible behaviors when another request is selected while the body of the
+/// current one has edits that were not saved
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UnsavedEditsBehavior {
+    /// prompt the user to either save or discard the edits
+    #[default]
+    Ask,
+    /// save the collection with the edits before leaving the request
+    Save,
+    /// restore the body as it was before the edits
+    Discard,
+}
+
+/// encodings a request asks the server to respond with
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    /// advertises gzip, deflate and brotli, responses compressed with them are
+    /// decoded before being shown
+    Compressed,
+    /// sends `Accept-Encoding: identity`, asking for an uncompressed response
+    Identity,
+}
+
+impl Compression {
+    /// value of the `Accept-Encoding` header sent with this compression
+    pub fn accept_encoding(&self) -> &'static str {
+        match self {
+            Compression::Compressed => "gzip, deflate, br",
+            Compression::Identity => "identity",
+        }
+    }
+}
+
+/// possible behaviors of `Enter` on the sidebar over the request that is
+/// already selected
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EnterOnSelected {
+    /// selects the request again, same as on any other request
+    #[default]
+    Reselect,
+    /// sends the request
+    Send,
+    /// moves the focus to the request editor
+    Edit,
+}
+
+/// what pressing `C-c` does
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CtrlCBehavior {
+    /// quits the application from anywhere
+    #[default]
+    Quit,
+    /// goes back one level like `Esc`, closing the current popup or pane and
+    /// leaving to the collection list, only quitting from the collection list
+    Back,
+}
+
+/// tabs of the response viewer that can be opened by default
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseTab {
+    #[default]
+    Pretty,
+    Raw,
+    Headers,
+    Cookies,
+}
+
+/// how requests are labeled on the sidebar
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SidebarLabel {
+    /// method followed by the name of the request
+    #[default]
+    Name,
+    /// path of the uri of the request
+    Path,
+    /// method followed by the path of the uri of the request
+    MethodPath,
+}
+
+/// pieces of information the status bar can show
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusBarItem {
+    /// name of the collection being viewed
+    Collection,
+    /// environment whose variables are being used
+    Environment,
+    /// whether requests are only sent, without saving anything
+    #[serde(rename = "dry-run")]
+    DryRun,
+}
+
+fn default_status_bar() -> Vec<StatusBarItem> {
+    vec![
+        StatusBarItem::Collection,
+        StatusBarItem::Environment,
+        StatusBarItem::DryRun,
+    ]
+}
+
+fn default_follow_redirects() -> bool {
+    true
+}
+
+fn default_expand_selected_request() -> bool {
+    true
+}
+
+fn default_persist_recent_requests() -> bool {
+    true
+}
+
+fn default_session_autosave_secs() -> u64 {
+    30
+}
+
+fn default_header_value_max_length() -> usize {
+    120
+}
+
+fn default_request_log_redact() -> bool {
+    true
+}
+
+fn default_tab_padding() -> usize {
+    1
+}
+
+fn default_toast_duration_ms() -> u64 {
+    2000
+}
+
+fn default_scrolloff() -> usize {
+    3
+}
+
+fn default_max_variable_depth() -> usize {
+    10
+}
+
+fn default_response_indent() -> usize {
+    2
+}
+
+/// amount of requests allowed to be in flight at the same time when no limit
+/// is configured
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 6;
+
+fn default_max_concurrent_requests() -> usize {
+    DEFAULT_MAX_CONCURRENT_REQUESTS
+}
+
+fn default_method() -> String {
+    String::from("GET")
+}
+
+fn default_auto_indent() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -124,6 +522,11 @@ pub fn get_config_dir_path() -> Option<PathBuf> {
     None
 }
 
+/// the colors file lives on the same directory as the configuration file
+pub fn get_colors_path() -> Option<PathBuf> {
+    get_config_dir_path().map(|path| path.with_file_name(COLORS_FILE))
+}
+
 fn load_default_config() -> Config {
     toml::from_str::<Config>(DEFAULT_CONFIG).expect("failed to parse default config string")
 }
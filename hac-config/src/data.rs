@@ -1,6 +1,12 @@
-use crate::{APP_NAME, COLLECTIONS_DIR, XDG_DEFAULTS, XDG_ENV_VARS};
+use crate::{
+    APP_NAME, COLLECTIONS_DIR, LAST_COLLECTION_FILE, RECENT_REQUESTS_FILE, SESSION_FILE,
+    XDG_DEFAULTS, XDG_ENV_VARS,
+};
 
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
 
 pub fn get_data_dir() -> PathBuf {
     let data_dir = std::env::var(XDG_ENV_VARS[1])
@@ -56,6 +62,110 @@ pub fn get_or_create_collections_dir() -> PathBuf {
     collections_dir
 }
 
+/// path of the last collection the user opened, if any
+pub fn load_last_collection() -> Option<PathBuf> {
+    std::fs::read_to_string(get_data_dir().join(LAST_COLLECTION_FILE))
+        .ok()
+        .map(|path| PathBuf::from(path.trim()))
+        .filter(|path| !path.as_os_str().is_empty())
+}
+
+/// remembers the path of the collection being opened, so we can go back to it
+/// on the next run
+pub fn save_last_collection(collection_path: &Path) {
+    let path = get_data_dir().join(LAST_COLLECTION_FILE);
+    if let Err(e) = std::fs::write(&path, collection_path.to_string_lossy().as_bytes()) {
+        tracing::error!("failed to save the last collection to {path:?}: {e}");
+    }
+}
+
+/// what was on view the last time a collection was open, saved periodically
+/// so it is not lost when the app doesn't exit cleanly. The hovered request is
+/// always kept, the rest only when `restore_session` is enabled
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hovered_request: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_environment: Option<String>,
+    /// first line shown on the sidebar
+    #[serde(default)]
+    pub sidebar_scroll: usize,
+}
+
+/// view state of every collection, keyed by the collection path
+fn load_sessions() -> BTreeMap<String, SessionState> {
+    std::fs::read_to_string(get_data_dir().join(SESSION_FILE))
+        .ok()
+        .and_then(|content| toml::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// view state of the collection at `collection_path` as it was last saved,
+/// if it was ever saved
+pub fn load_session(collection_path: &Path) -> Option<SessionState> {
+    load_sessions().remove(collection_path.to_string_lossy().as_ref())
+}
+
+/// saves the view state of the collection at `collection_path`, keeping the
+/// ones of other collections
+pub fn save_session(collection_path: &Path, session: &SessionState) {
+    let mut sessions = load_sessions();
+    sessions.insert(
+        collection_path.to_string_lossy().to_string(),
+        session.clone(),
+    );
+
+    let path = get_data_dir().join(SESSION_FILE);
+    let result = toml::to_string(&sessions)
+        .map_err(anyhow::Error::from)
+        .and_then(|content| std::fs::write(&path, content).map_err(anyhow::Error::from));
+    if let Err(e) = result {
+        tracing::error!("failed to save the session to {path:?}: {e}");
+    }
+}
+
+/// a request that was sent recently, it references the request by the path
+/// of its collection and its id, names are only kept for displaying
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentRequest {
+    pub collection_path: PathBuf,
+    pub collection_name: String,
+    pub request_id: String,
+    pub request_name: String,
+    pub method: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RecentRequests {
+    #[serde(default)]
+    requests: Vec<RecentRequest>,
+}
+
+/// requests sent on previous runs, most recent first
+pub fn load_recent_requests() -> Vec<RecentRequest> {
+    std::fs::read_to_string(get_data_dir().join(RECENT_REQUESTS_FILE))
+        .ok()
+        .and_then(|content| toml::from_str::<RecentRequests>(&content).ok())
+        .map(|recent| recent.requests)
+        .unwrap_or_default()
+}
+
+/// remembers the recently sent requests, so they are still listed on the
+/// next run
+pub fn save_recent_requests(requests: &[RecentRequest]) {
+    let path = get_data_dir().join(RECENT_REQUESTS_FILE);
+    let recent = RecentRequests {
+        requests: requests.to_vec(),
+    };
+    let result = toml::to_string(&recent)
+        .map_err(anyhow::Error::from)
+        .and_then(|content| std::fs::write(&path, content).map_err(anyhow::Error::from));
+    if let Err(e) = result {
+        tracing::error!("failed to save the recent requests to {path:?}: {e}");
+    }
+}
+
 pub fn log_file() -> (PathBuf, String) {
     (get_data_dir(), format!("{}.log", APP_NAME))
 }
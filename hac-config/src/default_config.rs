@@ -1,4 +1,115 @@
 pub static DEFAULT_CONFIG: &str = r##"
+# what to do when a collection file is removed while it is open, can be one of:
+# "ask", "recreate" or "discard"
+on_missing_collection = "ask"
+# ask for confirmation before sending request bodies bigger than this amount of
+# bytes, useful for APIs with low payload limits. 0 disables the warning
+max_request_body_bytes = 0
+# method selected when creating a new request, can be one of:
+# "GET", "POST", "PUT", "PATCH" or "DELETE"
+default_method = "GET"
+# keep the indentation of the current line when inserting new lines on the
+# editor, adding a level after an opening brace or bracket
+auto_indent = true
+# long uris scroll horizontally on the uri pane, set this to wrap them over a
+# few lines instead
+wrap_uri = false
+# screen shown when hac starts, can be one of:
+# "list" for the collection list or "last" for the last opened collection
+startup = "list"
+# name given to requests created without one, can be one of:
+# "method" for names like "New GET 3" or "timestamp" for "Unnamed Request <millis>"
+request_naming = "method"
+# what to do when importing a collection named like an existing one, can be one of:
+# "suffix" to keep both, "merge" to combine them by name or "overwrite". Imports run
+# on a terminal ask first, using this as the default answer, unless --on-conflict is given
+on_import_conflict = "suffix"
+# amount of spaces used to indent pretty printed responses
+response_indent = 2
+# render on the current screen instead of taking over the whole terminal, keeping
+# the scrollback of the session. The same as running with --inline
+inline = false
+# how many levels of variables referencing other variables are resolved, circular
+# references are always reported as errors
+max_variable_depth = 10
+# minimum amount of lines and columns to keep around the cursor when scrolling
+# the body editor, 0 lets the cursor reach the edges of the pane
+scrolloff = 3
+# how long, in milliseconds, short messages like "copied to clipboard" are shown
+# on the hint bar before being dismissed
+toast_duration_ms = 2000
+# maximum width, in columns, of the response content, which is centered on
+# wider panes. 0 uses the whole pane
+response_max_width = 0
+# what Enter does on the sidebar when the hovered request is already selected,
+# can be one of: "reselect", "send" or "edit"
+enter_on_selected = "reselect"
+# tab shown on the response viewer when a response arrives, can be one of:
+# "pretty", "raw", "headers" or "cookies"
+default_response_tab = "pretty"
+# pretty print json bodies when opening a request on the editor. The body is
+# only saved pretty printed after being edited
+prettify_on_open = false
+# amount of spaces on each side of the titles of the tab bars
+tab_padding = 1
+# whether redirects are followed automatically, when disabled the redirect
+# response is shown and can be followed manually from the response viewer
+follow_redirects = true
+# what C-c does, "quit" quits from anywhere, while "back" goes back one level
+# like Esc, closing popups and panes, and only quits from the collection list
+ctrl_c = "quit"
+# whether the list of recently sent requests is kept between runs
+persist_recent_requests = true
+# what to do when a request is named like another item of its folder, can be one of:
+# "suffix" to add a numeric suffix to the name or "warn" to ask for another name
+on_duplicate_name = "suffix"
+# header values longer than this are truncated on the headers tab, press x to
+# expand the header at the top of the view, 0 never truncates
+header_value_max_length = 120
+# append every sent request and its response to this file, one json entry per
+# line with the time, method, url, status and duration of the request
+# request_log = "/path/to/requests.log"
+# hide the values of headers and query parameters holding credentials, like
+# authorization or cookies, on the request log
+request_log_redact = true
+# what happens when hac starts without any collection, can be one of: "prompt" to
+# offer a starter collection with an example request, "create" to create it right
+# away or "ignore" to show the empty list
+on_empty_collections = "prompt"
+# amount of collections read when hac starts, the remaining ones are listed by
+# their file name and read when opened. Every collection is read when unset
+# max_collections_loaded = 50
+# what the status bar of the collection viewer shows, in order, can be any of:
+# "collection", "environment" or "dry-run". An empty list hides it
+status_bar = ["collection", "environment", "dry-run"]
+# what requests are labeled with on the sidebar, can be one of: "name",
+# "path" for the path of their uri, or "method_path" for the method and the path
+sidebar_label = "name"
+# proxy every request goes through, unless its collection or the request itself
+# sets another one. When unset, the proxy of HTTP_PROXY or HTTPS_PROXY is used,
+# and "none" sends requests directly
+# proxy = "http://localhost:8080"
+# accept invalid TLS certificates, like self signed ones
+accept_invalid_certs = false
+# pem file with a certificate to trust on top of the ones of the system
+# ca_certificate = "/path/to/ca.pem"
+# expand the directories holding a request when it is selected from outside of
+# the sidebar, like the recent requests list or the hover of the last session
+expand_selected_request = true
+# what to do with unsaved edits of a request body when selecting another
+# request, can be one of: "ask", "save" or "discard"
+on_unsaved_edits = "ask"
+# what requests send on Accept-Encoding, unless their collection or the request
+# itself sets it. Can be one of: "compressed" to advertise gzip, deflate and br,
+# or "identity" to ask for uncompressed responses
+# compression = "identity"
+# restore the view a collection was left with when opening it again, like the
+# hovered request, the active environment and the sidebar scroll
+restore_session = false
+# how often, in seconds, the view of the open collection is saved, so it isn't
+# lost on a crash. 0 only saves it when quitting or leaving the collection
+session_autosave_secs = 30
+
 [editor_keys.normal]
 "u" = "Undo"
 "n" = "FindNext"
@@ -14,7 +125,7 @@ pub static DEFAULT_CONFIG: &str = r##"
 "l" = "MoveRight"
 "Right" = "MoveRight"
 "S-G" = "MoveToBottom"
-"g" = { "g" = "MoveToTop" }
+"g" = { "g" = "MoveToTop", "s" = "EscapeJson", "p" = "UnescapeJson", "k" = "SortJsonKeys" }
 "$" = "MoveToLineEnd"
 "End" = "MoveToLineEnd"
 "Home" = "MoveToLineStart"
@@ -3,10 +3,15 @@ pub mod data;
 mod default_config;
 
 pub use config::{
-    default_as_str, get_config_dir_path, get_usual_path, load_config, Action, Config, KeyAction,
+    default_as_str, get_colors_path, get_config_dir_path, get_usual_path, load_config, Action,
+    Compression, Config, CtrlCBehavior, DuplicateName, EmptyCollections, EnterOnSelected,
+    ImportConflict, KeyAction, MissingCollectionBehavior, RequestNaming, ResponseTab, SidebarLabel,
+    Startup, StatusBarItem, UnsavedEditsBehavior, DEFAULT_MAX_CONCURRENT_REQUESTS,
 };
 pub use data::{
-    get_collections_dir, get_or_create_collections_dir, get_or_create_data_dir, log_file,
+    get_collections_dir, get_or_create_collections_dir, get_or_create_data_dir,
+    load_last_collection, load_recent_requests, load_session, log_file, save_last_collection,
+    save_recent_requests, save_session, RecentRequest, SessionState,
 };
 use serde::{Deserialize, Serialize};
 
@@ -18,7 +23,11 @@ pub enum EditorMode {
 
 pub static APP_NAME: &str = "hac";
 pub static COLLECTIONS_DIR: &str = "collections";
+pub static LAST_COLLECTION_FILE: &str = "last_collection";
+pub static RECENT_REQUESTS_FILE: &str = "recent_requests.toml";
+pub static SESSION_FILE: &str = "session.toml";
 pub static CONFIG_FILE: &str = "hac.toml";
+pub static COLORS_FILE: &str = "colors.toml";
 pub static THEMES_DIR: &str = "themes";
 pub static CONFIG_ENV_VAR: &str = "HAC_CONFIG";
 
@@ -3,3 +3,6 @@ pub mod collection;
 pub mod types;
 pub use types::Collection;
 mod errors;
+pub mod har;
+pub mod import;
+pub mod openapi;
@@ -1,35 +1,141 @@
-use crate::collection::types::{Collection, Info};
+use crate::collection::errors::CollectionError;
+use crate::collection::types::{Collection, HeaderMap, Info, Request, RequestKind, RequestMethod};
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
 use std::time::{self, UNIX_EPOCH};
 
-#[tracing::instrument(err)]
-pub fn get_collections_from_config() -> anyhow::Result<Vec<Collection>> {
+/// loads every collection on the collections directory, collections that fail
+/// to parse are skipped and returned as errors so the others can still be used
+#[tracing::instrument]
+pub fn get_collections_from_config() -> anyhow::Result<(Vec<Collection>, Vec<CollectionError>)> {
     let collections_dir = hac_config::get_or_create_collections_dir();
-    get_collections(collections_dir)
+    load_collections(collections_dir)
 }
 
+/// same as `get_collections_from_config`, but only the first `max` collections
+/// are read, see `load_collections_capped`
+#[tracing::instrument]
+pub fn get_capped_collections_from_config(
+    max: Option<usize>,
+) -> anyhow::Result<(Vec<Collection>, Vec<CollectionError>)> {
+    let collections_dir = hac_config::get_or_create_collections_dir();
+    load_collections_capped(collections_dir, max)
+}
+
+/// loads every collection on `collections_dir`, failing on the first one that
+/// can't be parsed
 #[tracing::instrument(skip(collections_dir), err)]
 pub fn get_collections<P>(collections_dir: P) -> anyhow::Result<Vec<Collection>>
 where
     P: AsRef<Path>,
 {
-    let items = std::fs::read_dir(&collections_dir)?;
+    let (collections, errors) = load_collections(collections_dir)?;
+
+    if let Some(err) = errors.into_iter().next() {
+        anyhow::bail!(err.to_string());
+    }
+
+    Ok(collections)
+}
+
+/// loads every collection on `collections_dir`, returning the ones that failed
+/// to parse as errors holding the position of the failure
+#[tracing::instrument(skip(collections_dir), err)]
+pub fn load_collections<P>(
+    collections_dir: P,
+) -> anyhow::Result<(Vec<Collection>, Vec<CollectionError>)>
+where
+    P: AsRef<Path>,
+{
+    load_collections_capped(collections_dir, None)
+}
 
+/// loads the collections on `collections_dir`, only reading the first `max`
+/// files ordered by file name. The remaining ones are listed as collections
+/// without requests named after their file, which are read when opened.
+///
+/// collections that were read are ordered by name ignoring case, the ones
+/// that weren't follow them in file name order
+#[tracing::instrument(skip(collections_dir), err)]
+pub fn load_collections_capped<P>(
+    collections_dir: P,
+    max: Option<usize>,
+) -> anyhow::Result<(Vec<Collection>, Vec<CollectionError>)>
+where
+    P: AsRef<Path>,
+{
+    let mut paths = std::fs::read_dir(&collections_dir)?
+        .flatten()
+        .map(|item| collections_dir.as_ref().join(item.file_name()))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let max = max.unwrap_or(paths.len());
     let mut collections = vec![];
+    let mut unloaded = vec![];
+    let mut errors = vec![];
+
+    for (idx, collection_name) in paths.into_iter().enumerate() {
+        if idx >= max {
+            unloaded.push(unloaded_collection(collection_name));
+            continue;
+        }
 
-    for item in items.into_iter().flatten() {
-        let file_name = item.file_name();
-        let collection_name = collections_dir.as_ref().join(file_name);
         let file = std::fs::read_to_string(&collection_name)?;
-        let mut collection: Collection = serde_json::from_str(&file)?;
-        collection.path = collection_name;
-        collections.push(collection);
+        match serde_json::from_str::<Collection>(&file) {
+            Ok(mut collection) => {
+                collection.path = collection_name;
+                collections.push(collection);
+            }
+            Err(e) => {
+                let err = CollectionError::from_serde(collection_name, e);
+                tracing::error!("{err}");
+                errors.push(err);
+            }
+        }
     }
 
-    collections.sort_by(|a, b| a.info.name.cmp(&b.info.name));
+    // the names of collections that weren't read are unknown, so they are kept
+    // in file name order after the ones that were
+    collections.sort_by_key(|collection| collection.info.name.to_lowercase());
+    collections.extend(unloaded);
 
-    Ok(collections)
+    Ok((collections, errors))
+}
+
+/// placeholder for a collection that wasn't read yet, its real name is only
+/// known once read, so it is shown by its file name, eg: `my_api.json`
+fn unloaded_collection(path: PathBuf) -> Collection {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    Collection {
+        info: Info {
+            name,
+            description: None,
+        },
+        path,
+        ..Default::default()
+    }
+}
+
+/// reads and parses the collection stored at `path`, parsing happens on a
+/// blocking thread as big collections can take a while
+pub async fn load_collection(path: PathBuf) -> anyhow::Result<Collection> {
+    let content = tokio::fs::read_to_string(&path).await?;
+    tokio::task::spawn_blocking(move || match serde_json::from_str::<Collection>(&content) {
+        Ok(mut collection) => {
+            collection.path = path;
+            Ok(collection)
+        }
+        Err(e) => Err(anyhow::anyhow!(
+            CollectionError::from_serde(path, e).to_string()
+        )),
+    })
+    .await?
 }
 
 pub fn create_from_form(name: String, description: String) -> Collection {
@@ -52,8 +158,173 @@ pub fn create_from_form(name: String, description: String) -> Collection {
             name,
             description: Some(description),
         },
-        requests: None,
         path: format!("{}.json", collection_name.to_string_lossy()).into(),
+        ..Default::default()
+    }
+}
+
+/// builds the collection offered to users without any collection, it holds
+/// a single example request so the usual flow can be tried right away
+pub fn starter_collection() -> Collection {
+    let request = Request {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: String::from("Example request"),
+        uri: String::from("https://httpbin.org/get"),
+        headers: Some(vec![HeaderMap {
+            pair: (String::from("Accept"), String::from("application/json")),
+            enabled: true,
+        }]),
+        ..Default::default()
+    };
+
+    let mut collection = create_from_form(
+        String::from("Getting started"),
+        String::from("an example collection, feel free to delete it"),
+    );
+    collection.requests = Some(Arc::new(RwLock::new(vec![RequestKind::Single(Arc::new(
+        RwLock::new(request),
+    ))])));
+    collection
+}
+
+/// builds the in-memory collection used by scratch mode, it holds a single
+/// empty request and has no path, so it is never written to disk
+pub fn scratch_collection() -> Collection {
+    let request = Request {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: String::from("scratch"),
+        ..Default::default()
+    };
+
+    Collection {
+        info: Info {
+            name: String::from("scratch"),
+            description: Some(String::from("requests here are never saved")),
+        },
+        requests: Some(Arc::new(RwLock::new(vec![RequestKind::Single(Arc::new(
+            RwLock::new(request),
+        ))]))),
+        ..Default::default()
+    }
+}
+
+/// generates a name for a request created without one, following the naming
+/// `scheme`. Names never collide with the ones already on `requests`
+pub fn auto_request_name(
+    scheme: hac_config::RequestNaming,
+    method: &RequestMethod,
+    requests: &[RequestKind],
+) -> String {
+    if let hac_config::RequestNaming::Timestamp = scheme {
+        let now = time::SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis();
+        return format!("Unnamed Request {}", now);
+    }
+
+    let mut names = vec![];
+    collect_names(requests, &mut names);
+    let prefix = format!("New {method} ");
+    let mut counter = names
+        .iter()
+        .filter(|name| name.starts_with(&prefix))
+        .count()
+        + 1;
+    while names.contains(&format!("{prefix}{counter}")) {
+        counter += 1;
+    }
+
+    format!("{prefix}{counter}")
+}
+
+/// finds the first request called `name`, looking inside directories as well
+pub fn find_request_by_name(requests: &[RequestKind], name: &str) -> Option<Arc<RwLock<Request>>> {
+    requests.iter().find_map(|item| match item {
+        RequestKind::Single(request) if request.read().unwrap().name.eq(name) => {
+            Some(request.clone())
+        }
+        RequestKind::Single(_) => None,
+        RequestKind::Nested(dir) => find_request_by_name(&dir.requests.read().unwrap(), name),
+    })
+}
+
+/// whether an item of the folder `parent`, or of the root of the collection
+/// when it has no parent, is already called `name`. The item with `id` is
+/// the one being renamed, so it never clashes with itself
+pub fn is_name_taken(
+    requests: &[RequestKind],
+    parent: Option<&str>,
+    name: &str,
+    id: Option<&str>,
+) -> bool {
+    let taken = |siblings: &[RequestKind]| {
+        siblings
+            .iter()
+            .any(|item| item.get_name().eq(name) && id.is_none_or(|id| item.get_id().ne(id)))
+    };
+
+    match parent {
+        None => taken(requests),
+        Some(parent) => requests.iter().any(|item| match item {
+            RequestKind::Nested(dir) if dir.id.eq(parent) => taken(&dir.requests.read().unwrap()),
+            _ => false,
+        }),
+    }
+}
+
+/// `name` followed by the first numeric suffix that isn't taken on the folder
+/// `parent`, eg: "get user (2)"
+pub fn unique_request_name(
+    requests: &[RequestKind],
+    parent: Option<&str>,
+    name: &str,
+    id: Option<&str>,
+) -> String {
+    let mut suffix = 2;
+    loop {
+        let candidate = format!("{name} ({suffix})");
+        if !is_name_taken(requests, parent, &candidate, id) {
+            return candidate;
+        }
+        suffix += 1;
+    }
+}
+
+/// every request of the tree, including the ones inside directories, in the
+/// order they appear on the sidebar
+pub fn flatten_requests(requests: &[RequestKind]) -> Vec<Arc<RwLock<Request>>> {
+    requests
+        .iter()
+        .flat_map(|item| match item {
+            RequestKind::Single(request) => vec![request.clone()],
+            RequestKind::Nested(dir) => flatten_requests(&dir.requests.read().unwrap()),
+        })
+        .collect()
+}
+
+/// sorts `requests` by the position of their ids on `run_order`, requests
+/// that are not on it keep their relative order and run after the others
+pub fn apply_run_order(
+    mut requests: Vec<Arc<RwLock<Request>>>,
+    run_order: &[String],
+) -> Vec<Arc<RwLock<Request>>> {
+    requests.sort_by_key(|request| {
+        let id = &request.read().unwrap().id;
+        run_order
+            .iter()
+            .position(|ordered| ordered.eq(id))
+            .unwrap_or(run_order.len())
+    });
+    requests
+}
+
+fn collect_names(requests: &[RequestKind], names: &mut Vec<String>) {
+    for item in requests {
+        names.push(item.get_name());
+        if let RequestKind::Nested(dir) = item {
+            collect_names(&dir.requests.read().unwrap(), names);
+        }
     }
 }
 
@@ -68,4 +339,188 @@ mod tests {
         assert!(collection.info.name.eq("any valid name"));
         assert!(collection.info.description.is_some())
     }
+
+    #[test]
+    fn test_scratch_collection_is_never_backed_by_a_file() {
+        let collection = scratch_collection();
+        let requests = collection.requests.as_ref().unwrap().read().unwrap();
+
+        assert!(collection.path.as_os_str().is_empty());
+        assert_eq!(requests.len(), 1);
+        assert!(matches!(requests[0], RequestKind::Single(_)));
+    }
+
+    #[test]
+    fn test_auto_request_name_skips_taken_names() {
+        let request = |name: &str, method: RequestMethod| {
+            RequestKind::Single(std::sync::Arc::new(std::sync::RwLock::new(
+                crate::collection::types::Request {
+                    id: name.into(),
+                    method,
+                    name: name.into(),
+                    ..Default::default()
+                },
+            )))
+        };
+        let requests = vec![
+            request("New GET 2", RequestMethod::Get),
+            request("New POST 1", RequestMethod::Post),
+        ];
+
+        let scheme = hac_config::RequestNaming::Method;
+        assert_eq!(
+            auto_request_name(scheme, &RequestMethod::Get, &requests),
+            "New GET 3"
+        );
+        assert_eq!(
+            auto_request_name(scheme, &RequestMethod::Post, &requests),
+            "New POST 2"
+        );
+        assert_eq!(
+            auto_request_name(scheme, &RequestMethod::Put, &requests),
+            "New PUT 1"
+        );
+    }
+
+    #[test]
+    fn test_two_requests_with_the_same_name_in_one_folder() {
+        let request = |id: &str, name: &str| {
+            RequestKind::Single(std::sync::Arc::new(std::sync::RwLock::new(
+                crate::collection::types::Request {
+                    id: id.into(),
+                    name: name.into(),
+                    parent: Some(String::from("users")),
+                    ..Default::default()
+                },
+            )))
+        };
+        let dir = crate::collection::types::Directory {
+            id: String::from("users"),
+            name: String::from("users"),
+            requests: Arc::new(RwLock::new(vec![request("1", "get user")])),
+        };
+        let requests = vec![RequestKind::Nested(dir)];
+
+        assert!(is_name_taken(&requests, Some("users"), "get user", None));
+        assert!(!is_name_taken(&requests, None, "get user", None));
+        assert!(!is_name_taken(
+            &requests,
+            Some("users"),
+            "get user",
+            Some("1")
+        ));
+        assert_eq!(
+            unique_request_name(&requests, Some("users"), "get user", None),
+            "get user (2)"
+        );
+
+        if let RequestKind::Nested(dir) = &requests[0] {
+            dir.requests
+                .write()
+                .unwrap()
+                .push(request("2", "get user (2)"));
+        }
+        assert_eq!(
+            unique_request_name(&requests, Some("users"), "get user", None),
+            "get user (3)"
+        );
+    }
+
+    #[test]
+    fn test_run_order_comes_before_tree_order() {
+        let request = |id: &str| {
+            Arc::new(RwLock::new(crate::collection::types::Request {
+                id: id.into(),
+                name: id.into(),
+                ..Default::default()
+            }))
+        };
+        let requests = vec![
+            request("users"),
+            request("orders"),
+            request("login"),
+            request("logout"),
+        ];
+        let run_order = vec![String::from("login"), String::from("orders")];
+
+        let ordered = apply_run_order(requests, &run_order)
+            .iter()
+            .map(|request| request.read().unwrap().id.clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(ordered, vec!["login", "orders", "users", "logout"]);
+    }
+
+    #[test]
+    fn test_parse_errors_hold_position() {
+        let dir = std::env::temp_dir().join("hac_test_parse_errors_hold_position");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("valid.json"), r#"{"info": {"name": "valid"}}"#).unwrap();
+        std::fs::write(
+            dir.join("broken.json"),
+            "{\n  \"info\": {\n    \"name\" \"x\"\n}",
+        )
+        .unwrap();
+
+        let (collections, errors) = load_collections(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(collections.len(), 1);
+        assert_eq!(errors.len(), 1);
+        let message = errors[0].to_string();
+        assert!(message.contains("broken.json"));
+        assert!(message.contains("failed to parse at line 3, column 12: expected `:`"));
+    }
+
+    #[test]
+    fn test_capped_collections_are_listed_without_reading() {
+        let dir = std::env::temp_dir().join("hac_test_capped_collections");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("alpha.json"), r#"{"info": {"name": "alpha"}}"#).unwrap();
+        // never read, so being invalid doesn't produce an error
+        std::fs::write(dir.join("beta_api.json"), "{").unwrap();
+
+        let (collections, errors) = load_collections_capped(&dir, Some(1)).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(errors.is_empty());
+        assert_eq!(collections.len(), 2);
+        assert_eq!(collections[0].info.name, "alpha");
+        assert_eq!(collections[1].info.name, "beta_api.json");
+        assert_eq!(collections[1].path, dir.join("beta_api.json"));
+        assert!(collections[1].requests.is_none());
+    }
+
+    #[test]
+    fn test_collections_are_sorted_ignoring_case() {
+        let dir = std::env::temp_dir().join("hac_test_collections_sorted_ignoring_case");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.json"), r#"{"info": {"name": "Zeta"}}"#).unwrap();
+        std::fs::write(dir.join("b.json"), r#"{"info": {"name": "alpha"}}"#).unwrap();
+        std::fs::write(dir.join("c.json"), r#"{"info": {"name": "Beta"}}"#).unwrap();
+        std::fs::write(dir.join("d.json"), "{").unwrap();
+
+        let (collections, _) = load_collections_capped(&dir, Some(3)).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let names = collections
+            .iter()
+            .map(|collection| collection.info.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["alpha", "Beta", "Zeta", "d.json"]);
+    }
+
+    #[tokio::test]
+    async fn test_load_collection_keeps_its_path() {
+        let dir = std::env::temp_dir().join("hac_test_load_collection_keeps_its_path");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("api.json");
+        std::fs::write(&path, r#"{"info": {"name": "api"}}"#).unwrap();
+
+        let collection = load_collection(path.clone()).await.unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(collection.info.name, "api");
+        assert_eq!(collection.path, path);
+    }
 }
@@ -1,11 +1,51 @@
+use std::path::PathBuf;
+
+#[derive(Debug)]
 pub enum CollectionError {
     Unknown(String),
+    /// the collection file is not valid json or doesn't match the shape of a
+    /// collection, we keep where it happened so users can fix it by hand
+    Parse {
+        path: PathBuf,
+        line: usize,
+        column: usize,
+        cause: String,
+    },
+}
+
+impl CollectionError {
+    pub fn from_serde(path: PathBuf, err: serde_json::Error) -> Self {
+        let (line, column) = (err.line(), err.column());
+        // serde already appends the position to the message, we remove it as
+        // we display the position ourselves
+        let cause = err
+            .to_string()
+            .trim_end_matches(&format!(" at line {line} column {column}"))
+            .to_string();
+
+        CollectionError::Parse {
+            path,
+            line,
+            column,
+            cause,
+        }
+    }
 }
 
 impl std::fmt::Display for CollectionError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             CollectionError::Unknown(msg) => write!(f, "{}", msg),
+            CollectionError::Parse {
+                path,
+                line,
+                column,
+                cause,
+            } => write!(
+                f,
+                "collection {:?} failed to parse at line {}, column {}: {}",
+                path, line, column, cause
+            ),
         }
     }
 }
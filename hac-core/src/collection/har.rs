@@ -0,0 +1,227 @@
+use crate::collection::types::{
+    BodyType, Collection, Directory, HeaderMap, Info, Request, RequestKind, RequestMethod,
+};
+
+use std::sync::{Arc, RwLock};
+
+use serde::Deserialize;
+
+/// headers that are either managed by the http client or only make sense for
+/// the browser session the har was captured on, so they are never imported
+const SKIPPED_HEADERS: [&str; 4] = ["cookie", "content-length", "host", "connection"];
+
+#[derive(Debug, Deserialize)]
+struct Har {
+    log: HarLog,
+}
+
+#[derive(Debug, Deserialize)]
+struct HarLog {
+    entries: Vec<HarEntry>,
+}
+
+/// only the request side of an entry is read, the captured response is not
+/// relevant to reproduce the request
+#[derive(Debug, Deserialize)]
+struct HarEntry {
+    request: HarRequest,
+}
+
+#[derive(Debug, Deserialize)]
+struct HarRequest {
+    method: String,
+    url: String,
+    #[serde(default)]
+    headers: Vec<HarHeader>,
+    #[serde(rename = "postData")]
+    post_data: Option<HarPostData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HarHeader {
+    name: String,
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct HarPostData {
+    #[serde(rename = "mimeType", default)]
+    mime_type: String,
+    text: Option<String>,
+}
+
+/// result of reading a har file, entries using methods hac doesn't support
+/// are skipped and counted
+#[derive(Debug)]
+pub struct HarImport {
+    pub collection: Collection,
+    pub imported: usize,
+    pub skipped: usize,
+}
+
+/// builds a collection called `name` out of the entries of a har file, every
+/// request is placed inside a directory with the same name as the collection.
+///
+/// the returned collection has no path, as where it is written depends on the
+/// collections that already exist
+pub fn collection_from_har(content: &str, name: &str) -> anyhow::Result<HarImport> {
+    let har = serde_json::from_str::<Har>(content)?;
+    let dir_id = uuid::Uuid::new_v4().to_string();
+
+    let total = har.log.entries.len();
+    let requests = har
+        .log
+        .entries
+        .into_iter()
+        .filter_map(|entry| request_from_entry(entry.request, &dir_id))
+        .map(|request| RequestKind::Single(Arc::new(RwLock::new(request))))
+        .collect::<Vec<_>>();
+    let imported = requests.len();
+
+    let directory = RequestKind::Nested(Directory {
+        id: dir_id,
+        name: name.to_string(),
+        requests: Arc::new(RwLock::new(requests)),
+    });
+
+    Ok(HarImport {
+        collection: Collection {
+            info: Info {
+                name: name.to_string(),
+                description: Some(String::from("imported from a har file")),
+            },
+            requests: Some(Arc::new(RwLock::new(vec![directory]))),
+            ..Default::default()
+        },
+        imported,
+        skipped: total - imported,
+    })
+}
+
+fn request_from_entry(entry: HarRequest, parent: &str) -> Option<Request> {
+    let method = entry.method.parse::<RequestMethod>().ok()?;
+    let headers = entry
+        .headers
+        .into_iter()
+        // http/2 pseudo headers like `:authority` start with a colon
+        .filter(|header| !header.name.starts_with(':'))
+        .filter(|header| !SKIPPED_HEADERS.contains(&header.name.to_ascii_lowercase().as_str()))
+        .map(|header| HeaderMap {
+            pair: (header.name, header.value),
+            enabled: true,
+        })
+        .collect::<Vec<_>>();
+    let (body, content_type) = entry
+        .post_data
+        .and_then(|post_data| {
+            let text = post_data.text.filter(|text| !text.is_empty())?;
+            Some((text, post_data.mime_type))
+        })
+        .unzip();
+    // bodies without a mime type are sent as json, like any other request
+    let content_type = content_type.filter(|mime| !mime.is_empty());
+
+    Some(Request {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: format!("{method} {}", url_path(&entry.url)),
+        method,
+        uri: entry.url,
+        headers: (!headers.is_empty()).then_some(headers),
+        parent: Some(parent.to_string()),
+        body_type: body.as_ref().map(|_| {
+            content_type
+                .as_deref()
+                .map_or(BodyType::Json, BodyType::for_content_type)
+        }),
+        body_content_type: content_type,
+        body,
+        ..Default::default()
+    })
+}
+
+/// the path of an url without the query string, used to name the requests
+fn url_path(url: &str) -> &str {
+    let without_scheme = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    let path = without_scheme
+        .find('/')
+        .map(|idx| &without_scheme[idx..])
+        .unwrap_or("/");
+    path.split(['?', '#']).next().unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_imports_requests_from_har() {
+        let har = r#"{
+            "log": {
+                "entries": [
+                    {
+                        "request": {
+                            "method": "POST",
+                            "url": "https://example.com/users?debug=1",
+                            "headers": [
+                                { "name": ":authority", "value": "example.com" },
+                                { "name": "Cookie", "value": "session=secret" },
+                                { "name": "Content-Type", "value": "application/json" }
+                            ],
+                            "postData": { "mimeType": "application/json", "text": "{\"name\":\"john\"}" }
+                        },
+                        "response": { "status": 201 }
+                    },
+                    {
+                        "request": { "method": "OPTIONS", "url": "https://example.com/users", "headers": [] }
+                    },
+                    {
+                        "request": {
+                            "method": "POST",
+                            "url": "https://example.com/login",
+                            "headers": [],
+                            "postData": { "mimeType": "application/x-www-form-urlencoded", "text": "user=john" }
+                        }
+                    }
+                ]
+            }
+        }"#;
+
+        let import = collection_from_har(har, "captured").unwrap();
+        assert_eq!(import.imported, 2);
+        assert_eq!(import.skipped, 1);
+
+        let requests = import.collection.requests.unwrap();
+        let requests = requests.read().unwrap();
+        let RequestKind::Nested(dir) = &requests[0] else {
+            panic!("expected a directory");
+        };
+        let dir_requests = dir.requests.read().unwrap();
+        let RequestKind::Single(request) = &dir_requests[0] else {
+            panic!("expected a request");
+        };
+        let request = request.read().unwrap();
+
+        assert_eq!(request.name, "POST /users");
+        assert_eq!(request.uri, "https://example.com/users?debug=1");
+        assert_eq!(request.parent.as_ref(), Some(&dir.id));
+        assert_eq!(request.body.as_deref(), Some("{\"name\":\"john\"}"));
+        assert_eq!(request.body_type, Some(BodyType::Json));
+        assert_eq!(
+            request.body_content_type.as_deref(),
+            Some("application/json")
+        );
+        let headers = request.headers.as_ref().unwrap();
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers[0].pair.0, "Content-Type");
+
+        let RequestKind::Single(form) = &dir_requests[1] else {
+            panic!("expected a request");
+        };
+        let form = form.read().unwrap();
+        assert_eq!(form.body_type, Some(BodyType::Text));
+        assert_eq!(
+            form.body_content_type.as_deref(),
+            Some("application/x-www-form-urlencoded")
+        );
+    }
+}
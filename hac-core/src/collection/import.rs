@@ -0,0 +1,303 @@
+use crate::collection::types::{Collection, Directory, RequestKind};
+
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+use hac_config::ImportConflict;
+
+/// prepares a collection coming from an importer to be written next to the
+/// `existing` ones, resolving name clashes according to `behavior`.
+///
+/// the returned collection has its path set to where it should be synced,
+/// which is the path of the clashing collection when overwriting or merging.
+/// merging shares the requests of the clashing collection, so it is updated in
+/// place as well
+pub fn resolve_conflict(
+    mut incoming: Collection,
+    existing: &[Collection],
+    behavior: ImportConflict,
+) -> Collection {
+    let Some(clash) = find_clash(&incoming, existing) else {
+        incoming.path = collection_path(&incoming.info.name);
+        return incoming;
+    };
+
+    match behavior {
+        ImportConflict::Overwrite => {
+            incoming.path = clash.path.clone();
+            incoming
+        }
+        ImportConflict::Merge => {
+            let merged = clash.clone();
+            let existing_requests = merged
+                .requests
+                .clone()
+                .unwrap_or_else(|| Arc::new(RwLock::new(vec![])));
+            if let Some(incoming_requests) = incoming.requests.as_ref() {
+                merge_requests(
+                    &mut existing_requests.write().unwrap(),
+                    incoming_requests.read().unwrap().clone(),
+                    None,
+                );
+            }
+            Collection {
+                requests: Some(existing_requests),
+                ..merged
+            }
+        }
+        ImportConflict::Suffix => {
+            let mut suffix = 2;
+            let name = loop {
+                let name = format!("{} ({})", incoming.info.name, suffix);
+                let taken = existing
+                    .iter()
+                    .any(|collection| collection.info.name.eq_ignore_ascii_case(&name));
+                if !taken {
+                    break name;
+                }
+                suffix += 1;
+            };
+            incoming.path = collection_path(&name);
+            incoming.info.name = name;
+            incoming
+        }
+    }
+}
+
+/// the existing collection named like `incoming`, if any. Names are compared
+/// ignoring case as they become file names
+pub fn find_clash<'a>(incoming: &Collection, existing: &'a [Collection]) -> Option<&'a Collection> {
+    existing.iter().find(|collection| {
+        collection
+            .info
+            .name
+            .eq_ignore_ascii_case(&incoming.info.name)
+    })
+}
+
+/// combines `incoming` into `existing` by name, directories with the same name
+/// have their requests merged and requests with the same name are replaced by
+/// the incoming ones
+fn merge_requests(
+    existing: &mut Vec<RequestKind>,
+    incoming: Vec<RequestKind>,
+    parent: Option<String>,
+) {
+    for item in incoming {
+        let clash = existing.iter().position(|current| {
+            current.get_name().eq(&item.get_name()) && current.is_dir() == item.is_dir()
+        });
+
+        match (clash, item) {
+            (Some(idx), RequestKind::Nested(dir)) => {
+                let RequestKind::Nested(current) = &existing[idx] else {
+                    unreachable!("clashes are always of the same kind");
+                };
+                let incoming = dir.requests.read().unwrap().clone();
+                merge_requests(
+                    &mut current.requests.write().unwrap(),
+                    incoming,
+                    Some(current.id.clone()),
+                );
+            }
+            (Some(idx), RequestKind::Single(req)) => {
+                req.write().unwrap().parent.clone_from(&parent);
+                existing[idx] = RequestKind::Single(req);
+            }
+            (None, RequestKind::Single(req)) => {
+                req.write().unwrap().parent.clone_from(&parent);
+                existing.push(RequestKind::Single(req));
+            }
+            (None, dir) => existing.push(dir),
+        }
+    }
+}
+
+/// appends every request and directory of `source` at the root of `target`,
+/// items whose name is already taken on `target` get a numbered suffix.
+///
+/// everything is copied with new ids, so both collections can keep being
+/// edited independently after merging
+pub fn append_collection(target: &mut Vec<RequestKind>, source: &Collection) {
+    let Some(requests) = source.requests.as_ref() else {
+        return;
+    };
+
+    for item in requests.read().unwrap().iter() {
+        let mut item = copy_with_new_ids(item, None);
+        let name = item.get_name();
+        let taken = |name: &str| target.iter().any(|current| current.get_name().eq(name));
+
+        if taken(&name) {
+            let mut suffix = 2;
+            while taken(&format!("{name} ({suffix})")) {
+                suffix += 1;
+            }
+            let name = format!("{name} ({suffix})");
+            match &mut item {
+                RequestKind::Single(req) => req.write().unwrap().name = name,
+                RequestKind::Nested(dir) => dir.name = name,
+            }
+        }
+
+        target.push(item);
+    }
+}
+
+fn copy_with_new_ids(item: &RequestKind, parent: Option<String>) -> RequestKind {
+    match item {
+        RequestKind::Single(req) => {
+            let mut req = req.read().unwrap().clone();
+            req.id = uuid::Uuid::new_v4().to_string();
+            req.parent = parent;
+            RequestKind::Single(Arc::new(RwLock::new(req)))
+        }
+        RequestKind::Nested(dir) => {
+            let id = uuid::Uuid::new_v4().to_string();
+            let requests = dir
+                .requests
+                .read()
+                .unwrap()
+                .iter()
+                .map(|item| copy_with_new_ids(item, Some(id.clone())))
+                .collect();
+            RequestKind::Nested(Directory {
+                id,
+                name: dir.name.clone(),
+                requests: Arc::new(RwLock::new(requests)),
+            })
+        }
+    }
+}
+
+fn collection_path(name: &str) -> PathBuf {
+    let collections_dir = hac_config::get_collections_dir();
+    let name_as_file_name = name.to_lowercase().replace(' ', "_");
+    let collection_name = collections_dir.join(name_as_file_name);
+    format!("{}.json", collection_name.to_string_lossy()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection::types::{Info, Request};
+
+    fn request(name: &str, uri: &str, parent: Option<&str>) -> RequestKind {
+        RequestKind::Single(Arc::new(RwLock::new(Request {
+            id: name.into(),
+            name: name.into(),
+            uri: uri.into(),
+            parent: parent.map(String::from),
+            ..Default::default()
+        })))
+    }
+
+    fn collection(name: &str, requests: Vec<RequestKind>) -> Collection {
+        Collection {
+            info: Info {
+                name: name.into(),
+                description: None,
+            },
+            requests: Some(Arc::new(RwLock::new(requests))),
+            path: format!("{name}.json").into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_suffixes_clashing_names() {
+        let existing = vec![collection("api", vec![]), collection("api (2)", vec![])];
+
+        let resolved =
+            resolve_conflict(collection("Api", vec![]), &existing, ImportConflict::Suffix);
+
+        assert_eq!(resolved.info.name, "Api (3)");
+        assert!(resolved.path.to_string_lossy().ends_with("api_(3).json"));
+    }
+
+    #[test]
+    fn test_merges_directories_and_requests_by_name() {
+        let existing = vec![collection(
+            "api",
+            vec![
+                request("health", "/old", None),
+                RequestKind::Nested(Directory {
+                    id: "users".into(),
+                    name: "users".into(),
+                    requests: Arc::new(RwLock::new(vec![request("list", "/users", Some("users"))])),
+                }),
+            ],
+        )];
+        let incoming = collection(
+            "api",
+            vec![
+                request("health", "/new", None),
+                RequestKind::Nested(Directory {
+                    id: "other_id".into(),
+                    name: "users".into(),
+                    requests: Arc::new(RwLock::new(vec![request(
+                        "create",
+                        "/users",
+                        Some("other_id"),
+                    )])),
+                }),
+            ],
+        );
+
+        let merged = resolve_conflict(incoming, &existing, ImportConflict::Merge);
+        let requests = merged.requests.unwrap();
+        let requests = requests.read().unwrap();
+
+        assert_eq!(merged.path, existing[0].path);
+        assert_eq!(requests.len(), 2);
+        let RequestKind::Single(health) = &requests[0] else {
+            panic!("expected a request");
+        };
+        assert_eq!(health.read().unwrap().uri, "/new");
+        let RequestKind::Nested(users) = &requests[1] else {
+            panic!("expected a directory");
+        };
+        let users = users.requests.read().unwrap();
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[1].get_name(), "create");
+        let RequestKind::Single(create) = &users[1] else {
+            panic!("expected a request");
+        };
+        assert_eq!(create.read().unwrap().parent.as_deref(), Some("users"));
+    }
+
+    #[test]
+    fn test_appends_a_copy_of_the_source_suffixing_clashes() {
+        let mut target = vec![request("health", "/health", None)];
+        let source = collection(
+            "other",
+            vec![
+                request("health", "/other/health", None),
+                RequestKind::Nested(Directory {
+                    id: "users".into(),
+                    name: "users".into(),
+                    requests: Arc::new(RwLock::new(vec![request("list", "/users", Some("users"))])),
+                }),
+            ],
+        );
+
+        append_collection(&mut target, &source);
+
+        assert_eq!(target.len(), 3);
+        assert_eq!(target[1].get_name(), "health (2)");
+        assert_ne!(target[1].get_id(), "health");
+        let RequestKind::Nested(users) = &target[2] else {
+            panic!("expected a directory");
+        };
+        assert_ne!(users.id, "users");
+        let users_requests = users.requests.read().unwrap();
+        let RequestKind::Single(list) = &users_requests[0] else {
+            panic!("expected a request");
+        };
+        assert_eq!(list.read().unwrap().parent.as_ref(), Some(&users.id));
+
+        // the source is left untouched
+        let source_requests = source.requests.as_ref().unwrap().read().unwrap();
+        assert_eq!(source_requests[0].get_name(), "health");
+    }
+}
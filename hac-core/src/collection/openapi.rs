@@ -0,0 +1,296 @@
+use crate::collection::types::{
+    BodyType, Collection, Directory, Info, Request, RequestKind, RequestMethod,
+};
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use serde::Deserialize;
+
+/// the parts of an OpenAPI 3 or Swagger 2 document needed to build requests,
+/// everything else on the spec is ignored
+#[derive(Debug, Deserialize)]
+struct OpenApi {
+    info: Option<OpenApiInfo>,
+    #[serde(default)]
+    servers: Vec<OpenApiServer>,
+    /// swagger 2 describes the server through `host` and `basePath`
+    host: Option<String>,
+    #[serde(rename = "basePath")]
+    base_path: Option<String>,
+    #[serde(default)]
+    schemes: Vec<String>,
+    #[serde(default)]
+    paths: BTreeMap<String, BTreeMap<String, serde_json::Value>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenApiInfo {
+    description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenApiServer {
+    url: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Operation {
+    summary: Option<String>,
+    #[serde(rename = "operationId")]
+    operation_id: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(rename = "requestBody")]
+    request_body: Option<RequestBody>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RequestBody {
+    #[serde(default)]
+    content: BTreeMap<String, MediaType>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MediaType {
+    example: Option<serde_json::Value>,
+}
+
+/// result of reading an OpenAPI spec, operations using methods hac doesn't
+/// support are skipped and counted
+#[derive(Debug)]
+pub struct OpenApiImport {
+    pub collection: Collection,
+    pub imported: usize,
+    pub skipped: usize,
+}
+
+/// builds a collection called `name` out of the operations of an OpenAPI
+/// spec written as json, yaml specs are rejected. Operations are grouped in a directory for their
+/// first tag, and path parameters become `{{variables}}`.
+///
+/// the returned collection has no path, as where it is written depends on the
+/// collections that already exist
+pub fn collection_from_openapi(content: &str, name: &str) -> anyhow::Result<OpenApiImport> {
+    let spec = match serde_json::from_str::<OpenApi>(content) {
+        Ok(spec) => spec,
+        Err(_) if is_yaml(content) => anyhow::bail!(
+            "OpenAPI specs written as yaml are not supported, convert the spec to json first"
+        ),
+        Err(e) => return Err(e.into()),
+    };
+    let base_url = base_url(&spec);
+
+    let mut requests: Vec<RequestKind> = vec![];
+    let mut directories: BTreeMap<String, Directory> = BTreeMap::new();
+    let mut imported = 0;
+    let mut skipped = 0;
+
+    for (path, operations) in spec.paths.iter() {
+        for (method, operation) in operations.iter() {
+            // path items also hold fields shared by every operation, like
+            // `parameters`, which are not methods
+            if !is_http_method(method) {
+                continue;
+            }
+            let Ok(method) = method.to_uppercase().parse::<RequestMethod>() else {
+                skipped += 1;
+                continue;
+            };
+            let operation =
+                serde_json::from_value::<Operation>(operation.clone()).unwrap_or_default();
+
+            let directory = operation.tags.first().map(|tag| {
+                directories.entry(tag.clone()).or_insert_with(|| Directory {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    name: tag.clone(),
+                    requests: Arc::new(RwLock::new(vec![])),
+                })
+            });
+            let parent = directory.as_ref().map(|directory| directory.id.clone());
+            let request = request_from_operation(method, &base_url, path, operation, parent);
+            let request = RequestKind::Single(Arc::new(RwLock::new(request)));
+
+            match directory {
+                Some(directory) => directory.requests.write().unwrap().push(request),
+                None => requests.push(request),
+            }
+            imported += 1;
+        }
+    }
+
+    let mut items = directories
+        .into_values()
+        .map(RequestKind::Nested)
+        .collect::<Vec<_>>();
+    items.extend(requests);
+
+    Ok(OpenApiImport {
+        collection: Collection {
+            info: Info {
+                name: name.to_string(),
+                description: spec
+                    .info
+                    .and_then(|info| info.description)
+                    .or_else(|| Some(String::from("imported from an OpenAPI spec"))),
+            },
+            requests: Some(Arc::new(RwLock::new(items))),
+            ..Default::default()
+        },
+        imported,
+        skipped,
+    })
+}
+
+fn request_from_operation(
+    method: RequestMethod,
+    base_url: &str,
+    path: &str,
+    operation: Operation,
+    parent: Option<String>,
+) -> Request {
+    let name = operation
+        .summary
+        .or(operation.operation_id)
+        .unwrap_or_else(|| format!("{method} {path}"));
+    let body = operation
+        .request_body
+        .and_then(|body| {
+            body.content
+                .into_iter()
+                .find(|(media_type, _)| media_type.contains("json"))
+        })
+        .and_then(|(_, media_type)| media_type.example)
+        .and_then(|example| serde_json::to_string_pretty(&example).ok());
+
+    Request {
+        id: uuid::Uuid::new_v4().to_string(),
+        name,
+        method,
+        uri: format!("{base_url}{}", path_to_variables(path)),
+        parent,
+        body_type: body.as_ref().map(|_| BodyType::Json),
+        body,
+        ..Default::default()
+    }
+}
+
+/// url every path of the spec is relative to, taken from the first server,
+/// or from the host of swagger 2 specs. Empty when the spec has neither
+fn base_url(spec: &OpenApi) -> String {
+    if let Some(server) = spec.servers.first() {
+        return server.url.trim_end_matches('/').to_string();
+    }
+
+    match spec.host.as_ref() {
+        Some(host) => {
+            let scheme = spec.schemes.first().map(String::as_str).unwrap_or("https");
+            let base_path = spec.base_path.as_deref().unwrap_or_default();
+            format!("{scheme}://{host}{}", base_path.trim_end_matches('/'))
+        }
+        None => String::default(),
+    }
+}
+
+/// path templates use `{name}` for parameters, which become hac variables
+fn path_to_variables(path: &str) -> String {
+    path.replace('{', "{{").replace('}', "}}")
+}
+
+/// json specs are objects, while yaml ones declare their version as a
+/// top-level key, eg: `openapi: 3.0.0`
+fn is_yaml(content: &str) -> bool {
+    !content.trim_start().starts_with('{')
+        && content
+            .lines()
+            .any(|line| line.starts_with("openapi:") || line.starts_with("swagger:"))
+}
+
+fn is_http_method(key: &str) -> bool {
+    matches!(
+        key,
+        "get" | "put" | "post" | "delete" | "options" | "head" | "patch" | "trace"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_imports_operations_from_openapi() {
+        let spec = r#"{
+            "openapi": "3.0.0",
+            "info": { "title": "users", "version": "1.0.0" },
+            "servers": [{ "url": "https://api.example.com/v1/" }],
+            "paths": {
+                "/users/{id}": {
+                    "parameters": [{ "name": "id", "in": "path" }],
+                    "get": { "summary": "Get user", "tags": ["users"] },
+                    "options": {}
+                },
+                "/health": {
+                    "post": {
+                        "operationId": "checkHealth",
+                        "requestBody": {
+                            "content": { "application/json": { "example": { "deep": true } } }
+                        }
+                    }
+                }
+            }
+        }"#;
+
+        let import = collection_from_openapi(spec, "users").unwrap();
+        assert_eq!(import.imported, 2);
+        assert_eq!(import.skipped, 1);
+
+        let requests = import.collection.requests.unwrap();
+        let requests = requests.read().unwrap();
+        let RequestKind::Nested(dir) = &requests[0] else {
+            panic!("expected a directory");
+        };
+        assert_eq!(dir.name, "users");
+        let dir_requests = dir.requests.read().unwrap();
+        let RequestKind::Single(request) = &dir_requests[0] else {
+            panic!("expected a request");
+        };
+        let request = request.read().unwrap();
+        assert_eq!(request.name, "Get user");
+        assert_eq!(request.uri, "https://api.example.com/v1/users/{{id}}");
+        assert_eq!(request.parent.as_ref(), Some(&dir.id));
+
+        let RequestKind::Single(request) = &requests[1] else {
+            panic!("expected a request");
+        };
+        let request = request.read().unwrap();
+        assert_eq!(request.name, "checkHealth");
+        assert_eq!(request.method, RequestMethod::Post);
+        assert_eq!(request.body.as_deref(), Some("{\n  \"deep\": true\n}"));
+    }
+
+    #[test]
+    fn test_swagger_base_url() {
+        let spec = r#"{
+            "swagger": "2.0",
+            "host": "api.example.com",
+            "basePath": "/v2",
+            "schemes": ["http"],
+            "paths": {}
+        }"#;
+        let spec = serde_json::from_str::<OpenApi>(spec).unwrap();
+
+        assert_eq!(base_url(&spec), "http://api.example.com/v2");
+    }
+
+    #[test]
+    fn test_yaml_specs_are_rejected() {
+        let spec = "info:\n  title: api\nopenapi: 3.0.0\npaths: {}\n";
+        let error = collection_from_openapi(spec, "api").unwrap_err();
+
+        assert!(error.to_string().contains("yaml are not supported"));
+        assert!(collection_from_openapi("not a spec", "api")
+            .unwrap_err()
+            .to_string()
+            .contains("expected"));
+    }
+}
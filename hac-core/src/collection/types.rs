@@ -1,17 +1,56 @@
+use std::collections::{BTreeMap, HashMap};
 use std::hash::Hash;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 
 use serde::{Deserialize, Serialize};
 
 /// a collection is represented as a file on the file system and holds every
 /// request and metadata
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct Collection {
     /// basic information about the collection such as name and description
     pub info: Info,
     /// maybe a vector of `RequestKind` that are part of the collection
     pub requests: Option<Arc<RwLock<Vec<RequestKind>>>>,
+    /// template applied to every request created on this collection
+    #[serde(
+        rename = "requestDefaults",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub request_defaults: Option<RequestDefaults>,
+    /// how trailing slashes on the uris of this collection are handled when
+    /// sending requests
+    #[serde(
+        rename = "trailingSlash",
+        default,
+        skip_serializing_if = "TrailingSlash::is_keep"
+    )]
+    pub trailing_slash: TrailingSlash,
+    /// variables that hold the same value on every environment, like the
+    /// version of the api, resolved beneath the variables of the environment
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variables: BTreeMap<String, String>,
+    /// named sets of variables, like `dev` or `prod`, substituted on `{{name}}`
+    /// placeholders of the requests when sending them. The `base_url` variable
+    /// of an environment is prepended to the relative uris of the requests
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub environments: BTreeMap<String, BTreeMap<String, String>>,
+    /// ids of the requests in the order they are sent when running the
+    /// collection, for chains where a request depends on another. When empty
+    /// requests run in the order they appear on the sidebar
+    #[serde(rename = "runOrder", default, skip_serializing_if = "Vec::is_empty")]
+    pub run_order: Vec<String>,
+    /// `.env` file whose variables are available to every request, beneath
+    /// the variables of the active environment. Relative paths start at the
+    /// directory of the collection file
+    #[serde(rename = "envFile", default, skip_serializing_if = "Option::is_none")]
+    pub env_file: Option<PathBuf>,
+    /// proxy and TLS settings of the requests of this collection, used when a
+    /// request doesn't set its own
+    #[serde(default, skip_serializing_if = "ClientSettings::is_empty")]
+    pub client: ClientSettings,
     /// path is a virtual field used only during runtime to know where to
     /// sync the file, this will be the absolute path to the file on the
     /// users computer
@@ -19,6 +58,214 @@ pub struct Collection {
     pub path: PathBuf,
 }
 
+/// variable of an environment holding the base url of its relative requests
+pub const BASE_URL_VARIABLE: &str = "base_url";
+
+impl Collection {
+    /// base url relative uris are resolved against while the environment
+    /// called `env` is active, falling back to the one of the collection
+    pub fn base_url(&self, env: Option<&str>) -> Option<&str> {
+        env.and_then(|env| self.environments.get(env))
+            .and_then(|variables| variables.get(BASE_URL_VARIABLE))
+            .or_else(|| self.variables.get(BASE_URL_VARIABLE))
+            .map(String::as_str)
+    }
+
+    /// variables of the environment called `name`, when there is no such
+    /// environment the error lists every environment of the collection
+    pub fn environment_variables(&self, name: &str) -> Result<HashMap<String, String>, String> {
+        match self.environments.get(name) {
+            Some(variables) => Ok(variables.clone().into_iter().collect()),
+            None if self.environments.is_empty() => Err(format!(
+                "unknown environment {name}, collection {} has no environments",
+                self.info.name
+            )),
+            None => Err(format!(
+                "unknown environment {name}, available environments are: {}",
+                self.environments
+                    .keys()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+
+    /// every variable available to the requests of the collection. Variables
+    /// of the collection are overridden by the ones of `env_file`, or the env
+    /// file of the collection when none is given, which are overridden by the
+    /// variables of the environment `env`
+    pub fn variables(
+        &self,
+        env: Option<&str>,
+        env_file: Option<&Path>,
+    ) -> Result<HashMap<String, String>, String> {
+        let env_file = env_file.map(Path::to_path_buf).or_else(|| {
+            self.env_file.as_ref().map(|path| match self.path.parent() {
+                Some(dir) if path.is_relative() => dir.join(path),
+                _ => path.clone(),
+            })
+        });
+
+        let mut variables = self
+            .variables
+            .clone()
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+        if let Some(path) = env_file {
+            let contents = std::fs::read_to_string(&path)
+                .map_err(|err| format!("failed to read env file {}: {err}", path.display()))?;
+            variables.extend(crate::variables::parse_env_file(&contents));
+        }
+        if let Some(env) = env {
+            variables.extend(self.environment_variables(env)?);
+        }
+
+        Ok(variables)
+    }
+}
+
+/// protocol version used to send a request
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum HttpVersion {
+    /// whatever the client picks, which is HTTP/1.1
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    /// HTTP/1.0, for legacy servers. Connections are never kept alive
+    #[serde(rename = "HTTP/1.0")]
+    Http10,
+}
+
+impl HttpVersion {
+    pub fn is_auto(&self) -> bool {
+        matches!(self, HttpVersion::Auto)
+    }
+}
+
+/// proxy, TLS, compression and connection settings of the client a request is
+/// sent through. They can be set on the config, on a collection and on a
+/// request, where unset settings fall back to the next one in that order
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ClientSettings {
+    /// url of the proxy every request goes through, `none` sends requests
+    /// directly, ignoring the proxy of the environment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// accepts invalid TLS certificates, like self signed ones
+    #[serde(
+        rename = "acceptInvalidCerts",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub accept_invalid_certs: Option<bool>,
+    /// pem file with a certificate trusted on top of the ones of the system
+    #[serde(
+        rename = "caCertificate",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub ca_certificate: Option<PathBuf>,
+    /// whether the request advertises compressed encodings or asks for an
+    /// uncompressed response, replacing any `Accept-Encoding` header
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<hac_config::Compression>,
+    /// sends HTTP/1 header names title cased, like `Content-Type`, for
+    /// servers that wrongly treat header names as case sensitive
+    #[serde(
+        rename = "titleCaseHeaders",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub title_case_headers: Option<bool>,
+    /// ip address of the local interface requests are sent from
+    #[serde(
+        rename = "localAddress",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub local_address: Option<String>,
+    /// milliseconds to wait for the connection to be established
+    #[serde(
+        rename = "connectTimeout",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub connect_timeout: Option<u64>,
+}
+
+impl ClientSettings {
+    pub fn is_empty(&self) -> bool {
+        self.eq(&ClientSettings::default())
+    }
+
+    /// these settings with the ones that are unset taken from `fallback`
+    pub fn or(&self, fallback: &ClientSettings) -> ClientSettings {
+        ClientSettings {
+            proxy: self.proxy.clone().or_else(|| fallback.proxy.clone()),
+            accept_invalid_certs: self.accept_invalid_certs.or(fallback.accept_invalid_certs),
+            ca_certificate: self
+                .ca_certificate
+                .clone()
+                .or_else(|| fallback.ca_certificate.clone()),
+            compression: self.compression.or(fallback.compression),
+            title_case_headers: self.title_case_headers.or(fallback.title_case_headers),
+            local_address: self
+                .local_address
+                .clone()
+                .or_else(|| fallback.local_address.clone()),
+            connect_timeout: self.connect_timeout.or(fallback.connect_timeout),
+        }
+    }
+}
+
+/// some servers treat `/users` and `/users/` as different routes, this lets a
+/// collection choose to normalize its uris before they are sent
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TrailingSlash {
+    /// uris are sent exactly as they were written
+    #[default]
+    Keep,
+    /// every uri path ends with a slash
+    Add,
+    /// no uri path ends with a slash
+    Strip,
+}
+
+impl TrailingSlash {
+    pub fn is_keep(&self) -> bool {
+        matches!(self, TrailingSlash::Keep)
+    }
+
+    /// cycles through the possible behaviors, used to toggle it from the ui
+    pub fn next(&self) -> Self {
+        match self {
+            TrailingSlash::Keep => TrailingSlash::Add,
+            TrailingSlash::Add => TrailingSlash::Strip,
+            TrailingSlash::Strip => TrailingSlash::Keep,
+        }
+    }
+}
+
+impl std::fmt::Display for TrailingSlash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrailingSlash::Keep => f.write_str("leave as is"),
+            TrailingSlash::Add => f.write_str("always add"),
+            TrailingSlash::Strip => f.write_str("always strip"),
+        }
+    }
+}
+
+/// values every new request of a collection starts with, requests are free to
+/// diverge from it after being created
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RequestDefaults {
+    pub headers: Option<Vec<HeaderMap>>,
+    pub auth_method: Option<AuthMethod>,
+}
+
 /// we store requests on a collection and on directories as a enum that could
 /// be either an request or a directory. This enables us to have nested
 /// directories, although we don't support that now and might not ever support.
@@ -64,16 +311,17 @@ impl RequestKind {
 /// represents name/value of a header, and wether it is enabled or not.
 ///
 /// disabled headers should not be sent on requests
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct HeaderMap {
     pub pair: (String, String),
     pub enabled: bool,
 }
 
 /// set of methods we currently support on HTTP requests
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum RequestMethod {
+    #[default]
     Get,
     Post,
     Put,
@@ -120,6 +368,21 @@ impl RequestMethod {
     }
 }
 
+impl std::str::FromStr for RequestMethod {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> anyhow::Result<RequestMethod, Self::Err> {
+        match value.to_ascii_uppercase().as_str() {
+            "GET" => Ok(RequestMethod::Get),
+            "POST" => Ok(RequestMethod::Post),
+            "PUT" => Ok(RequestMethod::Put),
+            "PATCH" => Ok(RequestMethod::Patch),
+            "DELETE" => Ok(RequestMethod::Delete),
+            _ => anyhow::bail!("invalid request method {value}"),
+        }
+    }
+}
+
 impl std::fmt::Display for RequestMethod {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -153,7 +416,7 @@ impl RequestMethod {
 /// This is how we store a request on the system, basically this stores all
 /// needed information about a request to be able to perform any actions we
 /// allow.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Request {
     /// we store an uuid on each request to be able to easily identify them
     /// as identifying by name is
@@ -181,12 +444,350 @@ pub struct Request {
     /// the type of the body to be used, like `application/json` or any other
     /// accepted body type
     pub body_type: Option<BodyType>,
+    /// when set, the response body is streamed straight into the file at this
+    /// path instead of being kept in memory, useful for big binary responses
+    #[serde(rename = "downloadTo", skip_serializing_if = "Option::is_none")]
+    pub download_to: Option<String>,
+    /// how long this request took to respond over all the times it was sent,
+    /// used to spot endpoints that are getting slower over time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timings: Option<RequestTimings>,
+    /// disabled requests are kept on the collection but skipped when running
+    /// multiple requests at once, like commenting out a request
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// responses saved as examples of what this request gives back, used to
+    /// document the expected output of the request
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub samples: Vec<ResponseSample>,
+    /// status the request is expected to respond with, responses are marked
+    /// as passing or failing based on it
+    #[serde(
+        rename = "expectedStatus",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub expected_status: Option<ExpectedStatus>,
+    /// sent as the `Host` header instead of the host of the uri, replacing any
+    /// `Host` header set on the request
+    #[serde(
+        rename = "hostOverride",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub host_override: Option<String>,
+    /// hostnames resolved to the given ip instead of going through DNS. As the
+    /// uri keeps the hostname, TLS still presents it through SNI, which allows
+    /// targeting a specific backend by ip
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub resolve: BTreeMap<String, String>,
+    /// cookies sent with this request only, merged into the `Cookie` header
+    /// of the request, taking precedence over cookies with the same name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub cookies: BTreeMap<String, String>,
+    /// protocol version the request is sent with, for servers that don't speak
+    /// the version the client would pick
+    #[serde(
+        rename = "httpVersion",
+        default,
+        skip_serializing_if = "HttpVersion::is_auto"
+    )]
+    pub http_version: HttpVersion,
+    /// when set, the body of every successful response is written to this
+    /// path. `{name}` is replaced by the name of the request and `{timestamp}`
+    /// by the milliseconds since the unix epoch, so each response gets its own
+    /// file. Relative paths are taken from the directory of the collection
+    #[serde(
+        rename = "saveResponseTo",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub save_response_to: Option<String>,
+    /// sends the `ETag` of the last response as `If-None-Match`, so servers
+    /// can answer with `304 Not Modified`, which shows the previous body
+    #[serde(
+        rename = "etagCache",
+        default,
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub etag_cache: bool,
+    /// raw requests are sent byte for byte, when set the line breaks of their
+    /// request line and headers are sent as `\r\n` instead, as the editor
+    /// only writes `\n`
+    #[serde(
+        rename = "rawCrlf",
+        default,
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub raw_crlf: bool,
+    /// proxy and TLS settings of this request, taking precedence over the
+    /// ones of the collection and of the config
+    #[serde(default, skip_serializing_if = "ClientSettings::is_empty")]
+    pub client: ClientSettings,
+    /// content type of `body`, sent as the `Content-Type` of the request. Only
+    /// set once the request keeps bodies for more than one content type, or
+    /// when it was imported with a body of a known content type
+    #[serde(
+        rename = "bodyContentType",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub body_content_type: Option<String>,
+    /// bodies kept for content types other than the one of `body`, keyed by
+    /// their content type, so the same request can be sent with another
+    /// representation of its payload. Only `body` is ever sent
+    #[serde(
+        rename = "alternateBodies",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub alternate_bodies: BTreeMap<String, String>,
+}
+
+/// content types every request can switch its body to, besides the ones it
+/// already keeps a body for
+pub const BODY_CONTENT_TYPES: [&str; 2] = ["application/json", "application/xml"];
+
+impl Request {
+    /// whether the request responded as expected, requests without an
+    /// expected status succeed on anything below 400
+    pub fn is_success(&self, status: u16) -> bool {
+        self.expected_status
+            .map_or(status.lt(&400), |expected| expected.matches(status))
+    }
+
+    /// content type of the body that is sent, requests that never switched
+    /// bodies are sent as json
+    pub fn active_content_type(&self) -> &str {
+        self.body_content_type
+            .as_deref()
+            .unwrap_or(BODY_CONTENT_TYPES[0])
+    }
+
+    /// content type after the active one, going through the common content
+    /// types and the ones the request keeps a body for, in alphabetical order
+    pub fn next_content_type(&self) -> String {
+        let mut content_types = BODY_CONTENT_TYPES
+            .iter()
+            .map(|content_type| content_type.to_string())
+            .chain(self.alternate_bodies.keys().cloned())
+            .chain(std::iter::once(self.active_content_type().to_string()))
+            .collect::<Vec<_>>();
+        content_types.sort();
+        content_types.dedup();
+
+        let active = content_types
+            .iter()
+            .position(|content_type| content_type.eq(self.active_content_type()))
+            .unwrap_or_default();
+        content_types[(active + 1) % content_types.len()].clone()
+    }
+
+    /// makes the body kept for `content_type` the one that is sent, keeping
+    /// the current body under its content type. Content types without a body
+    /// start empty
+    pub fn switch_body(&mut self, content_type: &str) {
+        let active = self.active_content_type().to_string();
+        if active.eq(content_type) {
+            return;
+        }
+
+        if let Some(body) = self.body.take().filter(|body| !body.is_empty()) {
+            self.alternate_bodies.insert(active, body);
+        }
+        self.body = self.alternate_bodies.remove(content_type);
+        self.body_content_type = Some(content_type.to_string());
+    }
+
+    /// path the response received at `timestamp` should be saved to, with
+    /// every placeholder of `save_response_to` replaced. Relative paths are
+    /// taken from `dir`, the directory of the collection
+    pub fn response_save_path(&self, dir: &Path, timestamp: u128) -> Option<PathBuf> {
+        let template = self.save_response_to.as_ref()?;
+        let name = self
+            .name
+            .chars()
+            .map(|c| match c.is_alphanumeric() || c.eq(&'-') {
+                true => c,
+                false => '_',
+            })
+            .collect::<String>();
+
+        let path = PathBuf::from(
+            template
+                .replace("{name}", &name)
+                .replace("{timestamp}", &timestamp.to_string()),
+        );
+        match path.is_relative() {
+            true => Some(dir.join(path)),
+            false => Some(path),
+        }
+    }
+
+    /// path the response body should be downloaded to, relative paths are
+    /// taken from `dir`, the directory of the collection
+    pub fn download_path(&self, dir: &Path) -> Option<PathBuf> {
+        self.download_to.as_ref().map(|path| dir.join(path))
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// an enabled `GET` request with nothing else set, as requests are enabled
+/// unless told otherwise this can't be derived
+impl Default for Request {
+    fn default() -> Self {
+        Request {
+            id: String::default(),
+            method: RequestMethod::default(),
+            name: String::default(),
+            uri: String::default(),
+            headers: None,
+            auth_method: None,
+            parent: None,
+            body: None,
+            body_type: None,
+            download_to: None,
+            timings: None,
+            enabled: default_enabled(),
+            samples: vec![],
+            expected_status: None,
+            host_override: None,
+            resolve: BTreeMap::default(),
+            cookies: BTreeMap::default(),
+            http_version: HttpVersion::default(),
+            save_response_to: None,
+            etag_cache: false,
+            raw_crlf: false,
+            client: ClientSettings::default(),
+            body_content_type: None,
+            alternate_bodies: BTreeMap::default(),
+        }
+    }
+}
+
+/// a status code, or range of status codes, a request is expected to respond
+/// with. Written as `200`, `200-204` or `2xx`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub enum ExpectedStatus {
+    Exact(u16),
+    /// inclusive range of status codes
+    Range(u16, u16),
+}
+
+impl ExpectedStatus {
+    pub fn matches(&self, status: u16) -> bool {
+        match self {
+            ExpectedStatus::Exact(expected) => status.eq(expected),
+            ExpectedStatus::Range(start, end) => (*start..=*end).contains(&status),
+        }
+    }
+}
+
+impl std::str::FromStr for ExpectedStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let invalid = || format!("invalid expected status: {value}");
+        let parse = |code: &str| {
+            code.trim()
+                .parse::<u16>()
+                .ok()
+                .filter(|code| (100..=599).contains(code))
+                .ok_or_else(invalid)
+        };
+
+        if let Some(class) = value.to_ascii_lowercase().strip_suffix("xx") {
+            let class = parse(&format!("{class}00"))?;
+            return Ok(ExpectedStatus::Range(class, class + 99));
+        }
+
+        match value.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse(start)?, parse(end)?);
+                if start > end {
+                    return Err(invalid());
+                }
+                Ok(ExpectedStatus::Range(start, end))
+            }
+            None => Ok(ExpectedStatus::Exact(parse(value)?)),
+        }
+    }
+}
+
+impl std::fmt::Display for ExpectedStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExpectedStatus::Exact(code) => write!(f, "{code}"),
+            ExpectedStatus::Range(start, end) if start % 100 == 0 && *end == start + 99 => {
+                write!(f, "{}xx", start / 100)
+            }
+            ExpectedStatus::Range(start, end) => write!(f, "{start}-{end}"),
+        }
+    }
+}
+
+impl TryFrom<String> for ExpectedStatus {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<ExpectedStatus> for String {
+    fn from(value: ExpectedStatus) -> Self {
+        value.to_string()
+    }
+}
+
+/// a response pinned to a request as an example of its output
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ResponseSample {
+    pub name: String,
+    pub status: Option<u16>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+    /// when the sample was saved, in milliseconds since the unix epoch
+    #[serde(rename = "savedAt")]
+    pub saved_at: u64,
+}
+
+/// running totals of the response times of a request, we store the total
+/// instead of the average itself so that recording a new run doesn't
+/// accumulate rounding errors
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct RequestTimings {
+    /// how many successful responses were recorded
+    pub runs: u64,
+    /// sum of the duration of every recorded response, in milliseconds
+    #[serde(rename = "totalMs")]
+    pub total_ms: u64,
+}
+
+impl RequestTimings {
+    pub fn record(&mut self, duration: std::time::Duration) {
+        self.runs = self.runs.saturating_add(1);
+        self.total_ms = self.total_ms.saturating_add(duration.as_millis() as u64);
+    }
+
+    pub fn average_ms(&self) -> u64 {
+        self.total_ms.checked_div(self.runs).unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum AuthMethod {
     Bearer,
     None,
+    /// a token is fetched from the token endpoint before sending the request,
+    /// and sent as a bearer token
+    OAuth2ClientCredentials(OAuth2ClientCredentials),
     A,
     B,
     C,
@@ -194,6 +795,22 @@ pub enum AuthMethod {
     E,
 }
 
+/// settings of the oauth2 client credentials flow. Any of the values can be
+/// written as `env:NAME` to read it from the environment variable `NAME` when
+/// the token is fetched, keeping secrets out of the collection file
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OAuth2ClientCredentials {
+    #[serde(rename = "tokenUrl")]
+    pub token_url: String,
+    #[serde(rename = "clientId")]
+    pub client_id: String,
+    #[serde(rename = "clientSecret")]
+    pub client_secret: String,
+    /// space separated list of scopes, sent as is to the token endpoint
+    #[serde(default)]
+    pub scopes: String,
+}
+
 #[derive(Default)]
 pub struct AuthKindIter {
     inner: u8,
@@ -204,6 +821,7 @@ impl std::fmt::Display for AuthMethod {
         match self {
             AuthMethod::None => write!(f, "None"),
             AuthMethod::Bearer => write!(f, "Bearer"),
+            AuthMethod::OAuth2ClientCredentials(_) => write!(f, "OAuth2 client credentials"),
             AuthMethod::A => write!(f, "Bearer"),
             AuthMethod::B => write!(f, "Bearer"),
             AuthMethod::C => write!(f, "Bearer"),
@@ -218,11 +836,12 @@ impl From<usize> for AuthMethod {
         match value {
             0 => AuthMethod::None,
             1 => AuthMethod::Bearer,
-            2 => AuthMethod::A,
-            3 => AuthMethod::B,
-            4 => AuthMethod::C,
-            5 => AuthMethod::D,
-            6 => AuthMethod::E,
+            2 => AuthMethod::OAuth2ClientCredentials(Default::default()),
+            3 => AuthMethod::A,
+            4 => AuthMethod::B,
+            5 => AuthMethod::C,
+            6 => AuthMethod::D,
+            7 => AuthMethod::E,
             _ => AuthMethod::None,
         }
     }
@@ -245,11 +864,12 @@ impl Iterator for AuthKindIter {
         let variant = match self.inner {
             0 => Some(AuthMethod::None),
             1 => Some(AuthMethod::Bearer),
-            2 => Some(AuthMethod::A),
-            3 => Some(AuthMethod::B),
-            4 => Some(AuthMethod::C),
-            5 => Some(AuthMethod::D),
-            6 => Some(AuthMethod::E),
+            2 => Some(AuthMethod::OAuth2ClientCredentials(Default::default())),
+            3 => Some(AuthMethod::A),
+            4 => Some(AuthMethod::B),
+            5 => Some(AuthMethod::C),
+            6 => Some(AuthMethod::D),
+            7 => Some(AuthMethod::E),
             _ => None,
         };
         self.inner += 1;
@@ -262,6 +882,30 @@ impl Iterator for AuthKindIter {
 pub enum BodyType {
     #[serde(rename = "json")]
     Json,
+    /// the body is read from the file at the given path when the request is
+    /// sent, keeping big payloads out of the collection
+    #[serde(rename = "file")]
+    File(String),
+    /// the body is a whole HTTP request, with its request line, headers and
+    /// body, sent verbatim over a socket to the host of the uri
+    #[serde(rename = "raw")]
+    Raw,
+    /// the body is sent as written, with the content type of
+    /// `body_content_type`, for bodies that are not json like xml or forms
+    #[serde(rename = "text")]
+    Text,
+}
+
+impl BodyType {
+    /// body type of a body written in `content_type`, only json content types
+    /// are treated as json
+    pub fn for_content_type(content_type: &str) -> BodyType {
+        let mime = content_type.split(';').next().unwrap_or_default().trim();
+        match mime.eq_ignore_ascii_case("application/json") || mime.ends_with("+json") {
+            true => BodyType::Json,
+            false => BodyType::Text,
+        }
+    }
 }
 
 /// a directory can hold a vector of requests, which will be
@@ -280,10 +924,249 @@ pub struct Directory {
 }
 
 /// basic information about a colleciton
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Info {
     /// name of the collection that will be displayed onscreen
     pub name: String,
     /// a optional description in case it is useful
     pub description: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected_status_parsing() {
+        assert_eq!("200".parse(), Ok(ExpectedStatus::Exact(200)));
+        assert_eq!("200-204".parse(), Ok(ExpectedStatus::Range(200, 204)));
+        assert_eq!("2xx".parse(), Ok(ExpectedStatus::Range(200, 299)));
+        assert!("204-200".parse::<ExpectedStatus>().is_err());
+        assert!("999".parse::<ExpectedStatus>().is_err());
+        assert!("ok".parse::<ExpectedStatus>().is_err());
+    }
+
+    #[test]
+    fn test_expected_status_matches() {
+        let expected: ExpectedStatus = "2xx".parse().unwrap();
+
+        assert!(expected.matches(201));
+        assert!(!expected.matches(404));
+        assert_eq!(expected.to_string(), "2xx");
+    }
+
+    #[test]
+    fn test_success_follows_the_expected_status() {
+        let mut request = Request::default();
+        assert!(request.is_success(302));
+        assert!(!request.is_success(404));
+
+        request.expected_status = Some(ExpectedStatus::Exact(404));
+        assert!(request.is_success(404));
+        assert!(!request.is_success(200));
+    }
+
+    #[test]
+    fn test_response_save_path_fills_placeholders() {
+        let request: Request = serde_json::from_str(
+            r#"{
+                "id": "id",
+                "method": "GET",
+                "name": "daily report",
+                "uri": "https://example.com/report",
+                "headers": null,
+                "auth_method": null,
+                "parent": null,
+                "body": null,
+                "bodyType": null,
+                "saveResponseTo": "reports/{name}-{timestamp}.json"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            request.response_save_path(Path::new("/collections"), 1700000000000),
+            Some(PathBuf::from(
+                "/collections/reports/daily_report-1700000000000.json"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_absolute_response_save_path_is_kept() {
+        let request = Request {
+            save_response_to: Some(String::from("/tmp/{name}.json")),
+            name: String::from("report"),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            request.response_save_path(Path::new("/collections"), 0),
+            Some(PathBuf::from("/tmp/report.json"))
+        );
+    }
+
+    #[test]
+    fn test_relative_download_path_is_taken_from_the_collection() {
+        let request = Request {
+            download_to: Some(String::from("downloads/file.bin")),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            request.download_path(Path::new("/collections")),
+            Some(PathBuf::from("/collections/downloads/file.bin"))
+        );
+    }
+
+    #[test]
+    fn test_unknown_environment_lists_available_ones() {
+        let collection: Collection = serde_json::from_str(
+            r#"{
+                "info": { "name": "api" },
+                "requests": null,
+                "environments": {
+                    "dev": { "base": "http://localhost" },
+                    "prod": { "base": "https://api.example.com" }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let variables = collection.environment_variables("dev").unwrap();
+        assert_eq!(variables["base"], "http://localhost");
+        assert_eq!(
+            collection.environment_variables("staging").unwrap_err(),
+            "unknown environment staging, available environments are: dev, prod"
+        );
+    }
+
+    #[test]
+    fn test_client_settings_fall_back_when_unset() {
+        let request = ClientSettings {
+            proxy: Some(String::from("none")),
+            ..Default::default()
+        };
+        let collection = ClientSettings {
+            proxy: Some(String::from("http://proxy.internal:3128")),
+            accept_invalid_certs: Some(true),
+            ca_certificate: None,
+            compression: Some(hac_config::Compression::Identity),
+            connect_timeout: Some(500),
+            ..Default::default()
+        };
+        let global = ClientSettings {
+            ca_certificate: Some(PathBuf::from("/etc/ca.pem")),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            request.or(&collection).or(&global),
+            ClientSettings {
+                proxy: Some(String::from("none")),
+                accept_invalid_certs: Some(true),
+                ca_certificate: Some(PathBuf::from("/etc/ca.pem")),
+                compression: Some(hac_config::Compression::Identity),
+                connect_timeout: Some(500),
+                ..Default::default()
+            }
+        );
+        assert!(ClientSettings::default().is_empty());
+    }
+
+    #[test]
+    fn test_switching_bodies_keeps_the_previous_one() {
+        let mut request: Request = serde_json::from_str(
+            r#"{
+                "id": "any_id",
+                "method": "POST",
+                "name": "any_name",
+                "uri": "/users",
+                "headers": null,
+                "auth_method": null,
+                "parent": null,
+                "body": "{\"id\":1}",
+                "bodyType": "json"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(request.next_content_type(), "application/xml");
+
+        request.switch_body("application/xml");
+        assert_eq!(request.body, None);
+        request.body = Some(String::from("<id>1</id>"));
+        assert_eq!(request.next_content_type(), "application/json");
+
+        request.switch_body("application/json");
+        assert_eq!(request.body.as_deref(), Some(r#"{"id":1}"#));
+        assert_eq!(request.alternate_bodies["application/xml"], "<id>1</id>");
+
+        let serialized = serde_json::to_value(&request).unwrap();
+        assert_eq!(serialized["bodyContentType"], "application/json");
+        assert_eq!(
+            serialized["alternateBodies"]["application/xml"],
+            "<id>1</id>"
+        );
+        assert_eq!(
+            BodyType::for_content_type("application/problem+json; charset=utf-8"),
+            BodyType::Json
+        );
+        assert_eq!(
+            BodyType::for_content_type("application/xml"),
+            BodyType::Text
+        );
+    }
+
+    #[test]
+    fn test_environment_overrides_env_file() {
+        let dir = std::env::temp_dir().join("hac_test_environment_overrides_env_file");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(".env"), "base=http://env-file\ntoken=secret\n").unwrap();
+        let mut collection: Collection = serde_json::from_str(
+            r#"{
+                "info": { "name": "api" },
+                "requests": null,
+                "envFile": ".env",
+                "environments": { "dev": { "base": "http://localhost" } }
+            }"#,
+        )
+        .unwrap();
+        collection.path = dir.join("api.json");
+
+        let variables = collection.variables(Some("dev"), None).unwrap();
+        let only_file = collection.variables(None, None).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(variables["base"], "http://localhost");
+        assert_eq!(variables["token"], "secret");
+        assert_eq!(only_file["base"], "http://env-file");
+    }
+
+    #[test]
+    fn test_environment_overrides_collection_variables() {
+        let collection: Collection = serde_json::from_str(
+            r#"{
+                "info": { "name": "api" },
+                "requests": null,
+                "variables": { "version": "v2", "base_url": "http://collection" },
+                "environments": {
+                    "dev": { "version": "v3" },
+                    "prod": { "base_url": "http://prod" }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            collection.variables(Some("dev"), None).unwrap()["version"],
+            "v3"
+        );
+        assert_eq!(
+            collection.variables(Some("prod"), None).unwrap()["version"],
+            "v2"
+        );
+        assert_eq!(collection.base_url(Some("dev")), Some("http://collection"));
+        assert_eq!(collection.base_url(Some("prod")), Some("http://prod"));
+        assert_eq!(collection.base_url(None), Some("http://collection"));
+    }
+}
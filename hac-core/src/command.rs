@@ -1,9 +1,29 @@
 use crate::collection::Collection;
 
+use std::path::PathBuf;
+
 #[derive(Debug)]
 pub enum Command {
     Quit,
     SelectCollection(Collection),
     Error(String),
     CreateCollection(Collection),
+    /// opens the given collection in scratch mode, where nothing is ever
+    /// synchronized to disk
+    OpenScratch(Collection),
+    /// reads the configuration file again, applying it without restarting
+    ReloadConfig,
+    /// leaves the current screen, going back to the previous one
+    Back,
+    /// suspends the application to show the given content on the pager of
+    /// the user, resuming once the pager exits
+    OpenPager(String),
+    /// navigates to the request with `request_id` on the collection stored at
+    /// `path`, opening the collection first if needed, and sends it right
+    /// away when `send` is set
+    OpenRequest {
+        path: PathBuf,
+        request_id: String,
+        send: bool,
+    },
 }
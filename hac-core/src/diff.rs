@@ -0,0 +1,264 @@
+use crate::collection::types::Request;
+use crate::net::request_client::effective_headers;
+
+/// a single line of a diff, tagged with the side it comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// line present on both sides
+    Same(String),
+    /// line only present on the first side
+    Removed(String),
+    /// line only present on the second side
+    Added(String),
+}
+
+impl DiffLine {
+    pub fn is_change(&self) -> bool {
+        !matches!(self, DiffLine::Same(_))
+    }
+}
+
+/// diff of one field of a request, like its headers or body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffSection {
+    pub title: &'static str,
+    pub lines: Vec<DiffLine>,
+}
+
+impl DiffSection {
+    pub fn has_changes(&self) -> bool {
+        self.lines.iter().any(DiffLine::is_change)
+    }
+}
+
+/// compares two requests field by field. Headers are compared as they would
+/// be sent, and bodies line by line
+pub fn diff_requests(a: &Request, b: &Request) -> Vec<DiffSection> {
+    let headers = |request: &Request| {
+        effective_headers(request)
+            .into_iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect::<Vec<_>>()
+    };
+    let body = |request: &Request| {
+        request
+            .body
+            .as_deref()
+            .unwrap_or_default()
+            .lines()
+            .map(String::from)
+            .collect::<Vec<_>>()
+    };
+
+    vec![
+        DiffSection {
+            title: "Method",
+            lines: diff_lines(&[a.method.to_string()], &[b.method.to_string()]),
+        },
+        DiffSection {
+            title: "Uri",
+            lines: diff_lines(std::slice::from_ref(&a.uri), std::slice::from_ref(&b.uri)),
+        },
+        DiffSection {
+            title: "Headers",
+            lines: diff_lines(&headers(a), &headers(b)),
+        },
+        DiffSection {
+            title: "Body",
+            lines: diff_lines(&body(a), &body(b)),
+        },
+    ]
+}
+
+/// how many lines can differ between the two sides before we give up on
+/// finding the shortest diff, and show every remaining line as replaced.
+/// Finding it takes memory quadratic to this distance
+const MAX_EDIT_DISTANCE: usize = 1000;
+
+/// line diff between `old` and `new` with the fewest added and removed
+/// lines, removals are listed before the additions that replace them
+pub fn diff_lines(old: &[String], new: &[String]) -> Vec<DiffLine> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a.eq(b)).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a.eq(b))
+        .count();
+
+    let mut lines = old[..prefix]
+        .iter()
+        .cloned()
+        .map(DiffLine::Same)
+        .collect::<Vec<_>>();
+    lines.extend(shortest_edit(
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    ));
+    lines.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .cloned()
+            .map(DiffLine::Same),
+    );
+
+    lines
+}
+
+/// myers' algorithm, which walks the edit graph one edit at a time, keeping
+/// for every diagonal how far along it can go. The furthest points of each
+/// step are kept so the path can be walked back once the end is reached
+fn shortest_edit(old: &[String], new: &[String]) -> Vec<DiffLine> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max = (n + m) as usize;
+    let offset = max as isize + 1;
+    let mut furthest = vec![0isize; 2 * max + 3];
+    let mut trace = vec![];
+
+    for d in 0..=max.min(MAX_EDIT_DISTANCE) as isize {
+        trace.push(furthest[(offset - d) as usize..=(offset + d) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let idx = (offset + k) as usize;
+            let mut x = match k.eq(&-d) || (k.ne(&d) && furthest[idx - 1] < furthest[idx + 1]) {
+                true => furthest[idx + 1],
+                false => furthest[idx - 1] + 1,
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize].eq(&new[y as usize]) {
+                x += 1;
+                y += 1;
+            }
+            furthest[idx] = x;
+            if x >= n && y >= m {
+                return walk_back(old, new, &trace);
+            }
+        }
+    }
+
+    old.iter()
+        .cloned()
+        .map(DiffLine::Removed)
+        .chain(new.iter().cloned().map(DiffLine::Added))
+        .collect()
+}
+
+/// rebuilds the diff from the end of the edit graph, `trace` holds the
+/// furthest points on the diagonals `-d..=d` before each step `d`
+fn walk_back(old: &[String], new: &[String], trace: &[Vec<isize>]) -> Vec<DiffLine> {
+    let (mut x, mut y) = (old.len() as isize, new.len() as isize);
+    let mut lines = vec![];
+
+    for (d, furthest) in trace.iter().enumerate().skip(1).rev() {
+        let d = d as isize;
+        let k = x - y;
+        let at = |k: isize| furthest[(k + d) as usize];
+        let prev_k = match k.eq(&-d) || (k.ne(&d) && at(k - 1) < at(k + 1)) {
+            true => k + 1,
+            false => k - 1,
+        };
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            lines.push(DiffLine::Same(old[x as usize].clone()));
+        }
+        match x.eq(&prev_x) {
+            true => {
+                y -= 1;
+                lines.push(DiffLine::Added(new[y as usize].clone()));
+            }
+            false => {
+                x -= 1;
+                lines.push(DiffLine::Removed(old[x as usize].clone()));
+            }
+        }
+    }
+    while x > 0 && y > 0 {
+        x -= 1;
+        y -= 1;
+        lines.push(DiffLine::Same(old[x as usize].clone()));
+    }
+
+    lines.reverse();
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_diff_lines_keeps_common_lines() {
+        let diff = diff_lines(
+            &lines("{\n\"a\": 1,\n\"b\": 2\n}"),
+            &lines("{\n\"a\": 1,\n\"b\": 3\n}"),
+        );
+
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Same("{".into()),
+                DiffLine::Same("\"a\": 1,".into()),
+                DiffLine::Removed("\"b\": 2".into()),
+                DiffLine::Added("\"b\": 3".into()),
+                DiffLine::Same("}".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_lines_with_an_empty_side() {
+        let diff = diff_lines(&[], &lines("a\nb"));
+
+        assert_eq!(
+            diff,
+            vec![DiffLine::Added("a".into()), DiffLine::Added("b".into())]
+        );
+    }
+
+    #[test]
+    fn test_diff_lines_finds_the_fewest_changes() {
+        let diff = diff_lines(&lines("a\nb\nc\na\nb\nb\na"), &lines("c\nb\na\nb\na\nc"));
+
+        let changes = diff.iter().filter(|line| line.is_change()).count();
+        let old = diff
+            .iter()
+            .filter_map(|line| match line {
+                DiffLine::Same(line) | DiffLine::Removed(line) => Some(line.as_str()),
+                DiffLine::Added(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let new = diff
+            .iter()
+            .filter_map(|line| match line {
+                DiffLine::Same(line) | DiffLine::Added(line) => Some(line.as_str()),
+                DiffLine::Removed(_) => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(changes, 5);
+        assert_eq!(old.join("\n"), "a\nb\nc\na\nb\nb\na");
+        assert_eq!(new.join("\n"), "c\nb\na\nb\na\nc");
+    }
+
+    #[test]
+    fn test_diff_lines_of_large_bodies() {
+        let old = (0..100_000).map(|i| i.to_string()).collect::<Vec<_>>();
+        let mut new = old.clone();
+        new[50_000] = String::from("changed");
+        let unrelated = (0..5_000).map(|i| format!("other {i}")).collect::<Vec<_>>();
+
+        let diff = diff_lines(&old, &new);
+        let replaced = diff_lines(&old[..5_000], &unrelated);
+
+        assert_eq!(diff.iter().filter(|line| line.is_change()).count(), 2);
+        assert_eq!(replaced.len(), 10_000);
+        assert!(replaced.iter().all(DiffLine::is_change));
+    }
+}
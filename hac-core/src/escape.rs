@@ -0,0 +1,37 @@
+/// turns `content` into a json string literal, quoting it and escaping every
+/// character json requires to be escaped, eg: `{"id": 1}` becomes
+/// `"{\"id\": 1}"`, which can be embedded as a field of another json
+pub fn escape_json(content: &str) -> String {
+    serde_json::to_string(content).expect("serializing a string never fails")
+}
+
+/// reverses `escape_json`, `content` has to be a single json string literal,
+/// surrounding whitespace is ignored
+pub fn unescape_json(content: &str) -> Result<String, String> {
+    serde_json::from_str::<String>(content.trim())
+        .map_err(|e| format!("the body is not a json string: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escaping_quotes_and_escapes_special_chars() {
+        let body = "{\n\t\"name\": \"a \\\\ b\"\n}";
+
+        assert_eq!(escape_json(body), r#""{\n\t\"name\": \"a \\\\ b\"\n}""#);
+    }
+
+    #[test]
+    fn test_unescaping_reverses_escaping() {
+        let body = r#"{"nested": "value", "list": [1, 2]}"#;
+
+        assert_eq!(unescape_json(&escape_json(body)).unwrap(), body);
+        assert_eq!(
+            unescape_json("  \"line\\nbreak\"\n").unwrap(),
+            "line\nbreak"
+        );
+        assert!(unescape_json(body).is_err());
+    }
+}
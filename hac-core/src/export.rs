@@ -0,0 +1,457 @@
+use crate::collection::types::{BodyType, Request, RequestMethod, TrailingSlash};
+use crate::net::request_client::effective_headers;
+use crate::net::request_manager::Response;
+use crate::net::substitution::substitute_variables;
+use crate::net::uri::normalize_trailing_slash;
+use crate::variables::{resolve, VariableError};
+
+use std::collections::HashMap;
+
+/// maximum amount of top level json fields we generate assertions for, we only
+/// want to bootstrap a test, not to assert the whole response
+const MAX_ASSERTED_FIELDS: usize = 5;
+
+/// formats we know how to generate assertion snippets for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssertionFormat {
+    /// a `jq` expression that exits with an error when the body doesn't match
+    Jq,
+    /// a python test using `pytest` and `requests`
+    Pytest,
+}
+
+/// the shape of the top level json value of a response body
+enum BodyShape {
+    Object(Vec<String>),
+    Array,
+    Other,
+}
+
+/// generates an assertion snippet from a response, asserting on its status code
+/// and on the presence of a few top level json fields
+pub fn assertion_snippet(
+    request: &Request,
+    response: &Response,
+    format: AssertionFormat,
+) -> String {
+    let status = response.status.map(|status| status.as_u16()).unwrap_or(200);
+    let shape = body_shape(response.body.as_deref());
+
+    match format {
+        AssertionFormat::Jq => jq_snippet(status, &shape),
+        AssertionFormat::Pytest => pytest_snippet(request, status, &shape),
+    }
+}
+
+fn body_shape(body: Option<&str>) -> BodyShape {
+    match body.and_then(|body| serde_json::from_str::<serde_json::Value>(body).ok()) {
+        Some(serde_json::Value::Object(map)) => BodyShape::Object(
+            map.keys()
+                .take(MAX_ASSERTED_FIELDS)
+                .map(String::from)
+                .collect(),
+        ),
+        Some(serde_json::Value::Array(_)) => BodyShape::Array,
+        _ => BodyShape::Other,
+    }
+}
+
+fn jq_snippet(status: u16, shape: &BodyShape) -> String {
+    let expression = match shape {
+        BodyShape::Object(keys) if !keys.is_empty() => keys
+            .iter()
+            .map(|key| format!("has({})", serde_json::Value::from(key.as_str())))
+            .collect::<Vec<_>>()
+            .join(" and "),
+        BodyShape::Object(_) => String::from("type == \"object\""),
+        BodyShape::Array => String::from("type == \"array\""),
+        BodyShape::Other => String::from("true"),
+    };
+
+    format!("# expects status {status}\njq -e '{expression}'\n")
+}
+
+fn pytest_snippet(request: &Request, status: u16, shape: &BodyShape) -> String {
+    let test_name = request
+        .name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    let method = request.method.to_string().to_lowercase();
+
+    let mut snippet = format!(
+        "import requests\n\n\ndef test_{test_name}():\n    response = requests.{method}({uri:?})\n\n    assert response.status_code == {status}\n",
+        uri = request.uri,
+    );
+
+    match shape {
+        BodyShape::Object(keys) => {
+            snippet.push_str("    body = response.json()\n");
+            keys.iter()
+                .for_each(|key| snippet.push_str(&format!("    assert {key:?} in body\n")));
+        }
+        BodyShape::Array => {
+            snippet.push_str("    body = response.json()\n");
+            snippet.push_str("    assert isinstance(body, list)\n");
+        }
+        BodyShape::Other => {}
+    }
+
+    snippet
+}
+
+/// generates a javascript `fetch` call equivalent to the request, with only
+/// the enabled headers
+pub fn to_fetch(request: &Request) -> String {
+    let mut headers: Vec<(String, String)> = vec![];
+    request
+        .headers
+        .iter()
+        .flatten()
+        .filter(|header| header.enabled)
+        .for_each(|header| {
+            let (name, value) = &header.pair;
+            // an object cannot hold the same key twice, so repeated headers are
+            // combined the same way `Headers.append` would do it
+            match headers
+                .iter_mut()
+                .find(|(existing, _)| existing.eq_ignore_ascii_case(name))
+            {
+                Some((_, existing)) => existing.push_str(&format!(", {value}")),
+                None => headers.push((name.clone(), value.clone())),
+            }
+        });
+
+    let has_body = !matches!(request.method, RequestMethod::Get);
+    let mut snippet = String::new();
+
+    if let (true, Some(BodyType::File(path))) = (has_body, request.body_type.as_ref()) {
+        snippet.push_str(&format!(
+            "// the body is read from {path} when sending
+"
+        ));
+    }
+
+    if let (true, Some(BodyType::Json | BodyType::Text)) = (has_body, request.body_type.as_ref()) {
+        if !headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+        {
+            headers.push(("Content-Type".into(), request.active_content_type().into()));
+        }
+    }
+
+    snippet.push_str(&format!("fetch({}, {{\n", js_string(&request.uri)));
+    snippet.push_str(&format!(
+        "  method: {},\n",
+        js_string(&request.method.to_string())
+    ));
+
+    if !headers.is_empty() {
+        snippet.push_str("  headers: {\n");
+        headers.iter().for_each(|(name, value)| {
+            snippet.push_str(&format!("    {}: {},\n", js_string(name), js_string(value)))
+        });
+        snippet.push_str("  },\n");
+    }
+
+    match (has_body, request.body_type.as_ref(), request.body.as_ref()) {
+        (true, Some(BodyType::Json | BodyType::Text), Some(body)) if !body.is_empty() => {
+            snippet.push_str(&format!("  body: {},\n", js_string(body)));
+        }
+        _ => {}
+    }
+
+    snippet.push_str("});\n");
+    snippet
+}
+
+/// the url the request will be sent to, with its variables resolved and the
+/// trailing slash normalized, query params included
+pub fn to_resolved_url(
+    request: &Request,
+    variables: &HashMap<String, String>,
+    max_depth: usize,
+    trailing_slash: TrailingSlash,
+) -> Result<String, VariableError> {
+    let uri = resolve(&request.uri, variables, max_depth)?;
+    Ok(normalize_trailing_slash(&uri, trailing_slash))
+}
+
+/// the request as it will be sent, with every variable resolved, written as
+/// the method and url, followed by the headers and the body. Placeholders of
+/// variables that are not defined are kept as they are
+pub fn to_resolved_text(
+    request: &Request,
+    variables: &HashMap<String, String>,
+    max_depth: usize,
+    trailing_slash: TrailingSlash,
+) -> Result<String, VariableError> {
+    let mut request = request.clone();
+    substitute_variables(&mut request, variables, max_depth)?;
+    request.uri = normalize_trailing_slash(&request.uri, trailing_slash);
+
+    // raw requests are sent as they are written
+    if let Some(BodyType::Raw) = request.body_type {
+        return Ok(request.body.unwrap_or_default());
+    }
+
+    let mut text = format!("{} {}\n", request.method, request.uri);
+    effective_headers(&request)
+        .into_iter()
+        .for_each(|(name, value)| text.push_str(&format!("{name}: {value}\n")));
+
+    if !matches!(request.method, RequestMethod::Get) {
+        match (request.body_type.as_ref(), request.body.as_ref()) {
+            (Some(BodyType::File(path)), _) => {
+                text.push_str(&format!("\n<body read from {path}>\n"))
+            }
+            (_, Some(body)) if !body.is_empty() => text.push_str(&format!("\n{body}\n")),
+            _ => {}
+        }
+    }
+
+    Ok(text)
+}
+
+/// the request written as the text of a raw HTTP/1.1 request, used as the
+/// starting point when switching a request to raw mode. The connection is
+/// asked to be closed, so we know when the response is over
+pub fn to_raw_request(request: &Request) -> String {
+    let (host, target) = split_uri(&request.uri);
+
+    let mut headers = effective_headers(request);
+    let has_header = |headers: &[(String, String)], header: &str| {
+        headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case(header))
+    };
+    if let (false, Some(host)) = (has_header(&headers, "host"), host) {
+        headers.insert(0, (String::from("Host"), host));
+    }
+
+    let body = match (&request.method, request.body_type.as_ref()) {
+        (RequestMethod::Get, _) | (_, Some(BodyType::File(_))) => None,
+        _ => request.body.as_deref().filter(|body| !body.is_empty()),
+    };
+    if let (false, Some(body)) = (has_header(&headers, "content-length"), body) {
+        headers.push((String::from("Content-Length"), body.len().to_string()));
+    }
+    if !has_header(&headers, "connection") {
+        headers.push((String::from("Connection"), String::from("close")));
+    }
+
+    let mut text = format!("{} {target} HTTP/1.1\n", request.method);
+    headers
+        .into_iter()
+        .for_each(|(name, value)| text.push_str(&format!("{name}: {value}\n")));
+    text.push('\n');
+    text.push_str(body.unwrap_or_default());
+    text
+}
+
+/// host, with its port, and request target of `uri`, taken from its text as
+/// uris that still hold variables can't be parsed as urls, or would have
+/// their placeholders percent encoded
+fn split_uri(uri: &str) -> (Option<String>, String) {
+    let Some((_, rest)) = uri.split_once("://") else {
+        return (None, uri.to_string());
+    };
+    let target_start = rest.find(['/', '?']).unwrap_or(rest.len());
+    let (host, target) = rest.split_at(target_start);
+    let target = match target.starts_with('/') {
+        true => target.to_string(),
+        false => format!("/{target}"),
+    };
+
+    (
+        Some(host.to_string()).filter(|host| !host.is_empty()),
+        target,
+    )
+}
+
+/// a json string is a valid javascript string literal, except for the line and
+/// paragraph separators that older engines don't accept unescaped
+fn js_string(value: &str) -> String {
+    serde_json::Value::from(value)
+        .to_string()
+        .replace('\u{2028}', "\\u2028")
+        .replace('\u{2029}', "\\u2029")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection::types::HeaderMap;
+    use crate::test_utils;
+
+    #[test]
+    fn test_jq_snippet_from_object() {
+        let request = test_utils::make_request(RequestMethod::Get, "https://example.com/users/1");
+        let response = Response {
+            body: Some(String::from(r#"{"id": 1, "name": "hac"}"#)),
+            ..test_utils::make_response(reqwest::StatusCode::OK)
+        };
+
+        let snippet = assertion_snippet(&request, &response, AssertionFormat::Jq);
+
+        assert_eq!(
+            snippet,
+            "# expects status 200\njq -e 'has(\"id\") and has(\"name\")'\n"
+        );
+    }
+
+    #[test]
+    fn test_pytest_snippet_from_array() {
+        let request = Request {
+            name: String::from("Get User"),
+            ..test_utils::make_request(RequestMethod::Get, "https://example.com/users/1")
+        };
+        let response = Response {
+            body: Some(String::from(r#"[{"id": 1}]"#)),
+            ..test_utils::make_response(reqwest::StatusCode::OK)
+        };
+
+        let snippet = assertion_snippet(&request, &response, AssertionFormat::Pytest);
+
+        let expected = [
+            "import requests",
+            "",
+            "",
+            "def test_get_user():",
+            "    response = requests.get(\"https://example.com/users/1\")",
+            "",
+            "    assert response.status_code == 200",
+            "    body = response.json()",
+            "    assert isinstance(body, list)",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(snippet, expected);
+    }
+
+    #[test]
+    fn test_fetch_snippet_escapes_body_and_merges_headers() {
+        let mut request =
+            test_utils::make_request(RequestMethod::Post, "https://example.com/users/1");
+        request.body = Some(String::from("{\n  \"name\": \"it's \\\"hac\\\"\"\n}"));
+        request.body_type = Some(BodyType::Json);
+        request.headers = Some(vec![
+            HeaderMap {
+                pair: (String::from("Accept"), String::from("text/plain")),
+                enabled: true,
+            },
+            HeaderMap {
+                pair: (String::from("accept"), String::from("application/json")),
+                enabled: true,
+            },
+            HeaderMap {
+                pair: (String::from("X-Disabled"), String::from("true")),
+                enabled: false,
+            },
+        ]);
+
+        let snippet = to_fetch(&request);
+
+        let expected = [
+            "fetch(\"https://example.com/users/1\", {",
+            "  method: \"POST\",",
+            "  headers: {",
+            "    \"Accept\": \"text/plain, application/json\",",
+            "    \"Content-Type\": \"application/json\",",
+            "  },",
+            r#"  body: "{\n  \"name\": \"it's \\\"hac\\\"\"\n}","#,
+            "});",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(snippet, expected);
+    }
+
+    #[test]
+    fn test_resolved_text_keeps_unknown_variables() {
+        let mut request = test_utils::make_request(RequestMethod::Post, "{{base}}/users/");
+        request.body = Some(String::from(r#"{"id": "{{id}}"}"#));
+        request.body_type = Some(BodyType::Json);
+        request.headers = Some(vec![HeaderMap {
+            pair: (
+                String::from("Authorization"),
+                String::from("Bearer {{token}}"),
+            ),
+            enabled: true,
+        }]);
+        let variables = HashMap::from([
+            (String::from("base"), String::from("https://example.com")),
+            (String::from("token"), String::from("secret")),
+        ]);
+
+        let text = to_resolved_text(&request, &variables, 10, TrailingSlash::Strip).unwrap();
+
+        let expected = [
+            "POST https://example.com/users",
+            "Authorization: Bearer secret",
+            "content-type: application/json",
+            "",
+            r#"{"id": "{{id}}"}"#,
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn test_resolved_url_keeps_the_query() {
+        let request = test_utils::make_request(RequestMethod::Get, "{{base}}/users/?page={{page}}");
+        let variables = HashMap::from([
+            (String::from("base"), String::from("https://example.com")),
+            (String::from("page"), String::from("2")),
+        ]);
+
+        assert_eq!(
+            to_resolved_url(&request, &variables, 10, TrailingSlash::Strip).unwrap(),
+            "https://example.com/users?page=2"
+        );
+    }
+
+    #[test]
+    fn test_raw_request_from_request() {
+        let mut request =
+            test_utils::make_request(RequestMethod::Post, "https://example.com:8443/users?page=2");
+        request.body = Some(String::from(r#"{"id": 1}"#));
+        request.body_type = Some(BodyType::Json);
+
+        let expected = [
+            "POST /users?page=2 HTTP/1.1",
+            "Host: example.com:8443",
+            "content-type: application/json",
+            "Content-Length: 9",
+            "Connection: close",
+            "",
+            r#"{"id": 1}"#,
+        ]
+        .join("\n");
+
+        assert_eq!(to_raw_request(&request), expected);
+    }
+
+    #[test]
+    fn test_raw_request_from_templated_uri() {
+        let request = test_utils::make_request(
+            RequestMethod::Get,
+            "https://{{host}}/users/{{id}}?page={{page}}",
+        );
+
+        let raw = to_raw_request(&request);
+
+        assert!(raw.starts_with("GET /users/{{id}}?page={{page}} HTTP/1.1\nHost: {{host}}\n"));
+    }
+}
@@ -1,14 +1,20 @@
-use crate::collection::{collection::create_from_form, Collection};
+use crate::collection::collection::{create_from_form, starter_collection};
+use crate::collection::Collection;
 use crate::fs::error::FsError;
 
 use std::path::Path;
 
 #[tracing::instrument(err, skip_all)]
-pub async fn delete_collection<P>(path: P) -> anyhow::Result<(), FsError>
+pub async fn delete_collection<P>(path: P, dry_run: bool) -> anyhow::Result<(), FsError>
 where
     P: AsRef<Path>,
 {
     let path = path.as_ref();
+    // if we are on a dry_run, the file is kept
+    if dry_run {
+        return Ok(());
+    }
+
     tokio::fs::remove_file(path)
         .await
         .map_err(|_| FsError::IOError(format!("failed to delete collection: {:?}", path)))?;
@@ -23,8 +29,20 @@ pub async fn create_collection(
     description: String,
     dry_run: bool,
 ) -> anyhow::Result<Collection, FsError> {
-    let collection = create_from_form(name, description);
+    write_new_collection(create_from_form(name, description), dry_run).await
+}
 
+/// creates the starter collection, holding an example request, on the
+/// collections directory
+#[tracing::instrument(err)]
+pub async fn create_starter_collection(dry_run: bool) -> anyhow::Result<Collection, FsError> {
+    write_new_collection(starter_collection(), dry_run).await
+}
+
+async fn write_new_collection(
+    collection: Collection,
+    dry_run: bool,
+) -> anyhow::Result<Collection, FsError> {
     if collection.path.exists() {
         return Err(FsError::CollectionAlreadyExists(
             collection.path.to_string_lossy().to_string(),
@@ -45,10 +63,15 @@ pub async fn create_collection(
     Ok(collection)
 }
 
-pub async fn sync_collection(collection: Collection) -> anyhow::Result<(), FsError> {
+pub async fn sync_collection(collection: Collection, dry_run: bool) -> anyhow::Result<(), FsError> {
     let collection_str = serde_json::to_string(&collection)
         .map_err(|e| FsError::SerializationError(e.to_string()))?;
 
+    // if we are on a dry_run, we skip syncing
+    if dry_run {
+        return Ok(());
+    }
+
     tokio::fs::write(&collection.path, collection_str)
         .await
         .map_err(|_| {
@@ -62,3 +85,30 @@ pub async fn sync_collection(collection: Collection) -> anyhow::Result<(), FsErr
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_dry_run_never_touches_the_collection_file() {
+        let dir = std::env::temp_dir().join("hac_dry_run_collection");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("api.json");
+        let collection = Collection {
+            path: path.clone(),
+            ..starter_collection()
+        };
+
+        sync_collection(collection.clone(), true).await.unwrap();
+        assert!(!path.exists());
+
+        sync_collection(collection, false).await.unwrap();
+        delete_collection(&path, true).await.unwrap();
+        assert!(path.exists());
+
+        delete_collection(&path, false).await.unwrap();
+        assert!(!path.exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
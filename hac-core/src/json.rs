@@ -0,0 +1,50 @@
+use crate::net::response_decoders::pretty_print_json;
+
+use serde_json::{Map, Value};
+
+/// sorts the keys of every object in `value`, at any depth. Arrays keep the
+/// order of their items, as it usually carries meaning
+pub fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut entries = object.into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_keys(value)))
+                    .collect::<Map<String, Value>>(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        value => value,
+    }
+}
+
+/// parses `content` as json and pretty prints it back with the keys of every
+/// object sorted
+pub fn sort_json_keys(content: &str) -> Result<String, String> {
+    let value = serde_json::from_str::<Value>(content)
+        .map_err(|e| format!("the body is not valid json: {e}"))?;
+    let sorted =
+        serde_json::to_string(&sort_keys(value)).expect("serializing a json value never fails");
+    Ok(pretty_print_json(&sorted))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_are_sorted_at_every_depth() {
+        let body = r#"{"b": [{"z": 1, "a": 2}, 3, 1], "a": {"y": null, "x": true}}"#;
+
+        let sorted = serde_json::to_string(&sort_keys(serde_json::from_str(body).unwrap()));
+
+        assert_eq!(
+            sorted.unwrap(),
+            r#"{"a":{"x":true,"y":null},"b":[{"a":2,"z":1},3,1]}"#
+        );
+        assert!(sort_json_keys("{not json").is_err());
+    }
+}
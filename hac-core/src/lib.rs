@@ -1,6 +1,15 @@
 pub mod collection;
 pub mod command;
+pub mod diff;
+pub mod escape;
+pub mod export;
 pub mod fs;
+pub mod json;
 pub mod net;
+pub mod query;
+pub mod schema;
 pub mod syntax;
+#[cfg(test)]
+mod test_utils;
 pub mod text_object;
+pub mod variables;
@@ -1,6 +1,18 @@
+pub mod etag_cache;
+pub mod network_errors;
+pub mod oauth2;
 pub mod request_client;
+pub mod request_log;
 pub mod request_manager;
 pub mod request_strategies;
 pub mod response_decoders;
+pub mod substitution;
+pub mod uri;
 
-pub use request_manager::handle_request;
+pub use request_client::{set_client_settings, set_follow_redirects};
+pub use request_log::set_request_log;
+pub use request_manager::{
+    handle_request, is_request_queued, set_max_concurrent_requests, RequestContext,
+};
+pub use response_decoders::set_response_indent;
+pub use substitution::substitute_variables;
@@ -0,0 +1,160 @@
+use crate::collection::types::{HeaderMap, Request};
+use crate::net::request_manager::Response;
+use crate::net::response_decoders::pretty_print_json;
+use crate::text_object::TextObject;
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use reqwest::StatusCode;
+
+static ETAG_CACHE: OnceLock<Mutex<HashMap<String, CachedResponse>>> = OnceLock::new();
+
+/// latest response with an `ETag` received by a request, for the method and
+/// url it was sent to
+#[derive(Debug, Clone, PartialEq)]
+struct CachedResponse {
+    etag: String,
+    body: Option<String>,
+}
+
+fn etag_cache() -> &'static Mutex<HashMap<String, CachedResponse>> {
+    ETAG_CACHE.get_or_init(Default::default)
+}
+
+/// key the responses of `request` are cached under. The same request sent to
+/// another url, like after its variables or environment changed, refers to
+/// a different resource, so it doesn't reuse the `ETag` of the previous one
+pub fn cache_key(request: &Request) -> String {
+    format!("{} {} {}", request.id, request.method, request.uri)
+}
+
+/// adds an `If-None-Match` header with the last `ETag` the request received,
+/// unless the request already sets one
+pub fn apply_if_none_match(request: &mut Request) {
+    let cached = etag_cache()
+        .lock()
+        .unwrap()
+        .get(&cache_key(request))
+        .cloned();
+    let Some(cached) = cached else {
+        return;
+    };
+
+    let headers = request.headers.get_or_insert_with(Vec::new);
+    let has_header = headers
+        .iter()
+        .any(|header| header.enabled && header.pair.0.eq_ignore_ascii_case("if-none-match"));
+    if !has_header {
+        headers.push(HeaderMap {
+            pair: (String::from("If-None-Match"), cached.etag),
+            enabled: true,
+        });
+    }
+}
+
+/// stores the `ETag` of a successful response of the request cached under
+/// `key`. A `304 Not Modified` response has no body, so the body of the
+/// response it refers to is put back on it
+pub fn handle_response(key: &str, response: &mut Response) {
+    let mut cache = etag_cache().lock().unwrap();
+
+    if response.status.eq(&Some(StatusCode::NOT_MODIFIED)) {
+        if let Some(body) = cache.get(key).and_then(|cached| cached.body.clone()) {
+            response.pretty_body = Some(TextObject::from(&pretty_print_json(&body)));
+            response.body = Some(body);
+        }
+        return;
+    }
+
+    if !response.status.is_some_and(|status| status.is_success()) {
+        return;
+    }
+
+    let etag = response
+        .headers
+        .as_ref()
+        .and_then(|headers| headers.get(reqwest::header::ETAG))
+        .and_then(|etag| etag.to_str().ok());
+    match etag {
+        Some(etag) => cache.insert(
+            key.to_string(),
+            CachedResponse {
+                etag: etag.to_string(),
+                body: response.body.clone(),
+            },
+        ),
+        None => cache.remove(key),
+    };
+}
+
+/// whether `response` is a `304 Not Modified` whose body was served from the
+/// previous response of the request
+pub fn is_served_from_cache(response: &Response) -> bool {
+    response.status.eq(&Some(StatusCode::NOT_MODIFIED)) && response.body.is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection::types::RequestMethod;
+    use crate::test_utils;
+
+    fn make_response(status: StatusCode, etag: Option<&str>, body: Option<&str>) -> Response {
+        let mut headers = reqwest::header::HeaderMap::new();
+        if let Some(etag) = etag {
+            headers.insert(reqwest::header::ETAG, etag.parse().unwrap());
+        }
+
+        Response {
+            body: body.map(String::from),
+            headers: Some(headers),
+            ..test_utils::make_response(status)
+        }
+    }
+
+    #[test]
+    fn test_not_modified_is_served_from_prior_body() {
+        let mut request = Request {
+            id: String::from("etag_cache_test"),
+            etag_cache: true,
+            ..test_utils::make_request(RequestMethod::Get, "https://api.example.com/users")
+        };
+        apply_if_none_match(&mut request);
+        assert!(request.headers.is_none());
+
+        let mut response = make_response(StatusCode::OK, Some("\"v1\""), Some("[1]"));
+        handle_response(&cache_key(&request), &mut response);
+
+        apply_if_none_match(&mut request);
+        let headers = request.headers.as_ref().unwrap();
+        assert_eq!(
+            headers[0].pair,
+            (String::from("If-None-Match"), String::from("\"v1\""))
+        );
+        // sending again doesn't add the header twice
+        apply_if_none_match(&mut request);
+        assert_eq!(request.headers.as_ref().unwrap().len(), 1);
+
+        let mut response = make_response(StatusCode::NOT_MODIFIED, None, None);
+        handle_response(&cache_key(&request), &mut response);
+        assert_eq!(response.body.as_deref(), Some("[1]"));
+        assert!(is_served_from_cache(&response));
+    }
+
+    #[test]
+    fn test_etag_is_not_sent_to_another_uri() {
+        let mut request = Request {
+            id: String::from("etag_cache_uri_test"),
+            etag_cache: true,
+            ..test_utils::make_request(RequestMethod::Get, "https://api.example.com/users")
+        };
+        let mut response = make_response(StatusCode::OK, Some("\"v1\""), Some("[1]"));
+        handle_response(&cache_key(&request), &mut response);
+
+        request.uri = String::from("https://staging.example.com/users");
+        apply_if_none_match(&mut request);
+
+        assert!(request.headers.is_none());
+    }
+}
@@ -0,0 +1,192 @@
+use crate::net::request_manager::{Response, SentRequest};
+
+use std::error::Error;
+use std::time::Instant;
+
+/// common reasons for a request to fail before a response arrives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkError {
+    Timeout,
+    ConnectionRefused,
+    Dns,
+    Tls,
+    Connect,
+    InvalidUrl,
+    Other,
+}
+
+impl NetworkError {
+    /// figures out why `error` happened, looking through every error that
+    /// caused it, as reqwest wraps the errors of hyper and of the OS
+    pub fn classify(error: &reqwest::Error) -> Self {
+        if error.is_timeout() {
+            return NetworkError::Timeout;
+        }
+        if error.is_builder() {
+            return NetworkError::InvalidUrl;
+        }
+
+        let mut source = error.source();
+        let mut messages = vec![];
+        while let Some(cause) = source {
+            if let Some(io) = cause.downcast_ref::<std::io::Error>() {
+                match io.kind() {
+                    std::io::ErrorKind::ConnectionRefused => {
+                        return NetworkError::ConnectionRefused
+                    }
+                    std::io::ErrorKind::TimedOut => return NetworkError::Timeout,
+                    _ => {}
+                }
+            }
+            messages.push(cause.to_string().to_lowercase());
+            source = cause.source();
+        }
+
+        let mentions = |needles: &[&str]| {
+            messages
+                .iter()
+                .any(|message| needles.iter().any(|needle| message.contains(needle)))
+        };
+        match error.is_connect() {
+            _ if mentions(&["dns error", "failed to lookup address", "name or service"]) => {
+                NetworkError::Dns
+            }
+            _ if mentions(&["certificate", "tls", "ssl", "handshake"]) => NetworkError::Tls,
+            _ if mentions(&["connection refused"]) => NetworkError::ConnectionRefused,
+            true => NetworkError::Connect,
+            false => NetworkError::Other,
+        }
+    }
+
+    /// figures out why a connection we opened ourselves failed, like the
+    /// ones of raw requests
+    pub fn classify_io(error: &std::io::Error) -> Self {
+        let message = error.to_string().to_lowercase();
+        match error.kind() {
+            std::io::ErrorKind::TimedOut => NetworkError::Timeout,
+            std::io::ErrorKind::ConnectionRefused => NetworkError::ConnectionRefused,
+            std::io::ErrorKind::InvalidData => NetworkError::Tls,
+            _ if message.contains("failed to lookup address") => NetworkError::Dns,
+            std::io::ErrorKind::ConnectionReset | std::io::ErrorKind::ConnectionAborted => {
+                NetworkError::Connect
+            }
+            _ => NetworkError::Other,
+        }
+    }
+
+    /// message explaining the failure to the user, `host` is the host the
+    /// request was sent to. `None` when there is nothing better to say than
+    /// the error itself
+    pub fn describe(&self, host: &str) -> Option<String> {
+        match self {
+            NetworkError::Timeout => Some(format!("{host} took too long to respond")),
+            NetworkError::ConnectionRefused => Some(format!(
+                "connection refused by {host}, is the server running?"
+            )),
+            NetworkError::Dns => Some(format!("could not resolve {host}, is the hostname right?")),
+            NetworkError::Tls => Some(format!(
+                "secure connection with {host} failed, is its certificate valid?"
+            )),
+            NetworkError::Connect => Some(format!("could not connect to {host}")),
+            NetworkError::InvalidUrl => Some(String::from(
+                "the url of the request is invalid, are all of its variables set?",
+            )),
+            NetworkError::Other => None,
+        }
+    }
+}
+
+/// human readable cause of `error` along with its technical detail, which is
+/// the full chain of errors that caused it
+pub fn describe_error(error: &reqwest::Error) -> (String, String) {
+    let host = error
+        .url()
+        .and_then(|url| url.host_str())
+        .unwrap_or("the server");
+
+    let mut detail = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        detail.push_str(&format!(": {cause}"));
+        source = cause.source();
+    }
+
+    match NetworkError::classify(error).describe(host) {
+        Some(cause) => (cause, detail),
+        None => (error.to_string(), detail),
+    }
+}
+
+/// response of a request that failed before any response arrived, `sent` is
+/// the request that went out, if it got to be built
+pub fn error_response(
+    cause: String,
+    detail: Option<String>,
+    sent: Option<SentRequest>,
+    start: Instant,
+) -> Response {
+    Response {
+        is_error: true,
+        cause: Some(cause),
+        cause_detail: detail,
+        body: None,
+        pretty_body: None,
+        body_size: None,
+        size: None,
+        headers_size: None,
+        status: None,
+        version: None,
+        headers: None,
+        duration: start.elapsed(),
+        download: None,
+        sent,
+        request_id: None,
+    }
+}
+
+/// response of a request reqwest failed to send, explaining why
+pub fn network_error_response(
+    error: reqwest::Error,
+    sent: Option<SentRequest>,
+    start: Instant,
+) -> Response {
+    let (cause, detail) = describe_error(&error);
+    error_response(cause, Some(detail), sent, start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_connection_refused_is_described() {
+        // nothing listens on the port we just released
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        let error = reqwest::get(format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap_err();
+        let (cause, detail) = describe_error(&error);
+
+        assert_eq!(
+            NetworkError::classify(&error),
+            NetworkError::ConnectionRefused
+        );
+        assert_eq!(
+            cause,
+            "connection refused by 127.0.0.1, is the server running?"
+        );
+        assert!(detail.starts_with("error sending request"));
+    }
+
+    #[test]
+    fn test_other_errors_are_not_described() {
+        assert_eq!(NetworkError::Other.describe("example.com"), None);
+        assert_eq!(
+            NetworkError::Dns.describe("exmaple.com").unwrap(),
+            "could not resolve exmaple.com, is the hostname right?"
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use crate::collection::types::{ClientSettings, OAuth2ClientCredentials};
+use crate::net::request_client::apply_client_settings;
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use lazy_static::lazy_static;
+use serde::Deserialize;
+
+/// tokens are refreshed a little before they expire, so a request doesn't get
+/// rejected because the token expired while it was in flight
+const EXPIRY_MARGIN: Duration = Duration::from_secs(10);
+
+lazy_static! {
+    static ref TOKEN_CACHE: Mutex<HashMap<CacheKey, CachedToken>> = Mutex::new(HashMap::new());
+}
+
+/// tokens are shared by every request using the same client on the same token
+/// endpoint with the same scopes
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    token_url: String,
+    client_id: String,
+    scopes: String,
+}
+
+#[derive(Debug, Clone)]
+struct CachedToken {
+    access_token: String,
+    /// tokens without an `expires_in` are reused until hac is closed
+    expires_at: Option<Instant>,
+}
+
+impl CachedToken {
+    fn is_valid(&self, now: Instant) -> bool {
+        self.expires_at.is_none_or(|expires_at| {
+            now.checked_add(EXPIRY_MARGIN)
+                .is_some_and(|now| now.lt(&expires_at))
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+impl From<&OAuth2ClientCredentials> for CacheKey {
+    fn from(credentials: &OAuth2ClientCredentials) -> Self {
+        CacheKey {
+            token_url: credentials.token_url.clone(),
+            client_id: credentials.client_id.clone(),
+            scopes: credentials.scopes.clone(),
+        }
+    }
+}
+
+/// the token cached for the given credentials while it is still valid,
+/// without fetching one
+pub fn cached_token(credentials: &OAuth2ClientCredentials) -> Option<String> {
+    let key = CacheKey::from(&resolve_credentials(credentials).ok()?);
+    TOKEN_CACHE
+        .lock()
+        .unwrap()
+        .get(&key)
+        .filter(|token| token.is_valid(Instant::now()))
+        .map(|token| token.access_token.clone())
+}
+
+/// gives back an access token for the given credentials, reusing the cached
+/// one while it is valid. Tokens are fetched through a client with the given
+/// settings, as the token endpoint usually sits behind the same proxies and
+/// certificates as the API. The error holds a message meant for the user
+pub async fn get_token(
+    credentials: &OAuth2ClientCredentials,
+    settings: &ClientSettings,
+) -> Result<String, String> {
+    let credentials = resolve_credentials(credentials)?;
+    let key = CacheKey::from(&credentials);
+
+    if let Some(token) = TOKEN_CACHE.lock().unwrap().get(&key) {
+        if token.is_valid(Instant::now()) {
+            return Ok(token.access_token.clone());
+        }
+    }
+
+    let token = fetch_token(&credentials, settings).await?;
+    let access_token = token.access_token.clone();
+    TOKEN_CACHE.lock().unwrap().insert(key, token);
+
+    Ok(access_token)
+}
+
+async fn fetch_token(
+    credentials: &OAuth2ClientCredentials,
+    settings: &ClientSettings,
+) -> Result<CachedToken, String> {
+    let mut form = vec![
+        ("grant_type", "client_credentials"),
+        ("client_id", credentials.client_id.as_str()),
+        ("client_secret", credentials.client_secret.as_str()),
+    ];
+    if !credentials.scopes.is_empty() {
+        form.push(("scope", credentials.scopes.as_str()));
+    }
+
+    let requested_at = Instant::now();
+    let client = apply_client_settings(reqwest::Client::builder(), settings)?
+        .build()
+        .map_err(|e| format!("failed to build the client to fetch oauth2 token: {e}"))?;
+    let response = client
+        .post(&credentials.token_url)
+        .form(&form)
+        .send()
+        .await
+        .map_err(|e| format!("failed to fetch oauth2 token: {e}"))?;
+
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("failed to read oauth2 token response: {e}"))?;
+
+    if !status.is_success() {
+        return Err(format!(
+            "token endpoint responded with {status} when fetching oauth2 token: {body}"
+        ));
+    }
+
+    parse_token_response(&body, requested_at)
+}
+
+fn parse_token_response(body: &str, requested_at: Instant) -> Result<CachedToken, String> {
+    let response = serde_json::from_str::<TokenResponse>(body)
+        .map_err(|e| format!("invalid oauth2 token response: {e}"))?;
+
+    Ok(CachedToken {
+        access_token: response.access_token,
+        expires_at: response
+            .expires_in
+            .and_then(|secs| requested_at.checked_add(Duration::from_secs(secs))),
+    })
+}
+
+/// replaces every `env:NAME` value with the contents of the environment
+/// variable `NAME`
+fn resolve_credentials(
+    credentials: &OAuth2ClientCredentials,
+) -> Result<OAuth2ClientCredentials, String> {
+    let resolve = |value: &str| match value.strip_prefix("env:") {
+        Some(name) => std::env::var(name)
+            .map_err(|_| format!("environment variable {name} used on oauth2 auth is not set")),
+        None => Ok(value.to_string()),
+    };
+
+    let credentials = OAuth2ClientCredentials {
+        token_url: resolve(&credentials.token_url)?,
+        client_id: resolve(&credentials.client_id)?,
+        client_secret: resolve(&credentials.client_secret)?,
+        scopes: resolve(&credentials.scopes)?,
+    };
+
+    if credentials.token_url.is_empty() {
+        return Err(String::from("oauth2 auth is missing the token url"));
+    }
+
+    Ok(credentials)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_expires_before_expires_in() {
+        let now = Instant::now();
+        let token =
+            parse_token_response(r#"{"access_token": "abc", "expires_in": 60}"#, now).unwrap();
+
+        assert_eq!(token.access_token, "abc");
+        assert!(token.is_valid(now));
+        assert!(!token.is_valid(now + Duration::from_secs(55)));
+
+        let token = parse_token_response(r#"{"access_token": "abc"}"#, now).unwrap();
+        assert!(token.is_valid(now + Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn test_only_valid_tokens_are_cached() {
+        let credentials = OAuth2ClientCredentials {
+            token_url: String::from("https://auth.example.com/cached"),
+            client_id: String::from("hac"),
+            client_secret: String::new(),
+            scopes: String::new(),
+        };
+        assert_eq!(cached_token(&credentials), None);
+
+        let now = Instant::now();
+        let token =
+            parse_token_response(r#"{"access_token": "abc", "expires_in": 60}"#, now).unwrap();
+        TOKEN_CACHE
+            .lock()
+            .unwrap()
+            .insert(CacheKey::from(&credentials), token);
+        assert_eq!(cached_token(&credentials).as_deref(), Some("abc"));
+
+        let expired = parse_token_response(r#"{"access_token": "abc", "expires_in": 1}"#, now);
+        TOKEN_CACHE
+            .lock()
+            .unwrap()
+            .insert(CacheKey::from(&credentials), expired.unwrap());
+        assert_eq!(cached_token(&credentials), None);
+    }
+
+    #[test]
+    fn test_resolves_credentials_from_env() {
+        std::env::set_var("HAC_TEST_OAUTH2_SECRET", "super secret");
+        let credentials = OAuth2ClientCredentials {
+            token_url: String::from("https://auth.example.com/token"),
+            client_id: String::from("hac"),
+            client_secret: String::from("env:HAC_TEST_OAUTH2_SECRET"),
+            scopes: String::new(),
+        };
+
+        let resolved = resolve_credentials(&credentials).unwrap();
+        assert_eq!(resolved.client_secret, "super secret");
+
+        let credentials = OAuth2ClientCredentials {
+            client_secret: String::from("env:HAC_TEST_OAUTH2_MISSING"),
+            ..credentials
+        };
+        assert!(resolve_credentials(&credentials)
+            .unwrap_err()
+            .contains("HAC_TEST_OAUTH2_MISSING"));
+    }
+}
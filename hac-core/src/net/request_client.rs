@@ -1,4 +1,44 @@
-use crate::collection::types::Request;
+use crate::collection::types::{AuthMethod, ClientSettings, HttpVersion, Request, RequestMethod};
+use crate::net::network_errors::describe_error;
+use crate::net::oauth2;
+
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+static FOLLOW_REDIRECTS: RwLock<bool> = RwLock::new(true);
+static CLIENT_SETTINGS: RwLock<Option<ClientSettings>> = RwLock::new(None);
+
+/// how long a connection check waits for the server before giving up
+const CONNECTION_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// sets whether redirect responses are followed automatically, when they are
+/// not, the redirect itself is shown and can be followed manually.
+///
+/// setting it again, like when the config is reloaded, only affects requests
+/// dispatched afterwards
+pub fn set_follow_redirects(follow: bool) {
+    *FOLLOW_REDIRECTS.write().unwrap() = follow;
+}
+
+fn follow_redirects() -> bool {
+    *FOLLOW_REDIRECTS.read().unwrap()
+}
+
+/// sets the proxy and TLS settings used by requests whose collection and
+/// request don't set their own.
+///
+/// setting them again, like when the config is reloaded, only affects
+/// requests dispatched afterwards
+pub fn set_client_settings(settings: ClientSettings) {
+    *CLIENT_SETTINGS.write().unwrap() = Some(settings);
+}
+
+/// settings of the config, used beneath the ones of collections and requests
+pub fn global_client_settings() -> ClientSettings {
+    CLIENT_SETTINGS.read().unwrap().clone().unwrap_or_default()
+}
 
 #[derive(Debug)]
 pub struct RequestClient {
@@ -8,8 +48,48 @@ pub struct RequestClient {
 impl RequestClient {
     pub fn new() -> Self {
         RequestClient {
-            client: reqwest::Client::new(),
+            client: decoding_client(false)
+                .build()
+                .expect("failed to build the http client"),
+        }
+    }
+
+    /// builds a client that honors the dns overrides, protocol version and
+    /// client settings of the request, every hostname on `resolve` has to map
+    /// to a valid ip address
+    pub fn for_request(request: &Request) -> Result<Self, String> {
+        let accepts_encoding = effective_headers(request)
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("accept-encoding"));
+        if request.resolve.is_empty()
+            && follow_redirects()
+            && request.http_version.is_auto()
+            && request.client.is_empty()
+            && !accepts_encoding
+        {
+            return Ok(Self::new());
+        }
+
+        let mut builder = decoding_client(accepts_encoding);
+        if !follow_redirects() {
+            builder = builder.redirect(reqwest::redirect::Policy::none());
+        }
+        if let HttpVersion::Http10 = request.http_version {
+            // HTTP/1.0 servers close the connection after responding, so
+            // there is nothing worth keeping on the pool
+            builder = builder.http1_only().pool_max_idle_per_host(0);
         }
+        for (host, ip) in request.resolve.iter() {
+            let ip = ip
+                .parse::<IpAddr>()
+                .map_err(|_| format!("invalid ip address to resolve {host} to: {ip}"))?;
+            // the port is ignored by reqwest, the port of the uri is used instead
+            builder = builder.resolve(host, SocketAddr::new(ip, 0));
+        }
+        builder = apply_client_settings(builder, &request.client)?;
+
+        let client = builder.build().map_err(|e| e.to_string())?;
+        Ok(RequestClient { client })
     }
 
     pub fn get(&self, request: &Request) -> reqwest::RequestBuilder {
@@ -42,20 +122,463 @@ impl RequestClient {
         request: &Request,
         mut request_builder: reqwest::RequestBuilder,
     ) -> reqwest::RequestBuilder {
-        if let Some(ref headers) = request.headers {
-            for header in headers.iter().filter(|header| header.enabled) {
-                let header_name = header.pair.0.clone();
-                let header_value = header.pair.1.clone();
-                request_builder = request_builder.header(header_name, header_value);
-            }
+        for (header_name, header_value) in effective_headers(request) {
+            request_builder = request_builder.header(header_name, header_value);
+        }
+
+        if let HttpVersion::Http10 = request.http_version {
+            request_builder = request_builder.version(reqwest::Version::HTTP_10);
         }
 
         request_builder
     }
 }
 
+/// client builder that decodes gzip, deflate and brotli responses when
+/// `decode` is set. Decoding makes reqwest advertise those encodings on
+/// requests that don't ask for any themselves, so it is only turned on for
+/// requests sending their own `Accept-Encoding`
+fn decoding_client(decode: bool) -> reqwest::ClientBuilder {
+    reqwest::Client::builder()
+        .gzip(decode)
+        .deflate(decode)
+        .brotli(decode)
+}
+
+/// without a proxy, reqwest uses the one of the environment, like `HTTPS_PROXY`
+pub(crate) fn apply_client_settings(
+    mut builder: reqwest::ClientBuilder,
+    settings: &ClientSettings,
+) -> Result<reqwest::ClientBuilder, String> {
+    match settings.proxy.as_deref() {
+        Some("none") => builder = builder.no_proxy(),
+        Some(proxy) => {
+            let proxy = reqwest::Proxy::all(proxy)
+                .map_err(|e| format!("invalid proxy url {proxy}: {e}"))?;
+            builder = builder.proxy(proxy);
+        }
+        None => {}
+    }
+    if settings.accept_invalid_certs.unwrap_or_default() {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+    if let Some(path) = settings.ca_certificate.as_ref() {
+        let certificate = std::fs::read(path)
+            .map_err(|e| e.to_string())
+            .and_then(|pem| reqwest::Certificate::from_pem(&pem).map_err(|e| e.to_string()))
+            .map_err(|e| format!("failed to load certificate {}: {e}", path.display()))?;
+        builder = builder.add_root_certificate(certificate);
+    }
+    if settings.title_case_headers.unwrap_or_default() {
+        builder = builder.http1_title_case_headers();
+    }
+    if let Some(address) = settings.local_address.as_ref() {
+        let address = address
+            .parse::<IpAddr>()
+            .map_err(|_| format!("invalid local address: {address}"))?;
+        builder = builder.local_address(address);
+    }
+    if let Some(timeout) = settings.connect_timeout {
+        builder = builder.connect_timeout(Duration::from_millis(timeout));
+    }
+
+    Ok(builder)
+}
+
+/// parses a list of cookies as written on a `Cookie` header, eg: `a=1; b=2`.
+/// Returns `None` when any of the entries is not a `name=value` pair
+pub fn parse_cookies(value: &str) -> Option<Vec<(String, String)>> {
+    value
+        .split(';')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=')?;
+            let name = name.trim();
+            (!name.is_empty()).then(|| (name.to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// formats cookies the way they are sent on a `Cookie` header
+pub fn format_cookies<'a>(cookies: impl IntoIterator<Item = (&'a String, &'a String)>) -> String {
+    cookies
+        .into_iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// merges the cookies of a request into the cookies already present on the
+/// `Cookie` headers, cookies of the request replace the ones with the same name
+pub fn merge_cookies(existing: &[&str], cookies: &BTreeMap<String, String>) -> String {
+    let mut merged = existing
+        .iter()
+        .flat_map(|value| parse_cookies(value).unwrap_or_default())
+        .filter(|(name, _)| !cookies.contains_key(name))
+        .collect::<Vec<_>>();
+    merged.extend(
+        cookies
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone())),
+    );
+
+    format_cookies(merged.iter().map(|(name, value)| (name, value)))
+}
+
+/// downloads the document at `url` as text, like a spec served by an API,
+/// sending `token` as a bearer token when the document is protected.
+/// Responses with an error status are reported as errors
+pub async fn fetch_document(url: &str, token: Option<&str>) -> anyhow::Result<String> {
+    let mut builder = RequestClient::new().client.get(url);
+    if let Some(token) = token {
+        builder = builder.bearer_auth(token);
+    }
+
+    let response = builder.send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        anyhow::bail!("failed to fetch {url}, server responded with {status}");
+    }
+
+    Ok(response.text().await?)
+}
+
+/// sends a `HEAD` request to `url` through a client with the given settings,
+/// to check whether the server can be reached at all. Any response counts, so
+/// it gives back the status of the response and how long it took to arrive
+pub async fn check_connection(
+    url: &str,
+    settings: &ClientSettings,
+) -> Result<(reqwest::StatusCode, Duration), String> {
+    let client = apply_client_settings(decoding_client(false), settings)?
+        .timeout(CONNECTION_CHECK_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let start = Instant::now();
+    let response = client
+        .head(url)
+        .send()
+        .await
+        .map_err(|e| describe_error(&e).0)?;
+
+    Ok((response.status(), start.elapsed()))
+}
+
+/// every header sent with the request, in the order they are sent. Besides the
+/// enabled headers of the request, requests with a body are sent as json, so
+/// they get a `Content-Type` unless the request sets one itself, a host
+/// override replaces any `Host` header of the request, the cookies of the
+/// request are merged into a single `Cookie` header, the compression of the
+/// request replaces any `Accept-Encoding` header and HTTP/1.0 requests ask for
+/// the connection to be closed. Requests that keep bodies for many content
+/// types are sent with the content type of the body they send instead.
+///
+/// oauth2 requests get their token as an `Authorization` header, which is only
+/// known once fetched, so `<oauth2 token>` stands in for tokens that weren't
+/// fetched yet
+pub fn effective_headers(request: &Request) -> Vec<(String, String)> {
+    let mut headers = request
+        .headers
+        .iter()
+        .flatten()
+        .filter(|header| header.enabled)
+        .map(|header| header.pair.clone())
+        .collect::<Vec<_>>();
+
+    if let Some(host) = request.host_override.as_ref() {
+        headers.retain(|(name, _)| !name.eq_ignore_ascii_case("host"));
+        headers.push(("host".into(), host.clone()));
+    }
+
+    if !request.cookies.is_empty() {
+        let existing = headers
+            .iter()
+            .filter(|(name, _)| name.eq_ignore_ascii_case("cookie"))
+            .map(|(_, value)| value.as_str())
+            .collect::<Vec<_>>();
+        let cookie = merge_cookies(&existing, &request.cookies);
+        headers.retain(|(name, _)| !name.eq_ignore_ascii_case("cookie"));
+        headers.push(("cookie".into(), cookie));
+    }
+
+    if let Some(AuthMethod::OAuth2ClientCredentials(credentials)) = request.auth_method.as_ref() {
+        // requests being sent already carry the token they fetched
+        let has_authorization = headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("authorization"));
+        if !has_authorization {
+            let token =
+                oauth2::cached_token(credentials).unwrap_or_else(|| String::from("<oauth2 token>"));
+            headers.push(("authorization".into(), format!("Bearer {token}")));
+        }
+    }
+
+    if let Some(compression) = request.client.compression {
+        headers.retain(|(name, _)| !name.eq_ignore_ascii_case("accept-encoding"));
+        headers.push((
+            "accept-encoding".into(),
+            compression.accept_encoding().into(),
+        ));
+    }
+
+    if let HttpVersion::Http10 = request.http_version {
+        headers.retain(|(name, _)| !name.eq_ignore_ascii_case("connection"));
+        headers.push(("connection".into(), "close".into()));
+    }
+
+    let has_body = !matches!(request.method, RequestMethod::Get);
+    if has_body && request.body_content_type.is_some() {
+        headers.retain(|(name, _)| !name.eq_ignore_ascii_case("content-type"));
+    }
+
+    let has_content_type = headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case("content-type"));
+
+    if has_body && !has_content_type {
+        headers.push(("content-type".into(), request.active_content_type().into()));
+    }
+
+    headers
+}
+
+/// builds the request that follows a redirect response with the given status
+/// to `location`, which may be relative to `url`, the url that responded.
+/// Auth, headers and cookies are kept, and as browsers do, every redirect
+/// other than 307 and 308 is followed with a GET without body
+pub fn redirect_request(
+    request: &Request,
+    url: &str,
+    status: u16,
+    location: &str,
+) -> Option<Request> {
+    let target = reqwest::Url::parse(url).ok()?.join(location).ok()?;
+    let mut redirect = request.clone();
+    redirect.uri = target.to_string();
+    if !matches!(status, 307 | 308) {
+        redirect.method = RequestMethod::Get;
+        redirect.body = None;
+        redirect.body_type = None;
+    }
+
+    Some(redirect)
+}
+
 impl Default for RequestClient {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection::types::{HeaderMap, OAuth2ClientCredentials};
+    use crate::test_utils;
+
+    #[test]
+    fn test_effective_headers_skip_disabled_and_add_content_type() {
+        let request = Request {
+            headers: Some(vec![
+                HeaderMap {
+                    pair: (String::from("Accept"), String::from("*/*")),
+                    enabled: true,
+                },
+                HeaderMap {
+                    pair: (String::from("X-Disabled"), String::from("true")),
+                    enabled: false,
+                },
+            ]),
+            ..test_utils::make_request(RequestMethod::Post, "https://example.com")
+        };
+
+        assert_eq!(
+            effective_headers(&request),
+            vec![
+                (String::from("Accept"), String::from("*/*")),
+                (
+                    String::from("content-type"),
+                    String::from("application/json")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_oauth2_token_is_an_effective_header() {
+        let mut request = test_utils::make_request(RequestMethod::Get, "https://example.com");
+        request.auth_method = Some(AuthMethod::OAuth2ClientCredentials(
+            OAuth2ClientCredentials {
+                token_url: String::from("https://auth.example.com/never-fetched"),
+                client_id: String::from("hac"),
+                ..Default::default()
+            },
+        ));
+
+        assert_eq!(
+            effective_headers(&request),
+            vec![(
+                String::from("authorization"),
+                String::from("Bearer <oauth2 token>")
+            )]
+        );
+    }
+
+    #[test]
+    fn test_host_override_replaces_the_host_header() {
+        let mut request = test_utils::make_request(RequestMethod::Get, "https://example.com");
+        request.headers = Some(vec![HeaderMap {
+            pair: (String::from("Host"), String::from("old.example.com")),
+            enabled: true,
+        }]);
+        request.host_override = Some(String::from("blue.example.com"));
+
+        assert_eq!(
+            effective_headers(&request),
+            vec![(String::from("host"), String::from("blue.example.com"))]
+        );
+    }
+
+    #[test]
+    fn test_http_10_requests_close_the_connection() {
+        let mut request = test_utils::make_request(RequestMethod::Get, "https://example.com");
+        request.http_version = HttpVersion::Http10;
+
+        let client = RequestClient::for_request(&request).unwrap();
+        let sent = client.get(&request).build().unwrap();
+
+        assert_eq!(sent.version(), reqwest::Version::HTTP_10);
+        assert_eq!(sent.headers()["connection"], "close");
+    }
+
+    #[test]
+    fn test_invalid_resolve_ip_is_an_error() {
+        let mut request = test_utils::make_request(RequestMethod::Post, "https://example.com");
+        request
+            .resolve
+            .insert(String::from("example.com"), String::from("not an ip"));
+
+        assert!(RequestClient::for_request(&request).is_err());
+
+        request
+            .resolve
+            .insert(String::from("example.com"), String::from("127.0.0.1"));
+        assert!(RequestClient::for_request(&request).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_local_address_is_an_error() {
+        let mut request = test_utils::make_request(RequestMethod::Post, "https://example.com");
+        request.client.local_address = Some(String::from("localhost"));
+
+        assert!(RequestClient::for_request(&request).is_err());
+
+        request.client.local_address = Some(String::from("127.0.0.1"));
+        assert!(RequestClient::for_request(&request).is_ok());
+    }
+
+    #[test]
+    fn test_request_cookies_win_over_cookie_header() {
+        let mut request = test_utils::make_request(RequestMethod::Get, "https://example.com");
+        request.headers = Some(vec![HeaderMap {
+            pair: (
+                String::from("Cookie"),
+                String::from("session=old; theme=dark"),
+            ),
+            enabled: true,
+        }]);
+        request
+            .cookies
+            .insert(String::from("session"), String::from("new"));
+        request
+            .cookies
+            .insert(String::from("user"), String::from("42"));
+
+        assert_eq!(
+            effective_headers(&request),
+            vec![(
+                String::from("cookie"),
+                String::from("theme=dark; session=new; user=42")
+            )]
+        );
+    }
+
+    #[test]
+    fn test_compression_replaces_the_accept_encoding_header() {
+        let mut request = test_utils::make_request(RequestMethod::Get, "https://example.com");
+        request.headers = Some(vec![HeaderMap {
+            pair: (String::from("Accept-Encoding"), String::from("gzip")),
+            enabled: true,
+        }]);
+        request.client.compression = Some(hac_config::Compression::Identity);
+
+        let client = RequestClient::for_request(&request).unwrap();
+        let sent = client.get(&request).build().unwrap();
+
+        assert_eq!(sent.headers()["accept-encoding"], "identity");
+        assert_eq!(sent.headers().get_all("accept-encoding").iter().count(), 1);
+    }
+
+    #[test]
+    fn test_switched_body_sets_the_content_type() {
+        let mut request = test_utils::make_request(RequestMethod::Post, "https://example.com");
+        request.headers = Some(vec![HeaderMap {
+            pair: (String::from("Content-Type"), String::from("text/plain")),
+            enabled: true,
+        }]);
+        request.switch_body("application/xml");
+
+        let client = RequestClient::for_request(&request).unwrap();
+        let sent = client.post(&request).build().unwrap();
+
+        assert_eq!(sent.headers()["content-type"], "application/xml");
+        assert_eq!(sent.headers().get_all("content-type").iter().count(), 1);
+    }
+
+    #[test]
+    fn test_redirect_request_resolves_relative_locations() {
+        let request = test_utils::make_request(RequestMethod::Post, "https://example.com");
+
+        let redirect = redirect_request(&request, "https://example.com/a/b", 302, "../c").unwrap();
+        assert_eq!(redirect.uri, "https://example.com/c");
+        assert_eq!(redirect.method, RequestMethod::Get);
+        assert_eq!(redirect.headers, request.headers);
+
+        let redirect =
+            redirect_request(&request, "https://example.com/a", 307, "https://other.com/").unwrap();
+        assert_eq!(redirect.uri, "https://other.com/");
+        assert_eq!(redirect.method, RequestMethod::Post);
+    }
+
+    #[tokio::test]
+    async fn test_check_connection_reports_the_status() {
+        let (port, server) = test_utils::serve_once_with(
+            b"HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n".to_vec(),
+        );
+
+        let url = format!("http://127.0.0.1:{port}/health");
+        let (status, _) = check_connection(&url, &ClientSettings::default())
+            .await
+            .unwrap();
+        let received = server.join().unwrap();
+
+        assert_eq!(status, reqwest::StatusCode::SERVICE_UNAVAILABLE);
+        assert!(received.starts_with("HEAD /health HTTP/1.1"));
+    }
+
+    #[test]
+    fn test_parse_cookies() {
+        assert_eq!(
+            parse_cookies(" a=1;b = 2 ; "),
+            Some(vec![
+                (String::from("a"), String::from("1")),
+                (String::from("b"), String::from("2")),
+            ])
+        );
+        assert_eq!(parse_cookies(""), Some(vec![]));
+        assert_eq!(parse_cookies("a=1; invalid"), None);
+        assert_eq!(parse_cookies("=1"), None);
+    }
+}
@@ -0,0 +1,230 @@
+use crate::net::request_manager::Response;
+
+use std::borrow::Cow;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+/// headers that usually carry credentials, their values are replaced by
+/// `REDACTED_VALUE` on the log unless redaction is disabled. Query parameters
+/// named like them, with `_` in place of `-`, are hidden as well
+const SECRET_HEADERS: [&str; 6] = [
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "x-auth-token",
+];
+
+const REDACTED_VALUE: &str = "<redacted>";
+
+static REQUEST_LOG: RwLock<Option<RequestLog>> = RwLock::new(None);
+
+#[derive(Debug)]
+struct RequestLog {
+    path: PathBuf,
+    redact: bool,
+    /// requests finish concurrently, so writes are serialized to keep every
+    /// entry on its own line
+    lock: Arc<Mutex<()>>,
+}
+
+/// a single line of the request log
+#[derive(Debug, Serialize, PartialEq)]
+struct LogEntry<'a> {
+    /// milliseconds since the unix epoch when the response arrived
+    timestamp: u128,
+    method: &'a str,
+    url: Cow<'a, str>,
+    status: Option<u16>,
+    duration_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+    headers: Vec<(&'a str, &'a str)>,
+}
+
+/// appends every request sent from now on, along with its response, to the
+/// file at `path`, or stops logging them when there is no path. When `redact`
+/// is set, values of headers and query parameters holding credentials are
+/// hidden.
+///
+/// setting it again, like when the config is reloaded, only affects requests
+/// that finish afterwards
+pub fn set_request_log(path: Option<PathBuf>, redact: bool) {
+    *REQUEST_LOG.write().unwrap() = path.map(|path| RequestLog {
+        path,
+        redact,
+        lock: Arc::new(Mutex::new(())),
+    });
+}
+
+/// records a finished request on the request log, when one is configured.
+/// `method` and `url` are only used when the request failed before being
+/// sent, otherwise what was actually sent is logged. The file is written on
+/// a blocking thread, so it never holds up the runtime
+pub fn log_response(method: &str, url: &str, response: &Response) {
+    let log = REQUEST_LOG.read().unwrap();
+    let Some(log) = log.as_ref() else {
+        return;
+    };
+
+    let entry = make_entry(method, url, response, log.redact);
+    let Ok(mut line) = serde_json::to_string(&entry) else {
+        return;
+    };
+    line.push('\n');
+
+    let (path, lock) = (log.path.clone(), log.lock.clone());
+    tokio::task::spawn_blocking(move || {
+        let _guard = lock.lock().unwrap();
+        let written = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(line.as_bytes()));
+        if let Err(e) = written {
+            tracing::error!("failed to write to the request log {path:?}: {e}");
+        }
+    });
+}
+
+fn is_secret(name: &str) -> bool {
+    let name = name.to_ascii_lowercase().replace('_', "-");
+    SECRET_HEADERS.contains(&name.as_str())
+}
+
+/// replaces the values of query parameters that carry credentials, like
+/// `?x_api_key=...`, with `REDACTED_VALUE`
+fn redact_query(url: &str) -> Cow<'_, str> {
+    let Some((base, rest)) = url.split_once('?') else {
+        return Cow::Borrowed(url);
+    };
+    let (query, fragment) = match rest.split_once('#') {
+        Some((query, fragment)) => (query, Some(fragment)),
+        None => (rest, None),
+    };
+
+    let has_secret = query
+        .split('&')
+        .any(|pair| is_secret(pair.split('=').next().unwrap_or_default()));
+    if !has_secret {
+        return Cow::Borrowed(url);
+    }
+
+    let query = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((name, _)) if is_secret(name) => format!("{name}={REDACTED_VALUE}"),
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+    match fragment {
+        Some(fragment) => Cow::Owned(format!("{base}?{query}#{fragment}")),
+        None => Cow::Owned(format!("{base}?{query}")),
+    }
+}
+
+fn make_entry<'a>(
+    method: &'a str,
+    url: &'a str,
+    response: &'a Response,
+    redact: bool,
+) -> LogEntry<'a> {
+    let (url, headers) = match response.sent.as_ref() {
+        Some(sent) => (sent.url.as_str(), sent.headers.as_slice()),
+        None => (url, [].as_slice()),
+    };
+
+    let headers = headers
+        .iter()
+        .map(|(name, value)| match redact && is_secret(name) {
+            true => (name.as_str(), REDACTED_VALUE),
+            false => (name.as_str(), value.as_str()),
+        })
+        .collect();
+    let url = match redact {
+        true => redact_query(url),
+        false => Cow::Borrowed(url),
+    };
+
+    LogEntry {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis(),
+        method,
+        url,
+        status: response.status.map(|status| status.as_u16()),
+        duration_ms: response.duration.as_millis(),
+        error: response.cause.as_deref(),
+        headers,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::request_manager::SentRequest;
+    use crate::test_utils;
+
+    use std::time::Duration;
+
+    fn make_response() -> Response {
+        Response {
+            duration: Duration::from_millis(42),
+            sent: Some(SentRequest {
+                request_line: String::from("GET /users HTTP/1.1"),
+                url: String::from("https://api.example.com/users"),
+                headers: vec![
+                    (String::from("Authorization"), String::from("Bearer abc")),
+                    (String::from("accept"), String::from("*/*")),
+                ],
+                body: None,
+            }),
+            ..test_utils::make_response(reqwest::StatusCode::OK)
+        }
+    }
+
+    #[test]
+    fn test_secret_headers_are_redacted() {
+        let response = make_response();
+        let entry = make_entry("GET", "{{base}}/users", &response, true);
+
+        assert_eq!(entry.url, "https://api.example.com/users");
+        assert_eq!(entry.status, Some(200));
+        assert_eq!(entry.duration_ms, 42);
+        assert_eq!(
+            entry.headers,
+            vec![("Authorization", REDACTED_VALUE), ("accept", "*/*")]
+        );
+
+        let entry = make_entry("GET", "{{base}}/users", &response, false);
+        assert_eq!(entry.headers[0], ("Authorization", "Bearer abc"));
+    }
+
+    #[test]
+    fn test_secret_query_values_are_redacted() {
+        assert_eq!(
+            redact_query("https://example.com/users?page=2&X_Api_Key=abc#top"),
+            "https://example.com/users?page=2&X_Api_Key=<redacted>#top"
+        );
+        assert_eq!(
+            redact_query("https://example.com/users?page=2"),
+            "https://example.com/users?page=2"
+        );
+
+        let mut response = make_response();
+        response.sent.as_mut().unwrap().url =
+            String::from("https://api.example.com/users?authorization=abc");
+        let entry = make_entry("GET", "{{base}}/users", &response, true);
+        assert_eq!(
+            entry.url,
+            "https://api.example.com/users?authorization=<redacted>"
+        );
+    }
+}
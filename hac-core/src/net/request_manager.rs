@@ -1,12 +1,55 @@
-use crate::collection::types::{BodyType, Request};
-use crate::net::request_strategies::{http_strategy::HttpResponse, RequestStrategy};
+use crate::collection::types::{
+    AuthMethod, BodyType, ClientSettings, Collection, HeaderMap as RequestHeader, Request,
+    TrailingSlash,
+};
+use crate::net::etag_cache;
+use crate::net::oauth2;
+use crate::net::request_client::global_client_settings;
+use crate::net::request_log;
+use crate::net::request_strategies::download_strategy::DownloadResponse;
+use crate::net::request_strategies::http_strategy::{FileBodyResponse, HttpResponse};
+use crate::net::request_strategies::raw_strategy::RawResponse;
+use crate::net::request_strategies::RequestStrategy;
+use crate::net::substitution::substitute_variables;
+use crate::net::uri::{normalize_trailing_slash, resolve_base_url};
 use crate::text_object::{Readonly, TextObject};
+use crate::variables::DEFAULT_MAX_VARIABLE_DEPTH;
 
-use std::sync::{Arc, RwLock};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
 use std::time::Duration;
 
+use lazy_static::lazy_static;
 use reqwest::header::{HeaderMap, HeaderValue};
 use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::Semaphore;
+
+static REQUEST_PERMITS: OnceLock<Semaphore> = OnceLock::new();
+
+lazy_static! {
+    static ref QUEUED_REQUESTS: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+/// sets how many requests can be in flight at the same time, every request
+/// dispatched after the limit is reached waits until another one finishes.
+///
+/// this can only be set once, before the first request is dispatched, later
+/// calls are ignored. A limit of 0 is treated as 1.
+pub fn set_max_concurrent_requests(limit: usize) {
+    if REQUEST_PERMITS.set(Semaphore::new(limit.max(1))).is_err() {
+        tracing::warn!("tried to set the max concurrent requests more than once");
+    }
+}
+
+/// wether the request with the given id is waiting for a free slot to be sent
+pub fn is_request_queued(request_id: &str) -> bool {
+    QUEUED_REQUESTS.lock().unwrap().contains(request_id)
+}
+
+fn request_permits() -> &'static Semaphore {
+    REQUEST_PERMITS.get_or_init(|| Semaphore::new(hac_config::DEFAULT_MAX_CONCURRENT_REQUESTS))
+}
 
 #[derive(Debug, PartialEq)]
 pub struct Response {
@@ -15,11 +58,101 @@ pub struct Response {
     pub headers: Option<HeaderMap<HeaderValue>>,
     pub duration: Duration,
     pub status: Option<reqwest::StatusCode>,
+    /// protocol version the server responded with
+    pub version: Option<reqwest::Version>,
     pub headers_size: Option<u64>,
     pub body_size: Option<u64>,
     pub size: Option<u64>,
     pub is_error: bool,
     pub cause: Option<String>,
+    /// technical detail of `cause`, like the chain of errors that caused it
+    pub cause_detail: Option<String>,
+    /// only present when the request streams its body into a file instead of
+    /// keeping it in memory
+    pub download: Option<DownloadProgress>,
+    /// the request exactly as it was dispatched, after every header, auth and
+    /// body was applied. `None` when the request failed before being built
+    pub sent: Option<SentRequest>,
+    /// id of the request this responds to, filled in once the response is
+    /// sent back, so it can be told apart from responses of other requests
+    pub request_id: Option<String>,
+}
+
+impl Response {
+    /// wether this response is still streaming its body into a file, which means
+    /// more responses for the same request are on their way
+    pub fn is_downloading(&self) -> bool {
+        self.download
+            .as_ref()
+            .is_some_and(|download| !download.finished)
+    }
+}
+
+/// what was actually put on the wire for a request, which may differ from
+/// what was configured on it after defaults, auth and substitutions
+#[derive(Debug, Clone, PartialEq)]
+pub struct SentRequest {
+    /// the request line, eg: `GET /users?page=2 HTTP/1.1`
+    pub request_line: String,
+    /// full url the request was sent to, after normalizations
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+impl From<&reqwest::Request> for SentRequest {
+    fn from(request: &reqwest::Request) -> Self {
+        let url = request.url();
+        let target = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+        let request_line = format!("{} {} {:?}", request.method(), target, request.version());
+
+        // hyper fills the host header when sending, we add it here so the
+        // headers match what the server receives
+        let mut headers = vec![];
+        if !request.headers().contains_key(reqwest::header::HOST) {
+            if let Some(host) = url.host_str() {
+                let host = match url.port() {
+                    Some(port) => format!("{host}:{port}"),
+                    None => host.to_string(),
+                };
+                headers.push((String::from("host"), host));
+            }
+        }
+        headers.extend(request.headers().iter().map(|(name, value)| {
+            (
+                name.to_string(),
+                String::from_utf8_lossy(value.as_bytes()).to_string(),
+            )
+        }));
+
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|bytes| String::from_utf8_lossy(bytes).to_string());
+
+        SentRequest {
+            request_line,
+            url: url.to_string(),
+            headers,
+            body,
+        }
+    }
+}
+
+/// progress of a response body being streamed into a file on disk
+#[derive(Debug, Clone, PartialEq)]
+pub struct DownloadProgress {
+    /// path of the file the response body is written to
+    pub path: PathBuf,
+    /// amount of bytes written to the file so far
+    pub downloaded: u64,
+    /// total size of the body, taken from the `Content-Length` header, when
+    /// the server doesn't tell us the size this will be `None`
+    pub total: Option<u64>,
+    pub finished: bool,
 }
 
 pub struct RequestManager;
@@ -57,21 +190,490 @@ impl From<&str> for ContentType {
     }
 }
 
+/// what a request is sent with that comes from its collection instead of the
+/// request itself
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    /// base url of the active environment, prepended to relative uris
+    pub base_url: Option<String>,
+    pub trailing_slash: TrailingSlash,
+    /// proxy and TLS settings of the collection, used when the request
+    /// doesn't set its own
+    pub client: ClientSettings,
+    /// variables of the collection, its env file and the active environment,
+    /// substituted on the uri, including the base url, headers and body
+    pub variables: HashMap<String, String>,
+    pub max_variable_depth: usize,
+    /// directory of the collection, relative paths of the request are taken
+    /// from it
+    pub dir: PathBuf,
+}
+
+impl Default for RequestContext {
+    fn default() -> Self {
+        RequestContext {
+            base_url: None,
+            trailing_slash: TrailingSlash::default(),
+            client: ClientSettings::default(),
+            variables: HashMap::default(),
+            max_variable_depth: DEFAULT_MAX_VARIABLE_DEPTH,
+            dir: PathBuf::default(),
+        }
+    }
+}
+
+impl RequestContext {
+    /// context of a request sent from `collection` while `env` is active,
+    /// variables are read from `env_file`, or the env file of the collection
+    /// when there is none
+    pub fn from_collection(
+        collection: &Collection,
+        env: Option<&str>,
+        env_file: Option<&Path>,
+        max_variable_depth: usize,
+    ) -> Result<Self, String> {
+        Ok(RequestContext {
+            base_url: collection.base_url(env).map(String::from),
+            trailing_slash: collection.trailing_slash,
+            client: collection.client.clone(),
+            variables: collection.variables(env, env_file)?,
+            max_variable_depth,
+            dir: collection
+                .path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// sends `request` on a background task, the response is sent through
+/// `response_tx`. A relative uri is resolved against the base url of
+/// `context`, and then every variable of `context` is substituted, so the
+/// request is sent exactly as the preview shows it
 #[tracing::instrument(skip_all)]
-pub fn handle_request(request: &Arc<RwLock<Request>>, response_tx: UnboundedSender<Response>) {
-    let request = request.read().unwrap().clone();
+pub fn handle_request(
+    request: &Arc<RwLock<Request>>,
+    context: &RequestContext,
+    response_tx: UnboundedSender<Response>,
+) {
+    let mut request = request.read().unwrap().clone();
+    request.uri = resolve_base_url(&request.uri, context.base_url.as_deref());
+    let substituted =
+        substitute_variables(&mut request, &context.variables, context.max_variable_depth)
+            .map_err(|e| e.to_string());
+    request.uri = normalize_trailing_slash(&request.uri, context.trailing_slash);
+    request.client = request
+        .client
+        .or(&context.client)
+        .or(&global_client_settings());
+    let download_path = request.download_path(&context.dir);
+    let (method, uri) = (request.method.to_string(), request.uri.clone());
     tokio::spawn(async move {
-        let response = match request.body_type.as_ref() {
+        let permits = request_permits();
+        // the permit is held until the response is sent back, the semaphore is never
+        // closed so acquiring can't fail. The request is only queued when no permit
+        // was free to take right away
+        let _permit = match permits.try_acquire() {
+            Ok(permit) => permit,
+            Err(_) => {
+                QUEUED_REQUESTS.lock().unwrap().insert(request.id.clone());
+                let permit = permits
+                    .acquire()
+                    .await
+                    .expect("request semaphore was closed");
+                QUEUED_REQUESTS.lock().unwrap().remove(&request.id);
+                permit
+            }
+        };
+        let request_id = request.id.clone();
+
+        let prepared = match substituted {
+            Ok(()) => match load_file_body(&request).await {
+                Ok(file_body) => apply_auth(request)
+                    .await
+                    .map(|request| (request, file_body)),
+                Err(cause) => Err(cause),
+            },
+            Err(cause) => Err(cause),
+        };
+        let (mut request, file_body) = match prepared {
+            Ok(prepared) => prepared,
+            Err(cause) => {
+                let response = Response {
+                    is_error: true,
+                    cause: Some(cause),
+                    cause_detail: None,
+                    body: None,
+                    pretty_body: None,
+                    body_size: None,
+                    size: None,
+                    headers_size: None,
+                    status: None,
+                    version: None,
+                    headers: None,
+                    duration: Duration::default(),
+                    download: None,
+                    sent: None,
+                    request_id: Some(request_id),
+                };
+                request_log::log_response(&method, &uri, &response);
+                send_response(&response_tx, response);
+                return;
+            }
+        };
+
+        let etag_key = request.etag_cache.then(|| etag_cache::cache_key(&request));
+        if etag_key.is_some() {
+            etag_cache::apply_if_none_match(&mut request);
+        }
+
+        let mut response = match (download_path, request.body_type.as_ref()) {
+            // requests that download to a file report their progress through the same
+            // channel, so the strategy needs its own sender
+            (Some(path), _) => {
+                let strategy = DownloadResponse::new(path, response_tx.clone(), file_body);
+                RequestManager::handle(strategy, request).await
+            }
             // if we dont have a body type, this is a GET request, so we use HTTP strategy
-            None => RequestManager::handle(HttpResponse, request).await,
-            Some(body_type) => match body_type {
-                BodyType::Json => RequestManager::handle(HttpResponse, request).await,
+            (None, None) => RequestManager::handle(HttpResponse, request).await,
+            (None, Some(body_type)) => match body_type {
+                BodyType::Json | BodyType::Text => {
+                    RequestManager::handle(HttpResponse, request).await
+                }
+                BodyType::File(_) => {
+                    let strategy = FileBodyResponse::new(file_body.unwrap_or_default());
+                    RequestManager::handle(strategy, request).await
+                }
+                BodyType::Raw => RequestManager::handle(RawResponse, request).await,
             },
         };
 
-        response_tx
-            .send(response)
-            .is_err()
-            .then(|| std::process::abort());
+        if let Some(etag_key) = etag_key {
+            etag_cache::handle_response(&etag_key, &mut response);
+        }
+        response.request_id = Some(request_id);
+        request_log::log_response(&method, &uri, &response);
+        send_response(&response_tx, response);
     });
 }
+
+/// the receiver is gone when the screen that sent the request was closed
+/// before it responded, in which case nobody cares about the response anymore
+fn send_response(response_tx: &UnboundedSender<Response>, response: Response) {
+    if response_tx.send(response).is_err() {
+        tracing::debug!("discarding the response of a request nobody is waiting on");
+    }
+}
+
+/// adds the headers required by the auth method of the request, fetching a
+/// token first when needed. Failing to get a token gives back the cause, so we
+/// can error before anything is sent
+async fn apply_auth(mut request: Request) -> Result<Request, String> {
+    let Some(AuthMethod::OAuth2ClientCredentials(credentials)) = request.auth_method.as_ref()
+    else {
+        return Ok(request);
+    };
+
+    let token = oauth2::get_token(credentials, &request.client).await?;
+    request
+        .headers
+        .get_or_insert_with(Vec::new)
+        .push(RequestHeader {
+            pair: (String::from("Authorization"), format!("Bearer {token}")),
+            enabled: true,
+        });
+
+    Ok(request)
+}
+
+/// when the request body lives on a file, reads the bytes of the file so they
+/// are sent untouched, binary files included. Failing to read the file gives
+/// back the cause, so we can error before anything is sent
+async fn load_file_body(request: &Request) -> Result<Option<Vec<u8>>, String> {
+    let Some(BodyType::File(path)) = request.body_type.as_ref() else {
+        return Ok(None);
+    };
+
+    tokio::fs::read(path)
+        .await
+        .map(Some)
+        .map_err(|e| format!("failed to read body from file {path:?}: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection::types::RequestMethod;
+    use crate::test_utils;
+
+    async fn send(request: Request, context: &RequestContext) -> Response {
+        let (response_tx, mut response_rx) = tokio::sync::mpsc::unbounded_channel();
+        handle_request(&Arc::new(RwLock::new(request)), context, response_tx);
+        response_rx.recv().await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_variables_are_substituted_before_sending() {
+        let (port, server) = test_utils::serve_once();
+        let request = Request {
+            headers: Some(vec![RequestHeader {
+                pair: (
+                    String::from("Authorization"),
+                    String::from("Bearer {{token}}"),
+                ),
+                enabled: true,
+            }]),
+            body: Some(String::from(r#"{"name": "{{name}}"}"#)),
+            body_type: Some(BodyType::Json),
+            ..test_utils::make_request(RequestMethod::Post, "http://127.0.0.1:{{port}}/users")
+        };
+        let context = RequestContext {
+            variables: HashMap::from([
+                (String::from("port"), port.to_string()),
+                (String::from("token"), String::from("secret")),
+                (String::from("name"), String::from("hac")),
+            ]),
+            ..Default::default()
+        };
+
+        let response = send(request, &context).await;
+        let received = server.join().unwrap();
+        assert_eq!(response.request_id.as_deref(), Some("any_id"));
+        let sent = response.sent.unwrap();
+
+        assert_eq!(sent.url, format!("http://127.0.0.1:{port}/users"));
+        assert!(sent
+            .headers
+            .contains(&(String::from("authorization"), String::from("Bearer secret"))));
+        assert_eq!(sent.body.as_deref(), Some(r#"{"name": "hac"}"#));
+        assert!(received.starts_with("POST /users HTTP/1.1"));
+        assert!(received.ends_with(r#"{"name": "hac"}"#));
+    }
+
+    #[tokio::test]
+    async fn test_env_file_variables_are_sent() {
+        let (port, server) = test_utils::serve_once();
+        let dir = std::env::temp_dir().join("hac_test_env_file_variables_are_sent");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(".env"), format!("port={port}\ntoken=secret\n")).unwrap();
+        let collection = Collection {
+            env_file: Some(PathBuf::from(".env")),
+            path: dir.join("api.json"),
+            ..Default::default()
+        };
+        let request = Request {
+            headers: Some(vec![RequestHeader {
+                pair: (String::from("X-Token"), String::from("{{token}}")),
+                enabled: true,
+            }]),
+            ..test_utils::make_request(RequestMethod::Get, "http://127.0.0.1:{{port}}/users")
+        };
+
+        let context =
+            RequestContext::from_collection(&collection, None, None, DEFAULT_MAX_VARIABLE_DEPTH);
+        std::fs::remove_dir_all(&dir).unwrap();
+        let response = send(request, &context.unwrap()).await;
+        let received = server.join().unwrap();
+
+        assert_eq!(
+            response.sent.unwrap().url,
+            format!("http://127.0.0.1:{port}/users")
+        );
+        assert!(received.starts_with("GET /users HTTP/1.1"));
+        assert!(received.to_lowercase().contains("x-token: secret"));
+    }
+
+    #[tokio::test]
+    async fn test_templated_base_url_is_resolved() {
+        let (port, server) = test_utils::serve_once();
+        let request = test_utils::make_request(RequestMethod::Get, "/users/{{id}}");
+        let context = RequestContext {
+            base_url: Some(String::from("{{scheme}}://{{host}}")),
+            variables: HashMap::from([
+                (String::from("scheme"), String::from("http")),
+                (String::from("host"), format!("127.0.0.1:{port}")),
+                (String::from("id"), String::from("1")),
+            ]),
+            ..Default::default()
+        };
+
+        let response = send(request, &context).await;
+        let received = server.join().unwrap();
+
+        assert_eq!(
+            response.sent.unwrap().url,
+            format!("http://127.0.0.1:{port}/users/1")
+        );
+        assert!(received.starts_with("GET /users/1 HTTP/1.1"));
+    }
+
+    #[tokio::test]
+    async fn test_variables_are_substituted_on_raw_requests() {
+        let (port, server) = test_utils::serve_once();
+        let request = Request {
+            body: Some(String::from(
+                "GET /users HTTP/1.1\nHost: {{host}}\nX-Token: {{token}}\nConnection: close\n\n",
+            )),
+            raw_crlf: true,
+            body_type: Some(BodyType::Raw),
+            ..test_utils::make_request(RequestMethod::Get, "http://{{host}}/users")
+        };
+        let context = RequestContext {
+            variables: HashMap::from([
+                (String::from("host"), format!("127.0.0.1:{port}")),
+                (String::from("token"), String::from("secret")),
+            ]),
+            ..Default::default()
+        };
+
+        let response = send(request, &context).await;
+        let received = server.join().unwrap();
+
+        assert!(!response.is_error);
+        assert!(received.contains(&format!("Host: 127.0.0.1:{port}\r\n")));
+        assert!(received.contains("X-Token: secret\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_downloads_record_the_sent_request() {
+        let (port, server) = test_utils::serve_once_with(
+            b"HTTP/1.1 200 OK\r\nContent-Length: 3\r\n\r\nhac".to_vec(),
+        );
+        let path = std::env::temp_dir().join("hac_test_downloads_record_the_sent_request");
+        let request = Request {
+            download_to: Some(path.to_string_lossy().to_string()),
+            ..test_utils::make_request(RequestMethod::Get, &format!("http://127.0.0.1:{port}/file"))
+        };
+
+        let response = send(request, &RequestContext::default()).await;
+        server.join().unwrap();
+        let downloaded = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(!response.is_error);
+        assert_eq!(downloaded, b"hac");
+        assert_eq!(
+            response.sent.unwrap().url,
+            format!("http://127.0.0.1:{port}/file")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_download_leaves_no_file() {
+        // the connection is closed before the promised body is complete
+        let (port, server) = test_utils::serve_once_with(
+            b"HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\nhac".to_vec(),
+        );
+        let dir = std::env::temp_dir().join("hac_test_failed_download_leaves_no_file");
+        std::fs::create_dir_all(&dir).unwrap();
+        let request = Request {
+            download_to: Some(String::from("file.bin")),
+            ..test_utils::make_request(RequestMethod::Get, &format!("http://127.0.0.1:{port}/file"))
+        };
+        let context = RequestContext {
+            dir: dir.clone(),
+            ..Default::default()
+        };
+
+        let response = send(request, &context).await;
+        server.join().unwrap();
+        let files = std::fs::read_dir(&dir).unwrap().count();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(response.is_error);
+        assert!(response.sent.is_some());
+        assert_eq!(files, 0);
+    }
+
+    #[tokio::test]
+    async fn test_cyclic_variables_fail_before_sending() {
+        let request = test_utils::make_request(RequestMethod::Get, "http://127.0.0.1/{{a}}");
+        let context = RequestContext {
+            variables: HashMap::from([
+                (String::from("a"), String::from("{{b}}")),
+                (String::from("b"), String::from("{{a}}")),
+            ]),
+            ..Default::default()
+        };
+
+        let response = send(request, &context).await;
+
+        assert!(response.is_error);
+        assert!(response.sent.is_none());
+        assert_eq!(response.request_id.as_deref(), Some("any_id"));
+        assert!(response
+            .cause
+            .unwrap()
+            .contains("circular variable reference"));
+    }
+
+    #[tokio::test]
+    async fn test_load_file_body() {
+        let path = std::env::temp_dir().join("hac_test_load_file_body.bin");
+        std::fs::write(&path, [0xff, 0x00, 0x89]).unwrap();
+
+        let request = Request {
+            body_type: Some(BodyType::File(path.to_string_lossy().to_string())),
+            ..test_utils::make_request(RequestMethod::Post, "https://example.com")
+        };
+        let body = load_file_body(&request).await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(body, Some(vec![0xff, 0x00, 0x89]));
+    }
+
+    #[tokio::test]
+    async fn test_file_bodies_are_sent_as_bytes() {
+        let path = std::env::temp_dir().join("hac_test_send_file_body.bin");
+        std::fs::write(&path, [0xff, 0x00, 0x89]).unwrap();
+        let (port, server) = test_utils::serve_once();
+        let request = Request {
+            body_type: Some(BodyType::File(path.to_string_lossy().to_string())),
+            ..test_utils::make_request(RequestMethod::Post, &format!("http://127.0.0.1:{port}"))
+        };
+
+        let response = send(request, &RequestContext::default()).await;
+        let received = server.join().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(!response.is_error);
+        assert!(received.contains("content-length: 3\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_load_missing_file_body() {
+        let request = Request {
+            body_type: Some(BodyType::File(String::from(
+                "/this/file/does/not/exist.json",
+            ))),
+            ..test_utils::make_request(RequestMethod::Post, "https://example.com")
+        };
+
+        let cause = load_file_body(&request).await.unwrap_err();
+
+        assert!(cause.contains("/this/file/does/not/exist.json"));
+    }
+
+    #[test]
+    fn test_sent_request_reflects_the_built_request() {
+        let request = reqwest::Client::new()
+            .post("https://example.com:8080/users?page=2")
+            .header("accept", "*/*")
+            .body(r#"{"any":"body"}"#)
+            .build()
+            .unwrap();
+
+        let sent = SentRequest::from(&request);
+
+        assert_eq!(sent.request_line, "POST /users?page=2 HTTP/1.1");
+        assert_eq!(
+            sent.headers,
+            vec![
+                (String::from("host"), String::from("example.com:8080")),
+                (String::from("accept"), String::from("*/*")),
+            ]
+        );
+        assert_eq!(sent.body.as_deref(), Some(r#"{"any":"body"}"#));
+    }
+}
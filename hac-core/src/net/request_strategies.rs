@@ -1,4 +1,6 @@
+pub mod download_strategy;
 pub mod http_strategy;
+pub mod raw_strategy;
 
 use std::future::Future;
 
@@ -0,0 +1,185 @@
+use crate::collection::types::{Request, RequestMethod};
+use crate::net::network_errors::{error_response, network_error_response};
+use crate::net::request_client::RequestClient;
+use crate::net::request_manager::{DownloadProgress, Response, SentRequest};
+use crate::net::request_strategies::RequestStrategy;
+
+use std::ops::Add;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// minimum amount of time between two progress updates, sending one for every
+/// chunk would flood the channel on fast connections
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// streams the response body straight into a file instead of buffering it in
+/// memory, sending partial responses through `progress_tx` while downloading
+pub struct DownloadResponse {
+    path: PathBuf,
+    progress_tx: UnboundedSender<Response>,
+    /// bytes of the file the body of the request lives on, sent instead of
+    /// the body written on the request
+    file_body: Option<Vec<u8>>,
+}
+
+impl DownloadResponse {
+    pub fn new(
+        path: PathBuf,
+        progress_tx: UnboundedSender<Response>,
+        file_body: Option<Vec<u8>>,
+    ) -> Self {
+        DownloadResponse {
+            path,
+            progress_tx,
+            file_body,
+        }
+    }
+}
+
+impl RequestStrategy for DownloadResponse {
+    async fn handle(&self, request: Request) -> Response {
+        let now = Instant::now();
+        let client = match RequestClient::for_request(&request) {
+            Ok(client) => client,
+            Err(cause) => return error_response(cause, None, None, now),
+        };
+        let body = match self.file_body.as_ref() {
+            Some(file_body) => file_body.clone(),
+            None => request.body.clone().unwrap_or_default().into_bytes(),
+        };
+
+        let request_builder = match request.method {
+            RequestMethod::Get => client.get(&request),
+            RequestMethod::Post => client.post(&request).body(body),
+            RequestMethod::Put => client.put(&request).body(body),
+            RequestMethod::Patch => client.patch(&request).body(body),
+            RequestMethod::Delete => client.delete(&request).body(body),
+        };
+
+        let (client, built) = request_builder.build_split();
+        let built = match built {
+            Ok(built) => built,
+            Err(e) => return network_error_response(e, None, now),
+        };
+        let sent = SentRequest::from(&built);
+
+        let mut response = match client.execute(built).await {
+            Ok(response) => response,
+            Err(e) => return network_error_response(e, Some(sent), now),
+        };
+
+        // the body is written next to the destination and only moved over it once
+        // complete, so a failed download never leaves a truncated file behind
+        let partial_path = partial_path(&self.path);
+        let mut file = match tokio::fs::File::create(&partial_path).await {
+            Ok(file) => file,
+            Err(e) => return error_response(e.to_string(), None, Some(sent), now),
+        };
+
+        let headers = response.headers().to_owned();
+        let status = response.status();
+        let version = response.version();
+        let headers_size: u64 = headers
+            .iter()
+            .map(|(k, v)| k.as_str().len().add(v.as_bytes().len()).add(4) as u64)
+            .sum();
+
+        let mut progress = DownloadProgress {
+            path: self.path.clone(),
+            downloaded: 0,
+            total: response.content_length(),
+            finished: false,
+        };
+        let mut last_update = Instant::now();
+
+        loop {
+            match response.chunk().await {
+                Ok(Some(chunk)) => {
+                    if let Err(e) = file.write_all(&chunk).await {
+                        discard(&partial_path).await;
+                        return error_response(e.to_string(), None, Some(sent), now);
+                    }
+                    progress.downloaded = progress.downloaded.add(chunk.len() as u64);
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    discard(&partial_path).await;
+                    return network_error_response(e, Some(sent), now);
+                }
+            }
+
+            if last_update.elapsed().ge(&PROGRESS_INTERVAL) {
+                last_update = Instant::now();
+                let partial = Response {
+                    body: None,
+                    pretty_body: None,
+                    headers: Some(headers.clone()),
+                    duration: now.elapsed(),
+                    status: Some(status),
+                    version: Some(version),
+                    headers_size: Some(headers_size),
+                    body_size: Some(progress.downloaded),
+                    size: Some(headers_size.add(progress.downloaded)),
+                    is_error: false,
+                    cause: None,
+                    cause_detail: None,
+                    download: Some(progress.clone()),
+                    sent: Some(sent.clone()),
+                    request_id: Some(request.id.clone()),
+                };
+
+                if self.progress_tx.send(partial).is_err() {
+                    tracing::error!("failed to send download progress through channel");
+                }
+            }
+        }
+
+        if let Err(e) = file.flush().await {
+            discard(&partial_path).await;
+            return error_response(e.to_string(), None, Some(sent), now);
+        }
+        drop(file);
+
+        if let Err(e) = tokio::fs::rename(&partial_path, &self.path).await {
+            discard(&partial_path).await;
+            return error_response(e.to_string(), None, Some(sent), now);
+        }
+
+        progress.finished = true;
+
+        Response {
+            body: None,
+            pretty_body: None,
+            headers: Some(headers),
+            duration: now.elapsed(),
+            status: Some(status),
+            version: Some(version),
+            headers_size: Some(headers_size),
+            body_size: Some(progress.downloaded),
+            size: Some(headers_size.add(progress.downloaded)),
+            is_error: false,
+            cause: None,
+            cause_detail: None,
+            download: Some(progress),
+            sent: Some(sent),
+            request_id: None,
+        }
+    }
+}
+
+/// file the body is written to while it is being downloaded
+fn partial_path(path: &Path) -> PathBuf {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".part");
+    PathBuf::from(partial)
+}
+
+/// removes what was downloaded of a body that failed midway
+async fn discard(partial_path: &Path) {
+    if let Err(e) = tokio::fs::remove_file(partial_path).await {
+        tracing::error!("failed to remove partial download {partial_path:?}: {e}");
+    }
+}
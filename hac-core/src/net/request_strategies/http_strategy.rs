@@ -1,6 +1,7 @@
 use crate::collection::types::{Request, RequestMethod};
+use crate::net::network_errors::{error_response, network_error_response};
 use crate::net::request_client::RequestClient;
-use crate::net::request_manager::Response;
+use crate::net::request_manager::{Response, SentRequest};
 use crate::net::request_strategies::RequestStrategy;
 use crate::net::response_decoders::{decoder_from_headers, ResponseDecoder};
 
@@ -8,7 +9,10 @@ pub struct HttpResponse;
 
 impl RequestStrategy for HttpResponse {
     async fn handle(&self, request: Request) -> Response {
-        let client = RequestClient::default();
+        let client = match RequestClient::for_request(&request) {
+            Ok(client) => client,
+            Err(cause) => return error_response(cause, None, None, std::time::Instant::now()),
+        };
 
         match request.method {
             RequestMethod::Get => self.handle_get_request(client, request).await,
@@ -21,133 +25,163 @@ impl RequestStrategy for HttpResponse {
 }
 
 impl HttpResponse {
-    async fn handle_get_request(&self, client: RequestClient, request: Request) -> Response {
+    /// sends the request built by `builder`, keeping a copy of what was
+    /// actually sent on the response
+    async fn dispatch(&self, builder: reqwest::RequestBuilder) -> Response {
         let now = std::time::Instant::now();
-        match client.get(&request).send().await {
+        let (client, request) = builder.build_split();
+        let request = match request {
+            Ok(request) => request,
+            Err(e) => return network_error_response(e, None, now),
+        };
+        let sent = SentRequest::from(&request);
+
+        match client.execute(request).await {
             Ok(response) => {
                 let decoder = decoder_from_headers(response.headers());
-                decoder.decode(response, now).await
+                let mut response = decoder.decode(response, now).await;
+                response.sent = Some(sent);
+                response
             }
-            Err(e) => Response {
-                is_error: true,
-                cause: Some(e.to_string()),
-                body: None,
-                pretty_body: None,
-                body_size: None,
-                size: None,
-                headers_size: None,
-                status: None,
-                headers: None,
-                duration: now.elapsed(),
-            },
+            Err(e) => network_error_response(e, Some(sent), now),
         }
     }
 
+    async fn handle_get_request(&self, client: RequestClient, request: Request) -> Response {
+        let builder = client.get(&request);
+        self.dispatch(builder).await
+    }
+
+    // bodies are sent as they were written, `effective_headers` gives them
+    // their content type
     async fn handle_post_request(&self, client: RequestClient, request: Request) -> Response {
-        let now = std::time::Instant::now();
-        match client
-            .post(&request)
-            .json(&request.body.unwrap_or_default())
-            .send()
-            .await
-        {
-            Ok(response) => {
-                let decoder = decoder_from_headers(response.headers());
-                decoder.decode(response, now).await
-            }
-            Err(e) => Response {
-                is_error: true,
-                cause: Some(e.to_string()),
-                body: None,
-                pretty_body: None,
-                body_size: None,
-                size: None,
-                headers_size: None,
-                status: None,
-                headers: None,
-                duration: now.elapsed(),
-            },
-        }
+        let builder = client.post(&request).body(request.body.unwrap_or_default());
+        self.dispatch(builder).await
     }
 
     async fn handle_put_request(&self, client: RequestClient, request: Request) -> Response {
-        let now = std::time::Instant::now();
-        match client
-            .put(&request)
-            .json(&request.body.unwrap_or_default())
-            .send()
-            .await
-        {
-            Ok(response) => {
-                let decoder = decoder_from_headers(response.headers());
-                decoder.decode(response, now).await
-            }
-            Err(e) => Response {
-                is_error: true,
-                cause: Some(e.to_string()),
-                body: None,
-                pretty_body: None,
-                body_size: None,
-                size: None,
-                headers_size: None,
-                status: None,
-                headers: None,
-                duration: now.elapsed(),
-            },
-        }
+        let builder = client.put(&request).body(request.body.unwrap_or_default());
+        self.dispatch(builder).await
     }
 
     async fn handle_patch_request(&self, client: RequestClient, request: Request) -> Response {
-        let now = std::time::Instant::now();
-        match client
+        let builder = client
             .patch(&request)
-            .json(&request.body.unwrap_or_default())
-            .send()
-            .await
-        {
-            Ok(response) => {
-                let decoder = decoder_from_headers(response.headers());
-                decoder.decode(response, now).await
-            }
-            Err(e) => Response {
-                is_error: true,
-                cause: Some(e.to_string()),
-                body: None,
-                pretty_body: None,
-                body_size: None,
-                size: None,
-                headers_size: None,
-                status: None,
-                headers: None,
-                duration: now.elapsed(),
-            },
-        }
+            .body(request.body.unwrap_or_default());
+        self.dispatch(builder).await
     }
 
     async fn handle_delete_request(&self, client: RequestClient, request: Request) -> Response {
-        let now = std::time::Instant::now();
-        match client
+        let builder = client
             .delete(&request)
-            .json(&request.body.unwrap_or_default())
-            .send()
-            .await
-        {
-            Ok(response) => {
-                let decoder = decoder_from_headers(response.headers());
-                decoder.decode(response, now).await
-            }
-            Err(e) => Response {
-                is_error: true,
-                cause: Some(e.to_string()),
-                body: None,
-                pretty_body: None,
-                body_size: None,
-                size: None,
-                headers_size: None,
-                status: None,
-                headers: None,
-                duration: now.elapsed(),
-            },
-        }
+            .body(request.body.unwrap_or_default());
+        self.dispatch(builder).await
+    }
+}
+
+/// sends the bytes of the file the body of the request lives on, instead of
+/// the body written on the request
+pub struct FileBodyResponse {
+    body: Vec<u8>,
+}
+
+impl FileBodyResponse {
+    pub fn new(body: Vec<u8>) -> Self {
+        FileBodyResponse { body }
+    }
+}
+
+impl RequestStrategy for FileBodyResponse {
+    async fn handle(&self, request: Request) -> Response {
+        let client = match RequestClient::for_request(&request) {
+            Ok(client) => client,
+            Err(cause) => return error_response(cause, None, None, std::time::Instant::now()),
+        };
+        let body = self.body.clone();
+
+        let builder = match request.method {
+            RequestMethod::Get => client.get(&request),
+            RequestMethod::Post => client.post(&request).body(body),
+            RequestMethod::Put => client.put(&request).body(body),
+            RequestMethod::Patch => client.patch(&request).body(body),
+            RequestMethod::Delete => client.delete(&request).body(body),
+        };
+        HttpResponse.dispatch(builder).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[tokio::test]
+    async fn test_bodies_are_sent_as_written() {
+        let (port, server) = test_utils::serve_once();
+        let mut request = Request {
+            body: Some(String::from(r#"{"id":1}"#)),
+            ..test_utils::make_request(RequestMethod::Post, &format!("http://127.0.0.1:{port}"))
+        };
+        request.switch_body("application/xml");
+        request.body = Some(String::from("<id>1</id>"));
+
+        let response = HttpResponse.handle(request).await;
+        let received = server.join().unwrap();
+
+        assert_eq!(response.sent.unwrap().body.as_deref(), Some("<id>1</id>"));
+        assert!(received.contains("content-type: application/xml\r\n"));
+        assert!(received.ends_with("\r\n\r\n<id>1</id>"));
+    }
+
+    /// `body` as a gzip stream made of a single uncompressed block
+    fn gzip(body: &[u8]) -> Vec<u8> {
+        let crc = !body.iter().fold(!0u32, |crc, byte| {
+            (0..8).fold(crc ^ *byte as u32, |crc, _| match crc & 1 {
+                1 => (crc >> 1) ^ 0xedb8_8320,
+                _ => crc >> 1,
+            })
+        });
+        let len = body.len() as u16;
+
+        let mut gzip = vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff, 1];
+        gzip.extend_from_slice(&len.to_le_bytes());
+        gzip.extend_from_slice(&(!len).to_le_bytes());
+        gzip.extend_from_slice(body);
+        gzip.extend_from_slice(&crc.to_le_bytes());
+        gzip.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        gzip
+    }
+
+    #[tokio::test]
+    async fn test_compressed_responses_are_decoded() {
+        let body = gzip(br#"{"id":1}"#);
+        let mut response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Encoding: gzip\r\nContent-Length: {}\r\n\r\n",
+            body.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(&body);
+        let (port, server) = test_utils::serve_once_with(response);
+        let mut request =
+            test_utils::make_request(RequestMethod::Get, &format!("http://127.0.0.1:{port}"));
+        request.client.compression = Some(hac_config::Compression::Compressed);
+
+        let response = HttpResponse.handle(request).await;
+        let received = server.join().unwrap();
+
+        assert!(received.contains("accept-encoding: gzip, deflate, br\r\n"));
+        assert_eq!(response.body.as_deref(), Some(r#"{"id":1}"#));
+    }
+
+    #[tokio::test]
+    async fn test_encodings_are_not_advertised_unless_asked() {
+        let (port, server) = test_utils::serve_once();
+        let request =
+            test_utils::make_request(RequestMethod::Get, &format!("http://127.0.0.1:{port}"));
+
+        HttpResponse.handle(request).await;
+        let received = server.join().unwrap();
+
+        assert!(!received.to_lowercase().contains("accept-encoding"));
     }
 }
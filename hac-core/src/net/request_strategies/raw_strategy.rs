@@ -0,0 +1,487 @@
+use crate::collection::types::{ClientSettings, Request};
+use crate::net::network_errors::{error_response, NetworkError};
+use crate::net::request_manager::{Response, SentRequest};
+use crate::net::request_strategies::RequestStrategy;
+use crate::text_object::TextObject;
+
+use std::io;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{StatusCode, Version};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::client::danger::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
+};
+use tokio_rustls::rustls::crypto::{self, CryptoProvider};
+use tokio_rustls::rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use tokio_rustls::rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use tokio_rustls::TlsConnector;
+
+/// how long we wait for the server to start responding
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(30);
+/// servers keeping the connection alive never close it, so once a response
+/// started arriving, we stop reading after the connection goes quiet for this
+/// long
+const IDLE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// sends the body of the request, which holds a whole HTTP request, verbatim
+/// over a TCP or TLS connection to the host of the uri. The response is shown
+/// as the raw bytes the server sent back.
+///
+/// line breaks are only rewritten when the request asks for it with
+/// `raw_crlf`, so malformed requests can be sent as well
+///
+/// the TLS settings of the request are honored, but its proxy is not, as the
+/// request is written straight to the socket
+pub struct RawResponse;
+
+impl RequestStrategy for RawResponse {
+    async fn handle(&self, request: Request) -> Response {
+        let now = Instant::now();
+        let raw = raw_request_bytes(
+            request.body.as_deref().unwrap_or_default(),
+            request.raw_crlf,
+        );
+        let sent = sent_request(&request.uri, &raw);
+
+        let target = match Target::from_request(&request) {
+            Ok(target) => target,
+            Err(cause) => return error_response(cause, None, Some(sent), now),
+        };
+
+        match exchange(&target, &request.client, &raw).await {
+            Ok(bytes) if bytes.is_empty() => error_response(
+                String::from("the server closed the connection without responding"),
+                None,
+                Some(sent),
+                now,
+            ),
+            Ok(bytes) => raw_response(&bytes, sent, now),
+            Err(error) => {
+                let cause = NetworkError::classify_io(&error)
+                    .describe(&target.host)
+                    .unwrap_or_else(|| error.to_string());
+                error_response(cause, Some(error.to_string()), Some(sent), now)
+            }
+        }
+    }
+}
+
+/// where the raw request is sent to, taken from the uri of the request
+struct Target {
+    host: String,
+    /// address we connect to, which is the host itself unless the request
+    /// resolves it to a given ip
+    address: String,
+    port: u16,
+    tls: bool,
+}
+
+impl Target {
+    fn from_request(request: &Request) -> Result<Self, String> {
+        let url = reqwest::Url::parse(&request.uri)
+            .map_err(|_| format!("invalid uri to send the raw request to: {}", request.uri))?;
+        let tls = match url.scheme() {
+            "http" => false,
+            "https" => true,
+            scheme => return Err(format!("raw requests can't be sent over {scheme}")),
+        };
+        let host = url
+            .host_str()
+            .ok_or_else(|| format!("the uri {} has no host", request.uri))?
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_string();
+        let address = match request.resolve.get(&host) {
+            Some(ip) => ip
+                .parse::<IpAddr>()
+                .map_err(|_| format!("invalid ip address to resolve {host} to: {ip}"))?
+                .to_string(),
+            None => host.clone(),
+        };
+
+        Ok(Target {
+            port: url.port_or_known_default().unwrap_or(80),
+            host,
+            address,
+            tls,
+        })
+    }
+}
+
+/// the bytes of the text typed on the editor, untouched unless `crlf` is set,
+/// in which case the line breaks of the request line and headers are turned
+/// into `\r\n` as HTTP expects, the body is always kept as is
+pub fn raw_request_bytes(text: &str, crlf: bool) -> Vec<u8> {
+    if !crlf {
+        return text.as_bytes().to_vec();
+    }
+
+    let (head, body) = split_head(text);
+    let mut raw = head
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .collect::<Vec<_>>()
+        .join("\r\n");
+    raw.push_str("\r\n\r\n");
+    raw.push_str(body.unwrap_or_default());
+    raw.into_bytes()
+}
+
+/// splits a raw request or response on the first empty line, giving back the
+/// head and the body after it, when there is one. Lines can end in either
+/// `\r\n` or `\n`
+pub fn split_head(text: &str) -> (&str, Option<&str>) {
+    let crlf = text.find("\r\n\r\n").map(|idx| (idx, idx + 4));
+    let lf = text.find("\n\n").map(|idx| (idx, idx + 2));
+    let separator = match (crlf, lf) {
+        (Some(crlf), Some(lf)) => Some(crlf.min(lf)),
+        (crlf, lf) => crlf.or(lf),
+    };
+
+    match separator {
+        Some((head_end, body_start)) => (&text[..head_end], Some(&text[body_start..])),
+        None => (text.trim_end(), None),
+    }
+}
+
+fn sent_request(uri: &str, raw: &[u8]) -> SentRequest {
+    let text = String::from_utf8_lossy(raw);
+    let (head, body) = split_head(&text);
+    let mut lines = head.lines();
+
+    SentRequest {
+        request_line: lines.next().unwrap_or_default().to_string(),
+        url: uri.to_string(),
+        headers: lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+            .collect(),
+        body: body.filter(|body| !body.is_empty()).map(String::from),
+    }
+}
+
+async fn exchange(target: &Target, client: &ClientSettings, raw: &[u8]) -> io::Result<Vec<u8>> {
+    let stream = TcpStream::connect((target.address.as_str(), target.port)).await?;
+    if !target.tls {
+        return send_and_read(stream, raw).await;
+    }
+
+    let server_name = ServerName::try_from(target.host.clone())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let stream = tls_connector(client)?.connect(server_name, stream).await?;
+    send_and_read(stream, raw).await
+}
+
+async fn send_and_read<S>(mut stream: S, raw: &[u8]) -> io::Result<Vec<u8>>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    stream.write_all(raw).await?;
+    stream.flush().await?;
+
+    let mut response = vec![];
+    let mut buf = [0; 8192];
+    loop {
+        let timeout = match response.is_empty() {
+            true => RESPONSE_TIMEOUT,
+            false => IDLE_TIMEOUT,
+        };
+        let read = match tokio::time::timeout(timeout, stream.read(&mut buf)).await {
+            Ok(Ok(read)) => read,
+            // plenty of servers close TLS connections without notifying
+            Ok(Err(e)) if e.kind().eq(&io::ErrorKind::UnexpectedEof) && !response.is_empty() => {
+                break
+            }
+            Ok(Err(e)) => return Err(e),
+            Err(_) if !response.is_empty() => break,
+            Err(_) => return Err(io::Error::from(io::ErrorKind::TimedOut)),
+        };
+        if read.eq(&0) {
+            break;
+        }
+
+        response.extend_from_slice(&buf[..read]);
+        if is_complete(&response) {
+            break;
+        }
+    }
+
+    Ok(response)
+}
+
+/// whether the response received so far is whole, based on what its headers
+/// say about the body. Responses that say nothing are read until the server
+/// closes the connection or goes quiet
+fn is_complete(response: &[u8]) -> bool {
+    let Some((status, _, headers, head_size)) = parse_head(response) else {
+        return false;
+    };
+    if status.is_informational()
+        || status.eq(&StatusCode::NO_CONTENT)
+        || status.eq(&StatusCode::NOT_MODIFIED)
+    {
+        return true;
+    }
+
+    let is_chunked = headers
+        .get(reqwest::header::TRANSFER_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.to_ascii_lowercase().contains("chunked"));
+    if is_chunked {
+        return is_chunked_body_complete(&response[head_size..]);
+    }
+
+    headers
+        .get(reqwest::header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .is_some_and(|length| response.len() >= head_size + length)
+}
+
+/// whether a chunked body was received up to its last chunk and the trailers
+/// after it. Bodies that can't be parsed are read until the connection closes
+fn is_chunked_body_complete(mut body: &[u8]) -> bool {
+    loop {
+        let Some(line_end) = body.windows(2).position(|window| window.eq(b"\r\n")) else {
+            return false;
+        };
+        // chunk sizes can be followed by extensions, eg: `1a;name=value`
+        let size = String::from_utf8_lossy(&body[..line_end]);
+        let size = size.split(';').next().unwrap_or_default().trim();
+        let Ok(size) = usize::from_str_radix(size, 16) else {
+            return false;
+        };
+        body = &body[line_end + 2..];
+
+        if size.eq(&0) {
+            // the trailer section ends on an empty line, which comes right away
+            // when there are no trailers
+            return body.starts_with(b"\r\n")
+                || body.windows(4).any(|window| window.eq(b"\r\n\r\n"));
+        }
+
+        // every chunk is followed by a line break
+        match body.get(size + 2..) {
+            Some(rest) => body = rest,
+            None => return false,
+        }
+    }
+}
+
+/// status, version and headers of a raw response, along with the size of its
+/// head, `None` until the whole head was received or when it isn't HTTP
+fn parse_head(response: &[u8]) -> Option<(StatusCode, Option<Version>, HeaderMap, usize)> {
+    let head_end = response
+        .windows(4)
+        .position(|window| window.eq(b"\r\n\r\n"))?;
+    let head = String::from_utf8_lossy(&response[..head_end]);
+    let mut lines = head.lines();
+
+    let mut status_line = lines.next()?.split_whitespace();
+    let version = match status_line.next()? {
+        "HTTP/1.0" => Some(Version::HTTP_10),
+        "HTTP/1.1" => Some(Version::HTTP_11),
+        _ => None,
+    };
+    let status = StatusCode::from_bytes(status_line.next()?.as_bytes()).ok()?;
+
+    let mut headers = HeaderMap::new();
+    lines
+        .filter_map(|line| line.split_once(':'))
+        .for_each(|(name, value)| {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(name.trim().as_bytes()),
+                HeaderValue::from_str(value.trim()),
+            ) {
+                headers.append(name, value);
+            }
+        });
+
+    Some((status, version, headers, head_end + 4))
+}
+
+fn raw_response(bytes: &[u8], sent: SentRequest, start: Instant) -> Response {
+    let text = String::from_utf8_lossy(bytes).to_string();
+    let head = parse_head(bytes);
+    let head_size = head.as_ref().map(|(.., size)| *size as u64);
+    let (status, version, headers) = match head {
+        Some((status, version, headers, _)) => (Some(status), version, Some(headers)),
+        None => (None, None, None),
+    };
+
+    Response {
+        pretty_body: Some(TextObject::from(&text)),
+        body: Some(text),
+        headers,
+        duration: start.elapsed(),
+        status,
+        version,
+        headers_size: head_size,
+        body_size: head_size.map(|size| bytes.len() as u64 - size),
+        size: Some(bytes.len() as u64),
+        is_error: false,
+        cause: None,
+        cause_detail: None,
+        download: None,
+        sent: Some(sent),
+        request_id: None,
+    }
+}
+
+fn tls_connector(settings: &ClientSettings) -> io::Result<TlsConnector> {
+    let builder = ClientConfig::builder();
+    let config = match settings.accept_invalid_certs.unwrap_or_default() {
+        true => builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(AcceptInvalidCerts(
+                crypto::ring::default_provider(),
+            )))
+            .with_no_client_auth(),
+        false => {
+            let mut roots = RootCertStore::empty();
+            // certificates of the system we can't parse are skipped, as
+            // reqwest does
+            let (added, _) = roots.add_parsable_certificates(
+                rustls_native_certs::load_native_certs().unwrap_or_default(),
+            );
+            tracing::debug!("trusting {added} certificates of the system on a raw request");
+            if let Some(path) = settings.ca_certificate.as_ref() {
+                let pem = std::fs::read(path)?;
+                for cert in rustls_pemfile::certs(&mut pem.as_slice()) {
+                    roots
+                        .add(cert?)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                }
+            }
+            builder.with_root_certificates(roots).with_no_client_auth()
+        }
+    };
+
+    Ok(TlsConnector::from(Arc::new(config)))
+}
+
+/// trusts any certificate the server presents, still checking that the
+/// handshake is signed by it
+#[derive(Debug)]
+struct AcceptInvalidCerts(CryptoProvider);
+
+impl ServerCertVerifier for AcceptInvalidCerts {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, tokio_rustls::rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, tokio_rustls::rustls::Error> {
+        crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, tokio_rustls::rustls::Error> {
+        crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection::types::{BodyType, RequestMethod};
+    use crate::test_utils;
+
+    #[test]
+    fn test_raw_request_line_breaks_are_normalized() {
+        let raw = raw_request_bytes(
+            "GET / HTTP/1.1\nHost: example.com\nX-Any:  value \n\n{\n}",
+            true,
+        );
+
+        assert_eq!(
+            raw,
+            b"GET / HTTP/1.1\r\nHost: example.com\r\nX-Any:  value \r\n\r\n{\n}".to_vec()
+        );
+        assert_eq!(
+            raw_request_bytes("GET / HTTP/1.1\r\nHost: example.com\n", true),
+            b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\n".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_raw_request_line_breaks_are_kept_by_default() {
+        let text = "GET / HTTP/1.1\nHost: example.com\r\nX-Any: value\n\n";
+
+        assert_eq!(raw_request_bytes(text, false), text.as_bytes().to_vec());
+    }
+
+    #[test]
+    fn test_chunked_body_is_complete_after_the_last_chunk() {
+        assert!(is_chunked_body_complete(b"3\r\nhac\r\n0\r\n\r\n"));
+        assert!(is_chunked_body_complete(
+            b"3;ext=1\r\nhac\r\n0\r\nX-Trailer: 1\r\n\r\n"
+        ));
+        // a chunk whose data ends like the last chunk
+        assert!(!is_chunked_body_complete(b"5\r\n0\r\n\r\n"));
+        assert!(!is_chunked_body_complete(
+            b"3\r\nhac\r\n0\r\nX-Trailer: 1\r\n"
+        ));
+        assert!(!is_chunked_body_complete(b"a\r\nhac"));
+    }
+
+    #[tokio::test]
+    async fn test_raw_request_is_sent_verbatim() {
+        let (port, server) = test_utils::serve_once_with(
+            b"HTTP/1.1 400 Bad Request\r\nContent-Length: 3\r\n\r\nbad".to_vec(),
+        );
+
+        let request = Request {
+            body: Some(String::from("GET  /  HTTP/1.1\r\nx-b: 1\r\nX-A: 2\r\n\r\n")),
+            body_type: Some(BodyType::Raw),
+            ..test_utils::make_request(RequestMethod::Get, &format!("http://127.0.0.1:{port}"))
+        };
+        let response = RawResponse.handle(request).await;
+
+        assert_eq!(
+            server.join().unwrap(),
+            "GET  /  HTTP/1.1\r\nx-b: 1\r\nX-A: 2\r\n\r\n"
+        );
+        assert_eq!(response.status, Some(StatusCode::BAD_REQUEST));
+        assert_eq!(
+            response.body.as_deref(),
+            Some("HTTP/1.1 400 Bad Request\r\nContent-Length: 3\r\n\r\nbad")
+        );
+        assert_eq!(response.body_size, Some(3));
+        assert_eq!(response.sent.unwrap().request_line, "GET  /  HTTP/1.1");
+    }
+}
@@ -4,10 +4,40 @@ use crate::net::request_manager::{ContentType, Response};
 use crate::net::response_decoders::json_decoder::JsonDecoder;
 
 use std::future::Future;
+use std::sync::RwLock;
 use std::time::Instant;
 
 use reqwest::header::HeaderMap;
 
+/// amount of spaces used to indent pretty printed responses when no width was
+/// configured through `set_response_indent`
+pub const DEFAULT_RESPONSE_INDENT: usize = 2;
+
+static RESPONSE_INDENT: RwLock<usize> = RwLock::new(DEFAULT_RESPONSE_INDENT);
+
+/// sets how many spaces pretty printed responses are indented with.
+///
+/// setting it again, like when the config is reloaded, only affects responses
+/// decoded afterwards
+pub fn set_response_indent(width: usize) {
+    *RESPONSE_INDENT.write().unwrap() = width;
+}
+
+fn response_indent() -> usize {
+    *RESPONSE_INDENT.read().unwrap()
+}
+
+/// pretty prints a json document using the configured indentation width
+pub fn pretty_print_json(body: &str) -> String {
+    pretty_print_json_with_indent(body, response_indent())
+}
+
+fn pretty_print_json_with_indent(body: &str, indent: usize) -> String {
+    let mut formatter = jsonxf::Formatter::pretty_printer();
+    formatter.indent = " ".repeat(indent);
+    formatter.format(body).unwrap_or_else(|_| body.to_string())
+}
+
 pub trait ResponseDecoder {
     fn decode(
         &self,
@@ -25,3 +55,18 @@ pub fn decoder_from_headers(headers: &HeaderMap) -> impl ResponseDecoder {
         None => JsonDecoder,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pretty_prints_with_given_indent() {
+        let body = r#"{"user":{"id":1}}"#;
+
+        assert_eq!(
+            pretty_print_json_with_indent(body, 4),
+            "{\n    \"user\": {\n        \"id\": 1\n    }\n}"
+        );
+    }
+}
@@ -1,4 +1,5 @@
-use crate::net::{request_manager::Response, response_decoders::ResponseDecoder};
+use crate::net::request_manager::Response;
+use crate::net::response_decoders::{pretty_print_json, ResponseDecoder};
 use crate::text_object::TextObject;
 
 use std::{ops::Add, time::Instant};
@@ -10,6 +11,7 @@ impl ResponseDecoder for JsonDecoder {
         let duration = start.elapsed();
         let headers = Some(response.headers().to_owned());
         let status = Some(response.status());
+        let version = Some(response.version());
         let headers_size: u64 = response
             .headers()
             .iter()
@@ -19,12 +21,14 @@ impl ResponseDecoder for JsonDecoder {
         let mut body: Option<String> = None;
         let mut pretty_body = None;
 
-        if response.content_length().is_some_and(|len| len.gt(&0)) {
-            if let Ok(body_str) = response.text().await {
-                let pretty_body_str = jsonxf::pretty_print(&body_str).unwrap_or_default();
+        // decoded and chunked responses don't know their length upfront, so
+        // the body is read whole and only kept when there is one
+        if let Ok(body_str) = response.text().await {
+            if !body_str.is_empty() {
+                let pretty_body_str = pretty_print_json(&body_str);
                 pretty_body = Some(TextObject::from(&pretty_body_str));
                 body = Some(body_str);
-            };
+            }
         }
 
         let body_size = body.as_ref().map(|body| body.len()).unwrap_or_default() as u64;
@@ -36,11 +40,16 @@ impl ResponseDecoder for JsonDecoder {
             headers,
             duration,
             status,
+            version,
             size: Some(size),
             headers_size: Some(headers_size),
             body_size: Some(body_size),
             cause: None,
+            cause_detail: None,
             is_error: false,
+            download: None,
+            sent: None,
+            request_id: None,
         }
     }
 }
@@ -0,0 +1,64 @@
+use crate::collection::types::Request;
+use crate::variables::{resolve, VariableError};
+
+use std::collections::HashMap;
+
+/// replaces every `{{name}}` placeholder on the uri, headers and body of the
+/// request with the value of the variable, placeholders of variables that
+/// are not defined are sent as they are
+pub fn substitute_variables(
+    request: &mut Request,
+    variables: &HashMap<String, String>,
+    max_depth: usize,
+) -> Result<(), VariableError> {
+    request.uri = resolve(&request.uri, variables, max_depth)?;
+
+    for header in request.headers.iter_mut().flatten() {
+        header.pair.0 = resolve(&header.pair.0, variables, max_depth)?;
+        header.pair.1 = resolve(&header.pair.1, variables, max_depth)?;
+    }
+
+    if let Some(body) = request.body.as_mut() {
+        *body = resolve(body, variables, max_depth)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection::types::{HeaderMap, RequestMethod};
+    use crate::test_utils;
+
+    #[test]
+    fn test_substitutes_uri_headers_and_body() {
+        let mut request = Request {
+            headers: Some(vec![HeaderMap {
+                pair: (
+                    String::from("authorization"),
+                    String::from("Bearer {{token}}"),
+                ),
+                enabled: true,
+            }]),
+            body: Some(String::from(r#"{"name": "{{name}}"}"#)),
+            ..test_utils::make_request(RequestMethod::Post, "{{base}}/users")
+        };
+        let variables = HashMap::from([
+            (
+                String::from("base"),
+                String::from("https://api.example.com"),
+            ),
+            (String::from("token"), String::from("secret")),
+        ]);
+
+        substitute_variables(&mut request, &variables, 10).unwrap();
+
+        assert_eq!(request.uri, "https://api.example.com/users");
+        assert_eq!(
+            request.headers.unwrap()[0].pair.1,
+            String::from("Bearer secret")
+        );
+        assert_eq!(request.body.unwrap(), r#"{"name": "{{name}}"}"#);
+    }
+}
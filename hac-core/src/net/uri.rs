@@ -0,0 +1,135 @@
+use crate::collection::types::TrailingSlash;
+
+/// applies the trailing slash behavior of a collection to the path of an uri,
+/// the query string and fragment are left untouched
+pub fn normalize_trailing_slash(uri: &str, behavior: TrailingSlash) -> String {
+    if behavior.is_keep() {
+        return uri.to_string();
+    }
+
+    let path_end = uri.find(['?', '#']).unwrap_or(uri.len());
+    let (path, suffix) = uri.split_at(path_end);
+    let authority_start = path.find("://").map(|idx| idx + 3).unwrap_or_default();
+
+    match behavior {
+        TrailingSlash::Add if !path.ends_with('/') => format!("{path}/{suffix}"),
+        TrailingSlash::Strip => {
+            let stripped = path.trim_end_matches('/');
+            // stripping everything would leave us with only the scheme
+            if stripped.len() <= authority_start {
+                return uri.to_string();
+            }
+            format!("{stripped}{suffix}")
+        }
+        _ => uri.to_string(),
+    }
+}
+
+/// prepends `base_url` to `uri` when it is relative. Uris with a scheme, or
+/// starting with a variable that gives the host, are absolute and always
+/// take precedence over the base url
+pub fn resolve_base_url(uri: &str, base_url: Option<&str>) -> String {
+    let Some(base_url) = base_url.filter(|base_url| !base_url.is_empty()) else {
+        return uri.to_string();
+    };
+    if uri.contains("://") || uri.starts_with("{{") {
+        return uri.to_string();
+    }
+
+    let base_url = base_url.trim_end_matches('/');
+    match uri.trim_start_matches('/') {
+        "" => base_url.to_string(),
+        path if path.starts_with(['?', '#']) => format!("{base_url}{path}"),
+        path => format!("{base_url}/{path}"),
+    }
+}
+
+/// path of an uri without its scheme, host, query string or fragment. A
+/// leading variable, like `{{base_url}}/users`, is taken as the host
+pub fn uri_path(uri: &str) -> &str {
+    let path_end = uri.find(['?', '#']).unwrap_or(uri.len());
+    let uri = &uri[..path_end];
+
+    let authority = match (uri.find("://"), uri.strip_prefix("{{")) {
+        (Some(idx), _) => &uri[idx + 3..],
+        (None, Some(rest)) => rest.find("}}").map(|idx| &rest[idx + 2..]).unwrap_or(rest),
+        (None, None) if uri.starts_with('/') => return uri,
+        (None, None) => uri,
+    };
+
+    match authority.find('/') {
+        Some(idx) => &authority[idx..],
+        None => "/",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adds_trailing_slash_before_query() {
+        let uri = "https://example.com/users?page=2#top";
+
+        assert_eq!(
+            normalize_trailing_slash(uri, TrailingSlash::Add),
+            "https://example.com/users/?page=2#top"
+        );
+        assert_eq!(
+            normalize_trailing_slash("https://example.com/users/", TrailingSlash::Add),
+            "https://example.com/users/"
+        );
+    }
+
+    #[test]
+    fn test_uri_path() {
+        assert_eq!(
+            uri_path("https://example.com/users/1?page=2#top"),
+            "/users/1"
+        );
+        assert_eq!(uri_path("{{base_url}}/users/{{id}}"), "/users/{{id}}");
+        assert_eq!(uri_path("example.com/users"), "/users");
+        assert_eq!(uri_path("/users"), "/users");
+        assert_eq!(uri_path("https://example.com"), "/");
+    }
+
+    #[test]
+    fn test_strips_trailing_slashes() {
+        assert_eq!(
+            normalize_trailing_slash("https://example.com/users//?page=2", TrailingSlash::Strip),
+            "https://example.com/users?page=2"
+        );
+        assert_eq!(
+            normalize_trailing_slash("https://", TrailingSlash::Strip),
+            "https://"
+        );
+        assert_eq!(
+            normalize_trailing_slash("https://example.com/users/", TrailingSlash::Keep),
+            "https://example.com/users/"
+        );
+    }
+
+    #[test]
+    fn test_relative_uris_are_resolved_against_the_base_url() {
+        let base_url = Some("https://api.example.com/v1/");
+
+        assert_eq!(
+            resolve_base_url("/users?page=2", base_url),
+            "https://api.example.com/v1/users?page=2"
+        );
+        assert_eq!(
+            resolve_base_url("users", base_url),
+            "https://api.example.com/v1/users"
+        );
+        assert_eq!(resolve_base_url("", base_url), "https://api.example.com/v1");
+        assert_eq!(
+            resolve_base_url("http://localhost/users", base_url),
+            "http://localhost/users"
+        );
+        assert_eq!(
+            resolve_base_url("{{host}}/users", base_url),
+            "{{host}}/users"
+        );
+        assert_eq!(resolve_base_url("/users", None), "/users");
+    }
+}
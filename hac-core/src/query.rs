@@ -0,0 +1,102 @@
+use serde_json::{Map, Value};
+
+/// converts a flat json object into query parameters, sorted by their keys.
+/// Nested objects and arrays can't be represented as a single
+/// parameter, so they are rejected
+pub fn json_to_query(body: &str) -> Result<Vec<(String, String)>, String> {
+    let value = serde_json::from_str::<Value>(body)
+        .map_err(|e| format!("the body is not valid json: {e}"))?;
+    let Value::Object(object) = value else {
+        return Err(String::from(
+            "only json objects can be converted to query parameters",
+        ));
+    };
+
+    object
+        .into_iter()
+        .map(|(key, value)| match value {
+            Value::String(value) => Ok((key, value)),
+            Value::Null => Ok((key, String::default())),
+            Value::Bool(_) | Value::Number(_) => Ok((key, value.to_string())),
+            Value::Array(_) | Value::Object(_) => Err(format!(
+                "`{key}` holds a nested value, only flat objects can be converted to query parameters"
+            )),
+        })
+        .collect()
+}
+
+/// converts query parameters into a pretty printed json object, every value
+/// becomes a string as query parameters carry no type information
+pub fn query_to_json(params: &[(String, String)]) -> String {
+    let object = params
+        .iter()
+        .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+        .collect::<Map<String, Value>>();
+
+    serde_json::to_string_pretty(&Value::Object(object)).unwrap_or_default()
+}
+
+/// splits an uri into the part before the query string and its decoded query
+/// parameters, the fragment is dropped
+pub fn split_query(uri: &str) -> (String, Vec<(String, String)>) {
+    let uri = uri.split('#').next().unwrap_or_default();
+    match uri.split_once('?') {
+        Some((base, query)) => (
+            base.to_string(),
+            form_urlencoded::parse(query.as_bytes())
+                .into_owned()
+                .collect(),
+        ),
+        None => (uri.to_string(), vec![]),
+    }
+}
+
+/// appends `params` to the query string of `uri`, encoding them
+pub fn append_query(uri: &str, params: &[(String, String)]) -> String {
+    if params.is_empty() {
+        return uri.to_string();
+    }
+
+    let query = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(params)
+        .finish();
+    let separator = match uri.contains('?') {
+        true if uri.ends_with('?') || uri.ends_with('&') => "",
+        true => "&",
+        false => "?",
+    };
+
+    format!("{uri}{separator}{query}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_converts_flat_json_to_query_and_back() {
+        let params = json_to_query(r#"{"name": "john doe", "age": 30, "admin": false}"#).unwrap();
+        let uri = append_query("https://example.com/users?page=2", &params);
+
+        assert_eq!(
+            uri,
+            "https://example.com/users?page=2&admin=false&age=30&name=john+doe"
+        );
+
+        let (base, params) = split_query(&uri);
+        assert_eq!(base, "https://example.com/users");
+        assert_eq!(params[3], (String::from("name"), String::from("john doe")));
+        assert_eq!(
+            query_to_json(&params[3..]),
+            "{\n  \"name\": \"john doe\"\n}"
+        );
+    }
+
+    #[test]
+    fn test_rejects_nested_values() {
+        let result = json_to_query(r#"{"user": {"name": "john"}}"#);
+
+        assert!(result.unwrap_err().contains("`user`"));
+        assert!(json_to_query("[1, 2]").is_err());
+    }
+}
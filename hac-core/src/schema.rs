@@ -0,0 +1,142 @@
+use serde_json::{json, Map, Value};
+
+/// amount of items of an array we look at to infer the type of its elements,
+/// responses usually hold arrays of the same shape so a few are enough
+const MAX_SAMPLED_ITEMS: usize = 5;
+
+/// infers a rough json schema from a json document, describing only the
+/// field names and types found on it. Returns `None` when the document is
+/// not valid json
+pub fn infer_schema_from_str(body: &str) -> Option<String> {
+    let value = serde_json::from_str::<Value>(body).ok()?;
+    serde_json::to_string_pretty(&infer_schema(&value)).ok()
+}
+
+/// infers a rough json schema from an already parsed json value
+pub fn infer_schema(value: &Value) -> Value {
+    match value {
+        Value::Null => json!({ "type": "null" }),
+        Value::Bool(_) => json!({ "type": "boolean" }),
+        Value::Number(number) if number.is_f64() => json!({ "type": "number" }),
+        Value::Number(_) => json!({ "type": "integer" }),
+        Value::String(_) => json!({ "type": "string" }),
+        Value::Array(items) => {
+            let items = items
+                .iter()
+                .take(MAX_SAMPLED_ITEMS)
+                .map(infer_schema)
+                .reduce(merge_schemas)
+                .unwrap_or(json!({}));
+            json!({ "type": "array", "items": items })
+        }
+        Value::Object(fields) => {
+            let properties = fields
+                .iter()
+                .map(|(name, value)| (name.clone(), infer_schema(value)))
+                .collect::<Map<_, _>>();
+            json!({ "type": "object", "properties": properties })
+        }
+    }
+}
+
+/// merges the schemas of two sibling values, like two items of the same array,
+/// into a schema that describes both of them
+fn merge_schemas(left: Value, right: Value) -> Value {
+    if left.eq(&right) {
+        return left;
+    }
+
+    match (schema_type(&left), schema_type(&right)) {
+        (Some("object"), Some("object")) => {
+            let mut properties = left["properties"].as_object().cloned().unwrap_or_default();
+            if let Some(right) = right["properties"].as_object() {
+                right.iter().for_each(|(name, schema)| {
+                    let merged = match properties.remove(name) {
+                        Some(existing) => merge_schemas(existing, schema.clone()),
+                        None => schema.clone(),
+                    };
+                    properties.insert(name.clone(), merged);
+                });
+            }
+            json!({ "type": "object", "properties": properties })
+        }
+        (Some("array"), Some("array")) => {
+            let items = merge_schemas(left["items"].clone(), right["items"].clone());
+            json!({ "type": "array", "items": items })
+        }
+        // an integer is also a number, so we keep the broader type
+        (Some("integer"), Some("number")) | (Some("number"), Some("integer")) => {
+            json!({ "type": "number" })
+        }
+        _ => {
+            let mut variants = match left.get("oneOf").and_then(Value::as_array) {
+                Some(variants) => variants.clone(),
+                None => vec![left],
+            };
+            if !variants.contains(&right) {
+                variants.push(right);
+            }
+            json!({ "oneOf": variants })
+        }
+    }
+}
+
+fn schema_type(schema: &Value) -> Option<&str> {
+    schema.get("type").and_then(Value::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infers_nested_objects() {
+        let value = json!({ "id": 1, "name": "hac", "tags": ["api"], "owner": null });
+
+        let schema = infer_schema(&value);
+
+        assert_eq!(
+            schema,
+            json!({
+                "type": "object",
+                "properties": {
+                    "id": { "type": "integer" },
+                    "name": { "type": "string" },
+                    "tags": { "type": "array", "items": { "type": "string" } },
+                    "owner": { "type": "null" },
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_merges_array_items() {
+        let value = json!([
+            { "id": 1, "score": 1 },
+            { "id": 2, "score": 1.5, "email": "any@email.com" },
+            "unexpected",
+        ]);
+
+        let schema = infer_schema(&value);
+
+        assert_eq!(
+            schema,
+            json!({
+                "type": "array",
+                "items": {
+                    "oneOf": [
+                        {
+                            "type": "object",
+                            "properties": {
+                                "id": { "type": "integer" },
+                                "score": { "type": "number" },
+                                "email": { "type": "string" },
+                            }
+                        },
+                        { "type": "string" },
+                    ]
+                }
+            })
+        );
+    }
+}
@@ -97,4 +97,44 @@ impl Highlighter {
         }
         indent_level.saturating_sub(1)
     }
+
+    /// when the byte at `byte_idx` is a bracket or a brace, returns the byte
+    /// index of it and of its pair as `(opening, closing)`. Unbalanced brackets
+    /// have no pair and return `None`
+    pub fn find_matching_bracket(tree: &Tree, byte_idx: usize) -> Option<(usize, usize)> {
+        let node = tree
+            .root_node()
+            .descendant_for_byte_range(byte_idx, byte_idx.saturating_add(1))?;
+
+        if !matches!(node.kind(), "{" | "}" | "[" | "]") || node.start_byte().ne(&byte_idx) {
+            return None;
+        }
+
+        let parent = node.parent()?;
+        let opening = parent.child(0)?;
+        let closing = parent.child(parent.child_count().saturating_sub(1))?;
+
+        if opening.eq(&closing) || opening.is_missing() || closing.is_missing() {
+            return None;
+        }
+
+        Some((opening.start_byte(), closing.start_byte()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_matching_bracket() {
+        let buffer = r#"{"list": [1, 2], "key": "value"}"#;
+        let tree = HIGHLIGHTER.write().unwrap().parse(buffer).unwrap();
+
+        assert_eq!(Highlighter::find_matching_bracket(&tree, 0), Some((0, 31)));
+        assert_eq!(Highlighter::find_matching_bracket(&tree, 31), Some((0, 31)));
+        assert_eq!(Highlighter::find_matching_bracket(&tree, 9), Some((9, 14)));
+        assert_eq!(Highlighter::find_matching_bracket(&tree, 14), Some((9, 14)));
+        assert_eq!(Highlighter::find_matching_bracket(&tree, 2), None);
+    }
 }
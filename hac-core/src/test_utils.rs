@@ -0,0 +1,83 @@
+use crate::collection::types::{Request, RequestMethod};
+use crate::net::request_manager::Response;
+
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+/// request shared by the tests of every module, tests set whatever else they
+/// need on top of it with `..make_request(method, uri)`
+pub fn make_request(method: RequestMethod, uri: &str) -> Request {
+    Request {
+        id: String::from("any_id"),
+        method,
+        name: String::from("any_name"),
+        uri: uri.to_string(),
+        ..Default::default()
+    }
+}
+
+/// response with `status` and nothing else, tests set whatever else they need
+/// on top of it with `..make_response(status)`
+pub fn make_response(status: StatusCode) -> Response {
+    Response {
+        body: None,
+        pretty_body: None,
+        headers: None,
+        duration: Duration::default(),
+        status: Some(status),
+        version: None,
+        headers_size: None,
+        body_size: None,
+        size: None,
+        is_error: false,
+        cause: None,
+        cause_detail: None,
+        download: None,
+        sent: None,
+        request_id: None,
+    }
+}
+
+/// answers a single request on a local port with an empty `200`, giving back
+/// the port and everything that was received
+pub fn serve_once() -> (u16, std::thread::JoinHandle<String>) {
+    serve_once_with(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n".to_vec())
+}
+
+/// answers a single request on a local port with the raw `response`, giving
+/// back the port and everything that was received
+pub fn serve_once_with(response: Vec<u8>) -> (u16, std::thread::JoinHandle<String>) {
+    use std::io::{Read, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut received = String::new();
+        let mut buf = [0; 1024];
+        loop {
+            let read = stream.read(&mut buf).unwrap();
+            if read.eq(&0) {
+                break;
+            }
+            received.push_str(&String::from_utf8_lossy(&buf[..read]));
+            let Some((head, body)) = received.split_once("\r\n\r\n") else {
+                continue;
+            };
+            let length = head
+                .lines()
+                .filter_map(|line| line.split_once(':'))
+                .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+                .and_then(|(_, length)| length.trim().parse::<usize>().ok())
+                .unwrap_or_default();
+            if body.len().ge(&length) {
+                break;
+            }
+        }
+        stream.write_all(&response).unwrap();
+        received
+    });
+
+    (port, server)
+}
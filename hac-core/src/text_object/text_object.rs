@@ -116,6 +116,51 @@ impl TextObject<Write> {
         self.content.try_remove(col_offset..col_offset.add(1)).ok();
     }
 
+    /// byte index of the character under the cursor, if the cursor is inside
+    /// of the content
+    pub fn cursor_byte_idx(&self, cursor: &Cursor) -> Option<usize> {
+        let char_idx = self
+            .content
+            .try_line_to_char(cursor.row())
+            .ok()?
+            .add(cursor.col());
+        self.content.try_char_to_byte(char_idx).ok()
+    }
+
+    /// converts a byte index into a `(col, row)` position, where col is the
+    /// index of the character in the line
+    pub fn byte_to_position(&self, byte_idx: usize) -> Option<(usize, usize)> {
+        let row = self.content.try_byte_to_line(byte_idx).ok()?;
+        let char_idx = self.content.try_byte_to_char(byte_idx).ok()?;
+        Some((char_idx.sub(self.content.line_to_char(row)), row))
+    }
+
+    /// the indentation a new line inserted at the cursor should have, which is
+    /// the leading whitespace of the current line, plus one level when the
+    /// cursor is right after an opening token
+    pub fn indentation_for_newline(&self, cursor: &Cursor) -> String {
+        let Some(line) = self.content.get_line(cursor.row()) else {
+            return String::new();
+        };
+
+        let mut indentation = line
+            .chars()
+            .take(cursor.col())
+            .take_while(|c| c.eq(&' ') || c.eq(&'\t'))
+            .collect::<String>();
+
+        let last_char_before_cursor = line
+            .chars()
+            .take(cursor.col())
+            .filter(|c| !c.is_whitespace())
+            .last();
+        if last_char_before_cursor.is_some_and(is_opening_token) {
+            indentation.push_str("  ");
+        }
+
+        indentation
+    }
+
     pub fn current_line(&self, cursor: &Cursor) -> Option<&str> {
         self.content.line(cursor.row()).as_str()
     }
@@ -354,14 +399,14 @@ impl TextObject<Write> {
     pub fn insert_line_below(&mut self, cursor: &Cursor, tree: Option<&Tree>) {
         let indentation = self.get_scope_aware_indentation(cursor, tree);
         let next_line = self.content.line_to_char(cursor.row().add(1));
-        let line_with_indentation = format!("{}{}", indentation, &self.line_break.to_string());
+        let line_with_indentation = format!("{}{}", indentation, self.line_break);
         self.content.insert(next_line, &line_with_indentation);
     }
 
     pub fn insert_line_above(&mut self, cursor: &Cursor, tree: Option<&Tree>) {
         let indentation = self.get_scope_aware_indentation(cursor, tree);
         let curr_line = self.content.line_to_char(cursor.row());
-        let line_with_indentation = format!("{}{}", indentation, &self.line_break.to_string());
+        let line_with_indentation = format!("{}{}", indentation, self.line_break);
         self.content.insert(curr_line, &line_with_indentation);
     }
 
@@ -461,7 +506,11 @@ impl TextObject<Write> {
 
 impl<State> std::fmt::Display for TextObject<State> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&self.content.to_string())
+        // writing chunk by chunk avoids building an intermediate string out of the rope
+        for chunk in self.content.chunks() {
+            f.write_str(chunk)?;
+        }
+        Ok(())
     }
 }
 
@@ -472,3 +521,25 @@ fn is_opening_token(char: char) -> bool {
 fn is_closing_token(char: char) -> bool {
     matches!(char, ')' | '}' | ']' | '>')
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indentation_for_newline() {
+        let body = TextObject::from("{\n  \"list\": [\n    1\n  ]\n}").with_write();
+        let mut cursor = Cursor::default();
+
+        cursor.move_to_col(1);
+        assert_eq!(body.indentation_for_newline(&cursor), "  ");
+
+        cursor.move_to_row(1);
+        cursor.move_to_col(11);
+        assert_eq!(body.indentation_for_newline(&cursor), "    ");
+
+        cursor.move_to_row(2);
+        cursor.move_to_col(5);
+        assert_eq!(body.indentation_for_newline(&cursor), "    ");
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+/// how many levels of variables referencing other variables are resolved
+/// when no limit is given
+pub const DEFAULT_MAX_VARIABLE_DEPTH: usize = 10;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum VariableError {
+    /// a variable ends up referencing itself, holds the chain of variables
+    /// that form the cycle, starting and ending on the same variable
+    Cycle(Vec<String>),
+    /// variables are nested deeper than the allowed depth, holds the chain of
+    /// variables being resolved when the limit was reached
+    TooDeep(Vec<String>),
+}
+
+impl std::fmt::Display for VariableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VariableError::Cycle(chain) => {
+                write!(f, "circular variable reference: {}", chain.join(" -> "))
+            }
+            VariableError::TooDeep(chain) => write!(
+                f,
+                "variables are nested too deep while resolving: {}",
+                chain.join(" -> ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VariableError {}
+
+/// replaces every `{{name}}` on `template` with the value of the variable,
+/// values can reference other variables, which are resolved up to
+/// `max_depth` levels deep. Variables that are not defined are left as they
+/// are
+pub fn resolve(
+    template: &str,
+    variables: &HashMap<String, String>,
+    max_depth: usize,
+) -> Result<String, VariableError> {
+    resolve_with_chain(template, variables, max_depth, &mut vec![])
+}
+
+fn resolve_with_chain(
+    template: &str,
+    variables: &HashMap<String, String>,
+    max_depth: usize,
+    chain: &mut Vec<String>,
+) -> Result<String, VariableError> {
+    let mut resolved = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 2..start + len].trim();
+        resolved.push_str(&rest[..start]);
+
+        match variables.get(name) {
+            Some(value) => {
+                if let Some(idx) = chain.iter().position(|var| var.eq(name)) {
+                    let mut cycle = chain[idx..].to_vec();
+                    cycle.push(name.to_string());
+                    return Err(VariableError::Cycle(cycle));
+                }
+                if chain.len().ge(&max_depth) {
+                    let mut chain = chain.clone();
+                    chain.push(name.to_string());
+                    return Err(VariableError::TooDeep(chain));
+                }
+
+                chain.push(name.to_string());
+                resolved.push_str(&resolve_with_chain(value, variables, max_depth, chain)?);
+                chain.pop();
+            }
+            None => resolved.push_str(&rest[start..start + len + 2]),
+        }
+
+        rest = &rest[start + len + 2..];
+    }
+
+    resolved.push_str(rest);
+    Ok(resolved)
+}
+
+/// parses the contents of a `.env` file into variables. Each line holds a
+/// `NAME=value` pair, optionally prefixed by `export`. Values may be wrapped
+/// in single or double quotes, and double quoted values support `\n` and
+/// `\"` escapes. Blank lines, comments and malformed lines are ignored
+pub fn parse_env_file(contents: &str) -> HashMap<String, String> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (name, value) = line.split_once('=')?;
+            let name = name.trim();
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return None;
+            }
+            Some((name.to_string(), parse_env_value(value.trim())))
+        })
+        .collect()
+}
+
+fn parse_env_value(value: &str) -> String {
+    if let Some(inner) = value.strip_prefix('\'') {
+        return inner.split('\'').next().unwrap_or_default().to_string();
+    }
+
+    if let Some(inner) = value.strip_prefix('"') {
+        let mut parsed = String::new();
+        let mut chars = inner.chars();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '"' => break,
+                '\\' => match chars.next() {
+                    Some('n') => parsed.push('\n'),
+                    Some(escaped) => parsed.push(escaped),
+                    None => parsed.push('\\'),
+                },
+                _ => parsed.push(ch),
+            }
+        }
+        return parsed;
+    }
+
+    // unquoted values end on the first inline comment
+    match value.find(" #") {
+        Some(idx) => value[..idx].trim_end().to_string(),
+        None => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_resolves_nested_variables() {
+        let variables = vars(&[("host", "example.com"), ("base", "https://{{host}}/api")]);
+
+        let resolved = resolve("{{ base }}/users/{{id}}", &variables, 10).unwrap();
+
+        assert_eq!(resolved, "https://example.com/api/users/{{id}}");
+    }
+
+    #[test]
+    fn test_detects_cycles() {
+        let variables = vars(&[("a", "{{b}}"), ("b", "/{{a}}")]);
+
+        let err = resolve("{{a}}", &variables, 10).unwrap_err();
+
+        assert_eq!(
+            err,
+            VariableError::Cycle(vec!["a".into(), "b".into(), "a".into()])
+        );
+        assert_eq!(err.to_string(), "circular variable reference: a -> b -> a");
+    }
+
+    #[test]
+    fn test_stops_at_max_depth() {
+        let variables = vars(&[("a", "{{b}}"), ("b", "{{c}}"), ("c", "done")]);
+
+        assert_eq!(resolve("{{a}}", &variables, 3).unwrap(), "done");
+        assert!(matches!(
+            resolve("{{a}}", &variables, 2),
+            Err(VariableError::TooDeep(_))
+        ));
+    }
+
+    #[test]
+    fn test_parses_env_files() {
+        let contents = r#"
+# secrets for local testing
+export TOKEN=abc123
+BASE_URL = https://api.example.com # staging
+GREETING="hello \"world\"\nbye"
+RAW='no {{escapes}} here\n'
+not a variable
+"#;
+
+        assert_eq!(
+            parse_env_file(contents),
+            vars(&[
+                ("TOKEN", "abc123"),
+                ("BASE_URL", "https://api.example.com"),
+                ("GREETING", "hello \"world\"\nbye"),
+                ("RAW", "no {{escapes}} here\\n"),
+            ])
+        );
+    }
+}